@@ -0,0 +1,89 @@
+//! Build script capturing version metadata for `descartes version --full`.
+
+use std::process::Command;
+
+fn main() {
+    let git_commit =
+        git_output(&["rev-parse", "--short=12", "HEAD"]).unwrap_or_else(|| "unknown".to_string());
+
+    let rustc_version = Command::new(std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into()))
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    // Only refreshed when the build script reruns, i.e. when HEAD moves (see
+    // `watch_git_head`), so this is the time of the first build after the last
+    // commit or checkout rather than of every incremental rebuild.
+    let build_timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    // Cargo exposes enabled features as CARGO_FEATURE_<NAME>
+    let mut features: Vec<String> = std::env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|name| name.to_lowercase().replace('_', "-"))
+        })
+        .filter(|name| name != "default")
+        .collect();
+    features.sort();
+    let loop_backends: Vec<&str> = features
+        .iter()
+        .filter_map(|f| f.strip_prefix("loop-"))
+        .collect();
+
+    println!("cargo:rustc-env=DESCARTES_GIT_COMMIT={}", git_commit);
+    println!("cargo:rustc-env=DESCARTES_RUSTC_VERSION={}", rustc_version);
+    println!(
+        "cargo:rustc-env=DESCARTES_BUILD_TIMESTAMP={}",
+        build_timestamp
+    );
+    println!("cargo:rustc-env=DESCARTES_FEATURES={}", features.join(","));
+    println!(
+        "cargo:rustc-env=DESCARTES_LOOP_BACKENDS={}",
+        loop_backends.join(",")
+    );
+    println!(
+        "cargo:rustc-env=DESCARTES_BUILD_PROFILE={}",
+        std::env::var("PROFILE").unwrap_or_default()
+    );
+    println!(
+        "cargo:rustc-env=DESCARTES_BUILD_TARGET={}",
+        std::env::var("TARGET").unwrap_or_default()
+    );
+    println!("cargo:rerun-if-changed=build.rs");
+    watch_git_head();
+}
+
+/// Rerun when HEAD moves: on checkout (HEAD itself) and on commit (the branch
+/// ref HEAD points to). The index is not watched, so `git add` doesn't rebuild.
+fn watch_git_head() {
+    let Some(git_dir) = git_output(&["rev-parse", "--absolute-git-dir"]) else {
+        return;
+    };
+    let git_dir = std::path::Path::new(&git_dir);
+    println!("cargo:rerun-if-changed={}", git_dir.join("HEAD").display());
+
+    // Detached HEADs have no symbolic ref; packed refs have no loose file
+    if let Some(head_ref) = git_output(&["symbolic-ref", "-q", "HEAD"]) {
+        for path in [git_dir.join(&head_ref), git_dir.join("packed-refs")] {
+            if path.exists() {
+                println!("cargo:rerun-if-changed={}", path.display());
+            }
+        }
+    }
+}
+
+/// Trimmed stdout of a successful git command
+fn git_output(args: &[&str]) -> Option<String> {
+    Command::new("git")
+        .args(args)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+}
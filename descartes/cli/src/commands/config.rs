@@ -0,0 +1,41 @@
+/// Configuration file commands for Descartes CLI
+use anyhow::Result;
+use clap::Subcommand;
+use descartes_core::{config_json_schema, ConfigManager, ConfigMigration};
+use std::path::Path;
+
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// Print the JSON Schema for config.toml, for editor validation and completion
+    Schema,
+
+    /// Upgrade the config file to the current version, keeping a backup of the original
+    Migrate {
+        /// Print what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// Execute a config command
+pub async fn execute(cmd: &ConfigCommands, config_path: Option<&Path>) -> Result<()> {
+    match cmd {
+        ConfigCommands::Schema => {
+            println!("{}", serde_json::to_string_pretty(&config_json_schema())?);
+        }
+        ConfigCommands::Migrate { dry_run } => {
+            let manager = ConfigManager::load(config_path)?;
+            let path = manager.config_path();
+            if !path.exists() {
+                anyhow::bail!("No config file at {}", path.display());
+            }
+
+            let report = ConfigMigration::migrate_file(path, *dry_run)?;
+            print!("{}", report);
+            if *dry_run && !report.is_up_to_date() {
+                println!("Dry run: {} was not changed", path.display());
+            }
+        }
+    }
+    Ok(())
+}
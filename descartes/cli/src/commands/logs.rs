@@ -1,9 +1,13 @@
 use anyhow::Result;
 use chrono::{DateTime, Local};
 use colored::Colorize;
-use descartes_core::DescaratesConfig;
+use descartes_core::{DescaratesConfig, OutputLog, OutputStream, OutputSubscription};
 use serde_json::json;
 use std::time::{Duration, SystemTime};
+use tokio::task::JoinHandle;
+
+/// How often `logs --follow` polls the database for new events
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(500);
 
 pub async fn execute(
     config: &DescaratesConfig,
@@ -19,30 +23,28 @@ pub async fn execute(
 
     let pool = sqlx::sqlite::SqlitePool::connect(&db_url).await?;
 
-    // Build query
-    let mut query = String::from("SELECT * FROM events WHERE 1=1");
+    // Build filter
+    let mut filter = String::from("WHERE 1=1");
     let mut params: Vec<String> = Vec::new();
 
     if let Some(id) = agent_id {
-        query.push_str(" AND session_id = ?");
+        filter.push_str(" AND session_id = ?");
         params.push(id.to_string());
     }
 
     if let Some(etype) = event_type {
-        query.push_str(" AND event_type = ?");
+        filter.push_str(" AND event_type = ?");
         params.push(etype.to_string());
     }
 
-    query.push_str(" ORDER BY timestamp DESC");
-
-    if !follow {
-        query.push_str(&format!(" LIMIT {}", limit));
-    }
-
     if follow {
         println!("{}", "Following logs (Ctrl+C to stop)...".green().bold());
-        follow_logs(&pool, &query, &params, format).await?;
+        follow_logs(pool, filter, params, format, limit).await?;
     } else {
+        let query = format!(
+            "SELECT * FROM events {} ORDER BY timestamp DESC LIMIT {}",
+            filter, limit
+        );
         print_logs(&pool, &query, &params, format).await?;
     }
 
@@ -76,111 +78,170 @@ async fn print_logs(
 }
 
 async fn follow_logs(
-    pool: &sqlx::sqlite::SqlitePool,
-    query: &str,
-    params: &[String],
+    pool: sqlx::sqlite::SqlitePool,
+    filter: String,
+    params: Vec<String>,
     format: &str,
+    limit: usize,
 ) -> Result<()> {
-    let mut last_timestamp: i64 = 0;
+    let (mut lines, poller) = subscribe_logs(pool, filter, params, format, limit).await?;
+
+    while let Some(line) = lines.recv().await {
+        println!("{}", line.text());
+    }
 
+    // The log only closes once the poller has stopped, which means it failed
+    poller.await?
+}
+
+/// Render events matching `filter` into an [`OutputLog`] and subscribe to it
+///
+/// The subscription replays the newest `limit` existing events, then yields
+/// new events as a background poller finds them. `filter` is a `WHERE`
+/// clause over the `events` table whose placeholders are bound from `params`.
+pub async fn subscribe_logs(
+    pool: sqlx::sqlite::SqlitePool,
+    filter: String,
+    params: Vec<String>,
+    format: &str,
+    limit: usize,
+) -> Result<(OutputSubscription, JoinHandle<Result<()>>)> {
+    // Older events fall out of the log, so replaying from offset 0 starts
+    // at the earliest event still buffered
+    let log = OutputLog::new(limit);
+    let last_rowid = append_new_logs(&pool, &filter, &params, format, 0, &log).await?;
+    let lines = log.subscribe_from(0);
+
+    let format = format.to_string();
+    let poller =
+        tokio::spawn(async move { poll_logs(pool, filter, params, format, last_rowid, log).await });
+
+    Ok((lines, poller))
+}
+
+async fn poll_logs(
+    pool: sqlx::sqlite::SqlitePool,
+    filter: String,
+    params: Vec<String>,
+    format: String,
+    mut last_rowid: i64,
+    log: OutputLog,
+) -> Result<()> {
     loop {
-        // Query for new events since last timestamp
-        let follow_query = format!("{} AND timestamp > ?", query);
-        let mut sql_query = sqlx::query(&follow_query);
-
-        for param in params {
-            sql_query = sql_query.bind(param);
-        }
-        sql_query = sql_query.bind(last_timestamp);
-
-        let rows = sql_query.fetch_all(pool).await?;
-
-        if !rows.is_empty() {
-            match format {
-                "json" => print_logs_json(&rows)?,
-                _ => print_logs_text(&rows)?,
-            }
-
-            // Update last timestamp
-            if let Some(row) = rows.last() {
-                last_timestamp = sqlx::Row::get(row, "timestamp");
-            }
-        }
-
-        // Sleep for a short interval
-        tokio::time::sleep(Duration::from_millis(500)).await;
+        tokio::time::sleep(FOLLOW_POLL_INTERVAL).await;
+        last_rowid = append_new_logs(&pool, &filter, &params, &format, last_rowid, &log).await?;
     }
 }
 
-fn print_logs_text(rows: &[sqlx::sqlite::SqliteRow]) -> Result<()> {
+/// Append events written after `last_rowid` to `log`, returning the new cursor
+///
+/// Rows are tracked by rowid rather than timestamp so events written within
+/// the same second as the previous poll are not skipped.
+async fn append_new_logs(
+    pool: &sqlx::sqlite::SqlitePool,
+    filter: &str,
+    params: &[String],
+    format: &str,
+    last_rowid: i64,
+    log: &OutputLog,
+) -> Result<i64> {
     use sqlx::Row;
 
-    for row in rows {
-        let timestamp: i64 = row.get("timestamp");
-        let event_type: String = row.get("event_type");
-        let actor_type: String = row.get("actor_type");
-        let actor_id: String = row.get("actor_id");
-        let content: String = row.get("content");
-
-        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(timestamp as u64);
-        let datetime: DateTime<Local> = time.into();
-        let time_str = datetime.format("%Y-%m-%d %H:%M:%S");
-
-        // Color code by event type
-        let event_colored = match event_type.as_str() {
-            "agent_started" => event_type.green(),
-            "agent_completed" => event_type.blue(),
-            "agent_failed" => event_type.red(),
-            "agent_terminated" => event_type.yellow(),
-            "error" => event_type.red().bold(),
-            "warning" => event_type.yellow(),
-            _ => event_type.white(),
-        };
+    let follow_query = format!(
+        "SELECT rowid AS follow_rowid, * FROM events {} AND rowid > ? ORDER BY rowid",
+        filter
+    );
+    let mut sql_query = sqlx::query(&follow_query);
+    for param in params {
+        sql_query = sql_query.bind(param);
+    }
+    let rows = sql_query.bind(last_rowid).fetch_all(pool).await?;
 
-        // Format actor
-        let actor_colored = match actor_type.as_str() {
-            "User" => format!("{}:{}", actor_type, actor_id).cyan(),
-            "Agent" => format!("{}:{}", actor_type, actor_id).magenta(),
-            "System" => format!("{}:{}", actor_type, actor_id).blue(),
-            _ => format!("{}:{}", actor_type, actor_id).white(),
+    let mut last_rowid = last_rowid;
+    for row in &rows {
+        let line = match format {
+            "json" => serde_json::to_string(&log_to_json(row))?,
+            _ => format_log_text(row),
         };
+        log.append(OutputStream::Stdout, line);
+        last_rowid = row.get("follow_rowid");
+    }
 
-        println!(
-            "{} {} {} {}",
-            time_str.to_string().dimmed(),
-            event_colored,
-            actor_colored,
-            content
-        );
+    Ok(last_rowid)
+}
+
+fn print_logs_text(rows: &[sqlx::sqlite::SqliteRow]) -> Result<()> {
+    for row in rows {
+        println!("{}", format_log_text(row));
     }
 
     Ok(())
 }
 
-fn print_logs_json(rows: &[sqlx::sqlite::SqliteRow]) -> Result<()> {
+/// Render one event as a colored log line
+fn format_log_text(row: &sqlx::sqlite::SqliteRow) -> String {
     use sqlx::Row;
 
-    let events: Vec<_> = rows
-        .iter()
-        .map(|row| {
-            let metadata: Option<String> = row.get("metadata");
-            let metadata_json =
-                metadata.and_then(|m| serde_json::from_str::<serde_json::Value>(&m).ok());
-
-            json!({
-                "id": row.get::<String, _>("id"),
-                "event_type": row.get::<String, _>("event_type"),
-                "timestamp": row.get::<i64, _>("timestamp"),
-                "session_id": row.get::<String, _>("session_id"),
-                "actor_type": row.get::<String, _>("actor_type"),
-                "actor_id": row.get::<String, _>("actor_id"),
-                "content": row.get::<String, _>("content"),
-                "metadata": metadata_json,
-                "git_commit": row.get::<Option<String>, _>("git_commit"),
-            })
-        })
-        .collect();
+    let timestamp: i64 = row.get("timestamp");
+    let event_type: String = row.get("event_type");
+    let actor_type: String = row.get("actor_type");
+    let actor_id: String = row.get("actor_id");
+    let content: String = row.get("content");
+
+    let time = SystemTime::UNIX_EPOCH + Duration::from_secs(timestamp as u64);
+    let datetime: DateTime<Local> = time.into();
+    let time_str = datetime.format("%Y-%m-%d %H:%M:%S");
+
+    // Color code by event type
+    let event_colored = match event_type.as_str() {
+        "agent_started" => event_type.green(),
+        "agent_completed" => event_type.blue(),
+        "agent_failed" => event_type.red(),
+        "agent_terminated" => event_type.yellow(),
+        "error" => event_type.red().bold(),
+        "warning" => event_type.yellow(),
+        _ => event_type.white(),
+    };
+
+    // Format actor
+    let actor_colored = match actor_type.as_str() {
+        "User" => format!("{}:{}", actor_type, actor_id).cyan(),
+        "Agent" => format!("{}:{}", actor_type, actor_id).magenta(),
+        "System" => format!("{}:{}", actor_type, actor_id).blue(),
+        _ => format!("{}:{}", actor_type, actor_id).white(),
+    };
+
+    format!(
+        "{} {} {} {}",
+        time_str.to_string().dimmed(),
+        event_colored,
+        actor_colored,
+        content
+    )
+}
+
+fn print_logs_json(rows: &[sqlx::sqlite::SqliteRow]) -> Result<()> {
+    let events: Vec<_> = rows.iter().map(log_to_json).collect();
 
     println!("{}", serde_json::to_string_pretty(&events)?);
     Ok(())
 }
+
+fn log_to_json(row: &sqlx::sqlite::SqliteRow) -> serde_json::Value {
+    use sqlx::Row;
+
+    let metadata: Option<String> = row.get("metadata");
+    let metadata_json = metadata.and_then(|m| serde_json::from_str::<serde_json::Value>(&m).ok());
+
+    json!({
+        "id": row.get::<String, _>("id"),
+        "event_type": row.get::<String, _>("event_type"),
+        "timestamp": row.get::<i64, _>("timestamp"),
+        "session_id": row.get::<String, _>("session_id"),
+        "actor_type": row.get::<String, _>("actor_type"),
+        "actor_id": row.get::<String, _>("actor_id"),
+        "content": row.get::<String, _>("content"),
+        "metadata": metadata_json,
+        "git_commit": row.get::<Option<String>, _>("git_commit"),
+    })
+}
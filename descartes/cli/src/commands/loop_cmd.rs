@@ -201,6 +201,15 @@ async fn handle_start(args: &LoopStartArgs) -> Result<()> {
 
         Ok(())
     } else {
+        let compiled = super::version::loop_backends();
+        if !compiled.contains(&args.backend) {
+            return Err(anyhow::anyhow!(
+                "Loop backend '{}' is not compiled into this build (available: {})",
+                args.backend,
+                compiled.join(", ")
+            ));
+        }
+
         // Use generic iterative loop (existing behavior)
         println!("{}", "Starting iterative loop...".cyan());
         println!("  Command: {}", args.command.yellow());
@@ -1,4 +1,5 @@
 pub mod attach;
+pub mod config;
 pub mod doctor;
 pub mod init;
 pub mod kill;
@@ -7,6 +8,9 @@ pub mod loop_cmd;
 pub mod pause;
 pub mod ps;
 pub mod resume;
+pub mod scud;
 pub mod spawn;
 pub mod tasks;
+pub mod transcripts;
+pub mod version;
 pub mod workflow;
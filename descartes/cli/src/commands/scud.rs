@@ -0,0 +1,79 @@
+/// SCUD task file commands for Descartes CLI
+use anyhow::Result;
+use clap::Subcommand;
+use colored::Colorize;
+use descartes_core::{validate_scg, ScgValidationReport};
+use std::path::{Path, PathBuf};
+
+#[derive(Subcommand)]
+pub enum ScudCommands {
+    /// Check the task file for parse errors, duplicate IDs, dangling dependencies and cycles
+    Validate {
+        /// Task file to check (defaults to .scud/tasks/tasks.scg)
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+}
+
+/// Execute a scud command
+pub async fn execute(cmd: &ScudCommands, project_root: Option<PathBuf>) -> Result<()> {
+    let root = project_root.unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
+
+    match cmd {
+        ScudCommands::Validate { file } => {
+            let path = file
+                .clone()
+                .unwrap_or_else(|| root.join(".scud/tasks/tasks.scg"));
+            validate_file(&path)
+        }
+    }
+}
+
+/// Validate `path`, print the report and fail if any problem was found
+pub fn validate_file(path: &Path) -> Result<()> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+    let report = validate_scg(&content);
+
+    print!("{}", render_report(path, &report));
+
+    if !report.is_valid() {
+        anyhow::bail!(
+            "{} problem(s) found in {}",
+            report.issues.len(),
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Human-readable validation report, one line per issue
+pub fn render_report(path: &Path, report: &ScgValidationReport) -> String {
+    let mut out = String::new();
+    for issue in &report.issues {
+        out.push_str(&format!(
+            "{}:{}: {}: {}",
+            path.display(),
+            issue.line,
+            issue.kind.to_string().red(),
+            issue.message
+        ));
+        if let Some(phase) = &issue.phase {
+            out.push_str(&format!(" {}", format!("[{}]", phase).dimmed()));
+        }
+        out.push('\n');
+    }
+
+    let summary = format!(
+        "{} phase(s), {} task(s), {} problem(s)",
+        report.phases,
+        report.tasks,
+        report.issues.len()
+    );
+    if report.is_valid() {
+        out.push_str(&format!("{} {}\n", "✓".green(), summary));
+    } else {
+        out.push_str(&format!("{} {}\n", "✗".red(), summary));
+    }
+    out
+}
@@ -1,14 +1,15 @@
 use anyhow::Result;
 use colored::Colorize;
 use descartes_core::{
-    default_sessions_dir, get_system_prompt, get_tools, DescaratesConfig, Message, MessageRole,
-    ModelBackend, ModelRequest, ProviderFactory, ToolLevel, TranscriptWriter,
+    default_sessions_dir, get_system_prompt, get_tools, resolve_api_key, DescaratesConfig,
+    FinishReason, Message, MessageRole, ModelBackend, ModelRequest, ProviderFactory,
+    StateStoreResult, ToolLevel, TranscriptIndex, TranscriptWriter,
 };
 use futures::StreamExt;
 use indicatif::{ProgressBar, ProgressStyle};
 use std::collections::HashMap;
 use std::io::{self, BufRead};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::{info, warn};
 
 /// Parse tool level from string
@@ -154,9 +155,9 @@ pub async fn execute(
 
     // Execute with streaming or non-streaming
     // Fall back to non-streaming if provider doesn't support streaming
-    let response_content = if stream {
+    let (response_content, finish_reason) = if stream {
         match execute_streaming(backend.as_ref(), request.clone()).await {
-            Ok(content) => content,
+            Ok(result) => result,
             Err(e) if e.to_string().contains("Streaming not yet implemented")
                    || e.to_string().contains("Unsupported feature") => {
                 println!("{}", "(streaming not supported, using non-streaming mode)".dimmed());
@@ -170,9 +171,22 @@ pub async fn execute(
 
     // Log assistant response to transcript
     transcript.add_assistant_message(&response_content);
+    if let Some(reason) = finish_reason {
+        if reason == FinishReason::MaxTokens {
+            println!(
+                "{}",
+                "Response was truncated: the model hit its max_tokens limit.".yellow()
+            );
+        }
+        transcript.set_finish_reason(reason);
+    }
 
     // Save transcript
     let transcript_path = transcript.save()?;
+    // The index only speeds up `descartes transcripts --grep`, which resyncs anyway
+    if let Err(e) = index_transcript(&sessions_dir, &transcript_path).await {
+        warn!("Failed to index transcript: {}", e);
+    }
     println!(
         "\n{} {}",
         "Transcript saved:".dimmed(),
@@ -184,21 +198,33 @@ pub async fn execute(
     Ok(())
 }
 
+/// Add a saved transcript to its sessions directory's search index
+async fn index_transcript(sessions_dir: &Path, path: &Path) -> StateStoreResult<()> {
+    TranscriptIndex::open(sessions_dir)
+        .await?
+        .index_transcript(path)
+        .await
+}
+
 async fn execute_streaming(
     backend: &dyn ModelBackend,
     request: ModelRequest,
-) -> Result<String> {
+) -> Result<(String, Option<FinishReason>)> {
     println!("\n{}", "Streaming response:".green());
     println!("{}", "─".repeat(80).dimmed());
 
     let mut stream = backend.stream(request).await?;
     let mut full_response = String::new();
+    let mut finish_reason = None;
 
     while let Some(result) = stream.next().await {
         match result {
             Ok(response) => {
                 print!("{}", response.content);
                 full_response.push_str(&response.content);
+                if response.finish_reason != FinishReason::Streaming {
+                    finish_reason = Some(response.finish_reason);
+                }
                 use std::io::Write;
                 io::stdout().flush()?;
             }
@@ -210,13 +236,13 @@ async fn execute_streaming(
     }
 
     println!("\n{}", "─".repeat(80).dimmed());
-    Ok(full_response)
+    Ok((full_response, finish_reason))
 }
 
 async fn execute_non_streaming(
     backend: &dyn ModelBackend,
     request: ModelRequest,
-) -> Result<String> {
+) -> Result<(String, Option<FinishReason>)> {
     let spinner = ProgressBar::new_spinner();
     spinner.set_style(
         ProgressStyle::default_spinner()
@@ -239,24 +265,25 @@ async fn execute_non_streaming(
         println!("\nTokens used: {}", tokens.to_string().cyan());
     }
 
-    Ok(response.content)
+    Ok((response.content, Some(response.finish_reason)))
 }
 
 pub fn create_backend(
     config: &DescaratesConfig,
     provider: &str,
-    _model: &str,
+    model: &str,
 ) -> Result<Box<dyn ModelBackend>> {
     info!("Creating backend for provider: {}", provider);
 
     // Build config HashMap from DescaratesConfig
     let mut provider_config: HashMap<String, String> = HashMap::new();
+    provider_config.insert("model".to_string(), model.to_string());
 
     match provider {
         "anthropic" => {
-            match &config.providers.anthropic.api_key {
-                Some(api_key) if !api_key.is_empty() => {
-                    provider_config.insert("api_key".to_string(), api_key.clone());
+            match resolve_api_key(config.providers.anthropic.api_key.as_deref())? {
+                Some(api_key) => {
+                    provider_config.insert("api_key".to_string(), api_key);
                 }
                 _ => {
                     eprintln!();
@@ -278,11 +305,19 @@ pub fn create_backend(
                 "endpoint".to_string(),
                 config.providers.anthropic.endpoint.clone(),
             );
+            provider_config.insert(
+                "max_retries".to_string(),
+                config.providers.anthropic.max_retries.to_string(),
+            );
+            provider_config.insert(
+                "initial_backoff_ms".to_string(),
+                config.providers.anthropic.initial_backoff_ms.to_string(),
+            );
         }
         "openai" => {
-            match &config.providers.openai.api_key {
-                Some(api_key) if !api_key.is_empty() => {
-                    provider_config.insert("api_key".to_string(), api_key.clone());
+            match resolve_api_key(config.providers.openai.api_key.as_deref())? {
+                Some(api_key) => {
+                    provider_config.insert("api_key".to_string(), api_key);
                 }
                 _ => {
                     eprintln!();
@@ -296,10 +331,7 @@ pub fn create_backend(
                     anyhow::bail!("OpenAI API key not configured");
                 }
             }
-            provider_config.insert(
-                "endpoint".to_string(),
-                config.providers.openai.endpoint.clone(),
-            );
+            provider_config.extend(config.providers.openai.provider_settings());
         }
         "ollama" => {
             provider_config.insert(
@@ -308,9 +340,9 @@ pub fn create_backend(
             );
         }
         "deepseek" => {
-            match &config.providers.deepseek.api_key {
-                Some(api_key) if !api_key.is_empty() => {
-                    provider_config.insert("api_key".to_string(), api_key.clone());
+            match resolve_api_key(config.providers.deepseek.api_key.as_deref())? {
+                Some(api_key) => {
+                    provider_config.insert("api_key".to_string(), api_key);
                 }
                 _ => {
                     eprintln!();
@@ -328,9 +360,9 @@ pub fn create_backend(
             );
         }
         "groq" => {
-            match &config.providers.groq.api_key {
-                Some(api_key) if !api_key.is_empty() => {
-                    provider_config.insert("api_key".to_string(), api_key.clone());
+            match resolve_api_key(config.providers.groq.api_key.as_deref())? {
+                Some(api_key) => {
+                    provider_config.insert("api_key".to_string(), api_key);
                 }
                 _ => {
                     eprintln!();
@@ -350,9 +382,9 @@ pub fn create_backend(
             );
         }
         "grok" => {
-            match &config.providers.grok.api_key {
-                Some(api_key) if !api_key.is_empty() => {
-                    provider_config.insert("api_key".to_string(), api_key.clone());
+            match resolve_api_key(config.providers.grok.api_key.as_deref())? {
+                Some(api_key) => {
+                    provider_config.insert("api_key".to_string(), api_key);
                 }
                 _ => {
                     eprintln!();
@@ -4,7 +4,11 @@ use anyhow::Result;
 use clap::Subcommand;
 use colored::Colorize;
 use descartes_core::{
-    ScgTaskQueryBuilder, ScgTaskStorage, TaskPriority, TaskStatus,
+    ScgTaskQueryBuilder, ScgTaskStorage, TaskPriority, TaskSchedule, TaskStatus,
+};
+use descartes_daemon::events::EventCategory;
+use descartes_daemon::{
+    DescartesEvent, EventBus, EventFilter, ScgTaskEventEmitter, TaskEvent, TaskEventType,
 };
 use serde_json::json;
 use std::path::PathBuf;
@@ -33,6 +37,10 @@ pub enum TaskCommands {
         /// Limit number of results
         #[arg(short, long, default_value = "50")]
         limit: usize,
+
+        /// Skip this many results (for paging through long lists)
+        #[arg(long, default_value = "0")]
+        offset: usize,
     },
 
     /// Show details of a specific task
@@ -71,15 +79,30 @@ pub enum TaskCommands {
         #[arg(short, long, default_value = "table")]
         format: String,
     },
+
+    /// Stream task status transitions as they happen
+    Watch {
+        /// Only show transitions into this status (pending, in-progress, done, blocked)
+        #[arg(short, long)]
+        status: Option<String>,
+
+        /// Only show tasks in this phase/epic
+        #[arg(long)]
+        phase: Option<String>,
+    },
 }
 
 /// Execute a task command
-pub async fn execute(cmd: &TaskCommands, project_root: Option<PathBuf>) -> Result<()> {
+pub async fn execute(
+    cmd: &TaskCommands,
+    project_root: Option<PathBuf>,
+    schedule: TaskSchedule,
+) -> Result<()> {
     // Determine project root (current directory or specified)
     let root = project_root.unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
 
     // Create SCG storage
-    let storage = Arc::new(ScgTaskStorage::new(&root));
+    let storage = Arc::new(ScgTaskStorage::new(&root).with_schedule(schedule));
 
     // Refresh cache from disk
     if let Err(e) = storage.refresh_cache().await {
@@ -102,14 +125,27 @@ pub async fn execute(cmd: &TaskCommands, project_root: Option<PathBuf>) -> Resul
             search,
             format,
             limit,
+            offset,
         } => {
-            list_tasks(&storage, status.as_deref(), priority.as_deref(), search.as_deref(), format, *limit).await
+            list_tasks(
+                &storage,
+                status.as_deref(),
+                priority.as_deref(),
+                search.as_deref(),
+                format,
+                *limit,
+                *offset,
+            )
+            .await
         }
         TaskCommands::Show { id, format } => show_task(&storage, id, format).await,
         TaskCommands::Next { id_only } => next_task(&storage, *id_only).await,
         TaskCommands::Stats { format } => show_stats(&storage, format).await,
         TaskCommands::Use { tag } => use_phase(&storage, tag).await,
         TaskCommands::Phases { format } => list_phases(&storage, format).await,
+        TaskCommands::Watch { status, phase } => {
+            watch_tasks(&storage, status.as_deref(), phase.as_deref()).await
+        }
     }
 }
 
@@ -121,16 +157,22 @@ async fn list_tasks(
     search_term: Option<&str>,
     format: &str,
     limit: usize,
+    offset: usize,
 ) -> Result<()> {
     let tasks = storage.get_active_phase_tasks().await?;
 
     if tasks.is_empty() {
-        println!("{}", "No tasks found in active phase.".yellow());
+        if format == "json" {
+            // Keep the output parseable for scripts
+            println!("[]");
+        } else {
+            println!("{}", "No tasks found in active phase.".yellow());
+        }
         return Ok(());
     }
 
     // Build query
-    let mut query = ScgTaskQueryBuilder::new().limit(limit);
+    let mut query = ScgTaskQueryBuilder::new().limit(limit).offset(offset);
 
     if let Some(status_str) = status_filter {
         if let Some(status) = parse_status(status_str) {
@@ -153,13 +195,13 @@ async fn list_tasks(
     match format {
         "json" => print_tasks_json(&filtered)?,
         "scg" => print_tasks_scg(&filtered)?,
-        _ => print_tasks_table(&filtered)?,
+        _ => print_tasks_table(&filtered, offset, query.count(&tasks))?,
     }
 
     Ok(())
 }
 
-fn print_tasks_table(tasks: &[descartes_core::Task]) -> Result<()> {
+fn print_tasks_table(tasks: &[descartes_core::Task], offset: usize, total: usize) -> Result<()> {
     println!("\n{}", "Tasks".green().bold());
     println!("{}", "─".repeat(100).dimmed());
 
@@ -211,12 +253,27 @@ fn print_tasks_table(tasks: &[descartes_core::Task]) -> Result<()> {
     }
 
     println!("{}", "─".repeat(100).dimmed());
-    println!("\nTotal: {}", tasks.len().to_string().cyan());
+    if tasks.len() < total {
+        println!(
+            "\nShowing {}-{} of {}",
+            (offset + 1).to_string().cyan(),
+            (offset + tasks.len()).to_string().cyan(),
+            total.to_string().cyan()
+        );
+    } else {
+        println!("\nTotal: {}", tasks.len().to_string().cyan());
+    }
 
     Ok(())
 }
 
 fn print_tasks_json(tasks: &[descartes_core::Task]) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(&tasks_to_json(tasks))?);
+    Ok(())
+}
+
+/// Structured form of `tasks list --format json`, one object per task
+pub fn tasks_to_json(tasks: &[descartes_core::Task]) -> serde_json::Value {
     let json_tasks: Vec<_> = tasks
         .iter()
         .map(|t| {
@@ -230,12 +287,14 @@ fn print_tasks_json(tasks: &[descartes_core::Task]) -> Result<()> {
                 "complexity": complexity,
                 "assigned_to": t.assigned_to,
                 "dependencies": t.dependencies.iter().map(|d| d.to_string()).collect::<Vec<_>>(),
+                "metadata": t.metadata,
+                "created_at": t.created_at,
+                "updated_at": t.updated_at,
             })
         })
         .collect();
 
-    println!("{}", serde_json::to_string_pretty(&json_tasks)?);
-    Ok(())
+    serde_json::Value::Array(json_tasks)
 }
 
 fn print_tasks_scg(tasks: &[descartes_core::Task]) -> Result<()> {
@@ -543,6 +602,121 @@ async fn list_phases(storage: &Arc<ScgTaskStorage>, format: &str) -> Result<()>
     Ok(())
 }
 
+/// Watch the SCG task file and print status transitions until Ctrl+C
+async fn watch_tasks(
+    storage: &Arc<ScgTaskStorage>,
+    status_filter: Option<&str>,
+    phase_filter: Option<&str>,
+) -> Result<()> {
+    let status_filter = status_filter
+        .map(|s| parse_status(s).ok_or_else(|| anyhow::anyhow!("Unknown status filter: {}", s)))
+        .transpose()?;
+
+    let event_bus = Arc::new(EventBus::new());
+    let mut emitter = ScgTaskEventEmitter::with_defaults(storage.clone(), event_bus.clone());
+    emitter.initialize_cache().await?;
+
+    let (subscription_id, mut rx) = event_bus
+        .subscribe_filtered(EventFilter {
+            event_categories: vec![EventCategory::Task],
+            ..Default::default()
+        })
+        .await;
+    emitter.start_watching().await?;
+
+    println!(
+        "{} {}",
+        "Watching tasks in".green(),
+        storage.project_root().display().to_string().cyan()
+    );
+    println!("{}", "Press Ctrl+C to stop.".dimmed());
+
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            received = rx.recv() => match received {
+                Ok(DescartesEvent::TaskEvent(event)) => {
+                    if let Some(phase) = phase_filter {
+                        if !phase_contains(storage, phase, &event.task_id).await {
+                            continue;
+                        }
+                    }
+                    if let Some(line) = render_task_event(&event, status_filter.as_ref()) {
+                        println!("{}", line);
+                    }
+                }
+                Ok(_) => {}
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                    println!("{}", format!("... {} events skipped", skipped).yellow());
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+
+    emitter.stop_watching().await;
+    event_bus.unsubscribe(&subscription_id).await;
+    Ok(())
+}
+
+/// Whether `task_id` belongs to the phase tagged `phase`
+async fn phase_contains(storage: &ScgTaskStorage, phase: &str, task_id: &str) -> bool {
+    match storage.get_phase(phase).await {
+        Ok(Some(phase)) => phase.tasks.iter().any(|t| t.id == task_id),
+        _ => false,
+    }
+}
+
+/// Render a task event from the SCG emitter as one line of `tasks watch` output
+///
+/// Returns `None` for edits that don't change the status, and for transitions
+/// into a status other than `status_filter`.
+pub fn render_task_event(event: &TaskEvent, status_filter: Option<&TaskStatus>) -> Option<String> {
+    let new_status = event.data.get("new_status").and_then(|v| v.as_str())?;
+    let previous_status = event.data.get("previous_status").and_then(|v| v.as_str());
+
+    if let Some(status) = status_filter {
+        if new_status != format!("{:?}", status) {
+            return None;
+        }
+    }
+
+    let (label, transition) = match event.event_type {
+        TaskEventType::Created => ("created".green(), new_status.to_string()),
+        TaskEventType::Cancelled => ("deleted".red(), String::new()),
+        _ => {
+            let previous = previous_status?;
+            if previous == new_status {
+                return None;
+            }
+            let label = if previous == "Blocked" {
+                "unblocked".yellow()
+            } else {
+                "status".cyan()
+            };
+            (label, format!("{} -> {}", previous, new_status))
+        }
+    };
+
+    let title = event
+        .data
+        .get("task")
+        .and_then(|t| t.get("title"))
+        .and_then(|t| t.as_str())
+        .unwrap_or("");
+    let short_id: String = event.task_id.chars().take(8).collect();
+
+    let line = format!(
+        "{} {:<10} {} {:<24} {}",
+        event.timestamp.format("%H:%M:%S").to_string().dimmed(),
+        label,
+        short_id.cyan(),
+        transition,
+        title
+    );
+    Some(line.trim_end().to_string())
+}
+
 /// Parse status string to TaskStatus
 fn parse_status(s: &str) -> Option<TaskStatus> {
     match s.to_lowercase().as_str() {
@@ -0,0 +1,52 @@
+/// Session transcript search for Descartes CLI
+use anyhow::Result;
+use colored::Colorize;
+use descartes_core::{default_sessions_dir, TranscriptIndex};
+use std::path::PathBuf;
+
+/// Search transcripts through the index, bringing it up to date first
+pub async fn execute(
+    dir: Option<PathBuf>,
+    grep: Option<&str>,
+    rebuild: bool,
+    limit: usize,
+) -> Result<()> {
+    let sessions_dir = dir.unwrap_or_else(default_sessions_dir);
+    let index = TranscriptIndex::open(&sessions_dir).await?;
+
+    let updated = if rebuild {
+        index.rebuild().await?
+    } else {
+        index.sync().await?
+    };
+
+    let Some(text) = grep else {
+        println!(
+            "{} {} transcript(s) in {}",
+            if rebuild { "Reindexed" } else { "Updated" },
+            updated,
+            sessions_dir.display()
+        );
+        return Ok(());
+    };
+
+    let hits = index.search(text).await?;
+    if hits.is_empty() {
+        println!("{}", "No matching transcripts.".dimmed());
+        return Ok(());
+    }
+
+    for hit in hits.iter().take(limit) {
+        println!(
+            "{}  {}  {}",
+            hit.started_at.format("%Y-%m-%d %H:%M").to_string().dimmed(),
+            hit.task.bold(),
+            hit.path.display().to_string().dimmed()
+        );
+        println!("    {}", hit.snippet.replace('\n', " "));
+    }
+    if hits.len() > limit {
+        println!("{}", format!("... {} more", hits.len() - limit).dimmed());
+    }
+    Ok(())
+}
@@ -0,0 +1,118 @@
+//! Version command - prints version, build, feature and backend information for bug reports
+
+use anyhow::Result;
+use chrono::{TimeZone, Utc};
+use colored::Colorize;
+use descartes_core::ProviderFactory;
+use serde::Serialize;
+
+/// Harness backends compiled into iterative loops (the enabled `loop-*` features)
+pub fn loop_backends() -> Vec<String> {
+    split_env_list(env!("DESCARTES_LOOP_BACKENDS"))
+}
+
+/// Parse a comma-separated list exported by the build script
+fn split_env_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Version and build metadata, gathered at compile time
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionInfo {
+    pub version: String,
+    pub git_commit: String,
+    /// When the build script last ran, i.e. the first build after HEAD moved
+    pub build_date: String,
+    pub build_profile: String,
+    pub target: String,
+    pub rustc: String,
+    pub features: Vec<String>,
+    pub core_version: String,
+    pub daemon_version: String,
+    pub provider_backends: Vec<String>,
+    pub loop_backends: Vec<String>,
+}
+
+impl VersionInfo {
+    /// Collect version information for this binary
+    pub fn collect() -> Self {
+        let build_date = env!("DESCARTES_BUILD_TIMESTAMP")
+            .parse::<i64>()
+            .ok()
+            .and_then(|ts| Utc.timestamp_opt(ts, 0).single())
+            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: env!("DESCARTES_GIT_COMMIT").to_string(),
+            build_date,
+            build_profile: env!("DESCARTES_BUILD_PROFILE").to_string(),
+            target: env!("DESCARTES_BUILD_TARGET").to_string(),
+            rustc: env!("DESCARTES_RUSTC_VERSION").to_string(),
+            features: split_env_list(env!("DESCARTES_FEATURES")),
+            core_version: descartes_core::VERSION.to_string(),
+            daemon_version: descartes_daemon::VERSION.to_string(),
+            provider_backends: ProviderFactory::PROVIDERS
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            loop_backends: loop_backends(),
+        }
+    }
+
+    /// Render as plain text; `full` adds build, feature and subsystem details
+    pub fn render(&self, full: bool) -> String {
+        if !full {
+            return format!("descartes {}", self.version);
+        }
+
+        let list_or_none = |items: &[String]| {
+            if items.is_empty() {
+                "none".to_string()
+            } else {
+                items.join(", ")
+            }
+        };
+
+        [
+            format!("descartes {}", self.version),
+            format!("  Git commit:        {}", self.git_commit),
+            format!("  Build date:        {}", self.build_date),
+            format!("  Build profile:     {}", self.build_profile),
+            format!("  Target:            {}", self.target),
+            format!("  Rustc:             {}", self.rustc),
+            format!("  Features:          {}", list_or_none(&self.features)),
+            format!("  descartes-core:    {}", self.core_version),
+            format!("  descartes-daemon:  {}", self.daemon_version),
+            format!("  Providers:         {}", self.provider_backends.join(", ")),
+            format!("  Loop backends:     {}", list_or_none(&self.loop_backends)),
+        ]
+        .join("\n")
+    }
+}
+
+/// Execute the version command
+pub fn execute(full: bool, json: bool) -> Result<()> {
+    let info = VersionInfo::collect();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&info)?);
+    } else if full {
+        let rendered = info.render(true);
+        let mut lines = rendered.lines();
+        if let Some(header) = lines.next() {
+            println!("{}", header.bold());
+        }
+        for line in lines {
+            println!("{}", line);
+        }
+    } else {
+        println!("{}", info.render(false));
+    }
+
+    Ok(())
+}
@@ -6,11 +6,12 @@ use anyhow::Result;
 use clap::Subcommand;
 use colored::Colorize;
 use descartes_core::{
-    execute_workflow as run_workflow, get_workflow, list_workflows, prepare_workflow,
-    DescaratesConfig, ProviderFactory, WorkflowContext, WorkflowExecutorConfig,
+    execute_workflow as run_workflow, prepare_workflow, resolve_api_key, DescaratesConfig,
+    ProviderFactory, SqliteWorkflowStore, StateStoreConfig, WorkflowContext, WorkflowEvent,
+    WorkflowExecutorConfig, WorkflowProfile, WorkflowRegistry, WorkflowStateMachine,
 };
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tracing::info;
 
@@ -37,6 +38,10 @@ pub enum WorkflowCommands {
         /// Use a headless CLI adapter (claude-code, opencode)
         #[arg(long)]
         adapter: Option<String>,
+
+        /// Print per-step timing once the workflow finishes and save it with the run
+        #[arg(long)]
+        profile: bool,
     },
 
     /// Create an implementation plan
@@ -57,6 +62,10 @@ pub enum WorkflowCommands {
         /// Use a headless CLI adapter (claude-code, opencode)
         #[arg(long)]
         adapter: Option<String>,
+
+        /// Print per-step timing once the workflow finishes and save it with the run
+        #[arg(long)]
+        profile: bool,
     },
 
     /// Implement a plan from thoughts/plans/
@@ -75,6 +84,34 @@ pub enum WorkflowCommands {
         adapter: Option<String>,
     },
 
+    /// Run any workflow by name, including those in .descartes/workflows/
+    #[command(name = "run")]
+    Run {
+        /// Workflow name
+        #[arg(short, long)]
+        workflow: String,
+
+        /// Topic for the workflow
+        #[arg(short, long)]
+        topic: String,
+
+        /// Additional context
+        #[arg(short, long)]
+        context: Option<String>,
+
+        /// Working directory (defaults to current directory)
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+
+        /// Use a headless CLI adapter (claude-code, opencode)
+        #[arg(long)]
+        adapter: Option<String>,
+
+        /// Print per-step timing once the workflow finishes and save it with the run
+        #[arg(long)]
+        profile: bool,
+    },
+
     /// Show details about a specific workflow
     Info {
         /// Workflow name
@@ -109,15 +146,64 @@ pub enum WorkflowCommands {
 pub async fn execute(cmd: &WorkflowCommands, config: &DescaratesConfig) -> Result<()> {
     match cmd {
         WorkflowCommands::List => execute_list().await,
-        WorkflowCommands::Research { topic, context, dir, adapter } => {
-            execute_workflow_run("research_codebase", topic, context.as_deref(), dir.clone(), adapter.as_deref(), config).await
+        WorkflowCommands::Research {
+            topic,
+            context,
+            dir,
+            adapter,
+            profile,
+        } => {
+            execute_workflow_run(
+                "research_codebase",
+                topic,
+                context.as_deref(),
+                dir.clone(),
+                adapter.as_deref(),
+                *profile,
+                config,
+            )
+            .await
         }
-        WorkflowCommands::Plan { topic, context, dir, adapter } => {
-            execute_workflow_run("create_plan", topic, context.as_deref(), dir.clone(), adapter.as_deref(), config).await
+        WorkflowCommands::Plan {
+            topic,
+            context,
+            dir,
+            adapter,
+            profile,
+        } => {
+            execute_workflow_run(
+                "create_plan",
+                topic,
+                context.as_deref(),
+                dir.clone(),
+                adapter.as_deref(),
+                *profile,
+                config,
+            )
+            .await
         }
         WorkflowCommands::Implement { plan, dir, adapter } => {
             execute_implement(plan, dir.clone(), adapter.as_deref(), config).await
         }
+        WorkflowCommands::Run {
+            workflow,
+            topic,
+            context,
+            dir,
+            adapter,
+            profile,
+        } => {
+            execute_workflow_run(
+                workflow,
+                topic,
+                context.as_deref(),
+                dir.clone(),
+                adapter.as_deref(),
+                *profile,
+                config,
+            )
+            .await
+        }
         WorkflowCommands::Info { name } => execute_info(name).await,
         WorkflowCommands::Flow { prd, tag, resume, dir, adapter } => {
             execute_flow(prd.clone(), tag.clone(), *resume, dir.clone(), adapter.as_deref(), config).await
@@ -125,6 +211,16 @@ pub async fn execute(cmd: &WorkflowCommands, config: &DescaratesConfig) -> Resul
     }
 }
 
+/// Built-in workflows plus those discovered in `project_root`, warning about
+/// workflow files that could not be loaded
+fn project_workflows(project_root: &Path) -> WorkflowRegistry {
+    let (registry, warnings) = WorkflowRegistry::for_project(project_root);
+    for warning in warnings {
+        eprintln!("{} {}", "Warning: skipping workflow".yellow(), warning);
+    }
+    registry
+}
+
 async fn execute_list() -> Result<()> {
     println!();
     println!(
@@ -141,10 +237,14 @@ async fn execute_list() -> Result<()> {
     );
     println!();
 
-    let workflows = list_workflows();
+    let registry = project_workflows(&std::env::current_dir().unwrap_or_default());
 
-    for (name, description) in workflows {
-        println!("  {} - {}", name.green().bold(), description.dimmed());
+    for workflow in registry.all() {
+        println!(
+            "  {} - {}",
+            workflow.name.green().bold(),
+            workflow.description.dimmed()
+        );
     }
 
     println!();
@@ -163,6 +263,7 @@ async fn execute_workflow_run(
     context: Option<&str>,
     dir: Option<PathBuf>,
     adapter: Option<&str>,
+    profile: bool,
     config: &DescaratesConfig,
 ) -> Result<()> {
     println!();
@@ -172,7 +273,11 @@ async fn execute_workflow_run(
     );
     println!();
 
-    let workflow = get_workflow(workflow_name)
+    let working_dir = dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
+
+    let workflow = project_workflows(&working_dir)
+        .get(workflow_name)
+        .cloned()
         .ok_or_else(|| anyhow::anyhow!("Workflow '{}' not found", workflow_name))?;
 
     println!("  {}", workflow.description.dimmed());
@@ -182,8 +287,6 @@ async fn execute_workflow_run(
     }
     println!();
 
-    let working_dir = dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
-
     let mut wf_context = WorkflowContext::new(working_dir.clone(), topic)
         .map_err(|e| anyhow::anyhow!("Failed to create workflow context: {}", e))?;
 
@@ -191,6 +294,18 @@ async fn execute_workflow_run(
         wf_context = wf_context.with_context(ctx);
     }
 
+    // Profiled runs are recorded, so their timings can be compared later
+    let run = if profile {
+        let (machine, store) = start_workflow_run(config, workflow_name).await?;
+        wf_context = wf_context
+            .with_state(Arc::clone(&machine), Some(Arc::clone(&store)))
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to attach workflow state: {}", e))?;
+        Some((machine, store))
+    } else {
+        None
+    };
+
     // Prepare the workflow steps
     let prepared_steps = prepare_workflow(&workflow, &wf_context)
         .map_err(|e| anyhow::anyhow!("Failed to prepare workflow: {}", e))?;
@@ -277,6 +392,50 @@ async fn execute_workflow_run(
         if let Some(error) = &result.error {
             println!("    Error: {}", error.red());
         }
+        if result.attempts > 1 {
+            println!("    Attempts: {}", result.attempts);
+        }
+        if let Some(compensation) = &result.compensation {
+            if compensation.success {
+                println!("    {}", "Compensated".yellow());
+            } else {
+                println!(
+                    "    Compensation failed: {}",
+                    compensation
+                        .error
+                        .as_deref()
+                        .unwrap_or("unknown error")
+                        .red()
+                );
+            }
+        }
+    }
+
+    if profile {
+        println!();
+        println!("{}", "Profile:".green().bold());
+        println!();
+        for line in WorkflowProfile::from_results(&results)
+            .render_table()
+            .lines()
+        {
+            println!("  {}", line);
+        }
+    }
+
+    if let Some((machine, store)) = &run {
+        let event = if all_success {
+            WorkflowEvent::Complete
+        } else {
+            WorkflowEvent::Fail("one or more steps failed".to_string())
+        };
+        machine.process_event(event).await?;
+        record_transition(machine, store).await?;
+        println!();
+        println!(
+            "  Profile saved with workflow run {}",
+            machine.workflow_id().yellow()
+        );
     }
 
     println!();
@@ -290,6 +449,45 @@ async fn execute_workflow_run(
     Ok(())
 }
 
+/// Open the workflow run store and start a run of `workflow_name` in it
+async fn start_workflow_run(
+    config: &DescaratesConfig,
+    workflow_name: &str,
+) -> Result<(Arc<WorkflowStateMachine>, Arc<SqliteWorkflowStore>)> {
+    let data_dir = PathBuf::from(&config.storage.base_path).join("data");
+    std::fs::create_dir_all(&data_dir)?;
+    let db_url = format!(
+        "sqlite://{}?mode=rwc",
+        data_dir.join("workflows.db").display()
+    );
+    let store = Arc::new(SqliteWorkflowStore::new(&db_url, StateStoreConfig::default()).await?);
+
+    let run_id = format!("{}-{}", workflow_name, uuid::Uuid::new_v4());
+    let machine = Arc::new(WorkflowStateMachine::new(run_id));
+    machine.process_event(WorkflowEvent::Start).await?;
+    record_transition(&machine, &store).await?;
+
+    Ok((machine, store))
+}
+
+/// Save the workflow and its latest transition, with a context snapshot
+async fn record_transition(
+    machine: &WorkflowStateMachine,
+    store: &SqliteWorkflowStore,
+) -> Result<()> {
+    store.save_workflow(machine).await?;
+    if let Some(entry) = machine.get_history_tail(1).await.pop() {
+        store
+            .save_transition(
+                machine.workflow_id(),
+                &entry.transition,
+                &entry.context_snapshot,
+            )
+            .await?;
+    }
+    Ok(())
+}
+
 async fn execute_implement(plan: &str, dir: Option<PathBuf>, _adapter: Option<&str>, _config: &DescaratesConfig) -> Result<()> {
     println!();
     println!(
@@ -351,7 +549,9 @@ async fn execute_implement(plan: &str, dir: Option<PathBuf>, _adapter: Option<&s
 }
 
 async fn execute_info(name: &str) -> Result<()> {
-    let workflow = get_workflow(name)
+    let workflow = project_workflows(&std::env::current_dir().unwrap_or_default())
+        .get(name)
+        .cloned()
         .ok_or_else(|| anyhow::anyhow!("Workflow '{}' not found", name))?;
 
     println!();
@@ -475,26 +675,27 @@ fn create_backend(
 
     match provider {
         "anthropic" => {
-            if let Some(api_key) = &config.providers.anthropic.api_key {
-                if !api_key.is_empty() {
-                    provider_config.insert("api_key".to_string(), api_key.clone());
-                }
+            if let Some(api_key) = resolve_api_key(config.providers.anthropic.api_key.as_deref())? {
+                provider_config.insert("api_key".to_string(), api_key);
             }
             provider_config.insert(
                 "endpoint".to_string(),
                 config.providers.anthropic.endpoint.clone(),
             );
+            provider_config.insert(
+                "max_retries".to_string(),
+                config.providers.anthropic.max_retries.to_string(),
+            );
+            provider_config.insert(
+                "initial_backoff_ms".to_string(),
+                config.providers.anthropic.initial_backoff_ms.to_string(),
+            );
         }
         "openai" => {
-            if let Some(api_key) = &config.providers.openai.api_key {
-                if !api_key.is_empty() {
-                    provider_config.insert("api_key".to_string(), api_key.clone());
-                }
+            if let Some(api_key) = resolve_api_key(config.providers.openai.api_key.as_deref())? {
+                provider_config.insert("api_key".to_string(), api_key);
             }
-            provider_config.insert(
-                "endpoint".to_string(),
-                config.providers.openai.endpoint.clone(),
-            );
+            provider_config.extend(config.providers.openai.provider_settings());
         }
         "ollama" => {
             provider_config.insert(
@@ -503,10 +704,8 @@ fn create_backend(
             );
         }
         "grok" => {
-            if let Some(api_key) = &config.providers.grok.api_key {
-                if !api_key.is_empty() {
-                    provider_config.insert("api_key".to_string(), api_key.clone());
-                }
+            if let Some(api_key) = resolve_api_key(config.providers.grok.api_key.as_deref())? {
+                provider_config.insert("api_key".to_string(), api_key);
             }
             provider_config.insert(
                 "endpoint".to_string(),
@@ -517,6 +716,10 @@ fn create_backend(
             anyhow::bail!("Unknown provider: {}", provider);
         }
     }
+    provider_config.insert(
+        "model".to_string(),
+        get_model_for_provider(config, provider)?,
+    );
 
     let backend = ProviderFactory::create(provider, provider_config)?;
     Ok(backend)
@@ -542,6 +745,7 @@ fn create_adapter_backend(
     match adapter_name {
         "claude-code" | "claude" => {
             provider_config.insert("command".to_string(), "claude".to_string());
+            provider_config.insert("model".to_string(), "claude".to_string());
         }
         "opencode" => {
             provider_config.insert("command".to_string(), "opencode".to_string());
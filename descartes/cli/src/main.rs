@@ -18,7 +18,10 @@ fn load_config(config_path: Option<&Path>) -> anyhow::Result<DescaratesConfig> {
     Ok(manager.config().clone())
 }
 
-use commands::{attach, doctor, init, kill, logs, loop_cmd, pause, ps, resume, spawn, tasks, workflow};
+use commands::{
+    attach, config, doctor, init, kill, logs, loop_cmd, pause, ps, resume, scud, spawn, tasks,
+    transcripts, version, workflow,
+};
 
 #[derive(Parser)]
 #[command(name = "descartes")]
@@ -164,16 +167,54 @@ enum Commands {
         format: String,
     },
 
+    /// Search saved session transcripts
+    Transcripts {
+        /// Show transcripts containing this text
+        #[arg(long)]
+        grep: Option<String>,
+
+        /// Sessions directory (default: .scud/sessions)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+
+        /// Rebuild the search index from the transcript files
+        #[arg(long)]
+        rebuild: bool,
+
+        /// Limit number of results
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
+    },
+
     /// Launch the GUI
     Gui,
 
     /// Check system health and configuration
     Doctor,
 
+    /// Show version and build information
+    Version {
+        /// Include git commit, build date, features and subsystem versions
+        #[arg(long)]
+        full: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Manage tasks (uses SCG file storage)
     #[command(subcommand)]
     Tasks(tasks::TaskCommands),
 
+    /// Inspect SCUD task files
+    #[command(subcommand)]
+    Scud(scud::ScudCommands),
+
+    /// Inspect and migrate the configuration file
+    #[command(subcommand)]
+    Config(config::ConfigCommands),
+
     /// Run workflow commands (research, plan, implement)
     #[command(subcommand)]
     Workflow(workflow::WorkflowCommands),
@@ -278,6 +319,15 @@ async fn main() -> anyhow::Result<()> {
             .await?;
         }
 
+        Commands::Transcripts {
+            grep,
+            dir,
+            rebuild,
+            limit,
+        } => {
+            transcripts::execute(dir, grep.as_deref(), rebuild, limit).await?;
+        }
+
         Commands::Gui => {
             use std::process::Command;
 
@@ -312,9 +362,24 @@ async fn main() -> anyhow::Result<()> {
             doctor::execute().await?;
         }
 
+        Commands::Version { full, json } => {
+            version::execute(full, json)?;
+        }
+
         Commands::Tasks(cmd) => {
             // Tasks use project-local SCG storage, not config-based path
-            tasks::execute(&cmd, None).await?;
+            let schedule = load_config(args.config.as_deref())
+                .map(|config| config.scud.schedule)
+                .unwrap_or_default();
+            tasks::execute(&cmd, None, schedule).await?;
+        }
+
+        Commands::Scud(cmd) => {
+            scud::execute(&cmd, None).await?;
+        }
+
+        Commands::Config(cmd) => {
+            config::execute(&cmd, args.config.as_deref()).await?;
         }
 
         Commands::Workflow(cmd) => {
@@ -347,3 +347,47 @@ async fn test_logs_search_content() {
         "Should find two events with 'message' in content"
     );
 }
+
+/// Wait for the next followed log line and return its event type
+async fn next_event_type(lines: &mut descartes_core::OutputSubscription) -> String {
+    let line = tokio::time::timeout(tokio::time::Duration::from_secs(5), lines.recv())
+        .await
+        .expect("Timed out waiting for log line")
+        .expect("Log closed");
+    let event: serde_json::Value = serde_json::from_str(&line.text()).unwrap();
+    event["event_type"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_logs_follow_replays_recent_then_streams_new_events() {
+    use descartes_cli::commands::logs::subscribe_logs;
+
+    let temp_dir = create_temp_dir();
+    let pool = setup_test_db(&temp_dir).await;
+
+    insert_test_event(&pool, "first", "session", "System", "sys", "First event").await;
+    insert_test_event(&pool, "second", "session", "System", "sys", "Second event").await;
+    insert_test_event(&pool, "third", "session", "System", "sys", "Third event").await;
+
+    let (mut lines, poller) = subscribe_logs(
+        pool.clone(),
+        "WHERE session_id = ?".to_string(),
+        vec!["session".to_string()],
+        "json",
+        2,
+    )
+    .await
+    .expect("Failed to follow logs");
+
+    // Only the newest two events are still buffered
+    assert_eq!(lines.start_offset(), 1);
+    assert_eq!(next_event_type(&mut lines).await, "second");
+    assert_eq!(next_event_type(&mut lines).await, "third");
+
+    // Written in the same second as the backlog, still picked up
+    insert_test_event(&pool, "fourth", "session", "System", "sys", "Fourth event").await;
+    insert_test_event(&pool, "other", "elsewhere", "System", "sys", "Other event").await;
+    assert_eq!(next_event_type(&mut lines).await, "fourth");
+
+    poller.abort();
+}
@@ -0,0 +1,59 @@
+/// Tests for the scud command
+use descartes_cli::commands::scud::{render_report, validate_file};
+use descartes_core::validate_scg;
+use std::path::Path;
+
+const TASKS: &str = "\
+# SCUD Graph v1
+# Phase: api
+
+@nodes
+1 | Design schema | D | 3 | H
+2 | Write handlers | P | 5 | M
+2 | Write handlers again | P | 5 | M
+3 | Tests | P | two | L
+
+@edges
+2 -> 1
+1 -> 2
+3 -> 7
+";
+
+#[test]
+fn test_validate_reports_each_problem_with_location() {
+    colored::control::set_override(false);
+
+    let report = validate_scg(TASKS);
+    let rendered = render_report(Path::new("tasks.scg"), &report);
+    let lines: Vec<&str> = rendered.lines().collect();
+
+    assert_eq!(
+        lines,
+        vec![
+            "tasks.scg:7: duplicate id: task 2 already declared on line 6 [api]",
+            "tasks.scg:8: parse error: task 3 has non-numeric complexity 'two' [api]",
+            "tasks.scg:12: cycle: circular dependency: 1 -> 2 -> 1 [api]",
+            "tasks.scg:13: dangling dependency: edge 3 -> 7 references unknown task 7 [api]",
+            "✗ 1 phase(s), 3 task(s), 4 problem(s)",
+        ]
+    );
+}
+
+#[test]
+fn test_validate_file_fails_on_problems() {
+    colored::control::set_override(false);
+    let dir = tempfile::tempdir().unwrap();
+
+    let bad = dir.path().join("bad.scg");
+    std::fs::write(&bad, TASKS).unwrap();
+    let err = validate_file(&bad).unwrap_err();
+    assert!(err.to_string().starts_with("4 problem(s) found"));
+
+    let good = dir.path().join("good.scg");
+    std::fs::write(
+        &good,
+        "# SCUD Graph v1\n# Phase: api\n\n@nodes\n1 | Only | P | 1 | M\n",
+    )
+    .unwrap();
+    assert!(validate_file(&good).is_ok());
+}
@@ -0,0 +1,78 @@
+/// Tests for the version command
+use descartes_cli::commands::version::VersionInfo;
+use descartes_core::ProviderFactory;
+use std::collections::HashMap;
+
+#[test]
+fn test_short_version_is_crate_version() {
+    let info = VersionInfo::collect();
+    assert_eq!(
+        info.render(false),
+        format!("descartes {}", env!("CARGO_PKG_VERSION"))
+    );
+}
+
+#[test]
+fn test_full_version_includes_build_info() {
+    let info = VersionInfo::collect();
+    let output = info.render(true);
+
+    assert!(output.contains(env!("CARGO_PKG_VERSION")));
+    assert!(output.contains("Git commit:"));
+    assert!(output.contains("Build date:"));
+    assert!(output.contains("Features:"));
+    assert!(output.contains("descartes-core:"));
+    assert!(output.contains("descartes-daemon:"));
+    assert!(output.contains("anthropic"));
+    assert!(output.contains("opencode"));
+}
+
+#[test]
+fn test_full_version_lists_factory_providers() {
+    let info = VersionInfo::collect();
+    assert_eq!(info.provider_backends, ProviderFactory::PROVIDERS);
+    assert!(info.render(true).contains(&format!(
+        "Providers:         {}",
+        ProviderFactory::PROVIDERS.join(", ")
+    )));
+
+    // Every advertised name must be one the factory actually recognises
+    for name in ProviderFactory::PROVIDERS {
+        if let Err(e) = ProviderFactory::create(name, HashMap::new()) {
+            assert!(!e.to_string().contains("Unknown provider"), "{}", name);
+        }
+    }
+}
+
+#[test]
+fn test_full_version_lists_compiled_features() {
+    let mut info = VersionInfo::collect();
+    assert_eq!(info.features.join(","), env!("DESCARTES_FEATURES"));
+    // Default features enable every loop backend
+    assert_eq!(info.loop_backends, ["claude", "generic", "opencode"]);
+    for backend in &info.loop_backends {
+        assert!(info.features.contains(&format!("loop-{}", backend)));
+    }
+
+    info.features = vec!["loop-claude".to_string()];
+    info.loop_backends = vec!["claude".to_string()];
+    let output = info.render(true);
+    assert!(output.contains("Features:          loop-claude"));
+    assert!(output.contains("Loop backends:     claude"));
+
+    info.features.clear();
+    info.loop_backends.clear();
+    let output = info.render(true);
+    assert!(output.contains("Features:          none"));
+    assert!(output.contains("Loop backends:     none"));
+}
+
+#[test]
+fn test_version_info_serializes() {
+    let info = VersionInfo::collect();
+    let json = serde_json::to_value(&info).unwrap();
+
+    assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+    assert!(json["features"].is_array());
+    assert!(json["provider_backends"].is_array());
+}
@@ -163,6 +163,7 @@ const DEFAULT_AGENTS: &[(&str, &str)] = &[
 /// Loader for agent definitions from the filesystem.
 ///
 /// By default, agents are loaded from `~/.descartes/agents/`.
+#[derive(Clone)]
 pub struct AgentDefinitionLoader {
     /// Directory containing agent definition files
     agents_dir: PathBuf,
@@ -22,7 +22,7 @@ use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{Child, ChildStderr, ChildStdin, ChildStdout, Command};
-use tokio::sync::{broadcast, mpsc, Mutex};
+use tokio::sync::{broadcast, mpsc, watch, Mutex};
 use uuid::Uuid;
 
 /// Maximum buffer size for stdout/stderr streams (16KB)
@@ -293,7 +293,9 @@ impl LocalProcessRunner {
             loop {
                 let child_handle = {
                     let handle_read = handle.read();
-                    if handle_read.status.is_terminal() || handle_read.exit_status.is_some() {
+                    // SIGTERM marks the agent terminated before the process
+                    // has exited, so keep polling until the exit is recorded
+                    if handle_read.exit_status.is_some() {
                         break;
                     }
                     Arc::clone(&handle_read.child)
@@ -427,7 +429,9 @@ impl AgentRunner for LocalProcessRunner {
     }
 
     async fn kill(&self, agent_id: &Uuid) -> AgentResult<()> {
-        if let Some(handle) = self.agents.get(agent_id) {
+        // Clone the handle out so the map entry is not borrowed during `remove` below
+        let handle = self.agents.get(agent_id).map(|h| Arc::clone(&h));
+        if let Some(handle) = handle {
             let child = {
                 let handle_guard = handle.read();
                 Arc::clone(&handle_guard.child)
@@ -441,7 +445,7 @@ impl AgentRunner for LocalProcessRunner {
             {
                 let mut handle_guard = handle.write();
                 handle_guard.set_status(AgentStatus::Terminated);
-                handle_guard.exit_status = Some(ExitStatus {
+                handle_guard.set_exit_status(ExitStatus {
                     code: None,
                     success: false,
                 });
@@ -559,7 +563,7 @@ impl AgentRunner for LocalProcessRunner {
                     AgentSignal::Terminate => handle_guard.set_status(AgentStatus::Terminated),
                     AgentSignal::Kill => {
                         handle_guard.set_status(AgentStatus::Terminated);
-                        handle_guard.exit_status = Some(ExitStatus {
+                        handle_guard.set_exit_status(ExitStatus {
                             code: None,
                             success: false,
                         });
@@ -784,6 +788,8 @@ pub struct LocalAgentHandle {
     status: AgentStatus,
     /// Recorded exit status (if the process has completed)
     exit_status: Option<ExitStatus>,
+    /// Publishes the exit status once it is recorded
+    exit_watch: watch::Sender<Option<ExitStatus>>,
     /// Enable JSON streaming mode
     _json_streaming: bool,
     /// Buffered stdout lines
@@ -833,6 +839,7 @@ impl LocalAgentHandle {
             stdin: Arc::new(Mutex::new(stdin)),
             status: AgentStatus::Running,
             exit_status: None,
+            exit_watch: watch::channel(None).0,
             _json_streaming: json_streaming,
             stdout_buffer: Arc::new(Mutex::new(stdout_rx)),
             stderr_buffer: Arc::new(Mutex::new(stderr_rx)),
@@ -920,9 +927,15 @@ impl LocalAgentHandle {
         self.info.paused_at = None;
     }
 
+    /// Set the exit status and notify exit subscribers.
+    fn set_exit_status(&mut self, exit_status: ExitStatus) {
+        self.exit_status = Some(exit_status.clone());
+        self.exit_watch.send_replace(Some(exit_status));
+    }
+
     /// Record the final exit status from the process.
     fn record_exit_status(&mut self, exit_status: ExitStatus) {
-        self.exit_status = Some(exit_status.clone());
+        self.set_exit_status(exit_status.clone());
         if self.status == AgentStatus::Terminated {
             // Preserve explicit termination status
             return;
@@ -1054,7 +1067,7 @@ impl LocalAgentHandle {
             }
             AgentSignal::Kill => {
                 self.set_status(AgentStatus::Terminated);
-                self.exit_status = Some(ExitStatus {
+                self.set_exit_status(ExitStatus {
                     code: None,
                     success: false,
                 });
@@ -1078,7 +1091,7 @@ impl LocalAgentHandle {
             child.kill().await?;
         }
         self.set_status(AgentStatus::Terminated);
-        self.exit_status = Some(ExitStatus {
+        self.set_exit_status(ExitStatus {
             code: None,
             success: false,
         });
@@ -1129,6 +1142,11 @@ impl LocalAgentHandle {
         &self.info
     }
 
+    /// Get the exit status, once the process has exited.
+    pub fn exit_status(&self) -> Option<&ExitStatus> {
+        self.exit_status.as_ref()
+    }
+
     /// Get the stdin sender for writing to the agent.
     /// Returns an mpsc sender that can be used to forward stdin from TUI.
     pub fn get_stdin_sender(&self) -> mpsc::Sender<Vec<u8>> {
@@ -1260,7 +1278,7 @@ impl AgentHandle for AgentHandleWrapper {
         {
             let mut handle = self.handle.write();
             handle.set_status(AgentStatus::Terminated);
-            handle.exit_status = Some(ExitStatus {
+            handle.set_exit_status(ExitStatus {
                 code: None,
                 success: false,
             });
@@ -1286,6 +1304,11 @@ impl AgentHandle for AgentHandleWrapper {
         let handle = self.handle.read();
         handle.stderr_broadcast.subscribe()
     }
+
+    fn subscribe_exit(&self) -> Option<watch::Receiver<Option<ExitStatus>>> {
+        let handle = self.handle.read();
+        Some(handle.exit_watch.subscribe())
+    }
 }
 
 /// Graceful shutdown coordinator for agent processes.
@@ -1385,6 +1408,31 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[tokio::test]
+    async fn test_kill_removes_agent() {
+        let runner = LocalProcessRunner::new();
+        let config = AgentConfig {
+            name: "sleeper".to_string(),
+            // "sleep-cli" runs `sleep <task>`
+            model_backend: "sleep-cli".to_string(),
+            task: "30".to_string(),
+            context: None,
+            system_prompt: None,
+            environment: HashMap::new(),
+            model: None,
+            tool_level: None,
+            agents: None,
+        };
+        let handle = runner.spawn(config).await.unwrap();
+        let agent_id = handle.id();
+
+        tokio::time::timeout(Duration::from_secs(5), runner.kill(&agent_id))
+            .await
+            .expect("kill must not deadlock")
+            .unwrap();
+        assert!(runner.get_agent(&agent_id).await.unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn test_graceful_shutdown_creation() {
         let shutdown = GracefulShutdown::new(10);
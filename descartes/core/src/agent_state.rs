@@ -190,6 +190,73 @@ impl AgentStatus {
     }
 }
 
+/// Table of legal status transitions
+///
+/// The default table mirrors [`AgentStatus::can_transition_to`]; callers that
+/// need stricter or looser rules can start from it and `allow`/`deny` edges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusTransitionTable {
+    allowed: std::collections::HashSet<(AgentStatus, AgentStatus)>,
+}
+
+impl StatusTransitionTable {
+    /// Every status, in lifecycle order
+    pub const STATUSES: [AgentStatus; 8] = [
+        AgentStatus::Idle,
+        AgentStatus::Initializing,
+        AgentStatus::Running,
+        AgentStatus::Thinking,
+        AgentStatus::Paused,
+        AgentStatus::Completed,
+        AgentStatus::Failed,
+        AgentStatus::Terminated,
+    ];
+
+    /// The default table, shared by [`AgentRuntimeState::transition_to`]
+    pub fn standard() -> &'static Self {
+        static STANDARD: std::sync::OnceLock<StatusTransitionTable> = std::sync::OnceLock::new();
+        STANDARD.get_or_init(Self::default)
+    }
+
+    /// A table with no legal transitions
+    pub fn empty() -> Self {
+        Self {
+            allowed: std::collections::HashSet::new(),
+        }
+    }
+
+    /// Permit `from -> to`
+    pub fn allow(mut self, from: AgentStatus, to: AgentStatus) -> Self {
+        self.allowed.insert((from, to));
+        self
+    }
+
+    /// Forbid `from -> to`
+    pub fn deny(mut self, from: AgentStatus, to: AgentStatus) -> Self {
+        self.allowed.remove(&(from, to));
+        self
+    }
+
+    /// Whether `from -> to` is legal
+    pub fn is_allowed(&self, from: AgentStatus, to: AgentStatus) -> bool {
+        self.allowed.contains(&(from, to))
+    }
+}
+
+impl Default for StatusTransitionTable {
+    fn default() -> Self {
+        let mut allowed = std::collections::HashSet::new();
+        for from in Self::STATUSES {
+            for to in Self::STATUSES {
+                if from.can_transition_to(to) {
+                    allowed.insert((from, to));
+                }
+            }
+        }
+        Self { allowed }
+    }
+}
+
 // ============================================================================
 // AGENT RUNTIME STATE MODEL
 // ============================================================================
@@ -284,19 +351,40 @@ impl AgentRuntimeState {
         }
     }
 
-    /// Transition to a new status
+    /// Transition to a new status using the standard transition table
+    ///
+    /// On success, returns the lifecycle event the transition corresponds to, if any.
     pub fn transition_to(
         &mut self,
         new_status: AgentStatus,
         reason: Option<String>,
-    ) -> Result<(), String> {
-        if !self.status.can_transition_to(new_status) {
+    ) -> Result<Option<LifecycleEvent>, String> {
+        self.transition_with(StatusTransitionTable::standard(), new_status, reason)
+    }
+
+    /// Transition to a new status if `table` allows it
+    ///
+    /// Illegal transitions leave the state untouched. On success, returns the
+    /// lifecycle event the transition corresponds to, if any.
+    pub fn transition_with(
+        &mut self,
+        table: &StatusTransitionTable,
+        new_status: AgentStatus,
+        reason: Option<String>,
+    ) -> Result<Option<LifecycleEvent>, String> {
+        if !table.is_allowed(self.status, new_status) {
             return Err(format!(
                 "Invalid transition from {} to {}",
                 self.status, new_status
             ));
         }
 
+        let event = LifecycleEvent::for_transition(self.status, new_status);
+        self.apply_transition(new_status, reason);
+        Ok(event)
+    }
+
+    fn apply_transition(&mut self, new_status: AgentStatus, reason: Option<String>) {
         let old_status = self.status;
         self.status = new_status;
         self.updated_at = Utc::now();
@@ -323,8 +411,6 @@ impl AgentRuntimeState {
             timestamp: Utc::now(),
             reason,
         });
-
-        Ok(())
     }
 
     /// Update the current thought (for Thinking state)
@@ -559,6 +645,18 @@ pub enum AgentStreamMessage {
         agent_id: Uuid,
         timestamp: DateTime<Utc>,
     },
+
+    /// Fragment of a streamed tool call; `arguments_delta` fragments with the
+    /// same `call_id` are concatenated until they form a complete JSON object
+    ToolCallDelta {
+        agent_id: Uuid,
+        call_id: String,
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(default)]
+        arguments_delta: String,
+        timestamp: DateTime<Utc>,
+    },
 }
 
 /// Output stream type
@@ -582,6 +680,217 @@ pub enum LifecycleEvent {
     Terminated,
 }
 
+impl LifecycleEvent {
+    /// Lifecycle event signalled by a status transition, if any
+    pub fn for_transition(from: AgentStatus, to: AgentStatus) -> Option<Self> {
+        if from == to {
+            return None;
+        }
+        match (from, to) {
+            (AgentStatus::Idle, AgentStatus::Initializing) => Some(LifecycleEvent::Started),
+            (_, AgentStatus::Paused) => Some(LifecycleEvent::Paused),
+            (AgentStatus::Paused, AgentStatus::Running | AgentStatus::Thinking) => {
+                Some(LifecycleEvent::Resumed)
+            }
+            (_, AgentStatus::Completed) => Some(LifecycleEvent::Completed),
+            (_, AgentStatus::Failed) => Some(LifecycleEvent::Failed),
+            (_, AgentStatus::Terminated) => Some(LifecycleEvent::Terminated),
+            _ => None,
+        }
+    }
+}
+
+// ============================================================================
+// BUFFERED OUTPUT LOG
+// ============================================================================
+
+/// Default number of chunks an [`OutputLog`] retains
+pub const DEFAULT_OUTPUT_LOG_CAPACITY: usize = 10_000;
+
+/// A chunk of agent output together with its position in the log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputChunk {
+    /// Line offset of this chunk since the log was created
+    pub offset: u64,
+    pub stream: OutputStream,
+    /// Raw output bytes; terminal output is not guaranteed to be valid UTF-8
+    pub content: Vec<u8>,
+}
+
+impl OutputChunk {
+    /// Content as text, replacing invalid UTF-8
+    pub fn text(&self) -> std::borrow::Cow<'_, str> {
+        String::from_utf8_lossy(&self.content)
+    }
+}
+
+#[derive(Debug)]
+struct OutputLogBuffer {
+    chunks: std::collections::VecDeque<OutputChunk>,
+    next_offset: u64,
+    capacity: usize,
+}
+
+impl OutputLogBuffer {
+    fn earliest_offset(&self) -> u64 {
+        self.chunks
+            .front()
+            .map(|c| c.offset)
+            .unwrap_or(self.next_offset)
+    }
+
+    /// Chunks at or after `offset`, clamped to what is still buffered
+    fn chunks_from(&self, offset: u64) -> (u64, Vec<OutputChunk>) {
+        let start = offset.clamp(self.earliest_offset(), self.next_offset);
+        let skip = (start - self.earliest_offset()) as usize;
+        (start, self.chunks.iter().skip(skip).cloned().collect())
+    }
+}
+
+/// Bounded, line-addressed output history with live fan-out
+///
+/// Backs `tail -f` style views: [`OutputLog::subscribe_from`] replays buffered
+/// output from an offset and then continues with new output, so following
+/// logs and attaching to historical output share one code path.
+#[derive(Debug, Clone)]
+pub struct OutputLog {
+    buffer: std::sync::Arc<parking_lot::Mutex<OutputLogBuffer>>,
+    sender: tokio::sync::broadcast::Sender<OutputChunk>,
+}
+
+impl OutputLog {
+    /// Create a log retaining at most `capacity` chunks
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = tokio::sync::broadcast::channel(capacity.clamp(1, 1024));
+        Self {
+            buffer: std::sync::Arc::new(parking_lot::Mutex::new(OutputLogBuffer {
+                chunks: std::collections::VecDeque::new(),
+                next_offset: 0,
+                capacity: capacity.max(1),
+            })),
+            sender,
+        }
+    }
+
+    /// Append output, returning the offset it was assigned
+    pub fn append(&self, stream: OutputStream, content: impl Into<Vec<u8>>) -> u64 {
+        let mut buffer = self.buffer.lock();
+        let chunk = OutputChunk {
+            offset: buffer.next_offset,
+            stream,
+            content: content.into(),
+        };
+        buffer.next_offset += 1;
+        buffer.chunks.push_back(chunk.clone());
+        while buffer.chunks.len() > buffer.capacity {
+            buffer.chunks.pop_front();
+        }
+        // Sent under the lock so subscribers never observe a chunk twice or miss one
+        let _ = self.sender.send(chunk.clone());
+        chunk.offset
+    }
+
+    /// Oldest offset still buffered
+    pub fn earliest_offset(&self) -> u64 {
+        self.buffer.lock().earliest_offset()
+    }
+
+    /// Offset the next appended chunk will receive
+    pub fn next_offset(&self) -> u64 {
+        self.buffer.lock().next_offset
+    }
+
+    /// Buffered chunks at or after `offset`
+    pub fn snapshot_from(&self, offset: u64) -> Vec<OutputChunk> {
+        self.buffer.lock().chunks_from(offset).1
+    }
+
+    /// Replay buffered output from `offset`, then stream new output live
+    ///
+    /// If `offset` has already been trimmed the subscription starts at the
+    /// earliest available offset, reported by [`OutputSubscription::start_offset`].
+    pub fn subscribe_from(&self, offset: u64) -> OutputSubscription {
+        let buffer = self.buffer.lock();
+        let (start, backlog) = buffer.chunks_from(offset);
+        let receiver = self.sender.subscribe();
+        drop(buffer);
+
+        OutputSubscription {
+            start_offset: start,
+            next_offset: start,
+            backlog: backlog.into(),
+            receiver,
+            buffer: self.buffer.clone(),
+        }
+    }
+}
+
+impl Default for OutputLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_OUTPUT_LOG_CAPACITY)
+    }
+}
+
+/// Live view over an [`OutputLog`] created by [`OutputLog::subscribe_from`]
+#[derive(Debug)]
+pub struct OutputSubscription {
+    start_offset: u64,
+    next_offset: u64,
+    backlog: std::collections::VecDeque<OutputChunk>,
+    receiver: tokio::sync::broadcast::Receiver<OutputChunk>,
+    buffer: std::sync::Arc<parking_lot::Mutex<OutputLogBuffer>>,
+}
+
+impl OutputSubscription {
+    /// Offset the subscription actually started from
+    pub fn start_offset(&self) -> u64 {
+        self.start_offset
+    }
+
+    /// Offset of the next chunk this subscription will yield
+    pub fn next_offset(&self) -> u64 {
+        self.next_offset
+    }
+
+    /// Take the replayed chunks that were buffered when the subscription was
+    /// created; later chunks still arrive through [`OutputSubscription::recv`]
+    pub fn take_backlog(&mut self) -> Vec<OutputChunk> {
+        let backlog: Vec<OutputChunk> = self.backlog.drain(..).collect();
+        if let Some(last) = backlog.last() {
+            self.next_offset = last.offset + 1;
+        }
+        backlog
+    }
+
+    /// Wait for the next chunk; `None` once the log has been dropped
+    pub async fn recv(&mut self) -> Option<OutputChunk> {
+        use tokio::sync::broadcast::error::RecvError;
+
+        loop {
+            if let Some(chunk) = self.backlog.pop_front() {
+                self.next_offset = chunk.offset + 1;
+                return Some(chunk);
+            }
+
+            match self.receiver.recv().await {
+                // Already replayed from the backlog
+                Ok(chunk) if chunk.offset < self.next_offset => continue,
+                Ok(chunk) => {
+                    self.next_offset = chunk.offset + 1;
+                    return Some(chunk);
+                }
+                Err(RecvError::Lagged(_)) => {
+                    // Fell behind the channel; catch up from the buffer instead
+                    let (start, backlog) = self.buffer.lock().chunks_from(self.next_offset);
+                    self.next_offset = start;
+                    self.backlog = backlog.into();
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
 // ============================================================================
 // AGENT RUNTIME STATE COLLECTION
 // ============================================================================
@@ -621,6 +930,22 @@ pub struct AgentStatistics {
     /// Average execution time (in seconds)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub avg_execution_time: Option<f64>,
+
+    /// Count by model backend
+    #[serde(default)]
+    pub backend_counts: HashMap<String, usize>,
+
+    /// Tool calls made across all agents
+    #[serde(default)]
+    pub total_tool_calls: u64,
+
+    /// Tokens used across all agents
+    #[serde(default)]
+    pub total_tokens: u64,
+
+    /// Completed agents as a fraction of finished agents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success_rate: Option<f64>,
 }
 
 impl AgentStateCollection {
@@ -640,13 +965,26 @@ impl AgentStateCollection {
     /// Compute statistics for a collection of agents
     fn compute_statistics(agents: &[AgentRuntimeState]) -> AgentStatistics {
         let mut status_counts: HashMap<AgentStatus, usize> = HashMap::new();
+        let mut backend_counts: HashMap<String, usize> = HashMap::new();
         let mut total_active = 0;
         let mut total_completed = 0;
         let mut total_failed = 0;
+        let mut total_finished = 0;
+        let mut total_tool_calls = 0;
+        let mut total_tokens = 0;
         let mut execution_times = Vec::new();
 
         for agent in agents {
             *status_counts.entry(agent.status).or_insert(0) += 1;
+            *backend_counts
+                .entry(agent.model_backend.clone())
+                .or_insert(0) += 1;
+            total_tool_calls += agent_counter(agent, METADATA_TOOL_CALLS);
+            total_tokens += agent_counter(agent, METADATA_TOKENS);
+
+            if agent.status.is_terminal() {
+                total_finished += 1;
+            }
 
             if agent.is_active() {
                 total_active += 1;
@@ -669,14 +1007,103 @@ impl AgentStateCollection {
             None
         };
 
+        let success_rate = if total_finished > 0 {
+            Some(total_completed as f64 / total_finished as f64)
+        } else {
+            None
+        };
+
         AgentStatistics {
             status_counts,
             total_active,
             total_completed,
             total_failed,
             avg_execution_time,
+            backend_counts,
+            total_tool_calls,
+            total_tokens,
+            success_rate,
         }
     }
+
+    /// Recompute statistics across every agent currently in the collection
+    pub fn aggregate_statistics(&self) -> AgentStatistics {
+        Self::compute_statistics(&self.agents)
+    }
+}
+
+/// Metadata key holding an agent's tool call count
+///
+/// Counted by the stream parser as each streamed tool call completes.
+pub const METADATA_TOOL_CALLS: &str = "tool_calls";
+
+/// Metadata key holding an agent's token usage
+///
+/// Agents report their running total as a `tokens` progress detail, which
+/// the stream parser copies here.
+pub const METADATA_TOKENS: &str = "tokens";
+
+fn agent_counter(agent: &AgentRuntimeState, key: &str) -> u64 {
+    agent
+        .metadata
+        .get(key)
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0)
+}
+
+impl AgentStatistics {
+    /// Export as pretty-printed JSON
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Export as `metric,value` CSV rows
+    ///
+    /// Per-status and per-backend counts use `status:<name>` and
+    /// `backend:<name>` metric names.
+    pub fn to_csv(&self) -> String {
+        let mut rows = vec![
+            "metric,value".to_string(),
+            format!("total_active,{}", self.total_active),
+            format!("total_completed,{}", self.total_completed),
+            format!("total_failed,{}", self.total_failed),
+            format!("total_tool_calls,{}", self.total_tool_calls),
+            format!("total_tokens,{}", self.total_tokens),
+            format!(
+                "avg_execution_time,{}",
+                self.avg_execution_time
+                    .map(|v| v.to_string())
+                    .unwrap_or_default()
+            ),
+            format!(
+                "success_rate,{}",
+                self.success_rate.map(|v| v.to_string()).unwrap_or_default()
+            ),
+        ];
+        let mut statuses: Vec<_> = self
+            .status_counts
+            .iter()
+            .map(|(status, count)| (status.to_string(), count))
+            .collect();
+        statuses.sort();
+        for (status, count) in statuses {
+            rows.push(format!("status:{},{}", csv_field(&status), count));
+        }
+        let mut backends: Vec<_> = self.backend_counts.iter().collect();
+        backends.sort();
+        for (backend, count) in backends {
+            rows.push(format!("backend:{},{}", csv_field(backend), count));
+        }
+        rows.join("\n") + "\n"
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
 }
 
 // ============================================================================
@@ -704,6 +1131,152 @@ mod tests {
         assert!(!AgentStatus::Idle.can_transition_to(AgentStatus::Running));
     }
 
+    #[test]
+    fn test_transition_table_rejects_illegal_transitions() {
+        let table = StatusTransitionTable::default();
+        let mut agent = AgentRuntimeState::new(
+            Uuid::new_v4(),
+            "agent".to_string(),
+            "task".to_string(),
+            "anthropic".to_string(),
+        );
+
+        let event = agent
+            .transition_with(&table, AgentStatus::Initializing, None)
+            .unwrap();
+        assert!(matches!(event, Some(LifecycleEvent::Started)));
+        agent
+            .transition_with(&table, AgentStatus::Running, None)
+            .unwrap();
+        let event = agent
+            .transition_with(&table, AgentStatus::Completed, None)
+            .unwrap();
+        assert!(matches!(event, Some(LifecycleEvent::Completed)));
+
+        let timeline_len = agent.timeline.len();
+        assert!(agent
+            .transition_with(&table, AgentStatus::Running, None)
+            .is_err());
+        assert_eq!(agent.status, AgentStatus::Completed);
+        assert_eq!(agent.timeline.len(), timeline_len);
+
+        // Tables can be tightened per caller
+        let strict = table.deny(AgentStatus::Idle, AgentStatus::Terminated);
+        let mut idle = AgentRuntimeState::new(
+            Uuid::new_v4(),
+            "agent".to_string(),
+            "task".to_string(),
+            "anthropic".to_string(),
+        );
+        assert!(idle
+            .transition_with(&strict, AgentStatus::Terminated, None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_aggregate_statistics() {
+        let make = |backend: &str, status: AgentStatus, tool_calls: u64, tokens: u64| {
+            let mut agent = AgentRuntimeState::new(
+                Uuid::new_v4(),
+                "agent".to_string(),
+                "task".to_string(),
+                backend.to_string(),
+            );
+            agent.status = status;
+            if status.is_terminal() {
+                agent.started_at = Some(agent.created_at);
+                agent.completed_at = Some(agent.created_at + chrono::Duration::seconds(10));
+            }
+            agent.metadata.insert(
+                METADATA_TOOL_CALLS.to_string(),
+                serde_json::json!(tool_calls),
+            );
+            agent
+                .metadata
+                .insert(METADATA_TOKENS.to_string(), serde_json::json!(tokens));
+            agent
+        };
+
+        let mut slow = make("openai", AgentStatus::Failed, 1, 50);
+        slow.completed_at = Some(slow.created_at + chrono::Duration::seconds(40));
+
+        let collection = AgentStateCollection::new(vec![
+            make("anthropic", AgentStatus::Completed, 3, 1000),
+            make("anthropic", AgentStatus::Completed, 2, 500),
+            slow,
+            make("openai", AgentStatus::Running, 4, 200),
+        ]);
+        let stats = collection.aggregate_statistics();
+
+        assert_eq!(collection.total, 4);
+        assert_eq!(stats.total_tool_calls, 10);
+        assert_eq!(stats.total_tokens, 1750);
+        assert_eq!(stats.avg_execution_time, Some(20.0));
+        assert_eq!(stats.success_rate, Some(2.0 / 3.0));
+        assert_eq!(stats.status_counts[&AgentStatus::Completed], 2);
+        assert_eq!(stats.backend_counts["openai"], 2);
+
+        let csv = stats.to_csv();
+        assert!(csv.starts_with("metric,value\n"));
+        assert!(csv.contains("total_tokens,1750\n"));
+        assert!(csv.contains("backend:anthropic,2\n"));
+        assert!(csv.contains("status:completed,2\n"));
+
+        let json: serde_json::Value = serde_json::from_str(&stats.to_json().unwrap()).unwrap();
+        assert_eq!(json["total_tool_calls"], 10);
+    }
+
+    #[tokio::test]
+    async fn test_output_log_subscribe_from_replays_then_streams() {
+        let log = OutputLog::new(3);
+        for i in 0..5 {
+            log.append(OutputStream::Stdout, format!("line {}", i));
+        }
+
+        // Offsets 0 and 1 were trimmed, so the subscription starts at 2
+        let mut sub = log.subscribe_from(0);
+        assert_eq!(sub.start_offset(), 2);
+
+        let producer = log.clone();
+        let appender = tokio::spawn(async move {
+            for i in 5..8 {
+                producer.append(OutputStream::Stderr, format!("line {}", i));
+                tokio::task::yield_now().await;
+            }
+        });
+
+        let mut seen = Vec::new();
+        while seen.len() < 6 {
+            seen.push(sub.recv().await.unwrap());
+        }
+        appender.await.unwrap();
+
+        let offsets: Vec<u64> = seen.iter().map(|c| c.offset).collect();
+        assert_eq!(offsets, vec![2, 3, 4, 5, 6, 7]);
+        assert_eq!(seen[0].text(), "line 2");
+        assert_eq!(seen[5].stream, OutputStream::Stderr);
+        assert_eq!(sub.next_offset(), log.next_offset());
+    }
+
+    #[tokio::test]
+    async fn test_output_subscription_take_backlog() {
+        let log = OutputLog::new(10);
+        log.append(OutputStream::Stdout, "one");
+        log.append(OutputStream::Stderr, vec![0xff, b'!']);
+
+        let mut sub = log.subscribe_from(0);
+        let backlog = sub.take_backlog();
+        assert_eq!(backlog.len(), 2);
+        assert_eq!(backlog[1].text(), "\u{FFFD}!");
+        assert_eq!(sub.next_offset(), 2);
+
+        // Live output continues after the backlog without repeating it
+        log.append(OutputStream::Stdout, "three");
+        let chunk = sub.recv().await.unwrap();
+        assert_eq!(chunk.offset, 2);
+        assert_eq!(chunk.content, b"three");
+    }
+
     #[test]
     fn test_agent_status_terminal() {
         assert!(AgentStatus::Completed.is_terminal());
@@ -755,6 +1328,16 @@ mod tests {
 
         // Invalid transition
         assert!(agent.transition_to(AgentStatus::Completed, None).is_err());
+
+        // Transitions report the lifecycle event they signal
+        assert!(matches!(
+            agent.transition_to(AgentStatus::Running, None),
+            Ok(None)
+        ));
+        assert!(matches!(
+            agent.transition_to(AgentStatus::Paused, None),
+            Ok(Some(LifecycleEvent::Paused))
+        ));
     }
 
     #[test]
@@ -31,8 +31,9 @@
 
 use crate::agent_state::{
     AgentError, AgentProgress, AgentRuntimeState, AgentStatus, AgentStreamMessage, LifecycleEvent,
-    OutputStream,
+    OutputStream, METADATA_TOKENS, METADATA_TOOL_CALLS,
 };
+use crate::traits::ToolCall;
 use chrono::Utc;
 use serde_json;
 use std::collections::HashMap;
@@ -68,6 +69,9 @@ pub enum StreamParseError {
 
     #[error("Stream closed unexpectedly")]
     StreamClosed,
+
+    #[error("Incomplete tool call {call_id}: {reason}")]
+    IncompleteToolCall { call_id: String, reason: String },
 }
 
 pub type StreamResult<T> = Result<T, StreamParseError>;
@@ -127,6 +131,15 @@ pub trait StreamHandler: Send + Sync {
 
     /// Called when a heartbeat is received
     fn on_heartbeat(&mut self, agent_id: Uuid, timestamp: chrono::DateTime<Utc>);
+
+    /// Called once a streamed tool call's arguments form a complete JSON object
+    fn on_tool_call(
+        &mut self,
+        _agent_id: Uuid,
+        _tool_call: ToolCall,
+        _timestamp: chrono::DateTime<Utc>,
+    ) {
+    }
 }
 
 // ============================================================================
@@ -149,6 +162,14 @@ pub struct ParserConfig {
 
     /// Buffer capacity for async reading
     pub buffer_capacity: usize,
+
+    /// Maximum bytes of arguments buffered for a single streamed tool call
+    #[serde(default = "default_max_tool_call_bytes")]
+    pub max_tool_call_bytes: usize,
+}
+
+fn default_max_tool_call_bytes() -> usize {
+    1024 * 1024 // 1 MB
 }
 
 impl Default for ParserConfig {
@@ -158,10 +179,18 @@ impl Default for ParserConfig {
             skip_invalid_json: true,
             auto_create_agents: true,
             buffer_capacity: 8192, // 8 KB
+            max_tool_call_bytes: default_max_tool_call_bytes(),
         }
     }
 }
 
+/// A tool call whose arguments are still arriving
+#[derive(Debug, Default)]
+struct PendingToolCall {
+    name: Option<String>,
+    arguments: String,
+}
+
 // ============================================================================
 // AGENT STREAM PARSER
 // ============================================================================
@@ -180,6 +209,9 @@ pub struct AgentStreamParser {
     /// Registered event handlers
     handlers: Vec<Box<dyn StreamHandler>>,
 
+    /// Streamed tool calls awaiting complete arguments, keyed by (agent, call ID)
+    pending_tool_calls: HashMap<(Uuid, String), PendingToolCall>,
+
     /// Statistics
     messages_processed: u64,
     errors_encountered: u64,
@@ -197,6 +229,7 @@ impl AgentStreamParser {
             config,
             agents: HashMap::new(),
             handlers: Vec::new(),
+            pending_tool_calls: HashMap::new(),
             messages_processed: 0,
             errors_encountered: 0,
         }
@@ -285,7 +318,32 @@ impl AgentStreamParser {
             }
         }
 
-        Ok(())
+        self.finish()
+    }
+
+    /// Signal the end of the stream.
+    ///
+    /// Fails if any streamed tool call never received complete arguments;
+    /// such calls are discarded rather than dispatched half-formed.
+    /// `process_stream` calls this automatically.
+    pub fn finish(&mut self) -> StreamResult<()> {
+        let mut incomplete: Vec<_> = self.pending_tool_calls.drain().collect();
+        if incomplete.is_empty() {
+            return Ok(());
+        }
+
+        self.errors_encountered += incomplete.len() as u64;
+        incomplete.sort_by(|a, b| a.0 .1.cmp(&b.0 .1));
+        let ((_, call_id), pending) = incomplete.remove(0);
+        let reason = if pending.name.is_none() {
+            "stream ended before the tool name arrived".to_string()
+        } else {
+            format!(
+                "stream ended with incomplete arguments ({} bytes buffered)",
+                pending.arguments.len()
+            )
+        };
+        Err(StreamParseError::IncompleteToolCall { call_id, reason })
     }
 
     /// Process a synchronous iterator of JSON lines
@@ -396,6 +454,16 @@ impl AgentStreamParser {
             } => {
                 self.handle_heartbeat(agent_id, timestamp)?;
             }
+
+            AgentStreamMessage::ToolCallDelta {
+                agent_id,
+                call_id,
+                name,
+                arguments_delta,
+                timestamp,
+            } => {
+                self.handle_tool_call_delta(agent_id, call_id, name, arguments_delta, timestamp)?;
+            }
         }
 
         Ok(())
@@ -443,8 +511,9 @@ impl AgentStreamParser {
         timestamp: chrono::DateTime<Utc>,
     ) -> StreamResult<()> {
         // Update agent state
+        let mut lifecycle = None;
         if let Some(agent) = self.agents.get_mut(&agent_id) {
-            agent
+            lifecycle = agent
                 .transition_to(status, Some("Status update from stream".to_string()))
                 .map_err(StreamParseError::StateTransitionError)?;
 
@@ -471,6 +540,13 @@ impl AgentStreamParser {
             handler.on_status_update(agent_id, status, timestamp);
         }
 
+        // A status change such as Running -> Paused is also a lifecycle event
+        if let Some(event) = lifecycle {
+            for handler in &mut self.handlers {
+                handler.on_lifecycle(agent_id, event.clone(), timestamp);
+            }
+        }
+
         Ok(())
     }
 
@@ -522,7 +598,7 @@ impl AgentStreamParser {
     ) -> StreamResult<()> {
         // Update agent state
         if let Some(agent) = self.agents.get_mut(&agent_id) {
-            agent.update_progress(progress.clone());
+            Self::record_progress(agent, &progress);
         } else if self.config.auto_create_agents {
             let mut agent = AgentRuntimeState::new(
                 agent_id,
@@ -530,7 +606,7 @@ impl AgentStreamParser {
                 "Auto-created from stream".to_string(),
                 "unknown".to_string(),
             );
-            agent.update_progress(progress.clone());
+            Self::record_progress(&mut agent, &progress);
             self.agents.insert(agent_id, agent);
         } else {
             return Err(StreamParseError::UnknownAgent(agent_id));
@@ -544,6 +620,18 @@ impl AgentStreamParser {
         Ok(())
     }
 
+    /// Store progress, keeping the token usage it reports in the metadata
+    fn record_progress(agent: &mut AgentRuntimeState, progress: &AgentProgress) {
+        if let Some(tokens) = progress.details.get(METADATA_TOKENS) {
+            if tokens.is_u64() {
+                agent
+                    .metadata
+                    .insert(METADATA_TOKENS.to_string(), tokens.clone());
+            }
+        }
+        agent.update_progress(progress.clone());
+    }
+
     /// Handle output message
     fn handle_output(
         &mut self,
@@ -659,6 +747,71 @@ impl AgentStreamParser {
 
         Ok(())
     }
+
+    /// Buffer a tool call fragment, dispatching the call once its arguments
+    /// form a complete JSON object
+    fn handle_tool_call_delta(
+        &mut self,
+        agent_id: Uuid,
+        call_id: String,
+        name: Option<String>,
+        arguments_delta: String,
+        timestamp: chrono::DateTime<Utc>,
+    ) -> StreamResult<()> {
+        let key = (agent_id, call_id);
+        let pending = self.pending_tool_calls.entry(key.clone()).or_default();
+
+        if name.is_some() {
+            pending.name = name;
+        }
+
+        if pending.arguments.len() + arguments_delta.len() > self.config.max_tool_call_bytes {
+            self.pending_tool_calls.remove(&key);
+            return Err(StreamParseError::BufferOverflow);
+        }
+        pending.arguments.push_str(&arguments_delta);
+
+        let arguments = match serde_json::from_str::<serde_json::Value>(&pending.arguments) {
+            Ok(value @ serde_json::Value::Object(_)) => value,
+            // Still arriving
+            Err(e) if e.is_eof() => return Ok(()),
+            Ok(_) | Err(_) => {
+                self.pending_tool_calls.remove(&key);
+                return Err(StreamParseError::IncompleteToolCall {
+                    call_id: key.1,
+                    reason: "arguments are not a valid JSON object".to_string(),
+                });
+            }
+        };
+
+        // Wait for the name if it hasn't arrived yet
+        let Some(name) = pending.name.clone() else {
+            return Ok(());
+        };
+
+        self.pending_tool_calls.remove(&key);
+        if let Some(agent) = self.agents.get_mut(&agent_id) {
+            let count = agent
+                .metadata
+                .get(METADATA_TOOL_CALLS)
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0);
+            agent.metadata.insert(
+                METADATA_TOOL_CALLS.to_string(),
+                serde_json::json!(count + 1),
+            );
+        }
+        let tool_call = ToolCall {
+            id: key.1,
+            name,
+            arguments,
+        };
+        for handler in &mut self.handlers {
+            handler.on_tool_call(agent_id, tool_call.clone(), timestamp);
+        }
+
+        Ok(())
+    }
 }
 
 impl Default for AgentStreamParser {
@@ -760,6 +913,8 @@ mod tests {
         status_updates: Vec<(Uuid, AgentStatus)>,
         thought_updates: Vec<(Uuid, String)>,
         progress_updates: Vec<(Uuid, f32)>,
+        tool_calls: std::sync::Arc<parking_lot::Mutex<Vec<ToolCall>>>,
+        lifecycle_events: std::sync::Arc<parking_lot::Mutex<Vec<LifecycleEvent>>>,
     }
 
     impl TestHandler {
@@ -768,6 +923,8 @@ mod tests {
                 status_updates: Vec::new(),
                 thought_updates: Vec::new(),
                 progress_updates: Vec::new(),
+                tool_calls: Default::default(),
+                lifecycle_events: Default::default(),
             }
         }
     }
@@ -820,12 +977,34 @@ mod tests {
         fn on_lifecycle(
             &mut self,
             _agent_id: Uuid,
-            _event: LifecycleEvent,
+            event: LifecycleEvent,
             _timestamp: chrono::DateTime<Utc>,
         ) {
+            self.lifecycle_events.lock().push(event);
         }
 
         fn on_heartbeat(&mut self, _agent_id: Uuid, _timestamp: chrono::DateTime<Utc>) {}
+
+        fn on_tool_call(
+            &mut self,
+            _agent_id: Uuid,
+            tool_call: ToolCall,
+            _timestamp: chrono::DateTime<Utc>,
+        ) {
+            self.tool_calls.lock().push(tool_call);
+        }
+    }
+
+    fn tool_call_delta(agent_id: Uuid, call_id: &str, name: Option<&str>, delta: &str) -> String {
+        serde_json::json!({
+            "type": "tool_call_delta",
+            "agent_id": agent_id,
+            "call_id": call_id,
+            "name": name,
+            "arguments_delta": delta,
+            "timestamp": "2025-11-24T05:53:00Z",
+        })
+        .to_string()
     }
 
     #[test]
@@ -849,6 +1028,31 @@ mod tests {
         assert_eq!(agent.status, AgentStatus::Running);
     }
 
+    #[test]
+    fn test_status_update_emits_lifecycle_event() {
+        let agent_id = Uuid::new_v4();
+        let status = |status: &str| {
+            format!(
+                r#"{{"type":"status_update","agent_id":"{}","status":"{}","timestamp":"2025-11-24T05:53:00Z"}}"#,
+                agent_id, status
+            )
+        };
+
+        let mut parser = AgentStreamParser::new();
+        let handler = TestHandler::new();
+        let lifecycle_events = handler.lifecycle_events.clone();
+        parser.register_handler(handler);
+
+        parser
+            .process_lines([status("running"), status("paused"), status("running")])
+            .unwrap();
+
+        let events = lifecycle_events.lock();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], LifecycleEvent::Paused));
+        assert!(matches!(events[1], LifecycleEvent::Resumed));
+    }
+
     #[test]
     fn test_parse_thought_update() {
         let agent_id = Uuid::new_v4();
@@ -1026,4 +1230,158 @@ mod tests {
         // Heartbeat should update the timestamp to the heartbeat time
         assert_eq!(agent.updated_at, heartbeat_time);
     }
+
+    #[test]
+    fn test_tool_call_accumulated_across_chunks() {
+        let agent_id = Uuid::new_v4();
+        let handler = TestHandler::new();
+        let tool_calls = handler.tool_calls.clone();
+
+        let mut parser = AgentStreamParser::with_config(ParserConfig {
+            skip_invalid_json: false,
+            ..Default::default()
+        });
+        parser.register_handler(handler);
+
+        // Fragments split mid-key and mid-string, interleaved with a second call
+        parser
+            .process_lines([
+                tool_call_delta(agent_id, "call_1", Some("bash"), r#"{"comm"#),
+                tool_call_delta(agent_id, "call_2", Some("read"), r#"{"path": "src/"#),
+                tool_call_delta(agent_id, "call_1", None, r#"and": "echo }"#),
+            ])
+            .unwrap();
+        assert!(tool_calls.lock().is_empty());
+
+        parser
+            .process_lines([
+                tool_call_delta(agent_id, "call_1", None, r#""}"#),
+                tool_call_delta(agent_id, "call_2", None, r#"lib.rs"}"#),
+            ])
+            .unwrap();
+        parser.finish().unwrap();
+
+        let calls = tool_calls.lock();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].id, "call_1");
+        assert_eq!(calls[0].name, "bash");
+        assert_eq!(calls[0].arguments["command"], "echo }");
+        assert_eq!(calls[1].id, "call_2");
+        assert_eq!(calls[1].arguments["path"], "src/lib.rs");
+    }
+
+    #[test]
+    fn test_usage_recorded_in_agent_metadata() {
+        let agent_id = Uuid::new_v4();
+        let progress = format!(
+            r#"{{"type":"progress_update","agent_id":"{}","progress":{{"percentage":10.0,"details":{{"tokens":1200}}}},"timestamp":"2025-11-24T05:53:00Z"}}"#,
+            agent_id
+        );
+
+        let mut parser = AgentStreamParser::new();
+        parser
+            .process_lines([
+                progress,
+                tool_call_delta(agent_id, "call_1", Some("bash"), r#"{"command": "ls"}"#),
+                tool_call_delta(agent_id, "call_2", Some("read"), r#"{"path": "a"}"#),
+            ])
+            .unwrap();
+
+        let agent = parser.get_agent(&agent_id).unwrap();
+        assert_eq!(agent.metadata[METADATA_TOOL_CALLS], 2);
+        assert_eq!(agent.metadata[METADATA_TOKENS], 1200);
+
+        let agents = parser.agents().values().cloned().collect();
+        let collection = crate::agent_state::AgentStateCollection::new(agents);
+        let stats = collection.statistics.unwrap();
+        assert_eq!(stats.total_tool_calls, 2);
+        assert_eq!(stats.total_tokens, 1200);
+    }
+
+    #[test]
+    fn test_incomplete_tool_call_at_stream_end() {
+        let agent_id = Uuid::new_v4();
+        let handler = TestHandler::new();
+        let tool_calls = handler.tool_calls.clone();
+
+        let mut parser = AgentStreamParser::new();
+        parser.register_handler(handler);
+        parser
+            .process_lines([tool_call_delta(
+                agent_id,
+                "call_1",
+                Some("bash"),
+                r#"{"command": "l"#,
+            )])
+            .unwrap();
+
+        let err = parser.finish().unwrap_err();
+        assert!(matches!(
+            err,
+            StreamParseError::IncompleteToolCall { ref call_id, .. } if call_id == "call_1"
+        ));
+        assert!(tool_calls.lock().is_empty());
+
+        // Nothing left pending afterwards
+        assert!(parser.finish().is_ok());
+    }
+
+    #[test]
+    fn test_malformed_tool_call_arguments() {
+        let agent_id = Uuid::new_v4();
+        let mut parser = AgentStreamParser::with_config(ParserConfig {
+            skip_invalid_json: false,
+            ..Default::default()
+        });
+
+        let err = parser
+            .process_lines([tool_call_delta(
+                agent_id,
+                "call_1",
+                Some("bash"),
+                r#"{"command" "ls"}"#,
+            )])
+            .unwrap_err();
+        assert!(matches!(err, StreamParseError::IncompleteToolCall { .. }));
+        assert!(parser.finish().is_ok());
+    }
+
+    #[test]
+    fn test_tool_call_buffer_limit() {
+        let agent_id = Uuid::new_v4();
+        let mut parser = AgentStreamParser::with_config(ParserConfig {
+            skip_invalid_json: false,
+            max_tool_call_bytes: 16,
+            ..Default::default()
+        });
+
+        parser
+            .process_lines([tool_call_delta(
+                agent_id,
+                "call_1",
+                Some("write"),
+                r#"{"content": ""#,
+            )])
+            .unwrap();
+        let err = parser
+            .process_lines([tool_call_delta(agent_id, "call_1", None, "xxxxxxxxxx")])
+            .unwrap_err();
+        assert!(matches!(err, StreamParseError::BufferOverflow));
+    }
+
+    #[tokio::test]
+    async fn test_process_stream_reports_incomplete_tool_call() {
+        let agent_id = Uuid::new_v4();
+        let input = format!(
+            "{}\n",
+            tool_call_delta(agent_id, "call_1", Some("bash"), r#"{"command": "#)
+        );
+
+        let mut parser = AgentStreamParser::new();
+        let result = parser.process_stream(input.as_bytes()).await;
+        assert!(matches!(
+            result,
+            Err(StreamParseError::IncompleteToolCall { .. })
+        ));
+    }
 }
@@ -21,6 +21,9 @@
 /// 4. Automatic stashing of uncommitted changes
 /// 5. Rollback on error
 /// 6. Post-restore verification
+use crate::brain_restore::{
+    BrainRestore, RestoreOptions as BrainRestoreOptions, RestoreResult as BrainRestoreResult,
+};
 use crate::errors::{StateStoreError, StateStoreResult};
 use async_trait::async_trait;
 use gix::{bstr::ByteSlice, objs::Kind};
@@ -128,6 +131,93 @@ impl RepositoryBackup {
     }
 }
 
+/// Default template for backup/stash messages created during a restore
+pub const DEFAULT_BACKUP_MESSAGE_TEMPLATE: &str = "{prefix}Auto-stash before restore to {target}";
+
+/// Template for messages attached to backups created by the restore manager
+///
+/// Supported variables: `{prefix}`, `{agent_id}`, `{task_id}`, `{target}`,
+/// `{target_short}` and `{timestamp}` (RFC 3339, UTC). Unknown variables are
+/// left untouched; missing agent/task IDs render as an empty string.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupMessageTemplate {
+    /// Template string with `{variable}` placeholders
+    pub template: String,
+
+    /// Custom prefix substituted for `{prefix}`
+    pub prefix: String,
+}
+
+impl Default for BackupMessageTemplate {
+    fn default() -> Self {
+        Self {
+            template: DEFAULT_BACKUP_MESSAGE_TEMPLATE.to_string(),
+            prefix: String::new(),
+        }
+    }
+}
+
+impl BackupMessageTemplate {
+    /// Create a template from a format string
+    pub fn new(template: impl Into<String>) -> Self {
+        Self {
+            template: template.into(),
+            prefix: String::new(),
+        }
+    }
+
+    /// Set the custom prefix
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Render the template for a restore to `target_commit`
+    pub fn render(
+        &self,
+        target_commit: &str,
+        agent_id: Option<&str>,
+        task_id: Option<&str>,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    ) -> String {
+        let target_short: String = target_commit.chars().take(7).collect();
+        let timestamp = timestamp.to_rfc3339();
+
+        // Substitute in a single pass so placeholders inside values (e.g. a
+        // prefix containing "{agent_id}") are not expanded
+        let mut out = String::with_capacity(self.template.len());
+        let mut rest = self.template.as_str();
+        while let Some(start) = rest.find('{') {
+            out.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let value = rest.find('}').and_then(|end| {
+                let value = match &rest[1..end] {
+                    "prefix" => self.prefix.as_str(),
+                    "agent_id" => agent_id.unwrap_or(""),
+                    "task_id" => task_id.unwrap_or(""),
+                    "target_short" => &target_short,
+                    "target" => target_commit,
+                    "timestamp" => &timestamp,
+                    _ => return None,
+                };
+                Some((value, end))
+            });
+            match value {
+                Some((value, end)) => {
+                    out.push_str(value);
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    out.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
 /// Options for restore operation
 #[derive(Debug, Clone, Default)]
 pub struct RestoreOptions {
@@ -146,6 +236,15 @@ pub struct RestoreOptions {
 
     /// Whether to preserve untracked files (default: true)
     pub preserve_untracked: bool,
+
+    /// Template for the message of any backup stash created by the restore
+    pub message_template: BackupMessageTemplate,
+
+    /// Agent performing the restore, available to the message template
+    pub agent_id: Option<String>,
+
+    /// Task the restore belongs to, available to the message template
+    pub task_id: Option<String>,
 }
 
 impl RestoreOptions {
@@ -157,6 +256,7 @@ impl RestoreOptions {
             create_backup: true,
             force: false,
             preserve_untracked: true,
+            ..Default::default()
         }
     }
 
@@ -168,8 +268,32 @@ impl RestoreOptions {
             create_backup: true,
             force: true,
             preserve_untracked: false,
+            ..Default::default()
         }
     }
+
+    /// Set the backup message template
+    pub fn with_message_template(mut self, template: BackupMessageTemplate) -> Self {
+        self.message_template = template;
+        self
+    }
+
+    /// Set the agent and task IDs used when rendering backup messages
+    pub fn with_ids(mut self, agent_id: Option<String>, task_id: Option<String>) -> Self {
+        self.agent_id = agent_id;
+        self.task_id = task_id;
+        self
+    }
+
+    /// Render the backup message for a restore to `target_commit`
+    pub fn backup_message(&self, target_commit: &str) -> String {
+        self.message_template.render(
+            target_commit,
+            self.agent_id.as_deref(),
+            self.task_id.as_deref(),
+            chrono::Utc::now(),
+        )
+    }
 }
 
 /// Result of a restore operation
@@ -210,6 +334,63 @@ impl RestoreResult {
     }
 }
 
+/// Kind of change a restore would apply to a file
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FileChangeKind {
+    /// File exists in the target commit but not in the current one
+    Added,
+    /// File exists in both commits with different content
+    Modified,
+    /// File exists in the current commit but not in the target one
+    Deleted,
+}
+
+/// A single file that differs between the current and target commits
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FileChange {
+    /// Path relative to the repository root
+    pub path: String,
+
+    /// How the file would change
+    pub kind: FileChangeKind,
+
+    /// Lines added by the restore (0 for binary files)
+    pub insertions: usize,
+
+    /// Lines removed by the restore (0 for binary files)
+    pub deletions: usize,
+}
+
+/// Dry-run preview of a restore operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestorePreview {
+    /// Commit the repository is currently at
+    pub current_commit: String,
+
+    /// Commit the restore would move to
+    pub target_commit: String,
+
+    /// Files that would change, sorted by path
+    pub files: Vec<FileChange>,
+}
+
+impl RestorePreview {
+    /// Total lines added across all files
+    pub fn total_insertions(&self) -> usize {
+        self.files.iter().map(|f| f.insertions).sum()
+    }
+
+    /// Total lines removed across all files
+    pub fn total_deletions(&self) -> usize {
+        self.files.iter().map(|f| f.deletions).sum()
+    }
+
+    /// Whether the restore would leave the tree unchanged
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
 // ============================================================================
 // TRAIT DEFINITION
 // ============================================================================
@@ -250,6 +431,10 @@ pub trait BodyRestoreManager: Send + Sync {
 
     /// Get a list of recent commits
     async fn get_recent_commits(&self, limit: usize) -> StateStoreResult<Vec<CommitInfo>>;
+
+    /// Preview the files a restore to `target_commit` would change,
+    /// without touching HEAD or the working tree
+    async fn preview(&self, target_commit: &str) -> StateStoreResult<RestorePreview>;
 }
 
 // ============================================================================
@@ -340,6 +525,28 @@ impl GitBodyRestoreManager {
                 .with_parents(parents),
         )
     }
+
+    /// Run a read-only git diff between two commits and return stdout
+    fn git_diff(&self, args: &[&str], from: &str, to: &str) -> StateStoreResult<String> {
+        let output = std::process::Command::new("git")
+            .arg("diff")
+            .args(args)
+            .args(["--no-renames", from, to])
+            .current_dir(&self.repo_path)
+            .output()
+            .map_err(|e| {
+                StateStoreError::DatabaseError(format!("Failed to execute git diff: {}", e))
+            })?;
+
+        if !output.status.success() {
+            return Err(StateStoreError::DatabaseError(format!(
+                "git diff failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
 }
 
 #[async_trait]
@@ -391,45 +598,24 @@ impl BodyRestoreManager for GitBodyRestoreManager {
     }
 
     async fn has_uncommitted_changes(&self) -> StateStoreResult<bool> {
-        let repo = self.open_repo()?;
-
-        // Check for changes in the index and working tree
-        // This is a simplified check - in production you'd want more thorough status checking
-        let _index = repo
-            .index()
-            .map_err(|e| StateStoreError::DatabaseError(format!("Failed to read index: {}", e)))?;
-
-        // Get the HEAD tree
-        let mut head = repo
-            .head()
-            .map_err(|e| StateStoreError::DatabaseError(format!("Failed to read HEAD: {}", e)))?;
-
-        let head_commit_id = head
-            .try_peel_to_id_in_place()
-            .map_err(|e| StateStoreError::DatabaseError(format!("Failed to resolve HEAD: {}", e)))?
-            .ok_or_else(|| StateStoreError::DatabaseError("Could not peel HEAD to ID".to_string()))?
-            .detach();
-
-        let head_commit = repo
-            .find_object(head_commit_id)
+        // Shell out to git status, like stash: gitoxide has no index/worktree
+        // status. Untracked files are ignored since a checkout leaves them alone.
+        let output = std::process::Command::new("git")
+            .args(["status", "--porcelain", "--untracked-files=no"])
+            .current_dir(&self.repo_path)
+            .output()
             .map_err(|e| {
-                StateStoreError::DatabaseError(format!("Failed to find HEAD commit: {}", e))
-            })?
-            .try_into_commit()
-            .map_err(|e| StateStoreError::DatabaseError(format!("HEAD is not a commit: {}", e)))?;
-
-        let _head_tree_id = head_commit
-            .tree_id()
-            .map_err(|e| StateStoreError::DatabaseError(format!("Failed to get tree: {}", e)))?;
+                StateStoreError::DatabaseError(format!("Failed to execute git status: {}", e))
+            })?;
 
-        // Compare index with HEAD tree
-        // Note: This is a simplified implementation. A full implementation would need
-        // to properly diff the index with the HEAD tree and check working directory changes.
-        // For now, we'll use a heuristic based on index state.
+        if !output.status.success() {
+            return Err(StateStoreError::DatabaseError(format!(
+                "git status failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
 
-        // If the index has been modified since the last commit, we have changes
-        // This is a conservative check - it may report changes even if index matches HEAD
-        Ok(true) // Simplified: always assume there might be changes for safety
+        Ok(!output.stdout.trim_ascii().is_empty())
     }
 
     async fn create_backup(&self) -> StateStoreResult<RepositoryBackup> {
@@ -588,7 +774,7 @@ impl BodyRestoreManager for GitBodyRestoreManager {
         let mut backup_with_stash = backup.clone();
         if has_changes && options.stash_changes {
             match self
-                .stash_changes(&format!("Auto-stash before restore to {}", commit_hash))
+                .stash_changes(&options.backup_message(commit_hash))
                 .await
             {
                 Ok(stash_ref) => {
@@ -724,6 +910,66 @@ impl BodyRestoreManager for GitBodyRestoreManager {
 
         Ok(commits)
     }
+
+    async fn preview(&self, target_commit: &str) -> StateStoreResult<RestorePreview> {
+        if !self.verify_commit_exists(target_commit).await? {
+            return Err(StateStoreError::NotFound(format!(
+                "Commit does not exist: {}",
+                target_commit
+            )));
+        }
+
+        let current_commit = self.get_current_commit().await?;
+        let target_oid = self.parse_commit_hash(target_commit)?.to_string();
+
+        // Line counts per path ("-" for binary files)
+        let numstat = self.git_diff(&["--numstat"], &current_commit, &target_oid)?;
+        let mut stats = std::collections::HashMap::new();
+        for line in numstat.lines() {
+            let mut parts = line.splitn(3, '\t');
+            if let (Some(ins), Some(del), Some(path)) = (parts.next(), parts.next(), parts.next())
+            {
+                stats.insert(
+                    path.to_string(),
+                    (ins.parse().unwrap_or(0), del.parse().unwrap_or(0)),
+                );
+            }
+        }
+
+        let name_status = self.git_diff(&["--name-status"], &current_commit, &target_oid)?;
+        let mut files: Vec<FileChange> = name_status
+            .lines()
+            .filter_map(|line| {
+                let (status, path) = line.split_once('\t')?;
+                let kind = match status.chars().next()? {
+                    'A' => FileChangeKind::Added,
+                    'D' => FileChangeKind::Deleted,
+                    _ => FileChangeKind::Modified,
+                };
+                let (insertions, deletions) = stats.get(path).copied().unwrap_or((0, 0));
+                Some(FileChange {
+                    path: path.to_string(),
+                    kind,
+                    insertions,
+                    deletions,
+                })
+            })
+            .collect();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        debug!(
+            "Restore preview {} -> {}: {} files",
+            current_commit,
+            target_oid,
+            files.len()
+        );
+
+        Ok(RestorePreview {
+            current_commit,
+            target_commit: target_oid,
+            files,
+        })
+    }
 }
 
 // Blanket implementation for Arc<T> where T: BodyRestoreManager
@@ -773,12 +1019,35 @@ impl<T: BodyRestoreManager> BodyRestoreManager for Arc<T> {
     async fn get_recent_commits(&self, limit: usize) -> StateStoreResult<Vec<CommitInfo>> {
         (**self).get_recent_commits(limit).await
     }
+
+    async fn preview(&self, target_commit: &str) -> StateStoreResult<RestorePreview> {
+        (**self).preview(target_commit).await
+    }
 }
 
 // ============================================================================
 // INTEGRATION WITH AGENT HISTORY
 // ============================================================================
 
+/// Outcome of a coordinated brain and body restore
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoordinatedRestoreResult {
+    /// Whether both the body and brain legs succeeded
+    pub success: bool,
+
+    /// Result of the body (git) leg
+    pub body_result: RestoreResult,
+
+    /// Result of the brain leg, if it produced one
+    pub brain_result: Option<BrainRestoreResult>,
+
+    /// Whether the body was rolled back because the brain leg failed
+    pub rolled_back: bool,
+
+    /// Why the brain leg failed
+    pub error: Option<String>,
+}
+
 /// Coordinate brain and body restore operations
 pub struct CoordinatedRestore {
     body_manager: GitBodyRestoreManager,
@@ -815,6 +1084,79 @@ impl CoordinatedRestore {
         Ok(body_result)
     }
 
+    /// Restore the body to `commit_hash` and the brain to `timestamp` as one
+    /// operation
+    ///
+    /// The body is restored first. If the brain leg then errors or fails
+    /// validation, the body is rolled back to the backup taken by the checkout,
+    /// so the repository never stays at a commit the agent's brain state
+    /// doesn't match. The brain leg only builds an in-memory state, so it has
+    /// nothing to roll back.
+    pub async fn coordinated_restore(
+        &self,
+        brain: &dyn BrainRestore,
+        agent_id: &str,
+        timestamp: i64,
+        commit_hash: &str,
+        options: RestoreOptions,
+        brain_options: BrainRestoreOptions,
+    ) -> StateStoreResult<CoordinatedRestoreResult> {
+        info!(
+            "Starting coordinated restore of agent {} to commit {} at {}",
+            agent_id, commit_hash, timestamp
+        );
+
+        let body_result = self
+            .body_manager
+            .checkout_commit(
+                commit_hash,
+                RestoreOptions {
+                    create_backup: true,
+                    ..options
+                },
+            )
+            .await?;
+
+        let brain_outcome = match brain.load_events_until(agent_id, timestamp).await {
+            Ok(events) => brain.replay_events(events, brain_options).await,
+            Err(e) => Err(e),
+        };
+        let (brain_result, error) = match brain_outcome {
+            Ok(result) if result.success => {
+                info!("Coordinated restore to {} complete", commit_hash);
+                return Ok(CoordinatedRestoreResult {
+                    success: true,
+                    body_result,
+                    brain_result: Some(result),
+                    rolled_back: false,
+                    error: None,
+                });
+            }
+            Ok(result) => {
+                let error = format!("Brain restore failed: {:?}", result.validation_errors);
+                (Some(result), error)
+            }
+            Err(e) => (None, format!("Brain restore failed: {}", e)),
+        };
+
+        error!("{}. Rolling back body...", error);
+        if let Err(e) = self.body_manager.rollback(&body_result.backup).await {
+            error!("CRITICAL: Rollback failed: {}", e);
+            return Err(StateStoreError::DatabaseError(format!(
+                "{} and body rollback failed: {}",
+                error, e
+            )));
+        }
+
+        Ok(CoordinatedRestoreResult {
+            success: false,
+            body_result,
+            brain_result,
+            rolled_back: true,
+            error: Some(error),
+        })
+    }
+
     /// Get the body restore manager
     pub fn body_manager(&self) -> &GitBodyRestoreManager {
         &self.body_manager
@@ -941,6 +1283,20 @@ mod tests {
         assert!(backup.timestamp > 0);
     }
 
+    #[tokio::test]
+    async fn test_has_uncommitted_changes() {
+        let (_temp, repo_path) = create_test_repo();
+        let manager = GitBodyRestoreManager::new(&repo_path).unwrap();
+        assert!(!manager.has_uncommitted_changes().await.unwrap());
+
+        // Untracked files survive a checkout, so they don't count
+        std::fs::write(repo_path.join("scratch.txt"), "notes").unwrap();
+        assert!(!manager.has_uncommitted_changes().await.unwrap());
+
+        std::fs::write(repo_path.join("test.txt"), "edited").unwrap();
+        assert!(manager.has_uncommitted_changes().await.unwrap());
+    }
+
     #[tokio::test]
     async fn test_get_recent_commits() {
         let (_temp, repo_path) = create_test_repo();
@@ -1023,6 +1379,280 @@ mod tests {
         assert_eq!(current, second_commit);
     }
 
+    #[test]
+    fn test_backup_message_template_render() {
+        let template = BackupMessageTemplate::new(
+            "{prefix} agent={agent_id} task={task_id} -> {target_short} @ {timestamp}",
+        )
+        .with_prefix("[descartes]");
+        let ts = chrono::DateTime::parse_from_rfc3339("2025-01-02T03:04:05Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+
+        let message = template.render(
+            "0123456789abcdef0123456789abcdef01234567",
+            Some("agent-1"),
+            Some("task-42"),
+            ts,
+        );
+        assert_eq!(
+            message,
+            "[descartes] agent=agent-1 task=task-42 -> 0123456 @ 2025-01-02T03:04:05+00:00"
+        );
+
+        // Values are not re-expanded; unknown variables are left as-is
+        let template =
+            BackupMessageTemplate::new("{prefix} {agent_id} {unknown} {").with_prefix("{agent_id}");
+        assert_eq!(
+            template.render("abc", Some("agent-1"), None, ts),
+            "{agent_id} agent-1 {unknown} {"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_backup_stash_uses_message_template() {
+        let (_temp, repo_path) = create_test_repo();
+
+        std::fs::write(repo_path.join("test.txt"), "second content").unwrap();
+        Command::new("git")
+            .args(["commit", "-am", "Second commit"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+
+        let manager = GitBodyRestoreManager::new(&repo_path).unwrap();
+        let commits = manager.get_recent_commits(2).await.unwrap();
+        let first_commit = commits[1].hash.clone();
+
+        // Dirty the working tree so the restore has something to stash
+        std::fs::write(repo_path.join("test.txt"), "uncommitted").unwrap();
+
+        let options = RestoreOptions::safe()
+            .with_message_template(
+                BackupMessageTemplate::new("{prefix} {agent_id}/{task_id} {target_short}")
+                    .with_prefix("backup:"),
+            )
+            .with_ids(Some("agent-7".to_string()), Some("task-3".to_string()));
+        // The stash is created before checkout, so check it regardless of the outcome
+        let _ = manager.checkout_commit(&first_commit, options).await;
+
+        let output = Command::new("git")
+            .args(["stash", "list", "--format=%s"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        let stash_list = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stash_list.contains(&format!("backup: agent-7/task-3 {}", &first_commit[..7])),
+            "unexpected stash list: {}",
+            stash_list
+        );
+    }
+
+    #[tokio::test]
+    async fn test_preview_lists_changed_files() {
+        let (_temp, repo_path) = create_test_repo();
+        let manager = GitBodyRestoreManager::new(&repo_path).unwrap();
+        let target = manager.get_current_commit().await.unwrap();
+
+        // Modify one file, add another, then commit
+        std::fs::write(repo_path.join("test.txt"), "initial content\nmore\n").unwrap();
+        std::fs::write(repo_path.join("new.txt"), "brand new\n").unwrap();
+        Command::new("git")
+            .args(["add", "."])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["commit", "-m", "Second commit"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        let head_before = manager.get_current_commit().await.unwrap();
+
+        let preview = manager.preview(&target).await.unwrap();
+        assert_eq!(preview.target_commit, target);
+        assert_eq!(preview.current_commit, head_before);
+        assert_eq!(preview.files.len(), 2);
+
+        // Restoring to the first commit would delete new.txt and revert test.txt
+        assert_eq!(preview.files[0].path, "new.txt");
+        assert_eq!(preview.files[0].kind, FileChangeKind::Deleted);
+        assert_eq!(preview.files[0].deletions, 1);
+        assert_eq!(preview.files[1].path, "test.txt");
+        assert_eq!(preview.files[1].kind, FileChangeKind::Modified);
+        assert!(preview.total_deletions() >= 2);
+
+        // The preview must not move HEAD or touch the working tree
+        assert_eq!(manager.get_current_commit().await.unwrap(), head_before);
+        assert!(repo_path.join("new.txt").exists());
+    }
+
+    /// Brain restore whose every load and replay fails
+    struct FailingBrainRestore;
+
+    fn injected_failure<T>() -> StateStoreResult<T> {
+        Err(StateStoreError::DatabaseError(
+            "injected failure".to_string(),
+        ))
+    }
+
+    #[async_trait]
+    impl BrainRestore for FailingBrainRestore {
+        async fn load_events_until(
+            &self,
+            _agent_id: &str,
+            _timestamp: i64,
+        ) -> StateStoreResult<Vec<crate::agent_history::AgentHistoryEvent>> {
+            injected_failure()
+        }
+
+        async fn load_events_range(
+            &self,
+            _agent_id: &str,
+            _start: i64,
+            _end: i64,
+        ) -> StateStoreResult<Vec<crate::agent_history::AgentHistoryEvent>> {
+            injected_failure()
+        }
+
+        async fn filter_by_event_type(
+            &self,
+            _agent_id: &str,
+            _event_types: Vec<crate::agent_history::HistoryEventType>,
+        ) -> StateStoreResult<Vec<crate::agent_history::AgentHistoryEvent>> {
+            injected_failure()
+        }
+
+        async fn replay_events(
+            &self,
+            _events: Vec<crate::agent_history::AgentHistoryEvent>,
+            _options: BrainRestoreOptions,
+        ) -> StateStoreResult<BrainRestoreResult> {
+            injected_failure()
+        }
+
+        async fn restore_brain_state(
+            &self,
+            _snapshot_id: &uuid::Uuid,
+            _options: BrainRestoreOptions,
+        ) -> StateStoreResult<BrainRestoreResult> {
+            injected_failure()
+        }
+
+        fn apply_event(
+            &self,
+            _state: &mut crate::brain_restore::BrainState,
+            _event: &crate::agent_history::AgentHistoryEvent,
+        ) -> StateStoreResult<()> {
+            Ok(())
+        }
+
+        fn validate_state(
+            &self,
+            _state: &crate::brain_restore::BrainState,
+        ) -> StateStoreResult<Vec<String>> {
+            Ok(Vec::new())
+        }
+
+        fn check_dependencies(
+            &self,
+            _events: &[crate::agent_history::AgentHistoryEvent],
+        ) -> StateStoreResult<Vec<String>> {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Commit a change to test.txt, returning the commit before it
+    async fn add_second_commit(repo_path: &Path, manager: &GitBodyRestoreManager) -> String {
+        let first = manager.get_current_commit().await.unwrap();
+        std::fs::write(repo_path.join("test.txt"), "second content").unwrap();
+        Command::new("git")
+            .args(["commit", "-am", "Second commit"])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+        first
+    }
+
+    #[tokio::test]
+    async fn test_coordinated_restore_rolls_back_body_on_brain_failure() {
+        let (_temp, repo_path) = create_test_repo();
+        let restore = CoordinatedRestore::new(repo_path.clone()).unwrap();
+        let first = add_second_commit(&repo_path, restore.body_manager()).await;
+        let second = restore.body_manager().get_current_commit().await.unwrap();
+
+        let result = restore
+            .coordinated_restore(
+                &FailingBrainRestore,
+                "agent-1",
+                i64::MAX,
+                &first,
+                RestoreOptions::force(),
+                BrainRestoreOptions::default(),
+            )
+            .await
+            .unwrap();
+
+        assert!(!result.success);
+        assert!(result.rolled_back);
+        assert!(result.brain_result.is_none());
+        assert!(result.error.unwrap().contains("injected failure"));
+        assert_eq!(result.body_result.target_commit, first);
+        assert_eq!(result.body_result.backup.head_commit, second);
+        assert_eq!(
+            restore.body_manager().get_current_commit().await.unwrap(),
+            second
+        );
+    }
+
+    #[tokio::test]
+    async fn test_coordinated_restore_succeeds() {
+        use crate::agent_history::{
+            AgentHistoryEvent, AgentHistoryStore, HistoryEventType, SqliteAgentHistoryStore,
+        };
+
+        let (_temp, repo_path) = create_test_repo();
+        let restore = CoordinatedRestore::new(repo_path.clone()).unwrap();
+        let first = add_second_commit(&repo_path, restore.body_manager()).await;
+
+        let db = tempfile::NamedTempFile::new().unwrap();
+        let mut store = SqliteAgentHistoryStore::new(db.path().to_str().unwrap())
+            .await
+            .unwrap();
+        store.initialize().await.unwrap();
+        store
+            .record_event(&AgentHistoryEvent::new(
+                "agent-1".to_string(),
+                HistoryEventType::Thought,
+                serde_json::json!({"content": "checkpoint"}),
+            ))
+            .await
+            .unwrap();
+        let brain = crate::brain_restore::DefaultBrainRestore::new(store);
+
+        let result = restore
+            .coordinated_restore(
+                &brain,
+                "agent-1",
+                i64::MAX,
+                &first,
+                RestoreOptions::force(),
+                BrainRestoreOptions::default(),
+            )
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert!(!result.rolled_back);
+        let brain_state = result.brain_result.unwrap().brain_state.unwrap();
+        assert_eq!(brain_state.thought_history.len(), 1);
+        assert_eq!(
+            restore.body_manager().get_current_commit().await.unwrap(),
+            first
+        );
+    }
+
     #[tokio::test]
     async fn test_coordinated_restore() {
         let (_temp, repo_path) = create_test_repo();
@@ -80,6 +80,86 @@ impl BrainState {
             && self.memory.is_empty()
             && self.conversation_state.messages.is_empty()
     }
+
+    /// Export the decision tree as a Mermaid flowchart
+    ///
+    /// Each decision becomes a node labelled with its type and outcome, and
+    /// decisions are linked to their parents (via `parent_decision_id` or a
+    /// parent's `children`). When a decision's context lists
+    /// `options`, each option is drawn as a branch: the chosen one (matching
+    /// `outcome`) as a solid edge labelled with the context's `reasoning`, the
+    /// others as dashed "unchosen" edges.
+    pub fn decision_tree_mermaid(&self) -> String {
+        let node_ids: HashMap<Uuid, String> = self
+            .decision_tree
+            .iter()
+            .enumerate()
+            .map(|(i, d)| (d.decision_id, format!("d{}", i)))
+            .collect();
+
+        let parents_by_child: HashMap<Uuid, Uuid> = self
+            .decision_tree
+            .iter()
+            .flat_map(|d| d.children.iter().map(move |child| (*child, d.decision_id)))
+            .collect();
+
+        let mut out = String::from("graph TD\n");
+
+        for (i, decision) in self.decision_tree.iter().enumerate() {
+            let id = &node_ids[&decision.decision_id];
+            let label = match &decision.outcome {
+                Some(outcome) => format!("{}: {}", decision.decision_type, outcome),
+                None => decision.decision_type.clone(),
+            };
+            out.push_str(&format!("    {}[\"{}\"]\n", id, mermaid_escape(&label)));
+
+            let reasoning = decision.context.get("reasoning").and_then(|v| v.as_str());
+
+            if let Some(options) = decision.context.get("options").and_then(|v| v.as_array()) {
+                for (j, option) in options.iter().enumerate() {
+                    let option = option
+                        .as_str()
+                        .map(String::from)
+                        .unwrap_or_else(|| option.to_string());
+                    let option_id = format!("d{}_o{}", i, j);
+                    let (arrow, edge_label) =
+                        if decision.outcome.as_deref() == Some(option.as_str()) {
+                            let label = match reasoning {
+                                Some(r) => format!("chosen: {}", r),
+                                None => "chosen".to_string(),
+                            };
+                            ("-->", label)
+                        } else {
+                            ("-.->", "unchosen".to_string())
+                        };
+                    out.push_str(&format!(
+                        "    {} {}|\"{}\"| {}([\"{}\"])\n",
+                        id,
+                        arrow,
+                        mermaid_escape(&edge_label),
+                        option_id,
+                        mermaid_escape(&option)
+                    ));
+                }
+            }
+
+            if let Some(parent_id) = decision
+                .parent_decision_id
+                .filter(|parent| node_ids.contains_key(parent))
+                .or_else(|| parents_by_child.get(&decision.decision_id).copied())
+                .and_then(|parent| node_ids.get(&parent))
+            {
+                out.push_str(&format!("    {} --> {}\n", parent_id, id));
+            }
+        }
+
+        out
+    }
+}
+
+/// Escape text for use inside a quoted Mermaid label
+fn mermaid_escape(text: &str) -> String {
+    text.replace('"', "#quot;").replace('\n', " ")
 }
 
 /// A single thought entry in the agent's thought history
@@ -127,6 +207,59 @@ pub struct MessageEntry {
 // RESTORE OPTIONS AND CONFIGURATION
 // ============================================================================
 
+/// Role used for the synthetic summary message inserted by conversation windowing
+pub const TRUNCATION_SUMMARY_ROLE: &str = "system";
+
+/// Limits how much of the conversation is restored into a new session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConversationWindow {
+    /// Keep only the last N messages
+    KeepLastMessages(usize),
+
+    /// Keep the most recent messages that fit in an approximate token budget
+    /// (estimated at four characters per token)
+    MaxTokens(usize),
+}
+
+impl ConversationWindow {
+    /// Number of trailing messages from `messages` that fit in the window
+    fn retained_count(&self, messages: &[MessageEntry]) -> usize {
+        match *self {
+            ConversationWindow::KeepLastMessages(n) => n.min(messages.len()),
+            ConversationWindow::MaxTokens(max_tokens) => {
+                let mut used = 0;
+                let mut kept = 0;
+                for message in messages.iter().rev() {
+                    let tokens = estimate_tokens(&message.content);
+                    if used + tokens > max_tokens {
+                        break;
+                    }
+                    used += tokens;
+                    kept += 1;
+                }
+                kept
+            }
+        }
+    }
+
+    /// Whether `retained` plus the summary message standing in for the
+    /// truncated prefix fit in the window
+    fn fits_with_summary(&self, retained: &[MessageEntry], summary: &MessageEntry) -> bool {
+        match *self {
+            ConversationWindow::KeepLastMessages(_) => true,
+            ConversationWindow::MaxTokens(max_tokens) => {
+                let used: usize = retained.iter().map(|m| estimate_tokens(&m.content)).sum();
+                used + estimate_tokens(&summary.content) <= max_tokens
+            }
+        }
+    }
+}
+
+/// Approximate token count; conversation history isn't tied to one model
+fn estimate_tokens(content: &str) -> usize {
+    crate::tokenizer::count_tokens("", content)
+}
+
 /// Options for configuring the restore operation
 #[derive(Debug, Clone)]
 pub struct RestoreOptions {
@@ -147,6 +280,10 @@ pub struct RestoreOptions {
 
     /// Custom event filters
     pub event_filters: Vec<HistoryEventType>,
+
+    /// Restore only a recent window of the conversation, replacing the
+    /// truncated prefix with a single summary message
+    pub conversation_window: Option<ConversationWindow>,
 }
 
 impl Default for RestoreOptions {
@@ -158,6 +295,7 @@ impl Default for RestoreOptions {
             max_events: None,
             include_metadata: true,
             event_filters: Vec::new(),
+            conversation_window: None,
         }
     }
 }
@@ -194,6 +332,12 @@ impl RestoreOptions {
         self.event_filters = filters;
         self
     }
+
+    /// Restore only a window of the conversation
+    pub fn with_conversation_window(mut self, window: ConversationWindow) -> Self {
+        self.conversation_window = Some(window);
+        self
+    }
 }
 
 // ============================================================================
@@ -417,7 +561,7 @@ impl<S: AgentHistoryStore> DefaultBrainRestore<S> {
             .get("decision_id")
             .and_then(|v| v.as_str())
             .and_then(|s| Uuid::parse_str(s).ok())
-            .unwrap_or_else(Uuid::new_v4);
+            .unwrap_or(event.event_id);
 
         Some(DecisionNode {
             decision_id,
@@ -612,6 +756,17 @@ impl<S: AgentHistoryStore> BrainRestore for DefaultBrainRestore<S> {
             state.timestamp = last_event.timestamp;
         }
 
+        // Window the conversation if requested
+        if let Some(window) = options.conversation_window {
+            let truncated = apply_conversation_window(&mut state.conversation_state, window);
+            if truncated > 0 {
+                warnings.push(format!(
+                    "Conversation truncated: {} earlier messages summarized",
+                    truncated
+                ));
+            }
+        }
+
         // Validate if requested
         let mut validation_errors = Vec::new();
         if options.validate {
@@ -661,6 +816,14 @@ impl<S: AgentHistoryStore> BrainRestore for DefaultBrainRestore<S> {
             }
             HistoryEventType::Decision => {
                 if let Some(decision) = self.extract_decision(event) {
+                    if let Some(parent) = decision.parent_decision_id.and_then(|parent_id| {
+                        state
+                            .decision_tree
+                            .iter_mut()
+                            .find(|d| d.decision_id == parent_id)
+                    }) {
+                        parent.children.push(decision.decision_id);
+                    }
                     state.decision_tree.push(decision);
                 }
             }
@@ -782,6 +945,84 @@ pub fn create_snapshot_from_state(
     .with_agent_state(serde_json::to_value(brain_state).unwrap_or(Value::Null))
 }
 
+/// Truncate a conversation to the given window
+///
+/// Messages before the window are replaced by a single summary message so the
+/// restored session knows earlier context existed. Returns the number of
+/// messages that were removed; the conversation is left untouched when
+/// everything already fits.
+pub fn apply_conversation_window(
+    conversation: &mut ConversationState,
+    window: ConversationWindow,
+) -> usize {
+    let mut kept = window.retained_count(&conversation.messages);
+    // The summary counts against the window too, so drop retained messages
+    // until both fit (the summary alone is kept even if it overflows)
+    let (truncated, summary) = loop {
+        let truncated = conversation.messages.len() - kept;
+        if truncated == 0 {
+            return 0;
+        }
+        let (prefix, retained) = conversation.messages.split_at(truncated);
+        let summary = summarize_truncated_messages(prefix);
+        if kept == 0 || window.fits_with_summary(retained, &summary) {
+            break (truncated, summary);
+        }
+        kept -= 1;
+    };
+
+    let retained = conversation.messages.split_off(truncated);
+    conversation.messages = retained;
+    conversation.messages.insert(0, summary);
+
+    conversation.current_turn = conversation.messages.len() as i64;
+    conversation
+        .context
+        .insert("truncated_messages".to_string(), Value::from(truncated));
+
+    truncated
+}
+
+/// Build the summary entry standing in for a truncated conversation prefix
+fn summarize_truncated_messages(prefix: &[MessageEntry]) -> MessageEntry {
+    let mut role_counts: Vec<(&str, usize)> = Vec::new();
+    for message in prefix {
+        match role_counts
+            .iter_mut()
+            .find(|(role, _)| *role == message.role)
+        {
+            Some((_, count)) => *count += 1,
+            None => role_counts.push((&message.role, 1)),
+        }
+    }
+    let roles = role_counts
+        .iter()
+        .map(|(role, count)| format!("{} {}", count, role))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut content = format!(
+        "[Summary of {} earlier messages truncated on restore: {}]",
+        prefix.len(),
+        roles
+    );
+    if let Some(first) = prefix.iter().find(|m| m.role == "user") {
+        let excerpt: String = first.content.chars().take(200).collect();
+        content.push_str(&format!("\nConversation began with: {}", excerpt));
+    }
+
+    MessageEntry {
+        message_id: Uuid::new_v4(),
+        timestamp: prefix.last().map(|m| m.timestamp).unwrap_or_default(),
+        role: TRUNCATION_SUMMARY_ROLE.to_string(),
+        content,
+        metadata: Some(serde_json::json!({
+            "truncated_messages": prefix.len(),
+            "first_timestamp": prefix.first().map(|m| m.timestamp),
+        })),
+    }
+}
+
 /// Compare two brain states for differences
 pub fn compare_states(state1: &BrainState, state2: &BrainState) -> Vec<String> {
     let mut differences = Vec::new();
@@ -821,6 +1062,86 @@ pub fn compare_states(state1: &BrainState, state2: &BrainState) -> Vec<String> {
     differences
 }
 
+/// How two runs' decisions at the same position differ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DecisionDivergence {
+    /// Both runs decided here but chose differently
+    Changed,
+    /// Only the first run made a decision at this position
+    OnlyInFirst,
+    /// Only the second run made a decision at this position
+    OnlyInSecond,
+}
+
+/// One run's side of a divergent decision
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecisionSide {
+    pub decision_id: Uuid,
+    pub decision_type: String,
+    pub outcome: Option<String>,
+    /// The `reasoning` recorded in the decision's context, if any
+    pub reasoning: Option<String>,
+}
+
+impl From<&DecisionNode> for DecisionSide {
+    fn from(node: &DecisionNode) -> Self {
+        Self {
+            decision_id: node.decision_id,
+            decision_type: node.decision_type.clone(),
+            outcome: node.outcome.clone(),
+            reasoning: node
+                .context
+                .get("reasoning")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+        }
+    }
+}
+
+/// A position where two runs' decision trees diverge
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecisionDiff {
+    /// Position in both decision trees
+    pub index: usize,
+    pub divergence: DecisionDivergence,
+    /// The first run's decision (None if it made no decision here)
+    pub first: Option<DecisionSide>,
+    /// The second run's decision (None if it made no decision here)
+    pub second: Option<DecisionSide>,
+}
+
+/// Pair up two states' decisions by position and report where they diverge
+///
+/// Decisions at the same position diverge when their type or outcome
+/// differs. Positions past the end of the shorter tree are reported as
+/// present in only one run.
+pub fn diff_decisions(first: &BrainState, second: &BrainState) -> Vec<DecisionDiff> {
+    let len = first.decision_tree.len().max(second.decision_tree.len());
+    (0..len)
+        .filter_map(|index| {
+            let a = first.decision_tree.get(index);
+            let b = second.decision_tree.get(index);
+            let divergence = match (a, b) {
+                (Some(a), Some(b)) => {
+                    if a.decision_type == b.decision_type && a.outcome == b.outcome {
+                        return None;
+                    }
+                    DecisionDivergence::Changed
+                }
+                (Some(_), None) => DecisionDivergence::OnlyInFirst,
+                (None, Some(_)) => DecisionDivergence::OnlyInSecond,
+                (None, None) => return None,
+            };
+            Some(DecisionDiff {
+                index,
+                divergence,
+                first: a.map(DecisionSide::from),
+                second: b.map(DecisionSide::from),
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1175,6 +1496,206 @@ mod tests {
         assert!(differences[0].contains("Thought history length differs"));
     }
 
+    fn conversation_events(count: usize) -> Vec<AgentHistoryEvent> {
+        (0..count)
+            .map(|i| {
+                let mut event = AgentHistoryEvent::new(
+                    "agent-1".to_string(),
+                    HistoryEventType::Communication,
+                    json!({
+                        "role": if i % 2 == 0 { "user" } else { "assistant" },
+                        "content": format!("message {}", i)
+                    }),
+                );
+                event.timestamp = 1_000 + i as i64;
+                event
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_replay_with_conversation_window() {
+        let store = create_test_store().await;
+        let restore = DefaultBrainRestore::new(store);
+
+        let options = RestoreOptions::default()
+            .with_conversation_window(ConversationWindow::KeepLastMessages(3));
+        let result = restore
+            .replay_events(conversation_events(10), options)
+            .await
+            .unwrap();
+
+        assert!(result.success, "errors: {:?}", result.validation_errors);
+        let messages = result.brain_state.unwrap().conversation_state.messages;
+        assert_eq!(messages.len(), 4);
+
+        assert_eq!(messages[0].role, TRUNCATION_SUMMARY_ROLE);
+        assert!(messages[0].content.contains("7 earlier messages"));
+        assert!(messages[0].content.contains("message 0"));
+
+        let contents: Vec<&str> = messages[1..].iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, vec!["message 7", "message 8", "message 9"]);
+    }
+
+    #[test]
+    fn test_conversation_window_max_tokens() {
+        let mut conversation = ConversationState::default();
+        for i in 0..5 {
+            conversation.messages.push(MessageEntry {
+                message_id: Uuid::new_v4(),
+                timestamp: i,
+                role: "user".to_string(),
+                content: "x".repeat(400), // ~100 tokens each
+                metadata: None,
+            });
+        }
+        conversation.current_turn = 5;
+
+        // Three messages fit in 300 tokens, but the summary needs room too
+        let truncated =
+            apply_conversation_window(&mut conversation, ConversationWindow::MaxTokens(300));
+        assert_eq!(truncated, 3);
+        assert_eq!(conversation.messages.len(), 3);
+        assert_eq!(conversation.current_turn, 3);
+        let used: usize = conversation
+            .messages
+            .iter()
+            .map(|m| estimate_tokens(&m.content))
+            .sum();
+        assert!(used <= 300, "window overflowed: {} tokens", used);
+
+        // A window larger than the conversation leaves it untouched
+        let truncated =
+            apply_conversation_window(&mut conversation, ConversationWindow::KeepLastMessages(10));
+        assert_eq!(truncated, 0);
+        assert_eq!(conversation.messages.len(), 3);
+    }
+
+    #[test]
+    fn test_decision_tree_mermaid() {
+        let mut state = BrainState::new("agent-1".to_string());
+        let root_id = Uuid::new_v4();
+        state.decision_tree.push(DecisionNode {
+            decision_id: root_id,
+            timestamp: 1,
+            decision_type: "strategy".to_string(),
+            context: json!({
+                "options": ["refactor", "rewrite"],
+                "reasoning": "smaller diff"
+            }),
+            outcome: Some("refactor".to_string()),
+            parent_decision_id: None,
+            children: Vec::new(),
+        });
+        state.decision_tree.push(DecisionNode {
+            decision_id: Uuid::new_v4(),
+            timestamp: 2,
+            decision_type: "tool_choice".to_string(),
+            context: json!({}),
+            outcome: Some("edit".to_string()),
+            parent_decision_id: Some(root_id),
+            children: Vec::new(),
+        });
+
+        let mermaid = state.decision_tree_mermaid();
+
+        assert!(mermaid.starts_with("graph TD"));
+        assert!(mermaid.contains(r#"d0["strategy: refactor"]"#));
+        assert!(mermaid.contains(r#"d1["tool_choice: edit"]"#));
+        assert!(mermaid.contains(r#"d0 -->|"chosen: smaller diff"| d0_o0(["refactor"])"#));
+        assert!(mermaid.contains(r#"d0 -.->|"unchosen"| d0_o1(["rewrite"])"#));
+        assert!(mermaid.contains("d0 --> d1"));
+    }
+
+    #[test]
+    fn test_diff_decisions() {
+        let decision = |decision_type: &str, outcome: &str, reasoning: &str| DecisionNode {
+            decision_id: Uuid::new_v4(),
+            timestamp: 1,
+            decision_type: decision_type.to_string(),
+            context: json!({"reasoning": reasoning}),
+            outcome: Some(outcome.to_string()),
+            parent_decision_id: None,
+            children: Vec::new(),
+        };
+        let mut first = BrainState::new("agent-1".to_string());
+        first.decision_tree = vec![
+            decision("strategy", "refactor", "smaller diff"),
+            decision("tool_choice", "edit", "one file"),
+        ];
+        let mut second = BrainState::new("agent-1".to_string());
+        second.decision_tree = vec![
+            // Same choice, different wording: not a divergence
+            decision("strategy", "refactor", "keeps history"),
+            decision("tool_choice", "bash", "sed is faster"),
+            decision("verify", "run_tests", "changed many files"),
+        ];
+
+        let diffs = diff_decisions(&first, &second);
+
+        assert_eq!(diffs.len(), 2);
+        assert_eq!(diffs[0].index, 1);
+        assert_eq!(diffs[0].divergence, DecisionDivergence::Changed);
+        let (a, b) = (
+            diffs[0].first.as_ref().unwrap(),
+            diffs[0].second.as_ref().unwrap(),
+        );
+        assert_eq!(a.outcome.as_deref(), Some("edit"));
+        assert_eq!(a.reasoning.as_deref(), Some("one file"));
+        assert_eq!(b.outcome.as_deref(), Some("bash"));
+        assert_eq!(b.reasoning.as_deref(), Some("sed is faster"));
+
+        assert_eq!(diffs[1].index, 2);
+        assert_eq!(diffs[1].divergence, DecisionDivergence::OnlyInSecond);
+        assert!(diffs[1].first.is_none());
+        assert_eq!(diffs[1].second.as_ref().unwrap().decision_type, "verify");
+
+        // Swapping the runs flips which side the extra decision is on
+        let diffs = diff_decisions(&second, &first);
+        assert_eq!(diffs[1].divergence, DecisionDivergence::OnlyInFirst);
+        assert!(diff_decisions(&first, &first).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_decision_tree_mermaid_from_replay() {
+        let store = create_test_store().await;
+        let restore = DefaultBrainRestore::new(store);
+
+        let root = AgentHistoryEvent::new(
+            "agent-1".to_string(),
+            HistoryEventType::Decision,
+            json!({
+                "decision_type": "strategy",
+                "context": {"options": ["refactor", "rewrite"]},
+                "outcome": "refactor"
+            }),
+        );
+        let child = AgentHistoryEvent::new(
+            "agent-1".to_string(),
+            HistoryEventType::Decision,
+            json!({
+                "decision_type": "tool_choice",
+                "context": {},
+                "outcome": "edit"
+            }),
+        )
+        .with_parent(root.event_id);
+
+        let result = restore
+            .replay_events(vec![root, child], RestoreOptions::default())
+            .await
+            .unwrap();
+        assert!(result.success, "errors: {:?}", result.validation_errors);
+
+        let state = result.brain_state.unwrap();
+        assert_eq!(state.decision_tree[0].children.len(), 1);
+
+        let mermaid = state.decision_tree_mermaid();
+        assert!(mermaid.contains(r#"d0["strategy: refactor"]"#));
+        assert!(mermaid.contains(r#"d1["tool_choice: edit"]"#));
+        assert!(mermaid.contains("d0 --> d1"));
+    }
+
     #[tokio::test]
     async fn test_replay_empty_events() {
         let store = create_test_store().await;
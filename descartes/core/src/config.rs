@@ -1,13 +1,17 @@
 /// Configuration management for Descartes orchestration system.
 /// Handles loading, parsing, validation, and migration of .descartes/config.toml
+use crate::config_migration::{ConfigMigration, CURRENT_CONFIG_VERSION};
 use crate::errors::{AgentError, AgentResult};
+use crate::scg_task_storage::TaskSchedule;
+use crate::secrets::SecretRef;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
 
 /// Top-level configuration structure for Descartes
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DescaratesConfig {
     /// Configuration file version (for future migrations)
     #[serde(default = "default_version")]
@@ -36,6 +40,10 @@ pub struct DescaratesConfig {
     /// Logging and observability settings
     #[serde(default)]
     pub logging: LoggingConfig,
+
+    /// SCUD task graph settings
+    #[serde(default)]
+    pub scud: ScudConfig,
 }
 
 impl Default for DescaratesConfig {
@@ -48,19 +56,21 @@ impl Default for DescaratesConfig {
             security: SecurityConfig::default(),
             features: FeaturesConfig::default(),
             logging: LoggingConfig::default(),
+            scud: ScudConfig::default(),
         }
     }
 }
 
 fn default_version() -> String {
-    "1.0.0".to_string()
+    CURRENT_CONFIG_VERSION.to_string()
 }
 
 /// Provider configuration settings
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProvidersConfig {
     /// Default provider to use
     #[serde(default = "default_primary_provider")]
+    #[schemars(schema_with = "primary_provider_schema")]
     pub primary: String,
 
     /// OpenAI provider settings
@@ -96,6 +106,35 @@ fn default_primary_provider() -> String {
     "grok".to_string()
 }
 
+/// Built-in providers `providers.primary` can name
+const BUILTIN_PROVIDERS: &[&str] = &["openai", "anthropic", "ollama", "deepseek", "groq", "grok"];
+
+/// A built-in provider, or the name of a `[providers.custom]` entry
+fn primary_provider_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    use schemars::schema::{InstanceType, Metadata, SchemaObject, SubschemaValidation};
+
+    let builtin = SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        enum_values: Some(BUILTIN_PROVIDERS.iter().map(|p| (*p).into()).collect()),
+        ..Default::default()
+    };
+    let custom = SchemaObject {
+        metadata: Some(Box::new(Metadata {
+            description: Some("Name of a [providers.custom] entry".to_string()),
+            ..Default::default()
+        })),
+        ..gen.subschema_for::<String>().into_object()
+    };
+    SchemaObject {
+        subschemas: Some(Box::new(SubschemaValidation {
+            any_of: Some(vec![builtin.into(), custom.into()]),
+            ..Default::default()
+        })),
+        ..Default::default()
+    }
+    .into()
+}
+
 impl Default for ProvidersConfig {
     fn default() -> Self {
         Self {
@@ -111,8 +150,43 @@ impl Default for ProvidersConfig {
     }
 }
 
+impl ProvidersConfig {
+    /// Configured API keys by provider name, custom providers included
+    fn api_keys(&self) -> Vec<(&str, &str)> {
+        let builtin = [
+            ("openai", &self.openai.api_key),
+            ("anthropic", &self.anthropic.api_key),
+            ("deepseek", &self.deepseek.api_key),
+            ("groq", &self.groq.api_key),
+            ("grok", &self.grok.api_key),
+        ];
+        let custom = self
+            .custom
+            .iter()
+            .map(|(name, provider)| (name.as_str(), &provider.api_key));
+        builtin
+            .into_iter()
+            .chain(custom)
+            .filter_map(|(name, key)| Some((name, key.as_deref()?)))
+            .collect()
+    }
+}
+
+/// Resolve a configured API key, following `env:` and `file:` references.
+///
+/// Returns `None` when the key is missing or resolves to an empty value.
+pub fn resolve_api_key(api_key: Option<&str>) -> AgentResult<Option<String>> {
+    let Some(api_key) = api_key else {
+        return Ok(None);
+    };
+    let secret = SecretRef::parse(api_key)
+        .and_then(|secret| secret.resolve())
+        .map_err(|e| AgentError::ExecutionError(format!("Failed to resolve API key: {}", e)))?;
+    Ok(Some(secret).filter(|s| !s.is_empty()))
+}
+
 /// OpenAI provider configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct OpenAiConfig {
     /// Whether OpenAI provider is enabled
     #[serde(default)]
@@ -126,6 +200,19 @@ pub struct OpenAiConfig {
     #[serde(default = "default_openai_endpoint")]
     pub endpoint: String,
 
+    /// Base URL of an OpenAI-compatible server (vLLM, LiteLLM, ...).
+    /// Takes precedence over `endpoint` when set.
+    #[serde(default)]
+    pub base_url: Option<String>,
+
+    /// Header used to send the API key (defaults to "Authorization")
+    #[serde(default)]
+    pub auth_header: Option<String>,
+
+    /// Scheme prefixed to the API key (defaults to "Bearer"; empty sends the raw key)
+    #[serde(default)]
+    pub auth_scheme: Option<String>,
+
     /// Default model to use
     #[serde(default = "default_openai_model")]
     pub model: String,
@@ -142,9 +229,9 @@ pub struct OpenAiConfig {
     #[serde(default = "default_max_retries")]
     pub max_retries: u32,
 
-    /// Retry backoff in milliseconds
-    #[serde(default = "default_retry_backoff_ms")]
-    pub retry_backoff_ms: u64,
+    /// Backoff before the first retry in milliseconds; doubles on each retry
+    #[serde(default = "default_initial_backoff_ms", alias = "retry_backoff_ms")]
+    pub initial_backoff_ms: u64,
 
     /// Rate limit (requests per minute)
     #[serde(default = "default_rate_limit")]
@@ -165,11 +252,14 @@ impl Default for OpenAiConfig {
             enabled: false,
             api_key: None,
             endpoint: default_openai_endpoint(),
+            base_url: None,
+            auth_header: None,
+            auth_scheme: None,
             model: default_openai_model(),
             models: default_openai_models(),
             timeout_secs: default_timeout(),
             max_retries: default_max_retries(),
-            retry_backoff_ms: default_retry_backoff_ms(),
+            initial_backoff_ms: default_initial_backoff_ms(),
             rate_limit_rpm: default_rate_limit(),
             temperature: default_temperature(),
             max_tokens: default_max_tokens(),
@@ -177,6 +267,28 @@ impl Default for OpenAiConfig {
     }
 }
 
+impl OpenAiConfig {
+    /// URL requests are sent to: `base_url` if set, otherwise `endpoint`
+    pub fn effective_endpoint(&self) -> &str {
+        self.base_url.as_deref().unwrap_or(&self.endpoint)
+    }
+
+    /// Provider factory settings for this configuration (everything but the API key)
+    pub fn provider_settings(&self) -> Vec<(String, String)> {
+        let mut settings = vec![(
+            "endpoint".to_string(),
+            self.effective_endpoint().to_string(),
+        )];
+        if let Some(header) = &self.auth_header {
+            settings.push(("auth_header".to_string(), header.clone()));
+        }
+        if let Some(scheme) = &self.auth_scheme {
+            settings.push(("auth_scheme".to_string(), scheme.clone()));
+        }
+        settings
+    }
+}
+
 fn default_openai_endpoint() -> String {
     "https://api.openai.com/v1".to_string()
 }
@@ -194,7 +306,7 @@ fn default_openai_models() -> Vec<String> {
 }
 
 /// Anthropic provider configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AnthropicConfig {
     /// Whether Anthropic provider is enabled
     #[serde(default = "default_true")]
@@ -220,13 +332,13 @@ pub struct AnthropicConfig {
     #[serde(default = "default_timeout")]
     pub timeout_secs: u64,
 
-    /// Max retries on transient failures
+    /// Max retries on rate-limited (429) and overloaded (529/503) responses
     #[serde(default = "default_max_retries")]
     pub max_retries: u32,
 
-    /// Retry backoff in milliseconds
-    #[serde(default = "default_retry_backoff_ms")]
-    pub retry_backoff_ms: u64,
+    /// Backoff before the first retry in milliseconds; doubles on each retry
+    #[serde(default = "default_initial_backoff_ms", alias = "retry_backoff_ms")]
+    pub initial_backoff_ms: u64,
 
     /// Rate limit (requests per minute)
     #[serde(default = "default_rate_limit")]
@@ -251,7 +363,7 @@ impl Default for AnthropicConfig {
             models: default_anthropic_models(),
             timeout_secs: default_timeout(),
             max_retries: default_max_retries(),
-            retry_backoff_ms: default_retry_backoff_ms(),
+            initial_backoff_ms: default_initial_backoff_ms(),
             rate_limit_rpm: default_rate_limit(),
             temperature: default_temperature(),
             max_tokens: default_max_tokens(),
@@ -280,7 +392,7 @@ fn default_anthropic_models() -> Vec<String> {
 }
 
 /// Ollama local provider configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct OllamaConfig {
     /// Whether Ollama provider is enabled
     #[serde(default)]
@@ -302,9 +414,9 @@ pub struct OllamaConfig {
     #[serde(default = "default_max_retries")]
     pub max_retries: u32,
 
-    /// Retry backoff in milliseconds
-    #[serde(default = "default_retry_backoff_ms")]
-    pub retry_backoff_ms: u64,
+    /// Backoff before the first retry in milliseconds; doubles on each retry
+    #[serde(default = "default_initial_backoff_ms", alias = "retry_backoff_ms")]
+    pub initial_backoff_ms: u64,
 
     /// Temperature for generation
     #[serde(default = "default_temperature")]
@@ -323,7 +435,7 @@ impl Default for OllamaConfig {
             model: default_ollama_model(),
             timeout_secs: default_timeout(),
             max_retries: default_max_retries(),
-            retry_backoff_ms: default_retry_backoff_ms(),
+            initial_backoff_ms: default_initial_backoff_ms(),
             temperature: default_temperature(),
             max_tokens: default_max_tokens(),
         }
@@ -339,7 +451,7 @@ fn default_ollama_model() -> String {
 }
 
 /// DeepSeek provider configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DeepSeekConfig {
     /// Whether DeepSeek provider is enabled
     #[serde(default)]
@@ -365,9 +477,9 @@ pub struct DeepSeekConfig {
     #[serde(default = "default_max_retries")]
     pub max_retries: u32,
 
-    /// Retry backoff in milliseconds
-    #[serde(default = "default_retry_backoff_ms")]
-    pub retry_backoff_ms: u64,
+    /// Backoff before the first retry in milliseconds; doubles on each retry
+    #[serde(default = "default_initial_backoff_ms", alias = "retry_backoff_ms")]
+    pub initial_backoff_ms: u64,
 
     /// Temperature for generation
     #[serde(default = "default_temperature")]
@@ -387,7 +499,7 @@ impl Default for DeepSeekConfig {
             model: default_deepseek_model(),
             timeout_secs: default_timeout(),
             max_retries: default_max_retries(),
-            retry_backoff_ms: default_retry_backoff_ms(),
+            initial_backoff_ms: default_initial_backoff_ms(),
             temperature: default_temperature(),
             max_tokens: default_max_tokens(),
         }
@@ -403,7 +515,7 @@ fn default_deepseek_model() -> String {
 }
 
 /// Groq provider configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct GroqConfig {
     /// Whether Groq provider is enabled
     #[serde(default)]
@@ -429,9 +541,9 @@ pub struct GroqConfig {
     #[serde(default = "default_max_retries")]
     pub max_retries: u32,
 
-    /// Retry backoff in milliseconds
-    #[serde(default = "default_retry_backoff_ms")]
-    pub retry_backoff_ms: u64,
+    /// Backoff before the first retry in milliseconds; doubles on each retry
+    #[serde(default = "default_initial_backoff_ms", alias = "retry_backoff_ms")]
+    pub initial_backoff_ms: u64,
 
     /// Temperature for generation
     #[serde(default = "default_temperature")]
@@ -451,7 +563,7 @@ impl Default for GroqConfig {
             model: default_groq_model(),
             timeout_secs: default_timeout(),
             max_retries: default_max_retries(),
-            retry_backoff_ms: default_retry_backoff_ms(),
+            initial_backoff_ms: default_initial_backoff_ms(),
             temperature: default_temperature(),
             max_tokens: default_max_tokens(),
         }
@@ -467,7 +579,7 @@ fn default_groq_model() -> String {
 }
 
 /// Grok (xAI) provider configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct GrokConfig {
     /// Whether Grok provider is enabled
     #[serde(default = "default_true")]
@@ -493,9 +605,9 @@ pub struct GrokConfig {
     #[serde(default = "default_max_retries")]
     pub max_retries: u32,
 
-    /// Retry backoff in milliseconds
-    #[serde(default = "default_retry_backoff_ms")]
-    pub retry_backoff_ms: u64,
+    /// Backoff before the first retry in milliseconds; doubles on each retry
+    #[serde(default = "default_initial_backoff_ms", alias = "retry_backoff_ms")]
+    pub initial_backoff_ms: u64,
 
     /// Temperature for generation
     #[serde(default = "default_temperature")]
@@ -515,7 +627,7 @@ impl Default for GrokConfig {
             model: default_grok_model(),
             timeout_secs: default_timeout(),
             max_retries: default_max_retries(),
-            retry_backoff_ms: default_retry_backoff_ms(),
+            initial_backoff_ms: default_initial_backoff_ms(),
             temperature: default_temperature(),
             max_tokens: default_max_tokens(),
         }
@@ -531,7 +643,7 @@ fn default_grok_model() -> String {
 }
 
 /// Custom provider configuration (for proxies, self-hosted, etc.)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CustomProviderConfig {
     /// API endpoint URL
     pub endpoint: String,
@@ -565,7 +677,7 @@ fn default_max_retries() -> u32 {
     3
 }
 
-fn default_retry_backoff_ms() -> u64 {
+fn default_initial_backoff_ms() -> u64 {
     1000
 }
 
@@ -582,7 +694,7 @@ fn default_max_tokens() -> usize {
 }
 
 /// Agent behavior configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AgentBehaviorConfig {
     /// Default agent execution timeout in seconds
     #[serde(default = "default_agent_timeout")]
@@ -685,7 +797,7 @@ fn default_worker_threads() -> usize {
 }
 
 /// Storage and persistence configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct StorageConfig {
     /// Base storage directory (defaults to ~/.descartes)
     #[serde(default = "default_storage_path")]
@@ -726,7 +838,7 @@ fn default_storage_path() -> String {
 }
 
 /// Database configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DatabaseConfig {
     /// Database type (sqlite, postgres, mysql)
     #[serde(default = "default_db_type")]
@@ -788,7 +900,7 @@ fn default_backup_interval() -> u32 {
 }
 
 /// State store configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct StateStoreConfig {
     /// Enable state persistence
     #[serde(default = "default_true")]
@@ -827,7 +939,7 @@ fn default_serialization_format() -> String {
 }
 
 /// Event store configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct EventStoreConfig {
     /// Enable event persistence
     #[serde(default = "default_true")]
@@ -871,7 +983,7 @@ fn default_event_batch_size() -> usize {
 }
 
 /// Cache configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CacheConfig {
     /// Enable caching
     #[serde(default = "default_true")]
@@ -928,7 +1040,7 @@ fn default_max_cache_size() -> u64 {
 }
 
 /// Security configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SecurityConfig {
     /// Enable encryption for sensitive data
     #[serde(default = "default_true")]
@@ -1005,7 +1117,7 @@ fn default_audit_path() -> String {
 }
 
 /// Feature flags and experimental options
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FeaturesConfig {
     /// Enable experimental features
     #[serde(default)]
@@ -1045,8 +1157,16 @@ impl Default for FeaturesConfig {
     }
 }
 
+/// SCUD task graph configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ScudConfig {
+    /// How `tasks next` picks among ready tasks (critical_path, priority, fifo)
+    #[serde(default)]
+    pub schedule: TaskSchedule,
+}
+
 /// Logging and observability configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LoggingConfig {
     /// Log level (trace, debug, info, warn, error)
     #[serde(default = "default_log_level")]
@@ -1095,7 +1215,7 @@ fn default_log_format() -> String {
 }
 
 /// Log output targets
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LogTargets {
     /// Enable stdout logging
     #[serde(default = "default_true")]
@@ -1121,7 +1241,7 @@ impl Default for LogTargets {
 }
 
 /// Log file configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LogFileConfig {
     /// Log file path (relative to storage.base_path)
     pub path: String,
@@ -1147,6 +1267,213 @@ fn default_max_backups() -> u32 {
     10
 }
 
+/// Name of the audit log written next to the config file
+pub const CONFIG_AUDIT_LOG_FILE: &str = "config-audit.log";
+
+/// One field that differs between two configurations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigFieldChange {
+    /// Dotted path of the field, e.g. `providers.anthropic.model`
+    pub field: String,
+    /// Previous value (None if the field was added)
+    pub old: Option<serde_json::Value>,
+    /// New value (None if the field was removed)
+    pub new: Option<serde_json::Value>,
+}
+
+/// One line of the config audit log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigAuditEntry {
+    /// When the change was saved
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// OS user that saved the change, if known
+    pub user: Option<String>,
+    /// Config file that changed
+    pub config_path: PathBuf,
+    /// Fields that changed
+    pub changes: Vec<ConfigFieldChange>,
+}
+
+/// JSON Schema describing `config.toml`, generated from [`DescaratesConfig`]
+///
+/// Editors with schema support (e.g. Taplo / Even Better TOML) can use it to
+/// complete and validate config files.
+pub fn config_json_schema() -> serde_json::Value {
+    let schema = schemars::schema_for!(DescaratesConfig);
+    serde_json::to_value(schema).unwrap_or_default()
+}
+
+/// Fields that differ between `old` and `new`, sorted by path
+///
+/// Values of secret-looking fields (keys, tokens, passwords) are redacted.
+pub fn diff_configs(old: &DescaratesConfig, new: &DescaratesConfig) -> Vec<ConfigFieldChange> {
+    let mut old_fields = std::collections::BTreeMap::new();
+    let mut new_fields = std::collections::BTreeMap::new();
+    flatten_config_value(
+        "",
+        serde_json::to_value(old).unwrap_or_default(),
+        &mut old_fields,
+    );
+    flatten_config_value(
+        "",
+        serde_json::to_value(new).unwrap_or_default(),
+        &mut new_fields,
+    );
+
+    let fields: std::collections::BTreeSet<_> = old_fields
+        .keys()
+        .chain(new_fields.keys())
+        .cloned()
+        .collect();
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let old = old_fields.remove(&field);
+            let new = new_fields.remove(&field);
+            if old == new {
+                return None;
+            }
+            let (old, new) = if is_secret_field(&field) {
+                let redacted = |v: Option<serde_json::Value>| {
+                    v.map(|_| serde_json::Value::String("<redacted>".to_string()))
+                };
+                (redacted(old), redacted(new))
+            } else {
+                (old, new)
+            };
+            Some(ConfigFieldChange { field, old, new })
+        })
+        .collect()
+}
+
+/// Top-level section of the configuration a change falls in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSection {
+    Providers,
+    Agent,
+    Storage,
+    Security,
+    Features,
+    Logging,
+    Scud,
+    /// Anything else, e.g. the config version
+    Other,
+}
+
+impl ConfigSection {
+    /// Section a dotted field path belongs to
+    pub fn of(field: &str) -> Self {
+        match field.split('.').next().unwrap_or_default() {
+            "providers" => ConfigSection::Providers,
+            "agent" => ConfigSection::Agent,
+            "storage" => ConfigSection::Storage,
+            "security" => ConfigSection::Security,
+            "features" => ConfigSection::Features,
+            "logging" => ConfigSection::Logging,
+            "scud" => ConfigSection::Scud,
+            _ => ConfigSection::Other,
+        }
+    }
+}
+
+/// Changes between two configurations, grouped by section
+///
+/// Lets reload listeners restart only what a change touches, e.g. leave
+/// providers alone when only the log level changed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigDiff {
+    changes: Vec<ConfigFieldChange>,
+}
+
+impl ConfigDiff {
+    /// Diff `old` against `new` (see [`diff_configs`])
+    pub fn between(old: &DescaratesConfig, new: &DescaratesConfig) -> Self {
+        Self {
+            changes: diff_configs(old, new),
+        }
+    }
+
+    /// Whether nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Every changed field, sorted by path
+    pub fn changes(&self) -> &[ConfigFieldChange] {
+        &self.changes
+    }
+
+    /// Sections with at least one changed field
+    pub fn sections(&self) -> std::collections::BTreeSet<ConfigSection> {
+        self.changes
+            .iter()
+            .map(|c| ConfigSection::of(&c.field))
+            .collect()
+    }
+
+    /// Whether any field in `section` changed
+    pub fn affects(&self, section: ConfigSection) -> bool {
+        self.changes
+            .iter()
+            .any(|c| ConfigSection::of(&c.field) == section)
+    }
+
+    /// Changed fields in `section`
+    pub fn section_changes(
+        &self,
+        section: ConfigSection,
+    ) -> impl Iterator<Item = &ConfigFieldChange> {
+        self.changes
+            .iter()
+            .filter(move |c| ConfigSection::of(&c.field) == section)
+    }
+}
+
+fn flatten_config_value(
+    prefix: &str,
+    value: serde_json::Value,
+    out: &mut std::collections::BTreeMap<String, serde_json::Value>,
+) {
+    match value {
+        serde_json::Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                let path = if prefix.is_empty() {
+                    key
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten_config_value(&path, value, out);
+            }
+        }
+        // Absent optional fields and present-but-null fields are the same change
+        serde_json::Value::Null => {}
+        value => {
+            out.insert(prefix.to_string(), value);
+        }
+    }
+}
+
+/// Whether a field holds a credential
+///
+/// Every value under `custom_headers` is treated as secret since headers
+/// routinely carry credentials, as is any `auth*` setting. Otherwise the last
+/// path segment decides (`api_key`, `secret_key`, ...); settings that merely
+/// mention one, like `max_tokens` or `encrypt_api_keys`, are not secrets.
+fn is_secret_field(field: &str) -> bool {
+    let mut segments = field.split('.').map(|s| s.to_lowercase());
+    let name = segments.next_back().unwrap_or_default();
+    if segments.any(|segment| segment == "custom_headers") {
+        return true;
+    }
+    if name.starts_with("auth") || name.ends_with("authorization") {
+        return true;
+    }
+    ["key", "secret", "token", "password"]
+        .iter()
+        .any(|marker| name == *marker || name.ends_with(&format!("_{}", marker)))
+}
+
 /// Configuration loader and manager
 pub struct ConfigManager {
     config: DescaratesConfig,
@@ -1177,9 +1504,21 @@ impl ConfigManager {
             let content = std::fs::read_to_string(&path).map_err(|e| {
                 AgentError::ExecutionError(format!("Failed to read config file: {}", e))
             })?;
-            toml::from_str(&content).map_err(|e| {
-                AgentError::ExecutionError(format!("Failed to parse config file: {}", e))
-            })?
+            if ConfigMigration::detect_version(&content)? == CURRENT_CONFIG_VERSION {
+                toml::from_str(&content).map_err(|e| {
+                    AgentError::ExecutionError(format!("Failed to parse config file: {}", e))
+                })?
+            } else {
+                // Migrate in memory so renamed fields keep their values
+                let (config, report) = ConfigMigration::migrate_toml(&content)?;
+                if !report.is_up_to_date() {
+                    warn!(
+                        "Config {:?} uses version {}; run `descartes config migrate` to update it",
+                        path, report.from_version
+                    );
+                }
+                config
+            }
         } else {
             warn!("Config file not found at {:?}, using defaults", path);
             DescaratesConfig::default()
@@ -1215,14 +1554,65 @@ impl ConfigManager {
             AgentError::ExecutionError(format!("Failed to serialize config: {}", e))
         })?;
 
+        // Diff against what is on disk, so external edits since load are
+        // attributed to this save rather than lost
+        let previous = std::fs::read_to_string(&self.config_path)
+            .ok()
+            .and_then(|content| toml::from_str::<DescaratesConfig>(&content).ok());
+
         std::fs::write(&self.config_path, content).map_err(|e| {
             AgentError::ExecutionError(format!("Failed to write config file: {}", e))
         })?;
 
+        if let Some(previous) = previous {
+            self.record_audit(&previous)?;
+        }
+
         info!("Configuration saved to {:?}", self.config_path);
         Ok(())
     }
 
+    /// Path of the audit log that [`ConfigManager::save`] appends to
+    pub fn audit_log_path(&self) -> PathBuf {
+        self.config_path.with_file_name(CONFIG_AUDIT_LOG_FILE)
+    }
+
+    /// Append the fields that changed since `previous` to the audit log
+    fn record_audit(&self, previous: &DescaratesConfig) -> AgentResult<()> {
+        use std::io::Write;
+
+        let changes = diff_configs(previous, &self.config);
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        let entry = ConfigAuditEntry {
+            timestamp: chrono::Utc::now(),
+            user: std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .ok(),
+            config_path: self.config_path.clone(),
+            changes,
+        };
+        let line = serde_json::to_string(&entry).map_err(|e| {
+            AgentError::ExecutionError(format!("Failed to serialize config audit entry: {}", e))
+        })?;
+
+        let mut log = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.audit_log_path())
+            .map_err(|e| {
+                AgentError::ExecutionError(format!("Failed to open config audit log: {}", e))
+            })?;
+        writeln!(log, "{}", line).map_err(|e| {
+            AgentError::ExecutionError(format!("Failed to write config audit log: {}", e))
+        })?;
+
+        debug!("Recorded {} config change(s)", entry.changes.len());
+        Ok(())
+    }
+
     /// Validate configuration
     pub fn validate(&self) -> AgentResult<()> {
         // Validate provider settings
@@ -1234,6 +1624,13 @@ impl ConfigManager {
             warn!("No providers configured - ensure at least one provider is enabled");
         }
 
+        // API keys may be references, resolved at spawn time; check they're well-formed
+        for (provider, api_key) in self.config.providers.api_keys() {
+            SecretRef::parse(api_key).map_err(|e| {
+                AgentError::ExecutionError(format!("Invalid API key for {}: {}", provider, e))
+            })?;
+        }
+
         // Validate storage paths
         if self.config.storage.database.pool_size == 0 {
             return Err(AgentError::ExecutionError(
@@ -1269,6 +1666,10 @@ impl ConfigManager {
             self.config.providers.openai.enabled = true;
         }
 
+        if let Ok(url) = std::env::var("OPENAI_BASE_URL") {
+            self.config.providers.openai.base_url = Some(url);
+        }
+
         if let Ok(key) = std::env::var("ANTHROPIC_API_KEY") {
             self.config.providers.anthropic.api_key = Some(key);
         }
@@ -1310,7 +1711,7 @@ mod tests {
     #[test]
     fn test_default_config() {
         let config = DescaratesConfig::default();
-        assert_eq!(config.version, "1.0.0");
+        assert_eq!(config.version, "2.0.0");
         assert_eq!(config.providers.primary, "grok");
     }
 
@@ -1321,6 +1722,22 @@ mod tests {
         assert_eq!(config.timeout_secs, 120);
     }
 
+    #[test]
+    fn test_openai_base_url_override() {
+        let mut config = OpenAiConfig::default();
+        assert_eq!(config.effective_endpoint(), "https://api.openai.com/v1");
+
+        config.base_url = Some("http://localhost:8000/v1".to_string());
+        config.auth_header = Some("api-key".to_string());
+        config.auth_scheme = Some(String::new());
+        assert_eq!(config.effective_endpoint(), "http://localhost:8000/v1");
+
+        let settings: HashMap<_, _> = config.provider_settings().into_iter().collect();
+        assert_eq!(settings["endpoint"], "http://localhost:8000/v1");
+        assert_eq!(settings["auth_header"], "api-key");
+        assert_eq!(settings["auth_scheme"], "");
+    }
+
     #[test]
     fn test_agent_behavior_defaults() {
         let config = AgentBehaviorConfig::default();
@@ -1354,4 +1771,181 @@ mod tests {
 
         assert!(manager.validate().is_err());
     }
+
+    #[test]
+    fn test_api_keys_accept_secret_references() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut manager = ConfigManager::load(Some(&temp_dir.path().join("config.toml"))).unwrap();
+
+        // References are checked for shape on load and only resolved at spawn time
+        manager.config_mut().providers.anthropic.api_key =
+            Some("env:DESCARTES_TEST_UNSET_API_KEY".to_string());
+        assert!(manager.validate().is_ok());
+        let api_key = manager.config().providers.anthropic.api_key.as_deref();
+        let err = resolve_api_key(api_key).unwrap_err();
+        assert!(err.to_string().contains("is not set"));
+
+        manager.config_mut().providers.openai.api_key = Some("file:".to_string());
+        let err = manager.validate().unwrap_err();
+        assert!(err.to_string().contains("Invalid API key for openai"));
+
+        let inline = resolve_api_key(Some("sk-inline")).unwrap();
+        assert_eq!(inline.as_deref(), Some("sk-inline"));
+        assert_eq!(resolve_api_key(Some("")).unwrap(), None);
+    }
+
+    #[test]
+    fn test_config_json_schema() {
+        let schema = config_json_schema();
+        let definitions = &schema["definitions"];
+        assert_eq!(schema["title"], "DescaratesConfig");
+        assert!(schema["properties"]["providers"].is_object());
+
+        // Fields without serde defaults are required
+        let required = definitions["CustomProviderConfig"]["required"]
+            .as_array()
+            .unwrap();
+        assert!(required.contains(&"endpoint".into()) && required.contains(&"model".into()));
+        assert!(definitions["ProvidersConfig"].get("required").is_none());
+
+        let primary = &definitions["ProvidersConfig"]["properties"]["primary"];
+        assert_eq!(primary["default"], "grok");
+        let builtin = primary["anyOf"][0]["enum"].as_array().unwrap();
+        assert!(builtin.contains(&"anthropic".into()));
+        let schedules: Vec<_> = definitions["TaskSchedule"]["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|variant| variant["enum"][0].clone())
+            .collect();
+        assert_eq!(schedules, vec!["critical_path", "priority", "fifo"]);
+    }
+
+    #[test]
+    fn test_save_records_changed_fields_in_audit_log() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.toml");
+
+        let mut manager = ConfigManager::load(Some(&config_path)).unwrap();
+        manager.save().unwrap();
+        assert!(!manager.audit_log_path().exists());
+
+        manager.config_mut().providers.anthropic.model = "claude-opus-4".to_string();
+        manager.config_mut().providers.anthropic.api_key = Some("sk-test".to_string());
+        manager.save().unwrap();
+
+        let log = std::fs::read_to_string(manager.audit_log_path()).unwrap();
+        let entries: Vec<ConfigAuditEntry> = log
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 1);
+
+        let changes = &entries[0].changes;
+        let model = changes
+            .iter()
+            .find(|c| c.field == "providers.anthropic.model")
+            .unwrap();
+        assert_eq!(
+            model.new,
+            Some(serde_json::Value::String("claude-opus-4".to_string()))
+        );
+        let api_key = changes
+            .iter()
+            .find(|c| c.field == "providers.anthropic.api_key")
+            .unwrap();
+        assert_eq!(api_key.old, None);
+        assert_eq!(
+            api_key.new,
+            Some(serde_json::Value::String("<redacted>".to_string()))
+        );
+        assert!(!log.contains("sk-test"));
+
+        // Saving without changes adds nothing
+        manager.save().unwrap();
+        let log = std::fs::read_to_string(manager.audit_log_path()).unwrap();
+        assert_eq!(log.lines().count(), 1);
+    }
+
+    #[test]
+    fn test_auth_headers_are_redacted_in_audit_log() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.toml");
+
+        let mut manager = ConfigManager::load(Some(&config_path)).unwrap();
+        manager.save().unwrap();
+
+        let mut custom = CustomProviderConfig {
+            endpoint: "https://llm.internal".to_string(),
+            api_key: None,
+            model: "local".to_string(),
+            timeout_secs: 30,
+            use_bearer_auth: false,
+            custom_headers: HashMap::new(),
+        };
+        custom
+            .custom_headers
+            .insert("Authorization".to_string(), "Bearer hunter2".to_string());
+        manager
+            .config_mut()
+            .providers
+            .custom
+            .insert("proxy".to_string(), custom);
+        manager.config_mut().providers.openai.auth_header = Some("x-hunter2-key".to_string());
+        manager.save().unwrap();
+
+        let log = std::fs::read_to_string(manager.audit_log_path()).unwrap();
+        assert!(!log.contains("hunter2"));
+
+        let entry: ConfigAuditEntry = serde_json::from_str(log.lines().next().unwrap()).unwrap();
+        let redacted = Some(serde_json::Value::String("<redacted>".to_string()));
+        for field in [
+            "providers.custom.proxy.custom_headers.Authorization",
+            "providers.openai.auth_header",
+        ] {
+            let change = entry.changes.iter().find(|c| c.field == field).unwrap();
+            assert_eq!(change.new, redacted, "{} should be redacted", field);
+        }
+        let model = entry
+            .changes
+            .iter()
+            .find(|c| c.field == "providers.custom.proxy.model")
+            .unwrap();
+        assert_eq!(model.new, Some(serde_json::json!("local")));
+    }
+
+    #[test]
+    fn test_settings_mentioning_secrets_are_not_redacted() {
+        let old = DescaratesConfig::default();
+        let mut new = old.clone();
+        new.providers.anthropic.max_tokens += 1;
+        new.security.encrypt_api_keys = !old.security.encrypt_api_keys;
+        new.security.secret_key = Some("hunter2".to_string());
+
+        let changes = diff_configs(&old, &new);
+        let max_tokens = changes
+            .iter()
+            .find(|c| c.field == "providers.anthropic.max_tokens")
+            .unwrap();
+        assert_eq!(
+            max_tokens.new,
+            Some(serde_json::json!(old.providers.anthropic.max_tokens + 1))
+        );
+        let encrypt = changes
+            .iter()
+            .find(|c| c.field == "security.encrypt_api_keys")
+            .unwrap();
+        assert_eq!(
+            encrypt.new,
+            Some(serde_json::Value::Bool(new.security.encrypt_api_keys))
+        );
+        let secret = changes
+            .iter()
+            .find(|c| c.field == "security.secret_key")
+            .unwrap();
+        assert_eq!(
+            secret.new,
+            Some(serde_json::Value::String("<redacted>".to_string()))
+        );
+    }
 }
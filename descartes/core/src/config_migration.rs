@@ -2,9 +2,135 @@
 /// Handles migrations between different config versions to ensure backwards compatibility
 use crate::config::DescaratesConfig;
 use crate::errors::{AgentError, AgentResult};
-use serde_json::{json, Value};
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fmt;
+use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
 
+/// Config layout version written by this build
+pub const CURRENT_CONFIG_VERSION: &str = "2.0.0";
+
+/// Version assumed for config files that don't declare one
+const UNVERSIONED_CONFIG_VERSION: &str = "1.0.0";
+
+/// One edit to the raw config tree. Paths are dotted TOML keys.
+enum FieldOp {
+    /// Move a value to a new key, unless the new key is already set
+    Rename(&'static str, &'static str),
+    /// Set a key, given as a JSON literal, if the file doesn't set it
+    Default(&'static str, &'static str),
+}
+
+/// Edits that bring a config from `from_major` to the next major version
+struct MigrationStep {
+    from_major: u32,
+    ops: &'static [FieldOp],
+}
+
+/// Layout changes, oldest first
+const MIGRATION_STEPS: &[MigrationStep] = &[
+    MigrationStep {
+        from_major: 0,
+        ops: &[FieldOp::Rename("old_provider_field", "providers.primary")],
+    },
+    MigrationStep {
+        from_major: 1,
+        ops: &[
+            FieldOp::Rename(
+                "providers.openai.retry_backoff_ms",
+                "providers.openai.initial_backoff_ms",
+            ),
+            FieldOp::Rename(
+                "providers.anthropic.retry_backoff_ms",
+                "providers.anthropic.initial_backoff_ms",
+            ),
+            FieldOp::Rename(
+                "providers.ollama.retry_backoff_ms",
+                "providers.ollama.initial_backoff_ms",
+            ),
+            FieldOp::Rename(
+                "providers.deepseek.retry_backoff_ms",
+                "providers.deepseek.initial_backoff_ms",
+            ),
+            FieldOp::Rename(
+                "providers.groq.retry_backoff_ms",
+                "providers.groq.initial_backoff_ms",
+            ),
+            FieldOp::Rename(
+                "providers.grok.retry_backoff_ms",
+                "providers.grok.initial_backoff_ms",
+            ),
+            FieldOp::Default("security.enable_encryption", "true"),
+            FieldOp::Default("security.encryption_algorithm", "\"aes-256-gcm\""),
+            FieldOp::Default("scud.schedule", "\"critical_path\""),
+        ],
+    },
+];
+
+/// A field touched by a migration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FieldChange {
+    /// Value moved to another table
+    Moved { from: String, to: String },
+    /// Value kept its table but changed key
+    Renamed { from: String, to: String },
+    /// Field was missing and got the new version's default
+    Defaulted { field: String, value: String },
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldChange::Moved { from, to } => write!(f, "moved     {} -> {}", from, to),
+            FieldChange::Renamed { from, to } => write!(f, "renamed   {} -> {}", from, to),
+            FieldChange::Defaulted { field, value } => {
+                write!(f, "defaulted {} = {}", field, value)
+            }
+        }
+    }
+}
+
+/// What a migration changed, in the order the changes were applied
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationReport {
+    pub from_version: String,
+    pub to_version: String,
+    pub changes: Vec<FieldChange>,
+    /// Copy of the original file, once the migrated config has been written
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup_path: Option<PathBuf>,
+}
+
+impl MigrationReport {
+    /// Whether the config was already at the current version
+    pub fn is_up_to_date(&self) -> bool {
+        self.from_version == self.to_version
+    }
+}
+
+impl fmt::Display for MigrationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_up_to_date() {
+            return writeln!(f, "Config is already at version {}", self.to_version);
+        }
+        writeln!(
+            f,
+            "Config migrated from {} to {}",
+            self.from_version, self.to_version
+        )?;
+        for change in &self.changes {
+            writeln!(f, "  {}", change)?;
+        }
+        if let Some(backup) = &self.backup_path {
+            writeln!(f, "Original saved to {}", backup.display())?;
+        }
+        Ok(())
+    }
+}
+
 /// Configuration migration handler
 pub struct ConfigMigration;
 
@@ -94,50 +220,194 @@ impl ConfigMigration {
     pub fn migrate_from_json(mut json: Value, from_version: &str) -> AgentResult<Value> {
         info!("Migrating JSON configuration from version {}", from_version);
 
-        let from_major = parse_version(from_version)?;
-
         // Ensure version field exists
         if json.get("version").is_none() {
             json["version"] = Value::String(from_version.to_string());
         }
 
-        // Apply JSON-level migrations
-        for version in from_major..2 {
-            match version {
-                0 => {
-                    debug!("Applying JSON migration for v0.x to v1.x");
-                    Self::migrate_json_v0_to_v1(&mut json)?;
-                }
-                1 => {
-                    debug!("Applying JSON migration for v1.x to v2.x");
-                    Self::migrate_json_v1_to_v2(&mut json)?;
-                }
-                _ => {}
-            }
+        apply_steps(&mut json, parse_version(from_version)?)?;
+        Ok(json)
+    }
+
+    /// Version declared by a config.toml's contents
+    pub fn detect_version(content: &str) -> AgentResult<String> {
+        let table: toml::Table = toml::from_str(content).map_err(|e| {
+            AgentError::ExecutionError(format!("Failed to parse config file: {}", e))
+        })?;
+        Ok(table
+            .get("version")
+            .and_then(|v| v.as_str())
+            .unwrap_or(UNVERSIONED_CONFIG_VERSION)
+            .to_string())
+    }
+
+    /// Bring a config.toml's contents to the current layout.
+    ///
+    /// Steps run on the raw tree before it is parsed, so renamed keys carry
+    /// their values over instead of being dropped in favor of defaults.
+    pub fn migrate_toml(content: &str) -> AgentResult<(DescaratesConfig, MigrationReport)> {
+        let from_version = Self::detect_version(content)?;
+        let from_major = parse_version(&from_version)?;
+        let current_major = parse_version(CURRENT_CONFIG_VERSION)?;
+        if from_major > current_major {
+            return Err(AgentError::ExecutionError(format!(
+                "Config version {} is newer than this build supports ({})",
+                from_version, CURRENT_CONFIG_VERSION
+            )));
         }
 
-        Ok(json)
+        let mut json: Value = toml::from_str(content).map_err(|e| {
+            AgentError::ExecutionError(format!("Failed to parse config file: {}", e))
+        })?;
+        let changes = apply_steps(&mut json, from_major)?;
+        json["version"] = Value::String(CURRENT_CONFIG_VERSION.to_string());
+
+        let config = serde_json::from_value(json).map_err(|e| {
+            AgentError::ExecutionError(format!("Migrated config is invalid: {}", e))
+        })?;
+        let report = MigrationReport {
+            from_version: if from_major == current_major {
+                CURRENT_CONFIG_VERSION.to_string()
+            } else {
+                from_version
+            },
+            to_version: CURRENT_CONFIG_VERSION.to_string(),
+            changes,
+            backup_path: None,
+        };
+        Ok((config, report))
     }
 
-    fn migrate_json_v0_to_v1(json: &mut Value) -> AgentResult<()> {
-        // Example: Rename old field names
-        if let Some(old_provider) = json.get("old_provider_field").cloned() {
-            json["providers"]["primary"] = old_provider;
-            json.as_object_mut().unwrap().remove("old_provider_field");
+    /// Migrate the config file at `path` in place.
+    ///
+    /// The original is copied to `<name>.<timestamp>.bak` next to it, and the
+    /// migrated config is written to a new file that then replaces it. With
+    /// `dry_run`, or when the file is already current, nothing is written.
+    pub fn migrate_file(path: &Path, dry_run: bool) -> AgentResult<MigrationReport> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            AgentError::ExecutionError(format!("Failed to read config file: {}", e))
+        })?;
+        let (config, mut report) = Self::migrate_toml(&content)?;
+        if dry_run || report.is_up_to_date() {
+            return Ok(report);
         }
-        Ok(())
+
+        let migrated = toml::to_string_pretty(&config).map_err(|e| {
+            AgentError::ExecutionError(format!("Failed to serialize config: {}", e))
+        })?;
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "config.toml".to_string());
+        let backup = path.with_file_name(format!(
+            "{}.{}.bak",
+            file_name,
+            Utc::now().format("%Y%m%d%H%M%S")
+        ));
+        let staged = path.with_file_name(format!("{}.migrated", file_name));
+
+        let write_error = |e: std::io::Error| {
+            AgentError::ExecutionError(format!("Failed to migrate config: {}", e))
+        };
+        std::fs::copy(path, &backup).map_err(write_error)?;
+        std::fs::write(&staged, migrated).map_err(write_error)?;
+        std::fs::rename(&staged, path).map_err(write_error)?;
+
+        info!(
+            "Migrated {:?} from {} to {}, original kept at {:?}",
+            path, report.from_version, report.to_version, backup
+        );
+        report.backup_path = Some(backup);
+        Ok(report)
     }
+}
 
-    fn migrate_json_v1_to_v2(json: &mut Value) -> AgentResult<()> {
-        // Example: Add new required fields with defaults
-        if json.get("security").is_none() {
-            json["security"] = json!({
-                "enable_encryption": true,
-                "encryption_algorithm": "aes-256-gcm"
-            });
+/// Apply every step from `from_major` on, returning what changed
+fn apply_steps(json: &mut Value, from_major: u32) -> AgentResult<Vec<FieldChange>> {
+    let mut changes = Vec::new();
+    for step in MIGRATION_STEPS
+        .iter()
+        .filter(|s| s.from_major >= from_major)
+    {
+        debug!(
+            "Applying config migration v{} -> v{}",
+            step.from_major,
+            step.from_major + 1
+        );
+        for op in step.ops {
+            match *op {
+                FieldOp::Rename(from, to) => {
+                    let Some(value) = take_path(json, from) else {
+                        continue;
+                    };
+                    if get_path(json, to).is_some() {
+                        warn!("Dropping {} from config: {} is already set", from, to);
+                        continue;
+                    }
+                    set_path(json, to, value)?;
+                    let (from, to) = (from.to_string(), to.to_string());
+                    changes.push(if parent(&from) == parent(&to) {
+                        FieldChange::Renamed { from, to }
+                    } else {
+                        FieldChange::Moved { from, to }
+                    });
+                }
+                FieldOp::Default(field, literal) => {
+                    if get_path(json, field).is_some() {
+                        continue;
+                    }
+                    let value: Value = serde_json::from_str(literal).map_err(|e| {
+                        AgentError::ExecutionError(format!("Bad default for {}: {}", field, e))
+                    })?;
+                    set_path(json, field, value)?;
+                    changes.push(FieldChange::Defaulted {
+                        field: field.to_string(),
+                        value: literal.to_string(),
+                    });
+                }
+            }
         }
-        Ok(())
     }
+    Ok(changes)
+}
+
+fn parent(path: &str) -> &str {
+    path.rsplit_once('.').map_or("", |(parent, _)| parent)
+}
+
+fn get_path<'a>(json: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(json, |value, key| value.get(key))
+}
+
+fn take_path(json: &mut Value, path: &str) -> Option<Value> {
+    let (table, key) = match path.rsplit_once('.') {
+        Some((parent, key)) => (
+            parent
+                .split('.')
+                .try_fold(json, |value, key| value.get_mut(key))?,
+            key,
+        ),
+        None => (json, path),
+    };
+    table.as_object_mut()?.remove(key)
+}
+
+fn set_path(json: &mut Value, path: &str, value: Value) -> AgentResult<()> {
+    let mut keys: Vec<&str> = path.split('.').collect();
+    let last = keys.pop().unwrap_or(path);
+    let mut table = json;
+    for key in keys {
+        table = table
+            .as_object_mut()
+            .ok_or_else(|| AgentError::ExecutionError(format!("{} is not a table", path)))?
+            .entry(key)
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+    table
+        .as_object_mut()
+        .ok_or_else(|| AgentError::ExecutionError(format!("{} is not a table", path)))?
+        .insert(last.to_string(), value);
+    Ok(())
 }
 
 /// Parse semantic version into major version number
@@ -172,4 +442,73 @@ mod tests {
         let result = ConfigMigration::migrate(config, "1.0.0", "1.0.0");
         assert!(result.is_ok());
     }
+
+    /// A v1 config: provider backoffs under their old key, no [security] or [scud]
+    const V1_CONFIG: &str = r#"
+version = "1.0.0"
+
+[providers]
+primary = "anthropic"
+
+[providers.anthropic]
+model = "claude-3-5-sonnet-20241022"
+retry_backoff_ms = 2500
+
+[providers.ollama]
+retry_backoff_ms = 500
+"#;
+
+    #[test]
+    fn test_v1_config_migrates_to_current() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        std::fs::write(&path, V1_CONFIG).unwrap();
+
+        let report = ConfigMigration::migrate_file(&path, true).unwrap();
+        assert_eq!(report.from_version, "1.0.0");
+        assert_eq!(report.to_version, CURRENT_CONFIG_VERSION);
+        assert_eq!(
+            report.changes,
+            vec![
+                FieldChange::Renamed {
+                    from: "providers.anthropic.retry_backoff_ms".to_string(),
+                    to: "providers.anthropic.initial_backoff_ms".to_string(),
+                },
+                FieldChange::Renamed {
+                    from: "providers.ollama.retry_backoff_ms".to_string(),
+                    to: "providers.ollama.initial_backoff_ms".to_string(),
+                },
+                FieldChange::Defaulted {
+                    field: "security.enable_encryption".to_string(),
+                    value: "true".to_string(),
+                },
+                FieldChange::Defaulted {
+                    field: "security.encryption_algorithm".to_string(),
+                    value: "\"aes-256-gcm\"".to_string(),
+                },
+                FieldChange::Defaulted {
+                    field: "scud.schedule".to_string(),
+                    value: "\"critical_path\"".to_string(),
+                },
+            ]
+        );
+        // A dry run leaves the file alone
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), V1_CONFIG);
+        assert!(report.backup_path.is_none());
+
+        let report = ConfigMigration::migrate_file(&path, false).unwrap();
+        let backup = report.backup_path.unwrap();
+        assert_eq!(std::fs::read_to_string(backup).unwrap(), V1_CONFIG);
+
+        let migrated: DescaratesConfig =
+            toml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(migrated.version, CURRENT_CONFIG_VERSION);
+        assert_eq!(migrated.providers.primary, "anthropic");
+        assert_eq!(migrated.providers.anthropic.initial_backoff_ms, 2500);
+        assert_eq!(migrated.providers.ollama.initial_backoff_ms, 500);
+
+        let report = ConfigMigration::migrate_file(&path, false).unwrap();
+        assert!(report.is_up_to_date());
+        assert!(report.changes.is_empty());
+    }
 }
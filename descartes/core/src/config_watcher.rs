@@ -1,6 +1,6 @@
 /// Configuration file watcher and hot-reloading system
 /// Monitors config file changes and notifies subscribers of updates
-use crate::config::{ConfigManager, DescaratesConfig};
+use crate::config::{ConfigDiff, ConfigFieldChange, ConfigManager, DescaratesConfig};
 use crate::errors::AgentResult;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
@@ -16,13 +16,24 @@ pub struct ConfigChangeEvent {
     pub old_config: DescaratesConfig,
     /// New configuration
     pub new_config: DescaratesConfig,
+    /// What changed, by section
+    pub diff: ConfigDiff,
     /// Timestamp of the change
     pub timestamp: SystemTime,
 }
 
+impl ConfigChangeEvent {
+    /// Fields that differ between the old and new configuration
+    pub fn changes(&self) -> Vec<ConfigFieldChange> {
+        self.diff.changes().to_vec()
+    }
+}
+
 /// Configuration change listener
 pub trait ConfigChangeListener: Send + Sync {
     /// Called when configuration changes
+    ///
+    /// Check `event.diff` to only reinitialize the sections that changed.
     fn on_config_change(&self, event: ConfigChangeEvent);
 }
 
@@ -148,11 +159,18 @@ impl HotReloadManager {
         current_config: DescaratesConfig,
     ) -> AgentResult<Option<DescaratesConfig>> {
         if let Some(new_config) = self.watcher.load_if_changed()? {
+            let diff = ConfigDiff::between(&current_config, &new_config);
+            if diff.is_empty() {
+                debug!("Config file was rewritten without changes");
+                return Ok(Some(new_config));
+            }
+
             // Create change event
             let event = ConfigChangeEvent {
                 path: self.watcher.config_path().to_path_buf(),
-                old_config: current_config.clone(),
+                old_config: current_config,
                 new_config: new_config.clone(),
+                diff,
                 timestamp: SystemTime::now(),
             };
 
@@ -231,4 +249,54 @@ mod tests {
         let manager = HotReloadManager::new(config_path);
         assert!(manager.watcher().is_enabled());
     }
+
+    struct RecordingListener(Arc<Mutex<Vec<ConfigDiff>>>);
+
+    impl ConfigChangeListener for RecordingListener {
+        fn on_config_change(&self, event: ConfigChangeEvent) {
+            self.0.lock().unwrap().push(event.diff);
+        }
+    }
+
+    #[test]
+    fn test_log_level_change_does_not_flag_providers() {
+        use crate::config::ConfigSection;
+
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.toml");
+        let mut config_manager = ConfigManager::load(Some(&config_path)).unwrap();
+        config_manager.save().unwrap();
+        let current = config_manager.config().clone();
+
+        let reloads = HotReloadManager::new(config_path.clone());
+        let diffs = Arc::new(Mutex::new(Vec::new()));
+        reloads.on_change(Box::new(RecordingListener(Arc::clone(&diffs))));
+
+        config_manager.config_mut().logging.level = "debug".to_string();
+        config_manager.save().unwrap();
+        // Move the mtime forward explicitly; coarse filesystem timestamps could
+        // otherwise leave it unchanged and the reload would be skipped
+        std::fs::File::options()
+            .write(true)
+            .open(&config_path)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+        assert!(reloads.check_and_reload(current).unwrap().is_some());
+
+        let diffs = diffs.lock().unwrap();
+        assert_eq!(diffs.len(), 1);
+        let diff = &diffs[0];
+        assert!(diff.affects(ConfigSection::Logging));
+        assert!(!diff.affects(ConfigSection::Providers));
+        assert_eq!(
+            diff.sections().into_iter().collect::<Vec<_>>(),
+            vec![ConfigSection::Logging]
+        );
+        let fields: Vec<_> = diff
+            .section_changes(ConfigSection::Logging)
+            .map(|c| c.field.as_str())
+            .collect();
+        assert_eq!(fields, vec!["logging.level"]);
+    }
 }
@@ -44,6 +44,9 @@ pub enum DAGError {
     #[error("Cycle detected in DAG: {0}")]
     CycleDetected(String),
 
+    #[error("Cycle detected in DAG: {}", format_cycle(.0))]
+    Cycle(Vec<DAGNode>),
+
     #[error("Node not found: {0}")]
     NodeNotFound(Uuid),
 
@@ -81,6 +84,15 @@ pub enum DAGError {
 /// Result type for DAG operations
 pub type DAGResult<T> = Result<T, DAGError>;
 
+/// Render a cycle as "a -> b -> c -> a"
+fn format_cycle(path: &[DAGNode]) -> String {
+    let mut labels: Vec<&str> = path.iter().map(|n| n.label.as_str()).collect();
+    if let Some(first) = labels.first().copied() {
+        labels.push(first);
+    }
+    labels.join(" -> ")
+}
+
 /// Type of edge in the DAG
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
 #[serde(rename_all = "snake_case")]
@@ -304,6 +316,29 @@ impl DAGEdge {
     }
 }
 
+/// Spacing used by [`DAG::auto_layout`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LayoutConfig {
+    /// Distance between neighbouring nodes in the same layer
+    pub horizontal_spacing: f64,
+
+    /// Distance between consecutive layers
+    pub vertical_spacing: f64,
+
+    /// Top-left corner of the layout
+    pub origin: Position,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        LayoutConfig {
+            horizontal_spacing: 200.0,
+            vertical_spacing: 120.0,
+            origin: Position::default(),
+        }
+    }
+}
+
 /// Statistics about the DAG
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DAGStatistics {
@@ -318,6 +353,68 @@ pub struct DAGStatistics {
     pub is_connected: bool,
 }
 
+/// A node field that differs between two versions of a DAG
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeField {
+    Label,
+    Description,
+    Position,
+    Tags,
+    TaskId,
+    Metadata,
+}
+
+/// An edge field that differs between two versions of a DAG
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeField {
+    /// The edge connects different nodes
+    Endpoints,
+    EdgeType,
+    Label,
+    Metadata,
+}
+
+/// A node present in both versions with different contents
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeChange {
+    pub node_id: Uuid,
+    pub fields: Vec<NodeField>,
+}
+
+/// An edge present in both versions with different contents
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EdgeChange {
+    pub edge_id: Uuid,
+    pub fields: Vec<EdgeField>,
+}
+
+/// Changes between two versions of a DAG, as returned by [`DAG::diff`]
+///
+/// Nodes and edges are matched by ID; every list is sorted by ID.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DagDiff {
+    pub added_nodes: Vec<Uuid>,
+    pub removed_nodes: Vec<Uuid>,
+    pub modified_nodes: Vec<NodeChange>,
+    pub added_edges: Vec<Uuid>,
+    pub removed_edges: Vec<Uuid>,
+    pub modified_edges: Vec<EdgeChange>,
+}
+
+impl DagDiff {
+    /// Whether the two versions have the same nodes and edges
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.modified_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.modified_edges.is_empty()
+    }
+}
+
 /// The DAG structure containing nodes and edges
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DAG {
@@ -656,42 +753,59 @@ impl DAG {
 
     /// Validate the DAG structure (check for cycles)
     pub fn validate(&self) -> DAGResult<()> {
-        // Check for cycles using DFS
+        match self.find_cycle() {
+            Some(path) => Err(DAGError::Cycle(path)),
+            None => Ok(()),
+        }
+    }
+
+    /// Find a cycle, returning its nodes in edge order
+    ///
+    /// The last node has an edge back to the first. Returns None if the
+    /// graph is acyclic.
+    pub fn find_cycle(&self) -> Option<Vec<DAGNode>> {
         let mut visited = HashSet::new();
-        let mut rec_stack = HashSet::new();
+        let mut path = Vec::new();
 
         for node_id in self.nodes.keys() {
             if !visited.contains(node_id) {
-                self.detect_cycle_validation(*node_id, &mut visited, &mut rec_stack)?;
+                if let Some(cycle) = self.find_cycle_dfs(*node_id, &mut visited, &mut path) {
+                    return Some(
+                        cycle
+                            .into_iter()
+                            .filter_map(|id| self.nodes.get(&id).cloned())
+                            .collect(),
+                    );
+                }
             }
         }
 
-        Ok(())
+        None
     }
 
-    /// Detect cycles using depth-first search (for validation)
-    fn detect_cycle_validation(
+    /// Depth-first search that returns the first back edge's cycle
+    fn find_cycle_dfs(
         &self,
         node_id: Uuid,
         visited: &mut HashSet<Uuid>,
-        rec_stack: &mut HashSet<Uuid>,
-    ) -> DAGResult<()> {
+        path: &mut Vec<Uuid>,
+    ) -> Option<Vec<Uuid>> {
         visited.insert(node_id);
-        rec_stack.insert(node_id);
+        path.push(node_id);
 
         for successor in self.get_successors(node_id) {
+            if let Some(start_idx) = path.iter().position(|&n| n == successor) {
+                return Some(path[start_idx..].to_vec());
+            }
             if !visited.contains(&successor) {
-                self.detect_cycle_validation(successor, visited, rec_stack)?;
-            } else if rec_stack.contains(&successor) {
-                return Err(DAGError::CycleDetected(format!(
-                    "Cycle detected involving nodes: {} -> {}",
-                    node_id, successor
-                )));
+                if let Some(cycle) = self.find_cycle_dfs(successor, visited, path) {
+                    return Some(cycle);
+                }
             }
         }
 
-        rec_stack.remove(&node_id);
-        Ok(())
+        path.pop();
+        None
     }
 
     /// Detect all cycles in the graph
@@ -1173,6 +1287,154 @@ impl DAG {
         visited.len() == self.nodes.len()
     }
 
+    /// Assign layered positions to every node
+    ///
+    /// Each node's layer is its longest path from a root, so every edge
+    /// points down. Layers are centred under the widest one, and nodes
+    /// within a layer are ordered by the mean x of their predecessors, with
+    /// label and ID breaking ties, so the same DAG always gets the same
+    /// layout. Fails if the graph has a cycle.
+    pub fn auto_layout(&mut self, config: LayoutConfig) -> DAGResult<()> {
+        let order = self.topological_sort()?;
+
+        let mut layer_of: HashMap<Uuid, usize> = HashMap::new();
+        for &node_id in &order {
+            let layer = self
+                .get_predecessors(node_id)
+                .iter()
+                .filter_map(|pred| layer_of.get(pred))
+                .map(|layer| layer + 1)
+                .max()
+                .unwrap_or(0);
+            layer_of.insert(node_id, layer);
+        }
+
+        let layer_count = layer_of.values().max().map_or(0, |max| max + 1);
+        let mut layers: Vec<Vec<Uuid>> = vec![Vec::new(); layer_count];
+        for (&node_id, &layer) in &layer_of {
+            layers[layer].push(node_id);
+        }
+        let widest = layers.iter().map(Vec::len).max().unwrap_or(0);
+
+        let mut x_of: HashMap<Uuid, f64> = HashMap::new();
+        for (depth, mut layer) in layers.into_iter().enumerate() {
+            let keys: HashMap<Uuid, (f64, &str)> = layer
+                .iter()
+                .map(|&node_id| {
+                    let pred_xs: Vec<f64> = self
+                        .get_predecessors(node_id)
+                        .iter()
+                        .filter_map(|pred| x_of.get(pred).copied())
+                        .collect();
+                    let barycenter = if pred_xs.is_empty() {
+                        0.0
+                    } else {
+                        pred_xs.iter().sum::<f64>() / pred_xs.len() as f64
+                    };
+                    (node_id, (barycenter, self.nodes[&node_id].label.as_str()))
+                })
+                .collect();
+            layer.sort_by(|a, b| {
+                let (ka, kb) = (keys[a], keys[b]);
+                ka.0.total_cmp(&kb.0)
+                    .then_with(|| ka.1.cmp(kb.1))
+                    .then_with(|| a.cmp(b))
+            });
+
+            let indent = (widest - layer.len()) as f64 / 2.0;
+            for (index, node_id) in layer.into_iter().enumerate() {
+                let x = config.origin.x + (indent + index as f64) * config.horizontal_spacing;
+                let y = config.origin.y + depth as f64 * config.vertical_spacing;
+                x_of.insert(node_id, x);
+                if let Some(node) = self.nodes.get_mut(&node_id) {
+                    node.position = Position::new(x, y);
+                }
+            }
+        }
+
+        self.updated_at = chrono::Utc::now();
+        Ok(())
+    }
+
+    /// Changes needed to turn this DAG into `other`
+    ///
+    /// Nodes and edges are matched by ID. Timestamps are ignored, so re-saving
+    /// an unchanged DAG produces an empty diff.
+    pub fn diff(&self, other: &DAG) -> DagDiff {
+        let mut diff = DagDiff::default();
+
+        for (node_id, old) in &self.nodes {
+            let Some(new) = other.nodes.get(node_id) else {
+                diff.removed_nodes.push(*node_id);
+                continue;
+            };
+            let changed = [
+                (NodeField::Label, old.label != new.label),
+                (NodeField::Description, old.description != new.description),
+                (NodeField::Position, old.position != new.position),
+                (NodeField::Tags, old.tags != new.tags),
+                (NodeField::TaskId, old.task_id != new.task_id),
+                (NodeField::Metadata, old.metadata != new.metadata),
+            ];
+            let fields: Vec<NodeField> = changed
+                .into_iter()
+                .filter_map(|(field, differs)| differs.then_some(field))
+                .collect();
+            if !fields.is_empty() {
+                diff.modified_nodes.push(NodeChange {
+                    node_id: *node_id,
+                    fields,
+                });
+            }
+        }
+        diff.added_nodes = other
+            .nodes
+            .keys()
+            .filter(|id| !self.nodes.contains_key(id))
+            .copied()
+            .collect();
+
+        for (edge_id, old) in &self.edges {
+            let Some(new) = other.edges.get(edge_id) else {
+                diff.removed_edges.push(*edge_id);
+                continue;
+            };
+            let changed = [
+                (
+                    EdgeField::Endpoints,
+                    (old.from_node_id, old.to_node_id) != (new.from_node_id, new.to_node_id),
+                ),
+                (EdgeField::EdgeType, old.edge_type != new.edge_type),
+                (EdgeField::Label, old.label != new.label),
+                (EdgeField::Metadata, old.metadata != new.metadata),
+            ];
+            let fields: Vec<EdgeField> = changed
+                .into_iter()
+                .filter_map(|(field, differs)| differs.then_some(field))
+                .collect();
+            if !fields.is_empty() {
+                diff.modified_edges.push(EdgeChange {
+                    edge_id: *edge_id,
+                    fields,
+                });
+            }
+        }
+        diff.added_edges = other
+            .edges
+            .keys()
+            .filter(|id| !self.edges.contains_key(id))
+            .copied()
+            .collect();
+
+        diff.added_nodes.sort();
+        diff.removed_nodes.sort();
+        diff.modified_nodes.sort_by_key(|c| c.node_id);
+        diff.added_edges.sort();
+        diff.removed_edges.sort();
+        diff.modified_edges.sort_by_key(|c| c.edge_id);
+        diff
+    }
+
     /// Rebuild adjacency lists from edges (called after deserialization)
     pub fn rebuild_adjacency(&mut self) {
         self.adjacency_out.clear();
@@ -1570,10 +1832,118 @@ mod tests {
         dag.add_edge(DAGEdge::dependency(id2, id3)).unwrap();
         dag.add_edge(DAGEdge::dependency(id3, id1)).unwrap();
 
-        assert!(matches!(dag.validate(), Err(DAGError::CycleDetected(_))));
+        assert!(matches!(dag.validate(), Err(DAGError::Cycle(_))));
         assert!(dag.topological_sort().is_err());
     }
 
+    #[test]
+    fn test_auto_layout_layers_nodes_deterministically() {
+        let labels = ["fetch", "lint", "build", "package", "docs"];
+        let ids: Vec<Uuid> = (1..=5).map(|i| Uuid::from_u128(i as u128)).collect();
+        // fetch -> lint, fetch -> build, lint -> package, build -> package,
+        // plus a separate root "docs"
+        let edges = [(0, 1), (0, 2), (1, 3), (2, 3)];
+
+        let build = |node_order: &[usize]| {
+            let mut dag = DAG::new("Layout");
+            for &i in node_order {
+                dag.add_node(DAGNode::new(ids[i], labels[i])).unwrap();
+            }
+            for &(from, to) in &edges {
+                dag.add_edge(DAGEdge::dependency(ids[from], ids[to]))
+                    .unwrap();
+            }
+            dag.auto_layout(LayoutConfig::default()).unwrap();
+            dag
+        };
+
+        let dag = build(&[0, 1, 2, 3, 4]);
+        let pos = |i: usize| dag.nodes[&ids[i]].position;
+
+        // Layers follow the longest path from a root
+        assert_eq!(pos(0).y, 0.0);
+        assert_eq!(pos(4).y, 0.0);
+        assert_eq!(pos(1).y, 120.0);
+        assert_eq!(pos(2).y, 120.0);
+        assert_eq!(pos(3).y, 240.0);
+
+        // Nodes in a layer are spread apart, with "build" sorted before "lint"
+        assert_eq!(pos(1).x - pos(2).x, 200.0);
+        // The join sits centred under the layer above
+        assert_eq!(pos(3).x, (pos(1).x + pos(2).x) / 2.0);
+
+        // Insertion order doesn't change the layout
+        let shuffled = build(&[4, 3, 2, 1, 0]);
+        for id in &ids {
+            assert_eq!(shuffled.nodes[id].position, dag.nodes[id].position);
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_changes_by_id() {
+        let mut saved = DAG::new("Workflow");
+        let a = DAGNode::new_auto("A").with_position(0.0, 0.0);
+        let b = DAGNode::new_auto("B").with_tag("build");
+        let c = DAGNode::new_auto("C");
+        let (a_id, b_id, c_id) = (a.node_id, b.node_id, c.node_id);
+        saved.add_node(a).unwrap();
+        saved.add_node(b).unwrap();
+        saved.add_node(c).unwrap();
+        let ab = DAGEdge::dependency(a_id, b_id);
+        let bc = DAGEdge::dependency(b_id, c_id);
+        let (ab_id, bc_id) = (ab.edge_id, bc.edge_id);
+        saved.add_edge(ab).unwrap();
+        saved.add_edge(bc).unwrap();
+
+        assert!(saved.diff(&saved.clone()).is_empty());
+
+        let mut edited = saved.clone();
+        {
+            let a = edited.get_node_mut(a_id).unwrap();
+            a.label = "Start".to_string();
+            a.position = Position::new(40.0, 0.0);
+            a.updated_at = chrono::Utc::now();
+        }
+        edited.get_node_mut(b_id).unwrap().description = Some("Compile".to_string());
+        edited.edges.get_mut(&ab_id).unwrap().edge_type = EdgeType::SoftDependency;
+        edited.remove_node(c_id).unwrap();
+        let d = DAGNode::new_auto("D");
+        let d_id = d.node_id;
+        edited.add_node(d).unwrap();
+        let bd = DAGEdge::dependency(b_id, d_id);
+        let bd_id = bd.edge_id;
+        edited.add_edge(bd).unwrap();
+
+        let diff = saved.diff(&edited);
+        assert_eq!(diff.added_nodes, vec![d_id]);
+        assert_eq!(diff.removed_nodes, vec![c_id]);
+        let mut expected_nodes = vec![
+            NodeChange {
+                node_id: a_id,
+                fields: vec![NodeField::Label, NodeField::Position],
+            },
+            NodeChange {
+                node_id: b_id,
+                fields: vec![NodeField::Description],
+            },
+        ];
+        expected_nodes.sort_by_key(|c| c.node_id);
+        assert_eq!(diff.modified_nodes, expected_nodes);
+        assert_eq!(diff.added_edges, vec![bd_id]);
+        assert_eq!(diff.removed_edges, vec![bc_id]);
+        assert_eq!(
+            diff.modified_edges,
+            vec![EdgeChange {
+                edge_id: ab_id,
+                fields: vec![EdgeField::EdgeType],
+            }]
+        );
+
+        let json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(json["modified_edges"][0]["fields"][0], "edge_type");
+        assert_eq!(serde_json::from_value::<DagDiff>(json).unwrap(), diff);
+    }
+
     #[test]
     fn test_start_and_end_nodes() {
         let mut dag = DAG::new("Test");
@@ -12,6 +12,19 @@
 /// - Validation of workflow constraints
 /// - File I/O helpers for seamless integration
 ///
+/// # Round Trips
+///
+/// Each exported state and handler carries a `dag` table with the node or edge
+/// data Swarm.toml has no field for (IDs, labels, edge types, positions, tags
+/// and metadata the state fields don't capture), so importing an exported file
+/// gives back the same nodes and edges. Hand-written files have no `dag`
+/// tables and import as before, with fresh IDs and dependency edges.
+///
+/// Some Swarm.toml data has no DAG equivalent and is dropped on import: the
+/// file's `[metadata]`, `[agents]`, `[resources]` and `[guards]` tables, and
+/// each workflow's initial state, timeout and retry settings, guards and
+/// contracts. The DAG's own metadata and timestamps are not exported.
+///
 /// # Examples
 ///
 /// ```rust,no_run
@@ -28,12 +41,13 @@
 /// let swarm_toml = export_dag_to_swarm_toml(&dag, &config).unwrap();
 /// println!("{}", swarm_toml);
 /// ```
-use crate::dag::{DAGEdge, DAGError, DAGNode, DAGResult, EdgeType, DAG};
+use crate::dag::{DAGEdge, DAGError, DAGNode, DAGResult, EdgeType, Position, DAG};
+use crate::dag_toml::{edge_type_to_string, parse_edge_type};
 use crate::swarm_parser::{
-    AgentConfig, Handler, ResourceConfig, State, SwarmConfig, Workflow, WorkflowMetadata,
-    WorkflowMetadataDetails,
+    AgentConfig, DagEdgeInfo, DagNodeInfo, Handler, ResourceConfig, State, SwarmConfig, Workflow,
+    WorkflowMetadata, WorkflowMetadataDetails,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use uuid::Uuid;
 
@@ -238,6 +252,7 @@ pub fn export_dag_to_swarm_toml(dag: &DAG, config: &SwarmExportConfig) -> DAGRes
 /// Build a workflow from a DAG
 fn build_workflow_from_dag(dag: &DAG, config: &SwarmExportConfig) -> DAGResult<Workflow> {
     let mut states = HashMap::new();
+    let state_names = assign_state_names(dag, config);
 
     // Determine initial state
     let roots = dag.get_start_nodes();
@@ -252,17 +267,16 @@ fn build_workflow_from_dag(dag: &DAG, config: &SwarmExportConfig) -> DAGResult<W
     } else {
         // Use first root node
         let root_id = roots[0];
-        let root_node = dag
-            .get_node(root_id)
-            .ok_or(DAGError::NodeNotFound(root_id))?;
-        get_state_name(root_node, config)
+        state_names
+            .get(&root_id)
+            .cloned()
+            .ok_or(DAGError::NodeNotFound(root_id))?
     };
 
     // Convert each node to a state
     for node in dag.nodes.values() {
-        let state = build_state_from_node(node, dag, config)?;
-        let state_name = get_state_name(node, config);
-        states.insert(state_name, state);
+        let state = build_state_from_node(node, dag, config, &state_names)?;
+        states.insert(state_names[&node.node_id].clone(), state);
     }
 
     // Build workflow
@@ -292,6 +306,7 @@ fn build_state_from_node(
     node: &DAGNode,
     dag: &DAG,
     config: &SwarmExportConfig,
+    state_names: &HashMap<Uuid, String>,
 ) -> DAGResult<State> {
     // Determine if terminal (no outgoing edges)
     let is_terminal = dag.get_outgoing_edges(node.node_id).is_empty();
@@ -300,19 +315,22 @@ fn build_state_from_node(
     let mut handlers = Vec::new();
     if !is_terminal {
         for edge in dag.get_outgoing_edges(node.node_id) {
-            let target_node = dag
-                .get_node(edge.to_node_id)
+            let target_state = state_names
+                .get(&edge.to_node_id)
+                .cloned()
                 .ok_or(DAGError::NodeNotFound(edge.to_node_id))?;
 
             let event_name = get_event_name(edge, config);
-            let target_state = get_state_name(target_node, config);
             let guards = extract_guards_from_edge(edge);
 
-            handlers.push(Handler {
+            let mut handler = Handler {
                 event: event_name,
                 target: target_state,
                 guards,
-            });
+                dag: None,
+            };
+            handler.dag = Some(edge_info(edge, &handler)?);
+            handlers.push(handler);
         }
     }
 
@@ -327,7 +345,7 @@ fn build_state_from_node(
     let required_resources = extract_resources(node);
 
     // Extract timeout configuration
-    let (timeout_seconds, timeout_target) = extract_timeout_config(node, dag, config);
+    let (timeout_seconds, timeout_target) = extract_timeout_config(node, state_names);
 
     // Extract parent state
     let parent = extract_parent_state(node);
@@ -335,7 +353,7 @@ fn build_state_from_node(
     // Extract parallel execution flag
     let parallel_execution = extract_parallel_execution(node);
 
-    Ok(State {
+    let mut state = State {
         description: node
             .description
             .clone()
@@ -350,9 +368,106 @@ fn build_state_from_node(
         timeout_seconds,
         timeout_target,
         required_resources,
+        dag: None,
+    };
+    state.dag = Some(node_info(node, &state)?);
+    Ok(state)
+}
+
+/// Give every node a unique state name
+///
+/// Sanitizing can map two labels to the same name; the later node (by ID)
+/// gets a numeric suffix instead of overwriting the earlier state.
+fn assign_state_names(dag: &DAG, config: &SwarmExportConfig) -> HashMap<Uuid, String> {
+    let mut nodes: Vec<&DAGNode> = dag.nodes.values().collect();
+    nodes.sort_by_key(|n| n.node_id);
+
+    let mut taken = HashSet::new();
+    let mut names = HashMap::new();
+    for node in nodes {
+        let base = get_state_name(node, config);
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while !taken.insert(name.clone()) {
+            name = format!("{}_{}", base, suffix);
+            suffix += 1;
+        }
+        names.insert(node.node_id, name);
+    }
+    names
+}
+
+/// Node data the state can't hold, for restoring the node on import
+fn node_info(node: &DAGNode, state: &State) -> DAGResult<DagNodeInfo> {
+    let (metadata, omit_metadata) = metadata_delta(&node.metadata, &state_metadata(state))?;
+    Ok(DagNodeInfo {
+        node_id: node.node_id,
+        label: node.label.clone(),
+        task_id: node.task_id,
+        no_description: node.description.is_none(),
+        x: node.position.x,
+        y: node.position.y,
+        tags: node.tags.clone(),
+        metadata,
+        omit_metadata,
     })
 }
 
+/// Edge data the handler can't hold, for restoring the edge on import
+fn edge_info(edge: &DAGEdge, handler: &Handler) -> DAGResult<DagEdgeInfo> {
+    let (metadata, omit_metadata) = metadata_delta(&edge.metadata, &handler_metadata(handler))?;
+    Ok(DagEdgeInfo {
+        edge_id: edge.edge_id,
+        edge_type: edge_type_to_string(&edge.edge_type),
+        label: edge.label.clone(),
+        metadata,
+        omit_metadata,
+    })
+}
+
+/// Differences between `original` metadata and what the import will derive
+///
+/// Returns the entries to restore (as JSON text) and the derived keys to drop.
+fn metadata_delta(
+    original: &HashMap<String, serde_json::Value>,
+    derived: &HashMap<String, serde_json::Value>,
+) -> DAGResult<(HashMap<String, String>, Vec<String>)> {
+    let mut restore = HashMap::new();
+    for (key, value) in original {
+        if derived.get(key) != Some(value) {
+            let json = serde_json::to_string(value)
+                .map_err(|e| DAGError::SerializationError(e.to_string()))?;
+            restore.insert(key.clone(), json);
+        }
+    }
+
+    let mut omit: Vec<String> = derived
+        .keys()
+        .filter(|key| !original.contains_key(*key))
+        .cloned()
+        .collect();
+    omit.sort();
+    Ok((restore, omit))
+}
+
+/// Apply a [`metadata_delta`] to derived metadata
+fn restore_metadata(
+    metadata: &mut HashMap<String, serde_json::Value>,
+    restore: &HashMap<String, String>,
+    omit: &[String],
+) -> DAGResult<()> {
+    for key in omit {
+        metadata.remove(key);
+    }
+    for (key, json) in restore {
+        let value = serde_json::from_str(json).map_err(|e| {
+            DAGError::DeserializationError(format!("Invalid metadata '{}': {}", key, e))
+        })?;
+        metadata.insert(key.clone(), value);
+    }
+    Ok(())
+}
+
 /// Get state name from node
 fn get_state_name(node: &DAGNode, config: &SwarmExportConfig) -> String {
     if config.use_labels_as_state_names {
@@ -488,8 +603,7 @@ fn extract_resources(node: &DAGNode) -> Vec<String> {
 /// Extract timeout configuration from node metadata
 fn extract_timeout_config(
     node: &DAGNode,
-    dag: &DAG,
-    config: &SwarmExportConfig,
+    state_names: &HashMap<Uuid, String>,
 ) -> (Option<u64>, Option<String>) {
     let timeout_seconds = node
         .metadata
@@ -507,8 +621,7 @@ fn extract_timeout_config(
                 .get("timeout_target_id")
                 .and_then(|v| v.as_str())
                 .and_then(|id_str| Uuid::parse_str(id_str).ok())
-                .and_then(|uuid| dag.get_node(uuid))
-                .map(|target_node| get_state_name(target_node, config))
+                .and_then(|uuid| state_names.get(&uuid).cloned())
         });
 
     (timeout_seconds, timeout_target)
@@ -552,97 +665,26 @@ pub fn import_swarm_toml_to_dag(
 
     // Create nodes from states
     for (state_name, state) in &workflow.states {
-        let node_id = Uuid::new_v4();
-        let mut node = DAGNode::new(node_id, state_name);
-        node.description = Some(state.description.clone());
-
-        // Store metadata
-        if !state.agents.is_empty() {
-            node.metadata.insert(
-                "agents".to_string(),
-                serde_json::Value::Array(
-                    state
-                        .agents
-                        .iter()
-                        .map(|a| serde_json::Value::String(a.clone()))
-                        .collect(),
-                ),
-            );
-        }
-
-        if !state.entry_actions.is_empty() {
-            node.metadata.insert(
-                "entry_actions".to_string(),
-                serde_json::Value::Array(
-                    state
-                        .entry_actions
-                        .iter()
-                        .map(|a| serde_json::Value::String(a.clone()))
-                        .collect(),
-                ),
-            );
-        }
-
-        if !state.exit_actions.is_empty() {
-            node.metadata.insert(
-                "exit_actions".to_string(),
-                serde_json::Value::Array(
-                    state
-                        .exit_actions
-                        .iter()
-                        .map(|a| serde_json::Value::String(a.clone()))
-                        .collect(),
-                ),
-            );
-        }
-
-        if !state.required_resources.is_empty() {
-            node.metadata.insert(
-                "required_resources".to_string(),
-                serde_json::Value::Array(
-                    state
-                        .required_resources
-                        .iter()
-                        .map(|r| serde_json::Value::String(r.clone()))
-                        .collect(),
-                ),
-            );
-        }
-
-        if let Some(parent) = &state.parent {
-            node.metadata.insert(
-                "parent".to_string(),
-                serde_json::Value::String(parent.clone()),
-            );
-        }
-
-        if state.parallel_execution {
-            node.metadata.insert(
-                "parallel_execution".to_string(),
-                serde_json::Value::Bool(true),
-            );
-        }
-
-        if let Some(timeout) = state.timeout_seconds {
-            node.metadata.insert(
-                "timeout_seconds".to_string(),
-                serde_json::Value::Number(timeout.into()),
-            );
-        }
-
-        if let Some(timeout_target) = &state.timeout_target {
-            node.metadata.insert(
-                "timeout_target".to_string(),
-                serde_json::Value::String(timeout_target.clone()),
-            );
-        }
-
-        node.metadata.insert(
-            "terminal".to_string(),
-            serde_json::Value::Bool(state.terminal),
-        );
+        let mut metadata = state_metadata(state);
+        let mut node = match &state.dag {
+            Some(info) => {
+                restore_metadata(&mut metadata, &info.metadata, &info.omit_metadata)?;
+                let mut node = DAGNode::new(info.node_id, &info.label);
+                node.task_id = info.task_id;
+                node.description = (!info.no_description).then(|| state.description.clone());
+                node.position = Position::new(info.x, info.y);
+                node.tags = info.tags.clone();
+                node
+            }
+            None => {
+                let mut node = DAGNode::new(Uuid::new_v4(), state_name);
+                node.description = Some(state.description.clone());
+                node
+            }
+        };
+        node.metadata = metadata;
 
-        state_to_node.insert(state_name.clone(), node_id);
+        state_to_node.insert(state_name.clone(), node.node_id);
         dag.add_node(node)?;
     }
 
@@ -660,28 +702,23 @@ pub fn import_swarm_toml_to_dag(
                 ))
             })?;
 
-            let mut edge = DAGEdge::dependency(from_node_id, to_node_id);
-            edge.label = Some(handler.event.clone());
-
-            // Store guards in metadata
-            if !handler.guards.is_empty() {
-                edge.metadata.insert(
-                    "guards".to_string(),
-                    serde_json::Value::Array(
-                        handler
-                            .guards
-                            .iter()
-                            .map(|g| serde_json::Value::String(g.clone()))
-                            .collect(),
-                    ),
-                );
-            }
-
-            // Store event name in metadata
-            edge.metadata.insert(
-                "event".to_string(),
-                serde_json::Value::String(handler.event.clone()),
-            );
+            let mut metadata = handler_metadata(handler);
+            let mut edge = match &handler.dag {
+                Some(info) => {
+                    restore_metadata(&mut metadata, &info.metadata, &info.omit_metadata)?;
+                    let edge_type = parse_edge_type(&info.edge_type)?;
+                    let mut edge = DAGEdge::new(from_node_id, to_node_id, edge_type);
+                    edge.edge_id = info.edge_id;
+                    edge.label = info.label.clone();
+                    edge
+                }
+                None => {
+                    let mut edge = DAGEdge::dependency(from_node_id, to_node_id);
+                    edge.label = Some(handler.event.clone());
+                    edge
+                }
+            };
+            edge.metadata = metadata;
 
             dag.add_edge(edge)?;
         }
@@ -691,6 +728,98 @@ pub fn import_swarm_toml_to_dag(
     Ok(dag)
 }
 
+/// Node metadata the import derives from a state's own fields
+fn state_metadata(state: &State) -> HashMap<String, serde_json::Value> {
+    let strings = |values: &[String]| {
+        serde_json::Value::Array(
+            values
+                .iter()
+                .map(|v| serde_json::Value::String(v.clone()))
+                .collect(),
+        )
+    };
+    let mut metadata = HashMap::new();
+
+    if !state.agents.is_empty() {
+        metadata.insert("agents".to_string(), strings(&state.agents));
+    }
+
+    if !state.entry_actions.is_empty() {
+        metadata.insert("entry_actions".to_string(), strings(&state.entry_actions));
+    }
+
+    if !state.exit_actions.is_empty() {
+        metadata.insert("exit_actions".to_string(), strings(&state.exit_actions));
+    }
+
+    if !state.required_resources.is_empty() {
+        metadata.insert(
+            "required_resources".to_string(),
+            strings(&state.required_resources),
+        );
+    }
+
+    if let Some(parent) = &state.parent {
+        metadata.insert(
+            "parent".to_string(),
+            serde_json::Value::String(parent.clone()),
+        );
+    }
+
+    if state.parallel_execution {
+        metadata.insert(
+            "parallel_execution".to_string(),
+            serde_json::Value::Bool(true),
+        );
+    }
+
+    if let Some(timeout) = state.timeout_seconds {
+        metadata.insert(
+            "timeout_seconds".to_string(),
+            serde_json::Value::Number(timeout.into()),
+        );
+    }
+
+    if let Some(timeout_target) = &state.timeout_target {
+        metadata.insert(
+            "timeout_target".to_string(),
+            serde_json::Value::String(timeout_target.clone()),
+        );
+    }
+
+    metadata.insert(
+        "terminal".to_string(),
+        serde_json::Value::Bool(state.terminal),
+    );
+    metadata
+}
+
+/// Edge metadata the import derives from a handler's own fields
+fn handler_metadata(handler: &Handler) -> HashMap<String, serde_json::Value> {
+    let mut metadata = HashMap::new();
+
+    // Store guards in metadata
+    if !handler.guards.is_empty() {
+        metadata.insert(
+            "guards".to_string(),
+            serde_json::Value::Array(
+                handler
+                    .guards
+                    .iter()
+                    .map(|g| serde_json::Value::String(g.clone()))
+                    .collect(),
+            ),
+        );
+    }
+
+    // Store event name in metadata
+    metadata.insert(
+        "event".to_string(),
+        serde_json::Value::String(handler.event.clone()),
+    );
+    metadata
+}
+
 /// Save a DAG as Swarm.toml file
 pub fn save_dag_as_swarm_toml(dag: &DAG, path: &Path, config: &SwarmExportConfig) -> DAGResult<()> {
     let toml_content = export_dag_to_swarm_toml(dag, config)?;
@@ -792,10 +921,12 @@ mod tests {
                     event: "go".to_string(),
                     target: "End".to_string(),
                     guards: vec![],
+                    dag: None,
                 }],
                 timeout_seconds: None,
                 timeout_target: None,
                 required_resources: vec![],
+                dag: None,
             },
         );
 
@@ -813,6 +944,7 @@ mod tests {
                 timeout_seconds: None,
                 timeout_target: None,
                 required_resources: vec![],
+                dag: None,
             },
         );
 
@@ -880,6 +1012,83 @@ mod tests {
         assert_eq!(dag.edges.len(), dag2.edges.len());
     }
 
+    #[test]
+    fn test_roundtrip_preserves_every_node_and_edge_field() {
+        use serde_json::json;
+
+        let mut dag = DAG::new("Fidelity");
+        dag.description = Some("Every field survives".to_string());
+
+        let start = DAGNode::new_auto("Start")
+            .with_description("Entry point")
+            .with_task_id(Uuid::new_v4())
+            .with_position(10.5, -20.25)
+            .with_tag("entry")
+            .with_tag("ui")
+            .with_metadata("agents", json!("solo"))
+            .with_metadata("notes", json!({"owner": null, "ratio": 0.25}));
+        // Both labels sanitize to "Build_step"
+        let build_a = DAGNode::new_auto("Build step")
+            .with_metadata("parallel_execution", json!(false))
+            .with_metadata("entry_actions", json!(["compile"]));
+        let build_b = DAGNode::new_auto("Build-step").with_metadata("timeout_seconds", json!(30));
+        let review = DAGNode::new_auto("Review").with_position(300.0, 120.0);
+        let deploy = DAGNode::new_auto("Deploy").with_metadata("terminal", json!(false));
+        let done = DAGNode::new_auto("Done").with_metadata("terminal", json!(true));
+        let ids: Vec<Uuid> = [&start, &build_a, &build_b, &review, &deploy, &done]
+            .iter()
+            .map(|n| n.node_id)
+            .collect();
+        let build_b = build_b.with_metadata("timeout_target_id", json!(ids[5].to_string()));
+        for node in [start, build_a, build_b, review, deploy, done] {
+            dag.add_node(node).unwrap();
+        }
+
+        let edges = vec![
+            DAGEdge::dependency(ids[0], ids[1]).with_label("go"),
+            DAGEdge::new(ids[0], ids[2], EdgeType::SoftDependency),
+            DAGEdge::new(ids[1], ids[3], EdgeType::OptionalDependency)
+                .with_metadata("guards", json!("ready")),
+            DAGEdge::new(ids[2], ids[3], EdgeType::DataFlow)
+                .with_metadata("event", json!("artifacts")),
+            DAGEdge::new(ids[3], ids[4], EdgeType::Trigger),
+            DAGEdge::new(ids[4], ids[5], EdgeType::Custom("ShipIt".to_string()))
+                .with_label("Ship It")
+                .with_metadata("weight", json!(2)),
+        ];
+        for edge in edges {
+            dag.add_edge(edge).unwrap();
+        }
+
+        let config = SwarmExportConfig::default().with_agent("default", "claude-3-opus");
+        let toml = export_dag_to_swarm_toml(&dag, &config).unwrap();
+        let swarm_config: SwarmConfig = toml::from_str(&toml).unwrap();
+        let imported = import_swarm_toml_to_dag(&swarm_config, 0).unwrap();
+
+        assert_eq!(imported.name, dag.name);
+        assert_eq!(imported.description, dag.description);
+        assert_eq!(imported.nodes.len(), dag.nodes.len());
+        for node in dag.nodes.values() {
+            let copy = &imported.nodes[&node.node_id];
+            assert_eq!(copy.label, node.label);
+            assert_eq!(copy.description, node.description);
+            assert_eq!(copy.task_id, node.task_id);
+            assert_eq!(copy.position, node.position);
+            assert_eq!(copy.tags, node.tags);
+            assert_eq!(copy.metadata, node.metadata, "node {}", node.label);
+        }
+
+        assert_eq!(imported.edges.len(), dag.edges.len());
+        for edge in dag.edges.values() {
+            let copy = &imported.edges[&edge.edge_id];
+            assert_eq!(copy.from_node_id, edge.from_node_id);
+            assert_eq!(copy.to_node_id, edge.to_node_id);
+            assert_eq!(copy.edge_type, edge.edge_type);
+            assert_eq!(copy.label, edge.label);
+            assert_eq!(copy.metadata, edge.metadata);
+        }
+    }
+
     #[test]
     fn test_state_name_sanitization() {
         assert_eq!(sanitize_state_name("Hello World"), "Hello_World");
@@ -289,19 +289,19 @@ impl TomlDAG {
 }
 
 /// Parse edge type from string
-fn parse_edge_type(s: &str) -> DAGResult<EdgeType> {
+pub(crate) fn parse_edge_type(s: &str) -> DAGResult<EdgeType> {
     match s.to_lowercase().as_str() {
         "dependency" | "" => Ok(EdgeType::Dependency),
         "soft_dependency" => Ok(EdgeType::SoftDependency),
         "optional_dependency" => Ok(EdgeType::OptionalDependency),
         "data_flow" => Ok(EdgeType::DataFlow),
         "trigger" => Ok(EdgeType::Trigger),
-        other => Ok(EdgeType::Custom(other.to_string())),
+        _ => Ok(EdgeType::Custom(s.to_string())),
     }
 }
 
 /// Convert edge type to string
-fn edge_type_to_string(edge_type: &EdgeType) -> String {
+pub(crate) fn edge_type_to_string(edge_type: &EdgeType) -> String {
     match edge_type {
         EdgeType::Dependency => "dependency".to_string(),
         EdgeType::SoftDependency => "soft_dependency".to_string(),
@@ -370,12 +370,16 @@ fn json_value_to_toml(value: &serde_json::Value) -> DAGResult<toml::Value> {
 }
 
 /// Helper to load DAG from TOML file
+///
+/// Fails with [`DAGError::Cycle`] if the file describes a cyclic graph.
 pub fn load_dag_from_toml(path: &std::path::Path) -> DAGResult<DAG> {
     let content = std::fs::read_to_string(path)
         .map_err(|e| DAGError::DeserializationError(format!("Failed to read file: {}", e)))?;
 
     let toml_dag = TomlDAG::from_toml_str(&content)?;
-    toml_dag.to_dag()
+    let dag = toml_dag.to_dag()?;
+    dag.validate()?;
+    Ok(dag)
 }
 
 /// Helper to save DAG to TOML file
@@ -450,6 +454,49 @@ dependency_type = "dependency"
         assert_eq!(toml_dag.dependencies[0].depends_on.len(), 2);
     }
 
+    #[test]
+    fn test_load_rejects_cyclic_dag() {
+        let toml_str = r#"
+name = "Cyclic Workflow"
+
+[[nodes]]
+node_id = "550e8400-e29b-41d4-a716-446655440001"
+label = "build"
+
+[[nodes]]
+node_id = "550e8400-e29b-41d4-a716-446655440002"
+label = "test"
+
+[[nodes]]
+node_id = "550e8400-e29b-41d4-a716-446655440003"
+label = "deploy"
+
+[[dependencies]]
+task = "550e8400-e29b-41d4-a716-446655440002"
+depends_on = ["550e8400-e29b-41d4-a716-446655440001"]
+
+[[dependencies]]
+task = "550e8400-e29b-41d4-a716-446655440003"
+depends_on = ["550e8400-e29b-41d4-a716-446655440002"]
+
+[[dependencies]]
+task = "550e8400-e29b-41d4-a716-446655440001"
+depends_on = ["550e8400-e29b-41d4-a716-446655440003"]
+"#;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cyclic.toml");
+        std::fs::write(&path, toml_str).unwrap();
+
+        match load_dag_from_toml(&path) {
+            Err(DAGError::Cycle(path)) => {
+                let mut labels: Vec<_> = path.iter().map(|n| n.label.as_str()).collect();
+                labels.sort();
+                assert_eq!(labels, vec!["build", "deploy", "test"]);
+            }
+            other => panic!("expected a cycle error, got {:?}", other.map(|d| d.name)),
+        }
+    }
+
     #[test]
     fn test_edge_type_conversion() {
         assert_eq!(edge_type_to_string(&EdgeType::Dependency), "dependency");
@@ -12,6 +12,11 @@
 //! - **Boolean Operators**: `&&`, `||`, `!`
 //! - **Numeric Operations**: `+`, `-`, `*`, `/`
 //! - **Literals**: strings, numbers, booleans, null
+//! - **Functions**: `len(x)`, `contains(x, item)`, `startsWith(s, prefix)`,
+//!   `lower(s)`, `upper(s)`
+//! - **Indexing**: `findings[0]`, `results["lint"]`, `findings[0].severity`
+//! - **Conditionals**: `priority == "high" ? "1m" : "10m"` (right-associative,
+//!   only the taken branch is evaluated)
 //!
 //! # Example
 //!
@@ -107,14 +112,21 @@ impl EvalContext {
             return None;
         }
 
-        // First, check if it's a simple variable
+        // First, check if it's a simple variable or a whole nested object
         if parts.len() == 1 {
-            return self.variables.get(parts[0]);
+            return self
+                .variables
+                .get(parts[0])
+                .or_else(|| self.nested.get(parts[0]));
         }
 
         // Check if first part is a nested object
-        if let Some(root) = self.nested.get(parts[0]) {
-            return Self::traverse_path(root, &parts[1..]);
+        if let Some(value) = self
+            .nested
+            .get(parts[0])
+            .and_then(|root| Self::traverse_path(root, &parts[1..]))
+        {
+            return Some(value);
         }
 
         // Try as a simple variable with dots (unlikely but possible)
@@ -187,6 +199,65 @@ pub enum Expr {
 
     /// Parenthesized expression
     Group(Box<Expr>),
+
+    /// Built-in function call (e.g., `len(findings)`)
+    Call { function: Function, args: Vec<Expr> },
+
+    /// Array element or object field (e.g., `findings[0]`)
+    Index { target: Box<Expr>, index: Box<Expr> },
+
+    /// Conditional expression (cond ? then : otherwise)
+    Conditional {
+        cond: Box<Expr>,
+        then: Box<Expr>,
+        otherwise: Box<Expr>,
+    },
+}
+
+/// Built-in functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Function {
+    /// Length of a string, array or object
+    Len,
+    /// Whether an array holds a value, or a string holds a substring
+    Contains,
+    /// Whether a string starts with a prefix
+    StartsWith,
+    /// Lowercase a string
+    Lower,
+    /// Uppercase a string
+    Upper,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "len" => Some(Function::Len),
+            "contains" => Some(Function::Contains),
+            "startsWith" => Some(Function::StartsWith),
+            "lower" => Some(Function::Lower),
+            "upper" => Some(Function::Upper),
+            _ => None,
+        }
+    }
+
+    /// Name used to call the function
+    pub fn name(&self) -> &'static str {
+        match self {
+            Function::Len => "len",
+            Function::Contains => "contains",
+            Function::StartsWith => "startsWith",
+            Function::Lower => "lower",
+            Function::Upper => "upper",
+        }
+    }
+
+    fn arity(&self) -> usize {
+        match self {
+            Function::Len | Function::Lower | Function::Upper => 1,
+            Function::Contains | Function::StartsWith => 2,
+        }
+    }
 }
 
 /// Binary operators
@@ -260,7 +331,12 @@ enum Token {
     Slash,    // /
     LParen,   // (
     RParen,   // )
+    LBracket, // [
+    RBracket, // ]
+    Comma,    // ,
     Dot,      // .
+    Question, // ?
+    Colon,    // :
 
     // End of input
     Eof,
@@ -300,6 +376,11 @@ impl<'a> Tokenizer<'a> {
             '/' => Ok(Token::Slash),
             '(' => Ok(Token::LParen),
             ')' => Ok(Token::RParen),
+            '[' => Ok(Token::LBracket),
+            ']' => Ok(Token::RBracket),
+            ',' => Ok(Token::Comma),
+            '?' => Ok(Token::Question),
+            ':' => Ok(Token::Colon),
             '.' => Ok(Token::Dot),
 
             // Two-character operators
@@ -494,7 +575,14 @@ impl<'a> Parser<'a> {
         if self.current == Token::Eof {
             return Err(EvalError::EmptyExpression);
         }
-        self.parse_expression(0)
+        let expr = self.parse_conditional()?;
+        if self.current != Token::Eof {
+            return Err(EvalError::ParseError {
+                position: self.tokenizer.current_pos,
+                message: format!("Unexpected token: {:?}", self.current),
+            });
+        }
+        Ok(expr)
     }
 
     fn advance(&mut self) -> EvalResult<()> {
@@ -502,6 +590,32 @@ impl<'a> Parser<'a> {
         Ok(())
     }
 
+    /// Parse `cond ? then : otherwise`, which binds looser than any binary
+    /// operator and nests to the right
+    fn parse_conditional(&mut self) -> EvalResult<Expr> {
+        let cond = self.parse_expression(0)?;
+        if self.current != Token::Question {
+            return Ok(cond);
+        }
+        self.advance()?;
+
+        let then = self.parse_conditional()?;
+        if self.current != Token::Colon {
+            return Err(EvalError::ParseError {
+                position: self.tokenizer.current_pos,
+                message: "Expected ':' in conditional expression".to_string(),
+            });
+        }
+        self.advance()?;
+        let otherwise = self.parse_conditional()?;
+
+        Ok(Expr::Conditional {
+            cond: Box::new(cond),
+            then: Box::new(then),
+            otherwise: Box::new(otherwise),
+        })
+    }
+
     fn parse_expression(&mut self, min_precedence: u8) -> EvalResult<Expr> {
         let mut left = self.parse_unary()?;
 
@@ -586,8 +700,13 @@ impl<'a> Parser<'a> {
             }
             Token::Ident(name) => {
                 let mut path = name.clone();
+                let start = self.tokenizer.current_pos;
                 self.advance()?;
 
+                if self.current == Token::LParen {
+                    return self.parse_call(&path, start);
+                }
+
                 // Handle dot notation for paths
                 while self.current == Token::Dot {
                     self.advance()?;
@@ -607,7 +726,7 @@ impl<'a> Parser<'a> {
             }
             Token::LParen => {
                 self.advance()?;
-                let expr = self.parse_expression(0)?;
+                let expr = self.parse_conditional()?;
                 if self.current != Token::RParen {
                     return Err(EvalError::ParseError {
                         position: self.tokenizer.current_pos,
@@ -625,7 +744,84 @@ impl<'a> Parser<'a> {
             }
         };
 
-        Ok(expr)
+        self.parse_postfix(expr)
+    }
+
+    /// Parse `name(arg, ...)`; the current token is the opening parenthesis
+    fn parse_call(&mut self, name: &str, position: usize) -> EvalResult<Expr> {
+        let function = Function::from_name(name).ok_or_else(|| EvalError::ParseError {
+            position,
+            message: format!("Unknown function: {}", name),
+        })?;
+        self.advance()?;
+
+        let mut args = Vec::new();
+        if self.current != Token::RParen {
+            loop {
+                args.push(self.parse_conditional()?);
+                if self.current != Token::Comma {
+                    break;
+                }
+                self.advance()?;
+            }
+        }
+        if self.current != Token::RParen {
+            return Err(EvalError::ParseError {
+                position: self.tokenizer.current_pos,
+                message: "Expected ')' after function arguments".to_string(),
+            });
+        }
+        self.advance()?;
+
+        if args.len() != function.arity() {
+            return Err(EvalError::ParseError {
+                position,
+                message: format!(
+                    "{}() takes {} argument(s), got {}",
+                    function.name(),
+                    function.arity(),
+                    args.len()
+                ),
+            });
+        }
+        self.parse_postfix(Expr::Call { function, args })
+    }
+
+    /// Parse any `[index]` and `.field` accessors following an expression
+    fn parse_postfix(&mut self, mut expr: Expr) -> EvalResult<Expr> {
+        loop {
+            let index = match &self.current {
+                Token::LBracket => {
+                    self.advance()?;
+                    let index = self.parse_conditional()?;
+                    if self.current != Token::RBracket {
+                        return Err(EvalError::ParseError {
+                            position: self.tokenizer.current_pos,
+                            message: "Expected ']'".to_string(),
+                        });
+                    }
+                    index
+                }
+                Token::Dot => {
+                    self.advance()?;
+                    match &self.current {
+                        Token::Ident(field) => Expr::Literal(Value::String(field.clone())),
+                        _ => {
+                            return Err(EvalError::ParseError {
+                                position: self.tokenizer.current_pos,
+                                message: "Expected identifier after '.'".to_string(),
+                            })
+                        }
+                    }
+                }
+                _ => return Ok(expr),
+            };
+            self.advance()?;
+            expr = Expr::Index {
+                target: Box::new(expr),
+                index: Box::new(index),
+            };
+        }
     }
 }
 
@@ -661,6 +857,17 @@ impl ExpressionEvaluator {
         Ok(self.to_bool(&result))
     }
 
+    /// Evaluate a previously parsed expression against the given context
+    pub fn evaluate_ast(&self, expr: &Expr, context: &EvalContext) -> EvalResult<Value> {
+        self.eval_expr(expr, context)
+    }
+
+    /// Evaluate a previously parsed expression and coerce result to boolean
+    pub fn evaluate_ast_bool(&self, expr: &Expr, context: &EvalContext) -> EvalResult<bool> {
+        let result = self.eval_expr(expr, context)?;
+        Ok(self.to_bool(&result))
+    }
+
     fn eval_expr(&self, expr: &Expr, context: &EvalContext) -> EvalResult<Value> {
         match expr {
             Expr::Literal(v) => Ok(v.clone()),
@@ -672,6 +879,14 @@ impl ExpressionEvaluator {
 
             Expr::Binary { left, op, right } => {
                 let left_val = self.eval_expr(left, context)?;
+
+                // Short-circuit logical operators so the right side may be skipped
+                match op {
+                    BinaryOp::And if !self.to_bool(&left_val) => return Ok(Value::Bool(false)),
+                    BinaryOp::Or if self.to_bool(&left_val) => return Ok(Value::Bool(true)),
+                    _ => {}
+                }
+
                 let right_val = self.eval_expr(right, context)?;
                 self.eval_binary(*op, &left_val, &right_val)
             }
@@ -682,6 +897,104 @@ impl ExpressionEvaluator {
             }
 
             Expr::Group(inner) => self.eval_expr(inner, context),
+
+            Expr::Call { function, args } => {
+                let args = args
+                    .iter()
+                    .map(|arg| self.eval_expr(arg, context))
+                    .collect::<EvalResult<Vec<_>>>()?;
+                self.eval_call(*function, &args)
+            }
+
+            Expr::Index { target, index } => {
+                let target = self.eval_expr(target, context)?;
+                let index = self.eval_expr(index, context)?;
+                self.eval_index(&target, &index)
+            }
+
+            Expr::Conditional {
+                cond,
+                then,
+                otherwise,
+            } => {
+                // Only the taken branch is evaluated
+                if self.to_bool(&self.eval_expr(cond, context)?) {
+                    self.eval_expr(then, context)
+                } else {
+                    self.eval_expr(otherwise, context)
+                }
+            }
+        }
+    }
+
+    fn eval_call(&self, function: Function, args: &[Value]) -> EvalResult<Value> {
+        let type_error = || {
+            EvalError::TypeError(format!(
+                "{}() does not accept {}",
+                function.name(),
+                args.iter()
+                    .map(|a| format!("{:?}", a))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        };
+
+        match (function, args) {
+            (Function::Len, [value]) => {
+                let len = match value {
+                    Value::String(s) => s.chars().count(),
+                    Value::Array(a) => a.len(),
+                    Value::Object(o) => o.len(),
+                    _ => return Err(type_error()),
+                };
+                Ok(self.number_to_value(len as f64))
+            }
+            (Function::Contains, [Value::Array(items), item]) => {
+                for candidate in items {
+                    if self.compare_equality(candidate, item, false)? == Value::Bool(true) {
+                        return Ok(Value::Bool(true));
+                    }
+                }
+                Ok(Value::Bool(false))
+            }
+            (Function::Contains, [Value::String(s), Value::String(sub)]) => {
+                Ok(Value::Bool(s.contains(sub.as_str())))
+            }
+            (Function::StartsWith, [Value::String(s), Value::String(prefix)]) => {
+                Ok(Value::Bool(s.starts_with(prefix.as_str())))
+            }
+            (Function::Lower, [Value::String(s)]) => Ok(Value::String(s.to_lowercase())),
+            (Function::Upper, [Value::String(s)]) => Ok(Value::String(s.to_uppercase())),
+            _ => Err(type_error()),
+        }
+    }
+
+    fn eval_index(&self, target: &Value, index: &Value) -> EvalResult<Value> {
+        match (target, index) {
+            (Value::Array(items), Value::Number(n)) => {
+                let i = n.as_f64().unwrap_or(f64::NAN);
+                if i < 0.0 || i.fract() != 0.0 {
+                    return Err(EvalError::TypeError(format!(
+                        "Array index must be a non-negative integer, got {}",
+                        n
+                    )));
+                }
+                items.get(i as usize).cloned().ok_or_else(|| {
+                    EvalError::InvalidOperation(format!(
+                        "Index {} out of bounds for array of length {}",
+                        i,
+                        items.len()
+                    ))
+                })
+            }
+            (Value::Object(map), Value::String(key)) => map
+                .get(key)
+                .cloned()
+                .ok_or_else(|| EvalError::UnknownVariable(key.clone())),
+            _ => Err(EvalError::TypeError(format!(
+                "Cannot index {:?} with {:?}",
+                target, index
+            ))),
         }
     }
 
@@ -744,6 +1057,8 @@ impl ExpressionEvaluator {
         F: Fn(f64, f64) -> bool,
     {
         match (left, right) {
+            // Nothing is ordered relative to null
+            (Value::Null, _) | (_, Value::Null) => Ok(Value::Bool(false)),
             (Value::Number(a), Value::Number(b)) => {
                 let a = a.as_f64().unwrap_or(0.0);
                 let b = b.as_f64().unwrap_or(0.0);
@@ -1017,6 +1332,22 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_short_circuit_and_null_ordering() {
+        let eval = ExpressionEvaluator::new();
+        let ctx = EvalContext::new()
+            .with_variable("count", json!(3))
+            .with_variable("missing", json!(null));
+
+        // The right side would fail with an unknown variable if evaluated
+        assert_eq!(eval.evaluate("count > 1 || nope", &ctx).unwrap(), json!(true));
+        assert_eq!(eval.evaluate("count < 1 && nope", &ctx).unwrap(), json!(false));
+        assert!(eval.evaluate("count > 1 && nope", &ctx).is_err());
+
+        assert_eq!(eval.evaluate("missing >= 40", &ctx).unwrap(), json!(false));
+        assert_eq!(eval.evaluate("missing < 40", &ctx).unwrap(), json!(false));
+    }
+
     #[test]
     fn test_string_comparison() {
         let eval = ExpressionEvaluator::new();
@@ -1063,4 +1394,241 @@ mod tests {
             json!(true)
         );
     }
+
+    fn function_ctx() -> EvalContext {
+        context_from_json(json!({
+            "tags": ["urgent", "backend"],
+            "title": "Fix Login Bug",
+            "findings": [
+                {"severity": "high", "line": 10},
+                {"severity": "low", "line": 42}
+            ],
+            "scores": [1, 2.5, 3],
+            "review": {"lint": "passed", "reviewers": ["ana"]}
+        }))
+    }
+
+    #[test]
+    fn test_len_function() {
+        let eval = ExpressionEvaluator::new();
+        let ctx = function_ctx();
+
+        assert_eq!(eval.evaluate("len(tags)", &ctx).unwrap(), json!(2.0));
+        assert_eq!(eval.evaluate("len(title)", &ctx).unwrap(), json!(13.0));
+        assert_eq!(eval.evaluate("len(review)", &ctx).unwrap(), json!(2.0));
+        assert!(eval.evaluate_bool("len(findings) > 1", &ctx).unwrap());
+        assert!(matches!(
+            eval.evaluate("len(42)", &ctx),
+            Err(EvalError::TypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_contains_function() {
+        let eval = ExpressionEvaluator::new();
+        let ctx = function_ctx();
+
+        assert!(eval
+            .evaluate_bool("contains(tags, \"urgent\")", &ctx)
+            .unwrap());
+        assert!(!eval
+            .evaluate_bool("contains(tags, \"frontend\")", &ctx)
+            .unwrap());
+        assert!(eval.evaluate_bool("contains(scores, 3)", &ctx).unwrap());
+        assert!(eval
+            .evaluate_bool("contains(title, \"Login\")", &ctx)
+            .unwrap());
+        assert!(matches!(
+            eval.evaluate("contains(title, 3)", &ctx),
+            Err(EvalError::TypeError(_))
+        ));
+        assert!(matches!(
+            eval.evaluate("contains(review, \"lint\")", &ctx),
+            Err(EvalError::TypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_string_functions() {
+        let eval = ExpressionEvaluator::new();
+        let ctx = function_ctx();
+
+        assert!(eval
+            .evaluate_bool("startsWith(title, \"Fix\")", &ctx)
+            .unwrap());
+        assert!(!eval
+            .evaluate_bool("startsWith(title, \"fix\")", &ctx)
+            .unwrap());
+        assert!(eval
+            .evaluate_bool("startsWith(lower(title), \"fix\")", &ctx)
+            .unwrap());
+        assert_eq!(
+            eval.evaluate("lower(title)", &ctx).unwrap(),
+            json!("fix login bug")
+        );
+        assert_eq!(
+            eval.evaluate("upper(review.lint)", &ctx).unwrap(),
+            json!("PASSED")
+        );
+
+        for expr in ["startsWith(tags, \"u\")", "lower(42)", "upper(tags)"] {
+            assert!(
+                matches!(eval.evaluate(expr, &ctx), Err(EvalError::TypeError(_))),
+                "{}",
+                expr
+            );
+        }
+    }
+
+    #[test]
+    fn test_function_parse_errors() {
+        let eval = ExpressionEvaluator::new();
+
+        for expr in ["size(tags)", "len(tags, 1)", "contains(tags)", "len(tags"] {
+            assert!(
+                matches!(eval.parse(expr), Err(EvalError::ParseError { .. })),
+                "{}",
+                expr
+            );
+        }
+    }
+
+    #[test]
+    fn test_array_indexing() {
+        let eval = ExpressionEvaluator::new();
+        let ctx = function_ctx();
+
+        assert_eq!(eval.evaluate("tags[0]", &ctx).unwrap(), json!("urgent"));
+        assert_eq!(
+            eval.evaluate("findings[1].severity", &ctx).unwrap(),
+            json!("low")
+        );
+        assert_eq!(
+            eval.evaluate("findings[len(findings) - 1].line", &ctx)
+                .unwrap(),
+            json!(42)
+        );
+        assert_eq!(
+            eval.evaluate("review[\"reviewers\"][0]", &ctx).unwrap(),
+            json!("ana")
+        );
+        assert!(eval
+            .evaluate_bool("findings[0].severity == \"high\" && len(tags) == 2", &ctx)
+            .unwrap());
+
+        assert!(matches!(
+            eval.evaluate("tags[5]", &ctx),
+            Err(EvalError::InvalidOperation(_))
+        ));
+        assert!(matches!(
+            eval.evaluate("tags[\"first\"]", &ctx),
+            Err(EvalError::TypeError(_))
+        ));
+        assert!(matches!(
+            eval.evaluate("tags[0.5]", &ctx),
+            Err(EvalError::TypeError(_))
+        ));
+        assert!(matches!(
+            eval.evaluate("findings[0].missing", &ctx),
+            Err(EvalError::UnknownVariable(_))
+        ));
+        assert!(matches!(
+            eval.evaluate("len(missing)", &ctx),
+            Err(EvalError::UnknownVariable(_))
+        ));
+    }
+
+    #[test]
+    fn test_conditional_expressions() {
+        let eval = ExpressionEvaluator::new();
+        let ctx = EvalContext::new()
+            .with_variable("priority", json!("high"))
+            .with_variable("retries", json!(2));
+
+        assert_eq!(
+            eval.evaluate("priority == \"high\" ? \"1m\" : \"10m\"", &ctx)
+                .unwrap(),
+            json!("1m")
+        );
+        assert_eq!(
+            eval.evaluate("retries > 3 ? \"give up\" : \"retry\"", &ctx)
+                .unwrap(),
+            json!("retry")
+        );
+        // Binary operators bind tighter than the condition and branches
+        assert_eq!(
+            eval.evaluate("retries + 1 > 2 ? retries * 10 : 0", &ctx)
+                .unwrap(),
+            json!(20.0)
+        );
+        assert_eq!(
+            eval.evaluate("len(priority) + (retries > 1 ? 100 : 0)", &ctx)
+                .unwrap(),
+            json!(104.0)
+        );
+
+        // Only the taken branch is evaluated
+        assert_eq!(
+            eval.evaluate("true ? 1 : missing", &ctx).unwrap(),
+            json!(1.0)
+        );
+        assert_eq!(
+            eval.evaluate("false ? 1 / 0 : 2", &ctx).unwrap(),
+            json!(2.0)
+        );
+        assert!(matches!(
+            eval.evaluate("false ? 1 : missing", &ctx),
+            Err(EvalError::UnknownVariable(_))
+        ));
+
+        // Plain boolean expressions are unaffected
+        assert!(eval
+            .evaluate_bool("retries == 2 && priority != \"low\"", &ctx)
+            .unwrap());
+        assert!(eval
+            .evaluate_bool("retries > 1 ? true : false", &ctx)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_conditional_is_right_associative() {
+        let eval = ExpressionEvaluator::new();
+        let var = |name: &str| Box::new(Expr::Variable(name.to_string()));
+
+        assert_eq!(
+            eval.parse("a ? b : c ? d : e").unwrap(),
+            Expr::Conditional {
+                cond: var("a"),
+                then: var("b"),
+                otherwise: Box::new(Expr::Conditional {
+                    cond: var("c"),
+                    then: var("d"),
+                    otherwise: var("e"),
+                }),
+            }
+        );
+        assert_eq!(
+            eval.parse("a ? b ? c : d : e").unwrap(),
+            Expr::Conditional {
+                cond: var("a"),
+                then: Box::new(Expr::Conditional {
+                    cond: var("b"),
+                    then: var("c"),
+                    otherwise: var("d"),
+                }),
+                otherwise: var("e"),
+            }
+        );
+
+        let ctx = context_from_json(json!({"a": false, "b": 1, "c": true, "d": 2, "e": 3}));
+        assert_eq!(eval.evaluate("a ? b : c ? d : e", &ctx).unwrap(), json!(2));
+
+        for expr in ["a ? b", "a ? b :", "a : b", "a ? b : c d"] {
+            assert!(
+                matches!(eval.parse(expr), Err(EvalError::ParseError { .. })),
+                "{}",
+                expr
+            );
+        }
+    }
 }
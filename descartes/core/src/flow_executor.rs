@@ -578,8 +578,7 @@ impl FlowExecutor {
             name: format!("Flow: {}", phase),
             agent: agent_name,
             task: task.clone(),
-            parallel: false,
-            output: None,
+            ..Default::default()
         };
 
         let config = WorkflowExecutorConfig::default();
@@ -727,8 +726,7 @@ Reason: <brief explanation>
             name: "Flow: Error Recovery".to_string(),
             agent: "flow-orchestrator".to_string(),
             task: task.clone(),
-            parallel: false,
-            output: None,
+            ..Default::default()
         };
 
         let config = WorkflowExecutorConfig::default();
@@ -1096,8 +1094,7 @@ MESSAGE: <your assessment>"#,
             name: "QA: Commit Review".to_string(),
             agent: "flow-qa".to_string(),
             task: task.clone(),
-            parallel: false,
-            output: None,
+            ..Default::default()
         };
 
         let config = WorkflowExecutorConfig::default();
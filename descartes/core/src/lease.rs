@@ -7,11 +7,15 @@
 /// - Lease renewal for long-running operations
 /// - Prevention of deadlocks with timeout mechanisms
 /// - Tracking of which agent holds which files
-use crate::errors::AgentResult;
+use crate::errors::{AgentError, AgentResult};
 use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tracing::warn;
 use uuid::Uuid;
 
 /// A lease representing exclusive access to a file.
@@ -46,6 +50,12 @@ pub struct Lease {
 
     /// Maximum number of renewals allowed (-1 for unlimited)
     pub max_renewals: i32,
+
+    /// Fencing token, higher than that of any earlier lease on the same file.
+    /// Writes guarded by the lease should carry it so a holder whose lease
+    /// expired and was granted to someone else can be turned away.
+    #[serde(default)]
+    pub fencing_token: u64,
 }
 
 /// Status of a lease
@@ -96,6 +106,7 @@ impl Lease {
             status: LeaseStatus::Pending,
             renewal_count: 0,
             max_renewals,
+            fencing_token: 0,
         }
     }
 
@@ -191,6 +202,10 @@ pub struct LeaseAcquisitionResponse {
 
     /// Time waited for the lease in milliseconds
     pub wait_time_ms: u64,
+
+    /// Fencing token of the acquired lease (if successful)
+    #[serde(default)]
+    pub fencing_token: Option<u64>,
 }
 
 /// Parameters for renewing a lease
@@ -204,6 +219,9 @@ pub struct LeaseRenewalRequest {
 
     /// New TTL in seconds (if different from current)
     pub new_ttl_seconds: Option<u64>,
+
+    /// Fencing token of the lease; rejected once a newer lease exists on the file
+    pub fencing_token: u64,
 }
 
 /// Response from a lease renewal attempt
@@ -227,6 +245,9 @@ pub struct LeaseReleaseRequest {
 
     /// ID of the agent releasing the lease
     pub agent_id: Uuid,
+
+    /// Fencing token of the lease; rejected once a newer lease exists on the file
+    pub fencing_token: u64,
 }
 
 /// Response from a lease release attempt
@@ -267,6 +288,16 @@ pub trait LeaseManager: Send + Sync {
         request: LeaseReleaseRequest,
     ) -> AgentResult<LeaseReleaseResponse>;
 
+    /// Check a fencing token before writing to a leased file
+    ///
+    /// Fails if the token is older than the latest lease granted on the file,
+    /// i.e. the caller's lease has since been given to someone else.
+    async fn validate_fencing_token(
+        &self,
+        file_path: &std::path::Path,
+        fencing_token: u64,
+    ) -> AgentResult<()>;
+
     /// Get a lease by ID
     async fn get_lease(&self, lease_id: &Uuid) -> AgentResult<Option<Lease>>;
 
@@ -302,6 +333,138 @@ pub trait LeaseManager: Send + Sync {
     async fn force_release_agent_leases(&self, agent_id: &Uuid) -> AgentResult<usize>;
 }
 
+/// Acquire a lease that renews itself until the returned guard is dropped
+///
+/// A background task renews the lease at half its TTL. Fails if the lease
+/// can't be acquired.
+pub async fn acquire_with_autorenew(
+    manager: Arc<dyn LeaseManager>,
+    request: LeaseAcquisitionRequest,
+) -> AgentResult<LeaseGuard> {
+    let response = manager.acquire_lease(request).await?;
+    let lease = match response.lease {
+        Some(lease) if response.success => lease,
+        _ => {
+            return Err(AgentError::ExecutionError(format!(
+                "Failed to acquire lease: {}",
+                response
+                    .error
+                    .unwrap_or_else(|| "unknown error".to_string())
+            )))
+        }
+    };
+
+    let lost = Arc::new(AtomicBool::new(false));
+    let renewer = tokio::spawn(renew_until_lost(
+        Arc::clone(&manager),
+        lease.clone(),
+        Arc::clone(&lost),
+    ));
+    Ok(LeaseGuard {
+        lease,
+        manager,
+        lost,
+        renewer,
+        released: false,
+    })
+}
+
+async fn renew_until_lost(manager: Arc<dyn LeaseManager>, lease: Lease, lost: Arc<AtomicBool>) {
+    let period = (lease.ttl / 2)
+        .to_std()
+        .unwrap_or_default()
+        .max(std::time::Duration::from_millis(10));
+    loop {
+        tokio::time::sleep(period).await;
+        let renewal = manager
+            .renew_lease(LeaseRenewalRequest {
+                lease_id: lease.id,
+                agent_id: lease.agent_id,
+                new_ttl_seconds: None,
+                fencing_token: lease.fencing_token,
+            })
+            .await;
+        let error = match renewal {
+            Ok(response) if response.success => continue,
+            Ok(response) => response.error.unwrap_or_default(),
+            Err(e) => e.to_string(),
+        };
+        warn!(
+            "Lost lease {} on {}: {}",
+            lease.id,
+            lease.file_path.display(),
+            error
+        );
+        lost.store(true, Ordering::SeqCst);
+        return;
+    }
+}
+
+/// A lease kept alive by a background renewal task
+///
+/// Dropping the guard stops renewal and releases the lease. If a renewal
+/// fails (the lease expired or was taken away), [`LeaseGuard::is_lost`]
+/// turns true and the holder should stop working on the file.
+pub struct LeaseGuard {
+    lease: Lease,
+    manager: Arc<dyn LeaseManager>,
+    lost: Arc<AtomicBool>,
+    renewer: JoinHandle<()>,
+    released: bool,
+}
+
+impl LeaseGuard {
+    /// The lease as acquired
+    pub fn lease(&self) -> &Lease {
+        &self.lease
+    }
+
+    /// Whether a renewal failed, meaning the lease can no longer be relied on
+    pub fn is_lost(&self) -> bool {
+        self.lost.load(Ordering::SeqCst)
+    }
+
+    /// Shared flag set when a renewal fails, for polling from other tasks
+    pub fn lost_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.lost)
+    }
+
+    /// Stop renewing and release the lease, waiting for the release to finish
+    pub async fn release(mut self) -> AgentResult<LeaseReleaseResponse> {
+        self.renewer.abort();
+        self.released = true;
+        self.manager.release_lease(self.release_request()).await
+    }
+
+    fn release_request(&self) -> LeaseReleaseRequest {
+        LeaseReleaseRequest {
+            lease_id: self.lease.id,
+            agent_id: self.lease.agent_id,
+            fencing_token: self.lease.fencing_token,
+        }
+    }
+}
+
+impl Drop for LeaseGuard {
+    fn drop(&mut self) {
+        if self.released {
+            return;
+        }
+        self.renewer.abort();
+
+        let manager = Arc::clone(&self.manager);
+        let request = self.release_request();
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            handle.spawn(async move {
+                let lease_id = request.lease_id;
+                if let Err(e) = manager.release_lease(request).await {
+                    warn!("Failed to release lease {}: {}", lease_id, e);
+                }
+            });
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
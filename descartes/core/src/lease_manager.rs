@@ -70,6 +70,7 @@ impl SqliteLeaseManager {
                 status TEXT NOT NULL DEFAULT 'pending',
                 renewal_count INTEGER NOT NULL DEFAULT 0,
                 max_renewals INTEGER NOT NULL DEFAULT -1,
+                fencing_token INTEGER NOT NULL DEFAULT 0,
                 updated_at INTEGER NOT NULL
             );
 
@@ -84,6 +85,24 @@ impl SqliteLeaseManager {
         .await
         .map_err(|e| AgentError::ExecutionError(format!("Failed to create leases table: {}", e)))?;
 
+        // Databases created before fencing tokens lack the column
+        let (has_fencing_token,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM pragma_table_info('leases') WHERE name = 'fencing_token'",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            AgentError::ExecutionError(format!("Failed to inspect leases table: {}", e))
+        })?;
+        if has_fencing_token == 0 {
+            sqlx::query("ALTER TABLE leases ADD COLUMN fencing_token INTEGER NOT NULL DEFAULT 0")
+                .execute(&self.pool)
+                .await
+                .map_err(|e| {
+                    AgentError::ExecutionError(format!("Failed to add fencing_token column: {}", e))
+                })?;
+        }
+
         // Create lease history table
         sqlx::query(
             r#"
@@ -175,6 +194,7 @@ impl SqliteLeaseManager {
         let status_str: String = row.get("status");
         let renewal_count: i32 = row.get("renewal_count");
         let max_renewals: i32 = row.get("max_renewals");
+        let fencing_token: i64 = row.get("fencing_token");
 
         // Parse UUIDs
         let lease_id = Uuid::parse_str(&id)
@@ -214,9 +234,41 @@ impl SqliteLeaseManager {
             status,
             renewal_count: renewal_count as u32,
             max_renewals,
+            fencing_token: fencing_token as u64,
         })
     }
 
+    /// Latest fencing token granted on a file (0 if none)
+    async fn current_fencing_token(&self, file_path: &Path) -> AgentResult<u64> {
+        let (token,): (i64,) = sqlx::query_as(
+            "SELECT COALESCE(MAX(fencing_token), 0) FROM leases WHERE file_path = ?",
+        )
+        .bind(file_path.to_string_lossy().as_ref())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AgentError::ExecutionError(format!("Failed to fetch fencing token: {}", e)))?;
+        Ok(token as u64)
+    }
+
+    /// Why a renew/release carrying `fencing_token` must be refused, if it must
+    async fn stale_token_error(
+        &self,
+        lease: &Lease,
+        fencing_token: u64,
+    ) -> AgentResult<Option<String>> {
+        if fencing_token != lease.fencing_token {
+            return Ok(Some("Fencing token does not match this lease".to_string()));
+        }
+        let current = self.current_fencing_token(&lease.file_path).await?;
+        if fencing_token < current {
+            return Ok(Some(format!(
+                "Stale fencing token {}: file was re-leased with token {}",
+                fencing_token, current
+            )));
+        }
+        Ok(None)
+    }
+
     /// Record a lease history event
     #[allow(clippy::too_many_arguments)]
     async fn record_history(
@@ -293,24 +345,18 @@ impl LeaseManager for SqliteLeaseManager {
                 let now = Utc::now();
                 let expires_at = now + ttl;
 
-                let mut lease = Lease {
-                    id: lease_id,
-                    file_path: request.file_path.clone(),
-                    agent_id: request.agent_id,
-                    created_at: now,
-                    expires_at,
-                    ttl,
-                    status: LeaseStatus::Pending,
-                    renewal_count: 0,
-                    max_renewals: request.max_renewals,
-                };
-
-                // Insert into database
-                sqlx::query(
+                // Insert into database; the fencing token is assigned in the same
+                // statement so concurrent acquisitions can't share one
+                let (fencing_token,): (i64,) = sqlx::query_as(
                     r#"
                     INSERT INTO leases (id, file_path, agent_id, created_at, expires_at,
-                                       ttl_seconds, status, renewal_count, max_renewals, updated_at)
-                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                                       ttl_seconds, status, renewal_count, max_renewals,
+                                       fencing_token, updated_at)
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?,
+                            (SELECT COALESCE(MAX(fencing_token), 0) + 1 FROM leases
+                             WHERE file_path = ?),
+                            ?)
+                    RETURNING fencing_token
                     "#,
                 )
                 .bind(lease_id.to_string())
@@ -322,13 +368,27 @@ impl LeaseManager for SqliteLeaseManager {
                 .bind("pending")
                 .bind(0)
                 .bind(request.max_renewals)
+                .bind(request.file_path.to_string_lossy().as_ref())
                 .bind(now.timestamp())
-                .execute(&self.pool)
+                .fetch_one(&self.pool)
                 .await
                 .map_err(|e| {
                     AgentError::ExecutionError(format!("Failed to insert lease: {}", e))
                 })?;
 
+                let mut lease = Lease {
+                    id: lease_id,
+                    file_path: request.file_path.clone(),
+                    agent_id: request.agent_id,
+                    created_at: now,
+                    expires_at,
+                    ttl,
+                    status: LeaseStatus::Pending,
+                    renewal_count: 0,
+                    max_renewals: request.max_renewals,
+                    fencing_token: fencing_token as u64,
+                };
+
                 // Activate the lease
                 lease.activate();
 
@@ -360,6 +420,7 @@ impl LeaseManager for SqliteLeaseManager {
 
                 return Ok(LeaseAcquisitionResponse {
                     success: true,
+                    fencing_token: Some(lease.fencing_token),
                     lease: Some(lease),
                     error: None,
                     wait_time_ms,
@@ -374,6 +435,7 @@ impl LeaseManager for SqliteLeaseManager {
                     lease: None,
                     error: Some("File is already locked by another agent".to_string()),
                     wait_time_ms,
+                    fencing_token: None,
                 });
             }
 
@@ -385,6 +447,7 @@ impl LeaseManager for SqliteLeaseManager {
                     lease: None,
                     error: Some("Lease acquisition timeout".to_string()),
                     wait_time_ms,
+                    fencing_token: None,
                 });
             }
 
@@ -408,6 +471,23 @@ impl LeaseManager for SqliteLeaseManager {
                     });
                 }
 
+                if let Some(error) = self.stale_token_error(&l, request.fencing_token).await? {
+                    return Ok(LeaseRenewalResponse {
+                        success: false,
+                        lease: None,
+                        error: Some(error),
+                    });
+                }
+
+                // A released or expired lease may already belong to someone else
+                if l.status != LeaseStatus::Active {
+                    return Ok(LeaseRenewalResponse {
+                        success: false,
+                        lease: None,
+                        error: Some(format!("Lease is {}", l.status)),
+                    });
+                }
+
                 // Attempt renewal
                 if !l.renew() {
                     return Ok(LeaseRenewalResponse {
@@ -494,6 +574,12 @@ impl LeaseManager for SqliteLeaseManager {
                         error: Some("Agent does not own this lease".to_string()),
                     });
                 }
+                if let Some(error) = self.stale_token_error(&l, request.fencing_token).await? {
+                    return Ok(LeaseReleaseResponse {
+                        success: false,
+                        error: Some(error),
+                    });
+                }
                 l
             }
             None => {
@@ -536,6 +622,23 @@ impl LeaseManager for SqliteLeaseManager {
         })
     }
 
+    async fn validate_fencing_token(
+        &self,
+        file_path: &Path,
+        fencing_token: u64,
+    ) -> AgentResult<()> {
+        let current = self.current_fencing_token(file_path).await?;
+        if fencing_token < current {
+            return Err(AgentError::ExecutionError(format!(
+                "Stale fencing token {} for {}: file was re-leased with token {}",
+                fencing_token,
+                file_path.display(),
+                current
+            )));
+        }
+        Ok(())
+    }
+
     async fn get_lease(&self, lease_id: &Uuid) -> AgentResult<Option<Lease>> {
         let row = sqlx::query("SELECT * FROM leases WHERE id = ?")
             .bind(lease_id.to_string())
@@ -677,3 +780,135 @@ impl LeaseManager for SqliteLeaseManager {
         Ok(count as usize)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lease::acquire_with_autorenew;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    async fn create_manager(temp_dir: &TempDir) -> Arc<SqliteLeaseManager> {
+        let manager = SqliteLeaseManager::new(temp_dir.path().join("leases.db"))
+            .await
+            .unwrap();
+        manager.initialize().await.unwrap();
+        Arc::new(manager)
+    }
+
+    fn request(file: &str, agent_id: Uuid) -> LeaseAcquisitionRequest {
+        LeaseAcquisitionRequest {
+            file_path: PathBuf::from(file),
+            agent_id,
+            ttl_seconds: 1,
+            max_renewals: -1,
+            timeout_ms: None,
+            blocking: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_autorenewed_lease_outlives_ttl_and_is_released_on_drop() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = create_manager(&temp_dir).await;
+        let agent_id = Uuid::new_v4();
+
+        let guard = acquire_with_autorenew(manager.clone(), request("/tmp/a.rs", agent_id))
+            .await
+            .unwrap();
+        let lease_id = guard.lease().id;
+        tokio::time::sleep(std::time::Duration::from_millis(1200)).await;
+        let lease = manager.get_lease(&lease_id).await.unwrap().unwrap();
+        assert!(lease.renewal_count >= 1);
+        assert!(!guard.is_lost());
+
+        drop(guard);
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        let lease = manager.get_lease(&lease_id).await.unwrap().unwrap();
+        assert_eq!(lease.status, LeaseStatus::Released);
+        let locked = manager.is_file_locked(Path::new("/tmp/a.rs")).await;
+        assert!(!locked.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_failed_renewal_marks_guard_lost() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = create_manager(&temp_dir).await;
+        let agent_id = Uuid::new_v4();
+
+        let guard = acquire_with_autorenew(manager.clone(), request("/tmp/b.rs", agent_id))
+            .await
+            .unwrap();
+        let lost = guard.lost_flag();
+
+        // Someone else takes the lease away; the next renewal must fail
+        manager.force_release_agent_leases(&agent_id).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(700)).await;
+        assert!(guard.is_lost());
+        assert!(lost.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_stale_fencing_token_is_rejected_after_reacquisition() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = create_manager(&temp_dir).await;
+        let file = Path::new("/tmp/c.rs");
+        let (slow, fast) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let mut slow_request = request("/tmp/c.rs", slow);
+        slow_request.ttl_seconds = 0;
+        let first = manager.acquire_lease(slow_request).await.unwrap();
+        let slow_lease = first.lease.unwrap();
+        assert_eq!(first.fencing_token, Some(slow_lease.fencing_token));
+
+        // The slow holder pauses past its TTL and another agent takes the file
+        manager.cleanup_expired_leases().await.unwrap();
+        let second = manager
+            .acquire_lease(request("/tmp/c.rs", fast))
+            .await
+            .unwrap();
+        let fast_lease = second.lease.unwrap();
+        assert!(fast_lease.fencing_token > slow_lease.fencing_token);
+
+        let renewal = manager
+            .renew_lease(LeaseRenewalRequest {
+                lease_id: slow_lease.id,
+                agent_id: slow,
+                new_ttl_seconds: None,
+                fencing_token: slow_lease.fencing_token,
+            })
+            .await
+            .unwrap();
+        assert!(!renewal.success);
+        assert!(renewal.error.unwrap().starts_with("Stale fencing token"));
+        let release = manager
+            .release_lease(LeaseReleaseRequest {
+                lease_id: slow_lease.id,
+                agent_id: slow,
+                fencing_token: slow_lease.fencing_token,
+            })
+            .await
+            .unwrap();
+        assert!(!release.success);
+
+        // Writes guarded by the stale token are refused; the new holder's token passes
+        assert!(manager
+            .validate_fencing_token(file, slow_lease.fencing_token)
+            .await
+            .is_err());
+        manager
+            .validate_fencing_token(file, fast_lease.fencing_token)
+            .await
+            .unwrap();
+        let renewal = manager
+            .renew_lease(LeaseRenewalRequest {
+                lease_id: fast_lease.id,
+                agent_id: fast,
+                new_ttl_seconds: None,
+                fencing_token: fast_lease.fencing_token,
+            })
+            .await
+            .unwrap();
+        assert!(renewal.success);
+    }
+}
@@ -37,9 +37,11 @@ pub mod state_store;
 pub mod swarm_parser;
 pub mod task_queries;
 pub mod scg_task_storage;
+pub mod scg_validation;
 pub mod scud_plugin;
 pub mod thoughts;
 pub mod time_travel_integration;
+pub mod tokenizer;
 pub mod traits;
 pub mod workflow_commands;
 pub mod workflow_executor;
@@ -56,6 +58,7 @@ pub mod zmq_server;
 pub mod session;
 pub mod session_manager;
 pub mod session_transcript;
+pub mod transcript_index;
 
 // Daemon lifecycle management
 pub mod daemon_launcher;
@@ -70,10 +73,10 @@ pub use errors::{
 };
 
 pub use traits::{
-    ActorType, AgentConfig, AgentHandle, AgentInfo, AgentRunner, AgentSignal, AgentStatus,
-    AttachInfo, ContextSyncer, Event, ExitStatus, FinishReason, Message, MessageRole, ModelBackend,
-    ModelProviderMode, ModelRequest, ModelResponse, PauseMode, StateStore, Task, TaskComplexity,
-    TaskPriority, TaskStatus, Tool, ToolCall, ToolParameters,
+    ActorType, AgentConfig, AgentHandle, AgentInfo, AgentRecord, AgentRunner, AgentSignal,
+    AgentStatus, AttachInfo, ContextSyncer, Event, ExitStatus, FinishReason, Message, MessageRole,
+    ModelBackend, ModelProviderMode, ModelRequest, ModelResponse, PauseMode, StateStore, Task,
+    TaskComplexity, TaskPriority, TaskStatus, Tool, ToolCall, ToolParameters,
     // SCUD integration types (for gradual migration to unified task model)
     ScudPhase, ScudPriority, ScudStorage, ScudTask, ScudTaskStatus,
     parse_scg, serialize_scg, scud_to_task, task_to_scud,
@@ -81,7 +84,7 @@ pub use traits::{
 
 pub use providers::{
     AnthropicProvider, ClaudeCodeAdapter, HeadlessCliAdapter, OllamaProvider, OpenAiProvider,
-    ProviderFactory,
+    ProviderFactory, RetryPolicy,
 };
 
 pub use agent_runner::{GracefulShutdown, LocalAgentHandle, LocalProcessRunner, ProcessRunnerConfig};
@@ -95,7 +98,8 @@ pub use attach_protocol::{
 pub use agent_state::{
     AgentError as RuntimeAgentError, AgentProgress, AgentRuntimeState, AgentStateCollection,
     AgentStatistics, AgentStatus as RuntimeAgentStatus, AgentStreamMessage, LifecycleEvent,
-    OutputStream, StatusTransition,
+    OutputChunk, OutputLog, OutputStream, OutputSubscription, StatusTransition,
+    StatusTransitionTable,
 };
 
 pub use agent_stream_parser::{
@@ -104,16 +108,19 @@ pub use agent_stream_parser::{
 };
 
 pub use zmq_agent_runner::{
-    deserialize_zmq_message, serialize_zmq_message, validate_message_size, BatchAgentResult,
-    BatchControlCommand, BatchControlResponse, CommandResponse, ControlCommand, ControlCommandType,
-    CustomActionRequest, HealthCheckRequest, HealthCheckResponse, ListAgentsRequest,
-    ListAgentsResponse, LogStreamMessage, LogStreamType, OutputQueryRequest, OutputQueryResponse,
-    SpawnRequest, SpawnResponse, StatusUpdate, StatusUpdateType, ZmqAgentRunner, ZmqMessage,
-    ZmqOutputStream, ZmqRunnerConfig, DEFAULT_TIMEOUT_SECS, MAX_MESSAGE_SIZE, ZMQ_PROTOCOL_VERSION,
+    deserialize_zmq_message, serialize_zmq_message, serialize_zmq_message_with, spawn_batch,
+    validate_message_size, BatchAgentResult, BatchControlCommand, BatchControlResponse,
+    CommandResponse, ControlCommand, ControlCommandType, CustomActionRequest, HealthCheckRequest,
+    HealthCheckResponse, ListAgentsRequest, ListAgentsResponse, LogStreamMessage, LogStreamType,
+    OutputQueryRequest, OutputQueryResponse, SpawnRequest, SpawnResponse, StatusUpdate,
+    StatusUpdateType, ZmqAgentRunner, ZmqMessage, ZmqOutputStream, ZmqRunnerConfig,
+    COMPRESSION_THRESHOLD, DEFAULT_TIMEOUT_SECS, MAX_DECOMPRESSED_SIZE, MAX_MESSAGE_SIZE,
+    ZMQ_PROTOCOL_VERSION,
 };
 
 pub use zmq_communication::{
-    ConnectionState, ConnectionStats, SocketType, ZmqConnection, ZmqMessageRouter,
+    AdaptiveTimeoutPolicy, ConnectionState, ConnectionStats, HeartbeatPolicy, SocketType,
+    ZmqConnection, ZmqHeartbeat, ZmqMessageRouter, ZmqReceiver,
 };
 
 pub use zmq_client::ZmqClient;
@@ -130,8 +137,9 @@ pub use intercepting_backend::{
 };
 
 pub use lease::{
-    Lease, LeaseAcquisitionRequest, LeaseAcquisitionResponse, LeaseManager, LeaseReleaseRequest,
-    LeaseReleaseResponse, LeaseRenewalRequest, LeaseRenewalResponse, LeaseStatus,
+    acquire_with_autorenew, Lease, LeaseAcquisitionRequest, LeaseAcquisitionResponse, LeaseGuard,
+    LeaseManager, LeaseReleaseRequest, LeaseReleaseResponse, LeaseRenewalRequest,
+    LeaseRenewalResponse, LeaseStatus,
 };
 
 pub use lease_manager::SqliteLeaseManager;
@@ -139,24 +147,26 @@ pub use lease_manager::SqliteLeaseManager;
 pub use state_store::{AgentState, Migration, SqliteStateStore, StateTransition};
 
 pub use task_queries::{
-    KanbanBoard, SortOrder, TaskQueries, TaskQueryBuilder, TaskSortField, TaskStatistics,
+    KanbanBoard, SortOrder, TaskPage, TaskQueries, TaskQueryBuilder, TaskSortField, TaskStatistics,
 };
 
 pub use scg_task_storage::{
-    ScgPhaseStats, ScgSortField, ScgSortOrder, ScgTaskQueries, ScgTaskQueryBuilder, ScgTaskStorage,
+    critical_path_lengths, select_next_task, ScgPhaseStats, ScgSortField, ScgSortOrder,
+    ScgTaskQueries, ScgTaskQueryBuilder, ScgTaskStorage, TaskSchedule,
 };
 
 pub use config::{
-    AgentBehaviorConfig, AnthropicConfig, ConfigManager, DeepSeekConfig, DescaratesConfig,
-    FeaturesConfig, GroqConfig, LoggingConfig, OllamaConfig, OpenAiConfig,
-    ProvidersConfig, SecurityConfig, StorageConfig,
+    config_json_schema, diff_configs, resolve_api_key, AgentBehaviorConfig, AnthropicConfig,
+    ConfigAuditEntry, ConfigDiff, ConfigFieldChange, ConfigManager, ConfigSection, DeepSeekConfig,
+    DescaratesConfig, FeaturesConfig, GroqConfig, LoggingConfig, OllamaConfig, OpenAiConfig,
+    ProvidersConfig, ScudConfig, SecurityConfig, StorageConfig,
 };
 
 pub use config_loader::{
     ensure_config_directories, init_config, ConfigDiscoveryStrategy, ConfigLoader, ConfigValidator,
 };
 
-pub use config_migration::ConfigMigration;
+pub use config_migration::{ConfigMigration, FieldChange, MigrationReport, CURRENT_CONFIG_VERSION};
 
 pub use config_watcher::{
     ConfigChangeEvent, ConfigChangeListener, ConfigWatcher, HotReloadManager,
@@ -164,8 +174,8 @@ pub use config_watcher::{
 
 pub use state_machine::{
     DefaultStateHandler, HierarchicalState, SerializedWorkflow, StateHandler, StateHistoryEntry,
-    StateMachineError, StateMachineResult, TransitionMetadata, WorkflowEvent, WorkflowMetadata,
-    WorkflowOrchestrator, WorkflowState, WorkflowStateMachine,
+    StateMachineError, StateMachineResult, TransitionGuard, TransitionMetadata, WorkflowEvent,
+    WorkflowMetadata, WorkflowOrchestrator, WorkflowState, WorkflowStateMachine,
 };
 
 pub use state_machine_store::{
@@ -174,8 +184,8 @@ pub use state_machine_store::{
 
 pub use swarm_parser::{
     AgentConfig as SwarmAgentConfig, Contract, Handler, ResourceConfig, State, SwarmConfig,
-    SwarmParseError, SwarmParser, SwarmResult, ValidatedState, ValidatedWorkflow, Workflow,
-    WorkflowMetadata as SwarmWorkflowMetadata,
+    SwarmLintKind, SwarmLintWarning, SwarmParseError, SwarmParser, SwarmResult, ValidatedState,
+    ValidatedWorkflow, Workflow, WorkflowMetadata as SwarmWorkflowMetadata,
 };
 
 pub use thoughts::{
@@ -189,12 +199,13 @@ pub use agent_definitions::{
 
 pub use workflow_commands::{
     get_workflow, list_workflows, prepare_workflow, StepResult, WorkflowCommand, WorkflowContext,
-    WorkflowError, WorkflowExecutionResult, WorkflowRegistry, WorkflowResult, WorkflowStep,
+    WorkflowDiscoveryWarning, WorkflowError, WorkflowExecutionResult, WorkflowRegistry,
+    WorkflowResult, WorkflowStep, WorkflowVariables,
 };
 
 pub use workflow_executor::{
-    execute_step, execute_workflow, StepExecutionResult, WorkflowExecutionError,
-    WorkflowExecutorConfig,
+    execute_step, execute_workflow, CompensationResult, ContractPhase, StepExecutionResult,
+    StepProfile, WorkflowExecutionError, WorkflowExecutorConfig, WorkflowProfile,
 };
 
 pub use flow_executor::{
@@ -257,19 +268,22 @@ pub use agent_history::{
 };
 
 pub use body_restore::{
-    BodyRestoreManager, CommitInfo, CoordinatedRestore, GitBodyRestoreManager, RepositoryBackup,
-    RestoreOptions as BodyRestoreOptions, RestoreResult as BodyRestoreResult,
+    BackupMessageTemplate, BodyRestoreManager, CommitInfo, CoordinatedRestore,
+    CoordinatedRestoreResult, FileChange, FileChangeKind, GitBodyRestoreManager, RepositoryBackup,
+    RestoreOptions as BodyRestoreOptions, RestorePreview, RestoreResult as BodyRestoreResult,
 };
 
 pub use brain_restore::{
-    compare_states, create_snapshot_from_state, BrainRestore, BrainState, ConversationState,
-    DecisionNode, DefaultBrainRestore, MessageEntry, RestoreOptions as BrainRestoreOptions,
-    RestoreResult as BrainRestoreResult, ThoughtEntry,
+    apply_conversation_window, compare_states, create_snapshot_from_state, diff_decisions,
+    BrainRestore, BrainState, ConversationState, ConversationWindow, DecisionDiff,
+    DecisionDivergence, DecisionNode, DecisionSide, DefaultBrainRestore, MessageEntry,
+    RestoreOptions as BrainRestoreOptions, RestoreResult as BrainRestoreResult, ThoughtEntry,
 };
 
 pub use time_travel_integration::{
-    describe_rewind, slider_to_rewind_point, DefaultRewindManager, ResumeContext, RewindBackup,
-    RewindConfig, RewindConfirmation, RewindManager, RewindPoint, RewindProgress, RewindResult,
+    describe_rewind, slider_to_rewind_point, slider_to_rewind_point_with_granularity,
+    DefaultRewindManager, ResumeContext, RewindBackup, RewindConfig, RewindConfirmation,
+    RewindGranularity, RewindManager, RewindPoint, RewindPreview, RewindProgress, RewindResult,
     ValidationResult,
 };
 
@@ -289,7 +303,10 @@ mod tests {
 #[cfg(test)]
 mod providers_test;
 
-pub use dag::{DAGEdge, DAGError, DAGNode, DAGResult, DAGStatistics, EdgeType, Position, DAG};
+pub use dag::{
+    DAGEdge, DAGError, DAGNode, DAGResult, DAGStatistics, DagDiff, EdgeChange, EdgeField,
+    EdgeType, LayoutConfig, NodeChange, NodeField, Position, DAG,
+};
 
 pub use dag_toml::{
     load_dag_from_toml, save_dag_to_toml, TomlDAG, TomlDAGEdge, TomlDAGNode, TomlPosition,
@@ -314,21 +331,33 @@ pub use daemon_launcher::{
 
 pub use tools::{
     bash_tool, edit_tool, execute_bash, execute_edit, execute_read, execute_spawn_session,
-    execute_tool, execute_tool_async, execute_write, get_system_prompt, get_tools,
-    lisp_developer_system_prompt, minimal_system_prompt, orchestrator_system_prompt,
-    parse_tool_level, planner_system_prompt, read_tool, readonly_system_prompt,
-    researcher_system_prompt, spawn_session_tool, swank_compile_tool, swank_eval_tool,
-    swank_inspect_tool, swank_restart_tool, tool_level_to_allowed_tools, write_tool,
-    ExecutionContext, ToolLevel, ToolResult, SWANK_REGISTRY, execute_swank_compile,
+    execute_tool, execute_tool_async, execute_tool_with_checkpoint, execute_write,
+    get_system_prompt, get_tools, lisp_developer_system_prompt, minimal_system_prompt,
+    orchestrator_system_prompt, parse_tool_level, planner_system_prompt, read_tool,
+    readonly_system_prompt, researcher_system_prompt, spawn_session_tool, swank_compile_tool,
+    swank_eval_tool, swank_inspect_tool, swank_restart_tool, tool_level_to_allowed_tools,
+    write_tool, Artifact, ArtifactCollector, CheckpointConfig, CheckpointHook, ExecutionContext,
+    RewindCheckpointHook, ARTIFACTS_DIR,
+    ToolCheckpoints, ToolLevel, ToolResult, SWANK_REGISTRY, execute_swank_compile,
     execute_swank_eval, execute_swank_inspect, execute_swank_restart,
 };
 
 // Swank integration (Lisp live development)
 pub use swank::{
     find_available_port, LauncherError, SwankClient, SwankError, SwankFrame, SwankLauncher,
-    SwankMessage, SwankRestart, SwankSessionRegistry, DEFAULT_SWANK_PORT,
+    SwankMessage, SwankPool, SwankPoolConfig, SwankPoolError, SwankRestart, SwankSessionRegistry,
+    DEFAULT_SWANK_PORT,
 };
 
 pub use session_transcript::{
-    default_sessions_dir, TranscriptEntry, TranscriptMetadata, TranscriptWriter,
+    default_sessions_dir, SharedTranscript, TranscriptEntry, TranscriptMetadata, TranscriptWriter,
 };
+
+pub use transcript_index::{TranscriptHit, TranscriptIndex, TRANSCRIPT_INDEX_FILE};
+
+pub use tokenizer::{
+    count_tokens, register_tokenizer, tokenizer_for_model, HeuristicTokenizer, TiktokenTokenizer,
+    Tokenizer, TokenizerRegistry,
+};
+
+pub use scg_validation::{validate_scg, ScgIssue, ScgIssueKind, ScgValidationReport};
@@ -17,16 +17,25 @@ pub type StreamingResponse =
 // ============================================================================
 
 /// OpenAI provider using HTTP API.
+///
+/// Also works with OpenAI-compatible servers (vLLM, LiteLLM, ...) by passing
+/// their base URL as the endpoint and, if needed, a custom auth header.
 pub struct OpenAiProvider {
     _mode: ModelProviderMode,
     client: Option<reqwest::Client>,
     available_models: Vec<String>,
+    /// Model whose tokenizer `estimate_tokens` uses
+    model: String,
+    auth_header: String,
+    auth_scheme: String,
 }
 
 impl OpenAiProvider {
     /// Create a new OpenAI provider.
     pub fn new(api_key: String, endpoint: Option<String>) -> Self {
-        let endpoint = endpoint.unwrap_or_else(|| "https://api.openai.com/v1".to_string());
+        let endpoint = endpoint
+            .map(|e| e.trim_end_matches('/').to_string())
+            .unwrap_or_else(|| "https://api.openai.com/v1".to_string());
         Self {
             _mode: ModelProviderMode::Api { endpoint, api_key },
             client: None,
@@ -35,8 +44,56 @@ impl OpenAiProvider {
                 "gpt-4-turbo".to_string(),
                 "gpt-3.5-turbo".to_string(),
             ],
+            model: "gpt-4".to_string(),
+            auth_header: "Authorization".to_string(),
+            auth_scheme: "Bearer".to_string(),
         }
     }
+
+    /// Set the configured model, used to pick the tokenizer for estimates.
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Override the header used to send the API key.
+    ///
+    /// The header value is `"{scheme} {api_key}"`, or just the key when
+    /// `scheme` is empty (e.g. `api-key` for Azure-style gateways).
+    pub fn with_auth_header(
+        mut self,
+        header: impl Into<String>,
+        scheme: impl Into<String>,
+    ) -> Self {
+        self.auth_header = header.into();
+        self.auth_scheme = scheme.into();
+        self
+    }
+
+    /// Header name used to send the API key.
+    pub fn auth_header(&self) -> &str {
+        &self.auth_header
+    }
+
+    /// Scheme prefixed to the API key (empty for a raw key).
+    pub fn auth_scheme(&self) -> &str {
+        &self.auth_scheme
+    }
+}
+
+/// Attach the API key to a request using the given header and scheme.
+fn apply_auth(
+    builder: reqwest::RequestBuilder,
+    header: &str,
+    scheme: &str,
+    api_key: &str,
+) -> reqwest::RequestBuilder {
+    let value = if scheme.is_empty() {
+        api_key.to_string()
+    } else {
+        format!("{} {}", scheme, api_key)
+    };
+    builder.header(header, value)
 }
 
 #[async_trait]
@@ -57,11 +114,14 @@ impl ModelBackend for OpenAiProvider {
     async fn health_check(&self) -> AgentResult<bool> {
         if let Some(client) = &self.client {
             if let ModelProviderMode::Api { endpoint, api_key } = &self._mode {
-                let resp = client
-                    .get(format!("{}/models", endpoint))
-                    .bearer_auth(api_key)
-                    .send()
-                    .await;
+                let resp = apply_auth(
+                    client.get(format!("{}/models", endpoint)),
+                    &self.auth_header,
+                    &self.auth_scheme,
+                    api_key,
+                )
+                .send()
+                .await;
                 Ok(resp.is_ok())
             } else {
                 Ok(false)
@@ -85,13 +145,16 @@ impl ModelBackend for OpenAiProvider {
                 "temperature": request.temperature.unwrap_or(0.7),
             });
 
-            let response = client
-                .post(format!("{}/chat/completions", endpoint))
-                .bearer_auth(api_key)
-                .json(&payload)
-                .send()
-                .await
-                .map_err(ProviderError::ReqwestError)?;
+            let response = apply_auth(
+                client.post(format!("{}/chat/completions", endpoint)),
+                &self.auth_header,
+                &self.auth_scheme,
+                api_key,
+            )
+            .json(&payload)
+            .send()
+            .await
+            .map_err(ProviderError::ReqwestError)?;
 
             if !response.status().is_success() {
                 return Err(ProviderError::ApiError(format!(
@@ -106,18 +169,22 @@ impl ModelBackend for OpenAiProvider {
                 .await
                 .map_err(ProviderError::ReqwestError)?;
 
-            let content = body
-                .get("choices")
-                .and_then(|c| c.get(0))
+            let choice = body.get("choices").and_then(|c| c.get(0));
+            let content = choice
                 .and_then(|c| c.get("message"))
                 .and_then(|m| m.get("content"))
                 .and_then(|c| c.as_str())
                 .unwrap_or("")
                 .to_string();
+            let finish_reason = choice
+                .and_then(|c| c.get("finish_reason"))
+                .and_then(|r| r.as_str())
+                .map(FinishReason::from_provider)
+                .unwrap_or(FinishReason::Stop);
 
             Ok(ModelResponse {
                 content,
-                finish_reason: FinishReason::Stop,
+                finish_reason,
                 tokens_used: None,
                 tool_calls: None,
             })
@@ -144,6 +211,8 @@ impl ModelBackend for OpenAiProvider {
                 ProviderError::BackendError("Invalid mode for OpenAI provider".to_string()).into(),
             );
         };
+        let auth_header = self.auth_header.clone();
+        let auth_scheme = self.auth_scheme.clone();
 
         let payload = json!({
             "model": request.model,
@@ -154,13 +223,16 @@ impl ModelBackend for OpenAiProvider {
         });
 
         let stream = try_stream! {
-            let response = client
-                .post(format!("{}/chat/completions", endpoint))
-                .bearer_auth(&api_key)
-                .json(&payload)
-                .send()
-                .await
-                .map_err(ProviderError::ReqwestError)?;
+            let response = apply_auth(
+                client.post(format!("{}/chat/completions", endpoint)),
+                &auth_header,
+                &auth_scheme,
+                &api_key,
+            )
+            .json(&payload)
+            .send()
+            .await
+            .map_err(ProviderError::ReqwestError)?;
 
             if !response.status().is_success() {
                 Err(ProviderError::ApiError(format!(
@@ -171,6 +243,7 @@ impl ModelBackend for OpenAiProvider {
 
             let mut byte_stream = response.bytes_stream();
             let mut buffer = String::new();
+            let mut finish_reason = FinishReason::Stop;
 
             while let Some(chunk_result) = byte_stream.next().await {
                 let chunk = chunk_result.map_err(ProviderError::ReqwestError)?;
@@ -191,7 +264,7 @@ impl ModelBackend for OpenAiProvider {
                     if data == "[DONE]" {
                         yield ModelResponse {
                             content: String::new(),
-                            finish_reason: FinishReason::Stop,
+                            finish_reason: finish_reason.clone(),
                             tokens_used: None,
                             tool_calls: None,
                         };
@@ -199,6 +272,16 @@ impl ModelBackend for OpenAiProvider {
                     }
 
                     if let Ok(json) = serde_json::from_str::<serde_json::Value>(data) {
+                        // The final chunk carries why generation ended
+                        if let Some(reason) = json
+                            .get("choices")
+                            .and_then(|c| c.get(0))
+                            .and_then(|c| c.get("finish_reason"))
+                            .and_then(|r| r.as_str())
+                        {
+                            finish_reason = FinishReason::from_provider(reason);
+                        }
+
                         if let Some(content) = json
                             .get("choices")
                             .and_then(|c| c.get(0))
@@ -226,8 +309,7 @@ impl ModelBackend for OpenAiProvider {
     }
 
     async fn estimate_tokens(&self, text: &str) -> AgentResult<usize> {
-        // Simple heuristic: ~4 chars per token
-        Ok(text.len().div_ceil(4))
+        Ok(crate::tokenizer::count_tokens(&self.model, text))
     }
 
     async fn shutdown(&mut self) -> AgentResult<()> {
@@ -236,11 +318,117 @@ impl ModelBackend for OpenAiProvider {
     }
 }
 
+/// Retry policy for transient provider failures (rate limits, overload).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt (0 disables retrying)
+    pub max_retries: u32,
+    /// Backoff before the first retry; doubles on each subsequent retry
+    pub initial_backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff_ms: 1000,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Upper bound on a single backoff, including server-requested delays
+    const MAX_BACKOFF_MS: u64 = 60_000;
+
+    /// Whether a response status is worth retrying (429, 503 and Anthropic's 529 "overloaded").
+    pub fn is_retryable(status: reqwest::StatusCode) -> bool {
+        matches!(status.as_u16(), 429 | 503 | 529)
+    }
+
+    /// Delay before retry number `attempt` (starting at 1).
+    ///
+    /// Honors the server's `Retry-After` when given; otherwise uses exponential
+    /// backoff with jitter, between half and all of `initial_backoff_ms * 2^(attempt-1)`.
+    pub fn backoff(
+        &self,
+        attempt: u32,
+        retry_after: Option<std::time::Duration>,
+    ) -> std::time::Duration {
+        let max = std::time::Duration::from_millis(Self::MAX_BACKOFF_MS);
+        if let Some(retry_after) = retry_after {
+            return retry_after.min(max);
+        }
+
+        let base = self
+            .initial_backoff_ms
+            .saturating_mul(1u64 << attempt.saturating_sub(1).min(20))
+            .min(Self::MAX_BACKOFF_MS);
+        let jitter = if base > 1 {
+            rand::Rng::gen_range(&mut rand::thread_rng(), 0..=base / 2)
+        } else {
+            0
+        };
+        std::time::Duration::from_millis(base - base / 2 + jitter)
+    }
+}
+
+/// Parse a `Retry-After` header given in seconds.
+fn retry_after(response: &reqwest::Response) -> Option<std::time::Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(std::time::Duration::from_secs)
+}
+
+/// Send a request, retrying rate-limited and overloaded responses per `policy`.
+///
+/// Non-retryable responses (including 4xx other than 429) are returned as-is
+/// for the caller to handle.
+async fn send_with_retry<F>(
+    build_request: F,
+    policy: &RetryPolicy,
+    provider: &str,
+) -> Result<reqwest::Response, ProviderError>
+where
+    F: Fn() -> reqwest::RequestBuilder,
+{
+    let mut attempt = 0;
+    loop {
+        let response = build_request()
+            .send()
+            .await
+            .map_err(ProviderError::ReqwestError)?;
+
+        let status = response.status();
+        if !RetryPolicy::is_retryable(status) || attempt >= policy.max_retries {
+            return Ok(response);
+        }
+
+        attempt += 1;
+        let delay = policy.backoff(attempt, retry_after(&response));
+        tracing::warn!(
+            "{} returned {}; retry {}/{} in {}ms",
+            provider,
+            status,
+            attempt,
+            policy.max_retries,
+            delay.as_millis()
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
+
 /// Anthropic provider using HTTP API.
 pub struct AnthropicProvider {
     _mode: ModelProviderMode,
     client: Option<reqwest::Client>,
     available_models: Vec<String>,
+    retry_policy: RetryPolicy,
 }
 
 impl AnthropicProvider {
@@ -255,8 +443,20 @@ impl AnthropicProvider {
                 "claude-3-sonnet-20240229".to_string(),
                 "claude-3-haiku-20240307".to_string(),
             ],
+            retry_policy: RetryPolicy::default(),
         }
     }
+
+    /// Set how rate-limited (429) and overloaded (529/503) responses are retried.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Current retry policy.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
 }
 
 #[async_trait]
@@ -293,14 +493,18 @@ impl ModelBackend for AnthropicProvider {
                 "system": request.system_prompt.unwrap_or_default(),
             });
 
-            let response = client
-                .post(format!("{}/messages", endpoint))
-                .header("x-api-key", api_key)
-                .header("anthropic-version", "2023-06-01")
-                .json(&payload)
-                .send()
-                .await
-                .map_err(ProviderError::ReqwestError)?;
+            let response = send_with_retry(
+                || {
+                    client
+                        .post(format!("{}/messages", endpoint))
+                        .header("x-api-key", api_key)
+                        .header("anthropic-version", "2023-06-01")
+                        .json(&payload)
+                },
+                &self.retry_policy,
+                "Anthropic",
+            )
+            .await?;
 
             if !response.status().is_success() {
                 return Err(ProviderError::ApiError(format!(
@@ -322,10 +526,15 @@ impl ModelBackend for AnthropicProvider {
                 .and_then(|t| t.as_str())
                 .unwrap_or("")
                 .to_string();
+            let finish_reason = body
+                .get("stop_reason")
+                .and_then(|r| r.as_str())
+                .map(FinishReason::from_provider)
+                .unwrap_or(FinishReason::Stop);
 
             Ok(ModelResponse {
                 content,
-                finish_reason: FinishReason::Stop,
+                finish_reason,
                 tokens_used: None,
                 tool_calls: None,
             })
@@ -364,16 +573,21 @@ impl ModelBackend for AnthropicProvider {
             "system": request.system_prompt.unwrap_or_default(),
             "stream": true,
         });
+        let retry_policy = self.retry_policy;
 
         let stream = try_stream! {
-            let response = client
-                .post(format!("{}/messages", endpoint))
-                .header("x-api-key", &api_key)
-                .header("anthropic-version", "2023-06-01")
-                .json(&payload)
-                .send()
-                .await
-                .map_err(ProviderError::ReqwestError)?;
+            let response = send_with_retry(
+                || {
+                    client
+                        .post(format!("{}/messages", endpoint))
+                        .header("x-api-key", &api_key)
+                        .header("anthropic-version", "2023-06-01")
+                        .json(&payload)
+                },
+                &retry_policy,
+                "Anthropic",
+            )
+            .await?;
 
             if !response.status().is_success() {
                 Err(ProviderError::ApiError(format!(
@@ -384,6 +598,7 @@ impl ModelBackend for AnthropicProvider {
 
             let mut byte_stream = response.bytes_stream();
             let mut buffer = String::new();
+            let mut finish_reason = FinishReason::Stop;
 
             while let Some(chunk_result) = byte_stream.next().await {
                 let chunk = chunk_result.map_err(ProviderError::ReqwestError)?;
@@ -424,10 +639,19 @@ impl ModelBackend for AnthropicProvider {
                                     };
                                 }
                             }
+                            Some("message_delta") => {
+                                if let Some(reason) = json
+                                    .get("delta")
+                                    .and_then(|d| d.get("stop_reason"))
+                                    .and_then(|r| r.as_str())
+                                {
+                                    finish_reason = FinishReason::from_provider(reason);
+                                }
+                            }
                             Some("message_stop") => {
                                 yield ModelResponse {
                                     content: String::new(),
-                                    finish_reason: FinishReason::Stop,
+                                    finish_reason: finish_reason.clone(),
                                     tokens_used: None,
                                     tool_calls: None,
                                 };
@@ -448,8 +672,7 @@ impl ModelBackend for AnthropicProvider {
     }
 
     async fn estimate_tokens(&self, text: &str) -> AgentResult<usize> {
-        // Simple heuristic: ~3 chars per token for Claude
-        Ok(text.len().div_ceil(3))
+        Ok(crate::tokenizer::count_tokens("claude", text))
     }
 
     async fn shutdown(&mut self) -> AgentResult<()> {
@@ -463,6 +686,8 @@ pub struct GrokProvider {
     _mode: ModelProviderMode,
     client: Option<reqwest::Client>,
     available_models: Vec<String>,
+    /// Model whose tokenizer `estimate_tokens` uses
+    model: String,
 }
 
 impl GrokProvider {
@@ -478,8 +703,15 @@ impl GrokProvider {
                 "grok-4-1".to_string(),
                 "grok-3-latest".to_string(),
             ],
+            model: "grok-4-1-fast-reasoning".to_string(),
         }
     }
+
+    /// Set the configured model, used to pick the tokenizer for estimates.
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
 }
 
 #[async_trait]
@@ -549,18 +781,22 @@ impl ModelBackend for GrokProvider {
                 .await
                 .map_err(ProviderError::ReqwestError)?;
 
-            let content = body
-                .get("choices")
-                .and_then(|c| c.get(0))
+            let choice = body.get("choices").and_then(|c| c.get(0));
+            let content = choice
                 .and_then(|c| c.get("message"))
                 .and_then(|m| m.get("content"))
                 .and_then(|c| c.as_str())
                 .unwrap_or("")
                 .to_string();
+            let finish_reason = choice
+                .and_then(|c| c.get("finish_reason"))
+                .and_then(|r| r.as_str())
+                .map(FinishReason::from_provider)
+                .unwrap_or(FinishReason::Stop);
 
             Ok(ModelResponse {
                 content,
-                finish_reason: FinishReason::Stop,
+                finish_reason,
                 tokens_used: None,
                 tool_calls: None,
             })
@@ -615,6 +851,7 @@ impl ModelBackend for GrokProvider {
 
             let mut byte_stream = response.bytes_stream();
             let mut buffer = String::new();
+            let mut finish_reason = FinishReason::Stop;
 
             while let Some(chunk_result) = byte_stream.next().await {
                 let chunk = chunk_result.map_err(ProviderError::ReqwestError)?;
@@ -635,7 +872,7 @@ impl ModelBackend for GrokProvider {
                     if data == "[DONE]" {
                         yield ModelResponse {
                             content: String::new(),
-                            finish_reason: FinishReason::Stop,
+                            finish_reason: finish_reason.clone(),
                             tokens_used: None,
                             tool_calls: None,
                         };
@@ -643,6 +880,16 @@ impl ModelBackend for GrokProvider {
                     }
 
                     if let Ok(json) = serde_json::from_str::<serde_json::Value>(data) {
+                        // The final chunk carries why generation ended
+                        if let Some(reason) = json
+                            .get("choices")
+                            .and_then(|c| c.get(0))
+                            .and_then(|c| c.get("finish_reason"))
+                            .and_then(|r| r.as_str())
+                        {
+                            finish_reason = FinishReason::from_provider(reason);
+                        }
+
                         if let Some(content) = json
                             .get("choices")
                             .and_then(|c| c.get(0))
@@ -670,8 +917,7 @@ impl ModelBackend for GrokProvider {
     }
 
     async fn estimate_tokens(&self, text: &str) -> AgentResult<usize> {
-        // Simple heuristic: ~4 chars per token
-        Ok(text.len().div_ceil(4))
+        Ok(crate::tokenizer::count_tokens(&self.model, text))
     }
 
     async fn shutdown(&mut self) -> AgentResult<()> {
@@ -771,7 +1017,7 @@ impl ModelBackend for ClaudeCodeAdapter {
     }
 
     async fn estimate_tokens(&self, text: &str) -> AgentResult<usize> {
-        Ok(text.len().div_ceil(3))
+        Ok(crate::tokenizer::count_tokens("claude", text))
     }
 
     async fn shutdown(&mut self) -> AgentResult<()> {
@@ -784,6 +1030,8 @@ pub struct HeadlessCliAdapter {
     _mode: ModelProviderMode,
     command: String,
     _args: Vec<String>,
+    /// Model the CLI runs, used to pick the tokenizer for estimates
+    model: String,
 }
 
 impl HeadlessCliAdapter {
@@ -796,8 +1044,15 @@ impl HeadlessCliAdapter {
             },
             command,
             _args: args,
+            model: String::new(),
         }
     }
+
+    /// Set the model the CLI runs, used to pick the tokenizer for estimates.
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
 }
 
 #[async_trait]
@@ -864,7 +1119,7 @@ impl ModelBackend for HeadlessCliAdapter {
     }
 
     async fn estimate_tokens(&self, text: &str) -> AgentResult<usize> {
-        Ok(text.len().div_ceil(4))
+        Ok(crate::tokenizer::count_tokens(&self.model, text))
     }
 
     async fn shutdown(&mut self) -> AgentResult<()> {
@@ -883,6 +1138,8 @@ pub struct OllamaProvider {
     endpoint: String,
     timeout_secs: u64,
     available_models: Vec<String>,
+    /// Model whose tokenizer `estimate_tokens` uses
+    model: String,
 }
 
 impl OllamaProvider {
@@ -899,8 +1156,15 @@ impl OllamaProvider {
             endpoint,
             timeout_secs: timeout,
             available_models: vec![],
+            model: "llama2".to_string(),
         }
     }
+
+    /// Set the configured model, used to pick the tokenizer for estimates.
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
 }
 
 #[async_trait]
@@ -1107,7 +1371,7 @@ impl ModelBackend for OllamaProvider {
     }
 
     async fn estimate_tokens(&self, text: &str) -> AgentResult<usize> {
-        Ok(text.len().div_ceil(4))
+        Ok(crate::tokenizer::count_tokens(&self.model, text))
     }
 
     async fn shutdown(&mut self) -> AgentResult<()> {
@@ -1124,6 +1388,16 @@ impl ModelBackend for OllamaProvider {
 pub struct ProviderFactory;
 
 impl ProviderFactory {
+    /// Provider names accepted by [`ProviderFactory::create`].
+    pub const PROVIDERS: &'static [&'static str] = &[
+        "openai",
+        "anthropic",
+        "claude-code-cli",
+        "ollama",
+        "grok",
+        "headless-cli",
+    ];
+
     /// Create a provider by name and configuration.
     pub fn create(
         provider_name: &str,
@@ -1137,8 +1411,27 @@ impl ProviderFactory {
                         ProviderError::ConfigError("Missing 'api_key' for OpenAI".to_string())
                     })?
                     .clone();
-                let endpoint = config.get("endpoint").cloned();
-                Ok(Box::new(OpenAiProvider::new(api_key, endpoint)))
+                // `base_url` points at an OpenAI-compatible server and wins over `endpoint`
+                let endpoint = config
+                    .get("base_url")
+                    .or_else(|| config.get("endpoint"))
+                    .cloned();
+                let mut provider = OpenAiProvider::new(api_key, endpoint);
+                if config.contains_key("auth_header") || config.contains_key("auth_scheme") {
+                    let header = config
+                        .get("auth_header")
+                        .cloned()
+                        .unwrap_or_else(|| provider.auth_header().to_string());
+                    let scheme = config
+                        .get("auth_scheme")
+                        .cloned()
+                        .unwrap_or_else(|| provider.auth_scheme().to_string());
+                    provider = provider.with_auth_header(header, scheme);
+                }
+                if let Some(model) = config.get("model") {
+                    provider = provider.with_model(model.clone());
+                }
+                Ok(Box::new(provider))
             }
             "anthropic" => {
                 let api_key = config
@@ -1148,7 +1441,20 @@ impl ProviderFactory {
                     })?
                     .clone();
                 let endpoint = config.get("endpoint").cloned();
-                Ok(Box::new(AnthropicProvider::new(api_key, endpoint)))
+                let defaults = RetryPolicy::default();
+                let retry_policy = RetryPolicy {
+                    max_retries: config
+                        .get("max_retries")
+                        .and_then(|v| v.parse().ok())
+                        .unwrap_or(defaults.max_retries),
+                    initial_backoff_ms: config
+                        .get("initial_backoff_ms")
+                        .and_then(|v| v.parse().ok())
+                        .unwrap_or(defaults.initial_backoff_ms),
+                };
+                Ok(Box::new(
+                    AnthropicProvider::new(api_key, endpoint).with_retry_policy(retry_policy),
+                ))
             }
             "claude-code-cli" => {
                 let command = config.get("command").cloned();
@@ -1162,7 +1468,11 @@ impl ProviderFactory {
                 let timeout = config
                     .get("timeout_secs")
                     .and_then(|t| t.parse::<u64>().ok());
-                Ok(Box::new(OllamaProvider::new(endpoint, timeout)))
+                let mut provider = OllamaProvider::new(endpoint, timeout);
+                if let Some(model) = config.get("model") {
+                    provider = provider.with_model(model.clone());
+                }
+                Ok(Box::new(provider))
             }
             "grok" => {
                 let api_key = config
@@ -1172,7 +1482,11 @@ impl ProviderFactory {
                     })?
                     .clone();
                 let endpoint = config.get("endpoint").cloned();
-                Ok(Box::new(GrokProvider::new(api_key, endpoint)))
+                let mut provider = GrokProvider::new(api_key, endpoint);
+                if let Some(model) = config.get("model") {
+                    provider = provider.with_model(model.clone());
+                }
+                Ok(Box::new(provider))
             }
             "headless-cli" => {
                 let command = config
@@ -1185,7 +1499,11 @@ impl ProviderFactory {
                     .get("args")
                     .map(|a| a.split(',').map(String::from).collect())
                     .unwrap_or_default();
-                Ok(Box::new(HeadlessCliAdapter::new(command, args)))
+                let mut adapter = HeadlessCliAdapter::new(command, args);
+                if let Some(model) = config.get("model") {
+                    adapter = adapter.with_model(model.clone());
+                }
+                Ok(Box::new(adapter))
             }
             _ => Err(ProviderError::ConfigError(format!(
                 "Unknown provider: {}",
@@ -252,6 +252,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_provider_factory_openai_base_url() {
+        let mut config = HashMap::new();
+        config.insert("api_key".to_string(), "test-key".to_string());
+        config.insert(
+            "endpoint".to_string(),
+            "https://api.openai.com/v1".to_string(),
+        );
+        config.insert(
+            "base_url".to_string(),
+            "http://localhost:4000/v1/".to_string(),
+        );
+
+        let provider = ProviderFactory::create("openai", config).unwrap();
+        if let crate::traits::ModelProviderMode::Api { endpoint, .. } = provider.mode() {
+            assert_eq!(endpoint, "http://localhost:4000/v1");
+        } else {
+            panic!("Expected API mode");
+        }
+    }
+
+    #[test]
+    fn test_openai_provider_auth_header_override() {
+        let provider = OpenAiProvider::new("test-key".to_string(), None);
+        assert_eq!(provider.auth_header(), "Authorization");
+        assert_eq!(provider.auth_scheme(), "Bearer");
+
+        let provider = provider.with_auth_header("api-key", "");
+        assert_eq!(provider.auth_header(), "api-key");
+        assert_eq!(provider.auth_scheme(), "");
+    }
+
+    #[tokio::test]
+    async fn test_openai_provider_sends_custom_auth_header() {
+        let (endpoint, requests) = serve_responses(vec![
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 42\r\nconnection: close\r\n\r\n{\"choices\":[{\"message\":{\"content\":\"ok\"}}]}",
+        ])
+        .await;
+
+        let mut provider = OpenAiProvider::new("test-key".to_string(), Some(endpoint))
+            .with_auth_header("api-key", "");
+        provider.initialize().await.unwrap();
+
+        let response = provider.complete(test_request()).await.unwrap();
+        assert_eq!(response.content, "ok");
+
+        let request = requests.lock()[0].to_lowercase();
+        assert!(request.contains("\r\napi-key: test-key\r\n"), "{}", request);
+        assert!(!request.contains("authorization:"), "{}", request);
+    }
+
     #[test]
     fn test_anthropic_provider_with_custom_endpoint() {
         let provider = AnthropicProvider::new(
@@ -364,4 +415,211 @@ mod tests {
         assert_eq!(model_list.len(), 1);
         assert_eq!(model_list[0], "default");
     }
+
+    /// Serve canned HTTP responses in order, recording each raw request.
+    async fn serve_responses(
+        responses: Vec<&'static str>,
+    ) -> (String, std::sync::Arc<parking_lot::Mutex<Vec<String>>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = std::sync::Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let received = requests.clone();
+
+        tokio::spawn(async move {
+            for response in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0u8; 16 * 1024];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                received
+                    .lock()
+                    .push(String::from_utf8_lossy(&buf[..n]).into_owned());
+                socket.write_all(response.as_bytes()).await.unwrap();
+                let _ = socket.shutdown().await;
+            }
+        });
+
+        (format!("http://{}", addr), requests)
+    }
+
+    fn test_request() -> ModelRequest {
+        ModelRequest {
+            messages: vec![Message {
+                role: MessageRole::User,
+                content: "hi".to_string(),
+            }],
+            model: "claude-3-haiku-20240307".to_string(),
+            max_tokens: Some(16),
+            temperature: None,
+            system_prompt: None,
+            tools: None,
+        }
+    }
+
+    #[test]
+    fn test_retry_policy_backoff() {
+        let policy = RetryPolicy {
+            max_retries: 3,
+            initial_backoff_ms: 100,
+        };
+
+        for attempt in 1..=3 {
+            let base = 100u64 << (attempt - 1);
+            let delay = policy.backoff(attempt, None).as_millis() as u64;
+            assert!(
+                delay >= base / 2 && delay <= base,
+                "attempt {}: {}ms",
+                attempt,
+                delay
+            );
+        }
+
+        assert_eq!(
+            policy.backoff(1, Some(std::time::Duration::from_secs(2))),
+            std::time::Duration::from_secs(2)
+        );
+        assert!(RetryPolicy::is_retryable(
+            reqwest::StatusCode::TOO_MANY_REQUESTS
+        ));
+        assert!(RetryPolicy::is_retryable(
+            reqwest::StatusCode::from_u16(529).unwrap()
+        ));
+        assert!(!RetryPolicy::is_retryable(reqwest::StatusCode::BAD_REQUEST));
+    }
+
+    #[test]
+    fn test_anthropic_retry_policy_override() {
+        let provider = AnthropicProvider::new("test-key".to_string(), None);
+        assert_eq!(*provider.retry_policy(), RetryPolicy::default());
+
+        let provider = provider.with_retry_policy(RetryPolicy {
+            max_retries: 5,
+            initial_backoff_ms: 250,
+        });
+        assert_eq!(provider.retry_policy().max_retries, 5);
+        assert_eq!(provider.retry_policy().initial_backoff_ms, 250);
+    }
+
+    #[tokio::test]
+    async fn test_anthropic_retries_rate_limit() {
+        let (endpoint, requests) = serve_responses(vec![
+            "HTTP/1.1 429 Too Many Requests\r\nretry-after: 0\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+            "HTTP/1.1 529 Overloaded\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 34\r\nconnection: close\r\n\r\n{\"content\":[{\"text\":\"recovered\"}]}",
+        ])
+        .await;
+
+        let mut provider = AnthropicProvider::new("test-key".to_string(), Some(endpoint))
+            .with_retry_policy(RetryPolicy {
+                max_retries: 3,
+                initial_backoff_ms: 1,
+            });
+        provider.initialize().await.unwrap();
+
+        let response = provider.complete(test_request()).await.unwrap();
+        assert_eq!(response.content, "recovered");
+        assert_eq!(requests.lock().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_anthropic_does_not_retry_bad_request() {
+        let (endpoint, requests) = serve_responses(vec![
+            "HTTP/1.1 400 Bad Request\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+            "HTTP/1.1 400 Bad Request\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+        ])
+        .await;
+
+        let mut provider = AnthropicProvider::new("test-key".to_string(), Some(endpoint))
+            .with_retry_policy(RetryPolicy {
+                max_retries: 3,
+                initial_backoff_ms: 1,
+            });
+        provider.initialize().await.unwrap();
+
+        assert!(provider.complete(test_request()).await.is_err());
+        assert_eq!(requests.lock().len(), 1);
+    }
+
+    /// Drain a provider stream, returning the joined text and the final chunk's reason.
+    async fn collect_stream(
+        provider: &dyn ModelBackend,
+        request: ModelRequest,
+    ) -> (String, FinishReason) {
+        use futures::StreamExt;
+
+        let mut stream = provider.stream(request).await.unwrap();
+        let mut content = String::new();
+        let mut finish_reason = FinishReason::Streaming;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.unwrap();
+            content.push_str(&chunk.content);
+            finish_reason = chunk.finish_reason;
+        }
+        (content, finish_reason)
+    }
+
+    #[tokio::test]
+    async fn test_openai_stream_reports_length_finish_reason() {
+        let (endpoint, _) = serve_responses(vec![concat!(
+            "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\nconnection: close\r\n\r\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"The answer\"},\"finish_reason\":null}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\" is\"},\"finish_reason\":null}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{},\"finish_reason\":\"length\"}]}\n\n",
+            "data: [DONE]\n\n",
+        )])
+        .await;
+
+        let mut provider = OpenAiProvider::new("test-key".to_string(), Some(endpoint));
+        provider.initialize().await.unwrap();
+
+        let (content, finish_reason) = collect_stream(&provider, test_request()).await;
+        assert_eq!(content, "The answer is");
+        assert_eq!(finish_reason, FinishReason::MaxTokens);
+    }
+
+    #[tokio::test]
+    async fn test_anthropic_stream_reports_max_tokens_stop_reason() {
+        let (endpoint, _) = serve_responses(vec![concat!(
+            "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\nconnection: close\r\n\r\n",
+            "event: content_block_delta\n",
+            "data: {\"type\":\"content_block_delta\",\"delta\":{\"type\":\"text_delta\",\"text\":\"The answer\"}}\n\n",
+            "event: message_delta\n",
+            "data: {\"type\":\"message_delta\",\"delta\":{\"stop_reason\":\"max_tokens\"}}\n\n",
+            "event: message_stop\n",
+            "data: {\"type\":\"message_stop\"}\n\n",
+        )])
+        .await;
+
+        let mut provider = AnthropicProvider::new("test-key".to_string(), Some(endpoint));
+        provider.initialize().await.unwrap();
+
+        let (content, finish_reason) = collect_stream(&provider, test_request()).await;
+        assert_eq!(content, "The answer");
+        assert_eq!(finish_reason, FinishReason::MaxTokens);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_tokens_uses_configured_model() {
+        // One token per byte, so the count shows which tokenizer was chosen
+        crate::tokenizer::register_tokenizer(
+            "estimate-test-",
+            std::sync::Arc::new(crate::tokenizer::HeuristicTokenizer::new(1)),
+        );
+
+        for provider_name in ["openai", "grok", "ollama", "headless-cli"] {
+            let mut config = HashMap::new();
+            config.insert("api_key".to_string(), "test-key".to_string());
+            config.insert("command".to_string(), "test-command".to_string());
+            config.insert("model".to_string(), "estimate-test-model".to_string());
+
+            let provider = ProviderFactory::create(provider_name, config).unwrap();
+            assert_eq!(
+                provider.estimate_tokens("abcdef").await.unwrap(),
+                6,
+                "{} should estimate with its configured model",
+                provider_name
+            );
+        }
+    }
 }
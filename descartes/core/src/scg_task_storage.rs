@@ -5,10 +5,10 @@
 /// SCG (SCUD Graph) format for human-readable, git-friendly task files.
 use crate::errors::{StateStoreError, StateStoreResult};
 use crate::traits::{
-    scud_to_task, task_to_scud, ScudPhase, ScudStorage,
+    scud_to_task, task_to_scud, ScudPhase, ScudPriority, ScudStorage, ScudTask, ScudTaskStatus,
     Task, TaskComplexity, TaskPriority, TaskStatus,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -25,6 +25,9 @@ pub struct ScgTaskStorage {
 
     /// Active phase tag cache
     active_phase: RwLock<Option<String>>,
+
+    /// How `get_next_task` picks among ready tasks
+    schedule: TaskSchedule,
 }
 
 impl ScgTaskStorage {
@@ -36,9 +39,21 @@ impl ScgTaskStorage {
             project_root,
             phase_cache: RwLock::new(HashMap::new()),
             active_phase: RwLock::new(None),
+            schedule: TaskSchedule::default(),
         }
     }
 
+    /// Set how the next task is picked among ready tasks
+    pub fn with_schedule(mut self, schedule: TaskSchedule) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    /// Scheduling policy used by `get_next_task`
+    pub fn schedule(&self) -> TaskSchedule {
+        self.schedule
+    }
+
     /// Initialize the storage directory structure
     /// Creates .scud/ directory with necessary files
     pub async fn initialize(&self) -> StateStoreResult<()> {
@@ -261,11 +276,12 @@ impl ScgTaskStorage {
     }
 
     /// Get the next available task from the active phase
-    /// Returns a task that is Pending and has all dependencies met
+    /// Returns a task that is Pending and has all dependencies met, chosen
+    /// according to the storage's [`TaskSchedule`]
     pub async fn get_next_task(&self) -> StateStoreResult<Option<Task>> {
         match self.get_active_phase().await? {
             Some(phase) => {
-                if let Some(scud_task) = phase.find_next_task() {
+                if let Some(scud_task) = select_next_task(&phase.tasks, self.schedule) {
                     scud_to_task(scud_task)
                         .map(Some)
                         .map_err(|e| StateStoreError::DatabaseError(format!("UUID parse error: {}", e)))
@@ -314,6 +330,126 @@ pub struct ScgPhaseStats {
     pub total_complexity: u32,
 }
 
+/// Policy for picking the next task among those that are ready
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskSchedule {
+    /// Highest priority first, ties going to the task with the longest
+    /// remaining dependency chain
+    #[default]
+    CriticalPath,
+    /// Highest priority first, ties in file order
+    Priority,
+    /// File order, ignoring priority
+    Fifo,
+}
+
+impl std::str::FromStr for TaskSchedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "critical_path" => Ok(TaskSchedule::CriticalPath),
+            "priority" => Ok(TaskSchedule::Priority),
+            "fifo" => Ok(TaskSchedule::Fifo),
+            other => Err(format!(
+                "Unknown schedule '{}' (expected critical_path, priority or fifo)",
+                other
+            )),
+        }
+    }
+}
+
+/// Length of the longest chain of unfinished tasks starting at each
+/// unfinished task, counting the task itself
+///
+/// A task at the head of a long chain is on the critical path: every task
+/// after it waits on it, so starting it early shortens the whole graph.
+/// Dependency cycles are cut rather than followed.
+pub fn critical_path_lengths(tasks: &[ScudTask]) -> HashMap<String, usize> {
+    let remaining: Vec<&ScudTask> = tasks.iter().filter(|t| !is_finished(t)).collect();
+
+    let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+    for task in &remaining {
+        for dep in &task.dependencies {
+            dependents
+                .entry(dep.as_str())
+                .or_default()
+                .push(task.id.as_str());
+        }
+    }
+
+    fn chain<'a>(
+        id: &'a str,
+        dependents: &HashMap<&'a str, Vec<&'a str>>,
+        lengths: &mut HashMap<String, usize>,
+        visiting: &mut HashSet<&'a str>,
+    ) -> usize {
+        if let Some(&length) = lengths.get(id) {
+            return length;
+        }
+        if !visiting.insert(id) {
+            return 0;
+        }
+        let longest_tail = dependents
+            .get(id)
+            .map(|next| {
+                next.iter()
+                    .map(|n| chain(n, dependents, lengths, visiting))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .unwrap_or(0);
+        visiting.remove(id);
+        lengths.insert(id.to_string(), longest_tail + 1);
+        longest_tail + 1
+    }
+
+    let mut lengths = HashMap::new();
+    let mut visiting = HashSet::new();
+    for task in &remaining {
+        chain(task.id.as_str(), &dependents, &mut lengths, &mut visiting);
+    }
+    lengths
+}
+
+/// Pick the next Pending task whose dependencies are all done
+pub fn select_next_task(tasks: &[ScudTask], schedule: TaskSchedule) -> Option<&ScudTask> {
+    let mut ready = tasks
+        .iter()
+        .filter(|t| t.status == ScudTaskStatus::Pending && t.has_dependencies_met(tasks));
+
+    match schedule {
+        TaskSchedule::Fifo => ready.next(),
+        // `min_by_key` keeps the first of equal keys, preserving file order
+        TaskSchedule::Priority => ready.min_by_key(|t| std::cmp::Reverse(priority_rank(t))),
+        TaskSchedule::CriticalPath => {
+            let lengths = critical_path_lengths(tasks);
+            ready.min_by_key(|t| {
+                let length = lengths.get(&t.id).copied().unwrap_or(1);
+                std::cmp::Reverse((priority_rank(t), length))
+            })
+        }
+    }
+}
+
+fn is_finished(task: &ScudTask) -> bool {
+    matches!(
+        task.status,
+        ScudTaskStatus::Done | ScudTaskStatus::Cancelled | ScudTaskStatus::Expanded
+    )
+}
+
+fn priority_rank(task: &ScudTask) -> u8 {
+    match task.priority {
+        ScudPriority::Low => 0,
+        ScudPriority::Medium => 1,
+        ScudPriority::High => 2,
+        ScudPriority::Critical => 3,
+    }
+}
+
 /// Query builder for in-memory SCG task queries
 /// Provides similar interface to TaskQueryBuilder but works with in-memory data
 #[derive(Debug, Clone, Default)]
@@ -493,6 +629,11 @@ impl ScgTaskQueryBuilder {
             .collect()
     }
 
+    /// Count the tasks matching the filters, ignoring pagination
+    pub fn count(&self, tasks: &[Task]) -> usize {
+        tasks.iter().filter(|t| self.matches(t)).count()
+    }
+
     /// Check if a task matches the query filters
     fn matches(&self, task: &Task) -> bool {
         // Status filter
@@ -724,6 +865,7 @@ mod tests {
 
         assert_eq!(page1.len(), 3);
         assert_eq!(page2.len(), 3);
+        assert_eq!(ScgTaskQueryBuilder::new().limit(3).count(&tasks), 10);
     }
 
     #[test]
@@ -742,4 +884,36 @@ mod tests {
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].title, "High priority todo");
     }
+
+    #[test]
+    fn test_critical_path_schedule_prefers_bottleneck_task() {
+        let scud_task = |id: &str, deps: &[&str]| {
+            let mut task = ScudTask::new(id.to_string(), id.to_string(), String::new());
+            task.dependencies = deps.iter().map(|d| d.to_string()).collect();
+            task
+        };
+        // "side" comes first in file order but nothing waits on it, while
+        // "build" gates a three-task chain
+        let tasks = vec![
+            scud_task("side", &[]),
+            scud_task("build", &[]),
+            scud_task("test", &["build"]),
+            scud_task("release", &["test"]),
+        ];
+
+        let lengths = critical_path_lengths(&tasks);
+        assert_eq!(lengths["build"], 3);
+        assert_eq!(lengths["side"], 1);
+
+        let next = |schedule| select_next_task(&tasks, schedule).map(|t| t.id.as_str());
+        assert_eq!(next(TaskSchedule::CriticalPath), Some("build"));
+        assert_eq!(next(TaskSchedule::Priority), Some("side"));
+        assert_eq!(next(TaskSchedule::Fifo), Some("side"));
+
+        assert_eq!(
+            "critical_path".parse::<TaskSchedule>(),
+            Ok(TaskSchedule::CriticalPath)
+        );
+        assert!("random".parse::<TaskSchedule>().is_err());
+    }
 }
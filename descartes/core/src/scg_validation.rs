@@ -0,0 +1,492 @@
+//! Validation for SCUD Graph (.scg) task files
+//!
+//! The SCG parser is deliberately forgiving: malformed node lines are skipped,
+//! duplicate IDs overwrite each other and edges to unknown tasks are dropped.
+//! That keeps a half-edited file loadable, but it means mistakes only show up
+//! as odd scheduling during a loop run. [`validate_scg`] scans the raw file
+//! instead and reports every problem with the line it came from.
+
+use crate::dag::{DAGEdge, DAGNode, DAG};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use uuid::Uuid;
+
+const HEADER_PREFIX: &str = "# SCUD Graph";
+const PHASE_SEPARATOR: &str = "---";
+const STATUS_CODES: &[char] = &['P', 'I', 'D', 'R', 'B', 'F', 'C', 'X'];
+const PRIORITY_CODES: &[char] = &['C', 'H', 'M', 'L'];
+
+/// Category of problem found in a task file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScgIssueKind {
+    /// A line that the parser would silently skip or misread
+    Parse,
+    /// The same task ID declared twice in one phase
+    DuplicateId,
+    /// An edge naming a task that doesn't exist in any phase
+    DanglingDependency,
+    /// Tasks that depend on each other in a loop
+    Cycle,
+}
+
+impl fmt::Display for ScgIssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScgIssueKind::Parse => write!(f, "parse error"),
+            ScgIssueKind::DuplicateId => write!(f, "duplicate id"),
+            ScgIssueKind::DanglingDependency => write!(f, "dangling dependency"),
+            ScgIssueKind::Cycle => write!(f, "cycle"),
+        }
+    }
+}
+
+/// A single validation problem
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScgIssue {
+    pub kind: ScgIssueKind,
+    /// Phase the problem was found in, once the phase header has been read
+    pub phase: Option<String>,
+    /// 1-based line number in the file
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ScgIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}: {}", self.line, self.kind, self.message)?;
+        if let Some(phase) = &self.phase {
+            write!(f, " (phase {})", phase)?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of validating a task file
+#[derive(Debug, Clone, Default)]
+pub struct ScgValidationReport {
+    pub phases: usize,
+    pub tasks: usize,
+    pub issues: Vec<ScgIssue>,
+}
+
+impl ScgValidationReport {
+    /// Whether the file has no problems
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Issues of one kind
+    pub fn issues_of(&self, kind: ScgIssueKind) -> impl Iterator<Item = &ScgIssue> {
+        self.issues.iter().filter(move |i| i.kind == kind)
+    }
+}
+
+struct PhaseScan {
+    name: Option<String>,
+    /// Task ID -> line it was declared on
+    nodes: HashMap<String, usize>,
+    /// (dependent, dependency, line)
+    edges: Vec<(String, String, usize)>,
+}
+
+/// Validate the contents of a (possibly multi-phase) .scg file
+pub fn validate_scg(content: &str) -> ScgValidationReport {
+    let mut report = ScgValidationReport::default();
+    let mut phases = Vec::new();
+    let mut section: Vec<(usize, &str)> = Vec::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        if line.trim() == PHASE_SEPARATOR {
+            phases.push(scan_phase(&section, &mut report.issues));
+            section.clear();
+        } else {
+            section.push((idx + 1, line));
+        }
+    }
+    phases.push(scan_phase(&section, &mut report.issues));
+    phases.retain(|p| p.name.is_some() || !p.nodes.is_empty());
+
+    // Cross-phase dependencies are allowed, so an edge only dangles if no
+    // phase declares the task
+    let all_ids: HashSet<&str> = phases
+        .iter()
+        .flat_map(|p| p.nodes.keys().map(String::as_str))
+        .collect();
+
+    for phase in &phases {
+        for (dependent, dependency, line) in &phase.edges {
+            for id in [dependent, dependency] {
+                if !all_ids.contains(id.as_str()) {
+                    report.issues.push(ScgIssue {
+                        kind: ScgIssueKind::DanglingDependency,
+                        phase: phase.name.clone(),
+                        line: *line,
+                        message: format!(
+                            "edge {} -> {} references unknown task {}",
+                            dependent, dependency, id
+                        ),
+                    });
+                }
+            }
+        }
+        find_cycles(phase, &mut report.issues);
+    }
+
+    report.phases = phases.len();
+    report.tasks = phases.iter().map(|p| p.nodes.len()).sum();
+    report.issues.sort_by_key(|i| i.line);
+    report
+}
+
+fn scan_phase(lines: &[(usize, &str)], issues: &mut Vec<ScgIssue>) -> PhaseScan {
+    let mut scan = PhaseScan {
+        name: None,
+        nodes: HashMap::new(),
+        edges: Vec::new(),
+    };
+    let mut lines = lines
+        .iter()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .peekable();
+
+    let Some(&(header_line, header)) = lines.next() else {
+        return scan;
+    };
+    if !header.starts_with(HEADER_PREFIX) {
+        issues.push(parse_issue(
+            None,
+            header_line,
+            format!(
+                "expected '{} v1' header, found '{}'",
+                HEADER_PREFIX,
+                header.trim()
+            ),
+        ));
+    }
+
+    match lines.peek() {
+        Some(&&(line, text)) => {
+            let name = text
+                .strip_prefix("# Phase:")
+                .or_else(|| text.strip_prefix("# Epic:"))
+                .map(|n| n.trim().to_string());
+            if name.is_some() {
+                lines.next();
+            } else {
+                issues.push(parse_issue(None, line, "missing '# Phase: <tag>' line"));
+            }
+            scan.name = name;
+        }
+        None => {
+            issues.push(parse_issue(
+                None,
+                header_line,
+                "missing '# Phase: <tag>' line",
+            ));
+            return scan;
+        }
+    }
+
+    let mut current = "";
+    for &(line, raw) in lines {
+        let trimmed = raw.trim();
+        // Indented lines continue a multi-line detail and may contain anything
+        if current == "details" && raw.starts_with("  ") {
+            continue;
+        }
+        if let Some(header) = trimmed.strip_prefix('@') {
+            current = match header.trim_end_matches('{').trim() {
+                name @ ("meta" | "nodes" | "edges" | "parents" | "assignments" | "details") => name,
+                other => {
+                    issues.push(parse_issue(
+                        scan.name.clone(),
+                        line,
+                        format!("unknown section '@{}'", other),
+                    ));
+                    ""
+                }
+            };
+            continue;
+        }
+        if trimmed == "}" || trimmed.starts_with('#') {
+            continue;
+        }
+
+        match current {
+            "nodes" => scan_node(&mut scan, line, trimmed, issues),
+            "edges" => match trimmed.split_once("->") {
+                Some((dependent, dependency))
+                    if !dependent.trim().is_empty() && !dependency.trim().is_empty() =>
+                {
+                    scan.edges.push((
+                        dependent.trim().to_string(),
+                        dependency.trim().to_string(),
+                        line,
+                    ));
+                }
+                _ => issues.push(parse_issue(
+                    scan.name.clone(),
+                    line,
+                    format!("expected 'dependent -> dependency', found '{}'", trimmed),
+                )),
+            },
+            _ => {}
+        }
+    }
+
+    scan
+}
+
+fn scan_node(scan: &mut PhaseScan, line: usize, text: &str, issues: &mut Vec<ScgIssue>) {
+    let parts = split_by_pipe(text);
+    if parts.len() < 5 {
+        issues.push(parse_issue(
+            scan.name.clone(),
+            line,
+            format!(
+                "expected 'id | title | status | complexity | priority', found {} field(s)",
+                parts.len()
+            ),
+        ));
+        return;
+    }
+
+    let id = parts[0].clone();
+    if id.is_empty() {
+        issues.push(parse_issue(scan.name.clone(), line, "task id is empty"));
+        return;
+    }
+    if !code_is(&parts[2], STATUS_CODES) {
+        issues.push(parse_issue(
+            scan.name.clone(),
+            line,
+            format!("task {} has unknown status '{}'", id, parts[2]),
+        ));
+    }
+    if parts[3].parse::<u32>().is_err() {
+        issues.push(parse_issue(
+            scan.name.clone(),
+            line,
+            format!("task {} has non-numeric complexity '{}'", id, parts[3]),
+        ));
+    }
+    if !code_is(&parts[4], PRIORITY_CODES) {
+        issues.push(parse_issue(
+            scan.name.clone(),
+            line,
+            format!("task {} has unknown priority '{}'", id, parts[4]),
+        ));
+    }
+
+    if let Some(first) = scan.nodes.get(&id) {
+        issues.push(ScgIssue {
+            kind: ScgIssueKind::DuplicateId,
+            phase: scan.name.clone(),
+            line,
+            message: format!("task {} already declared on line {}", id, first),
+        });
+    } else {
+        scan.nodes.insert(id, line);
+    }
+}
+
+/// Report each dependency cycle within a phase once
+fn find_cycles(phase: &PhaseScan, issues: &mut Vec<ScgIssue>) {
+    let mut dag = DAG::new(phase.name.clone().unwrap_or_default());
+    let mut node_ids: HashMap<&str, Uuid> = HashMap::new();
+    let mut task_ids: HashMap<Uuid, &str> = HashMap::new();
+    for id in phase.nodes.keys() {
+        let node = DAGNode::new_auto(id.as_str());
+        node_ids.insert(id, node.node_id);
+        task_ids.insert(node.node_id, id);
+        let _ = dag.add_node(node);
+    }
+
+    // Edges point from dependent to dependency, so cycles read in edge order
+    let mut edge_lines: HashMap<(&str, &str), usize> = HashMap::new();
+    for (dependent, dependency, line) in &phase.edges {
+        let (Some(&from), Some(&to)) = (
+            node_ids.get(dependent.as_str()),
+            node_ids.get(dependency.as_str()),
+        ) else {
+            continue;
+        };
+        if from == to {
+            issues.push(cycle_issue(phase, *line, &[dependent.as_str()]));
+            continue;
+        }
+        let _ = dag.add_edge(DAGEdge::dependency(from, to));
+        edge_lines.insert((dependent.as_str(), dependency.as_str()), *line);
+    }
+
+    let mut cycles: Vec<Vec<&str>> = dag
+        .detect_cycles()
+        .into_iter()
+        .map(|cycle| {
+            let mut cycle: Vec<&str> = cycle.iter().map(|id| task_ids[id]).collect();
+            // Start at the smallest ID so the report doesn't depend on traversal order
+            let start = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap_or(0);
+            cycle.rotate_left(start);
+            cycle
+        })
+        .collect();
+    cycles.sort();
+    cycles.dedup();
+
+    for cycle in cycles {
+        // Report on the edge that closes the cycle when the file is read top to bottom
+        let line = (0..cycle.len())
+            .filter_map(|i| edge_lines.get(&(cycle[i], cycle[(i + 1) % cycle.len()])))
+            .copied()
+            .max()
+            .unwrap_or(0);
+        issues.push(cycle_issue(phase, line, &cycle));
+    }
+}
+
+fn cycle_issue(phase: &PhaseScan, line: usize, cycle: &[&str]) -> ScgIssue {
+    ScgIssue {
+        kind: ScgIssueKind::Cycle,
+        phase: phase.name.clone(),
+        line,
+        message: format!(
+            "circular dependency: {} -> {}",
+            cycle.join(" -> "),
+            cycle[0]
+        ),
+    }
+}
+
+fn parse_issue(phase: Option<String>, line: usize, message: impl Into<String>) -> ScgIssue {
+    ScgIssue {
+        kind: ScgIssueKind::Parse,
+        phase,
+        line,
+        message: message.into(),
+    }
+}
+
+fn code_is(field: &str, codes: &[char]) -> bool {
+    let mut chars = field.chars();
+    matches!((chars.next(), chars.next()), (Some(c), None) if codes.contains(&c))
+}
+
+/// Split on unescaped pipes, matching the SCG parser
+fn split_by_pipe(line: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                current.push(c);
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            '|' => parts.push(std::mem::take(&mut current).trim().to_string()),
+            _ => current.push(c),
+        }
+    }
+    parts.push(current.trim().to_string());
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID: &str = "\
+# SCUD Graph v1
+# Phase: api
+
+@nodes
+# id | title | status | complexity | priority
+1 | Design schema | D | 3 | H
+2 | Write handlers \\| routes | P | 5 | M
+3 | Tests | P | 2 | L
+
+@edges
+2 -> 1
+3 -> 2
+";
+
+    #[test]
+    fn test_valid_file_has_no_issues() {
+        let report = validate_scg(VALID);
+        assert!(report.is_valid(), "{:?}", report.issues);
+        assert_eq!(report.phases, 1);
+        assert_eq!(report.tasks, 3);
+    }
+
+    #[test]
+    fn test_parse_errors_report_line_numbers() {
+        let content = VALID
+            .replace("3 | Tests | P | 2 | L", "3 | Tests | Q | two")
+            .replace("3 -> 2", "3 => 2");
+        let report = validate_scg(&content);
+
+        let parse: Vec<_> = report.issues_of(ScgIssueKind::Parse).collect();
+        assert_eq!(parse.len(), 2);
+        assert_eq!(parse[0].line, 8);
+        assert!(parse[0].message.contains("found 4 field(s)"));
+        assert_eq!(parse[1].line, 12);
+        assert_eq!(parse[1].phase.as_deref(), Some("api"));
+    }
+
+    #[test]
+    fn test_duplicate_ids() {
+        let content = VALID.replace("3 | Tests", "1 | Tests");
+        let report = validate_scg(&content);
+
+        let dupes: Vec<_> = report.issues_of(ScgIssueKind::DuplicateId).collect();
+        assert_eq!(dupes.len(), 1);
+        assert_eq!(dupes[0].line, 8);
+        assert!(dupes[0].message.contains("already declared on line 6"));
+    }
+
+    #[test]
+    fn test_dangling_dependencies() {
+        let content = format!("{}4 -> 9\n", VALID);
+        let report = validate_scg(&content);
+
+        let dangling: Vec<_> = report.issues_of(ScgIssueKind::DanglingDependency).collect();
+        assert_eq!(dangling.len(), 2);
+        assert!(dangling.iter().all(|i| i.line == 13));
+        assert!(dangling[0].message.contains("unknown task 4"));
+        assert!(dangling[1].message.contains("unknown task 9"));
+    }
+
+    #[test]
+    fn test_cycles() {
+        let content = format!("{}1 -> 3\n", VALID);
+        let report = validate_scg(&content);
+
+        let cycles: Vec<_> = report.issues_of(ScgIssueKind::Cycle).collect();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].message, "circular dependency: 1 -> 3 -> 2 -> 1");
+        assert_eq!(cycles[0].line, 13);
+    }
+
+    #[test]
+    fn test_self_dependency_is_a_cycle() {
+        let content = format!("{}2 -> 2\n", VALID);
+        let report = validate_scg(&content);
+
+        let cycles: Vec<_> = report.issues_of(ScgIssueKind::Cycle).collect();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].message, "circular dependency: 2 -> 2");
+    }
+
+    #[test]
+    fn test_cross_phase_dependencies_are_not_dangling() {
+        let content = format!(
+            "{}\n---\n\n# SCUD Graph v1\n# Phase: ui\n\n@nodes\n10 | Screens | P | 3 | M\n\n@edges\n10 -> 3\n",
+            VALID
+        );
+        let report = validate_scg(&content);
+        assert!(report.is_valid(), "{:?}", report.issues);
+        assert_eq!(report.phases, 2);
+    }
+}
@@ -954,7 +954,7 @@ impl ScudIterativeLoop {
 
         // 5. Warn if exceeds token budget
         if let Some(max_tokens) = self.config.spec.max_spec_tokens {
-            let estimated_tokens = spec.len() / 4; // rough estimate
+            let estimated_tokens = crate::tokenizer::count_tokens("", &spec);
             if estimated_tokens > max_tokens {
                 warn!(
                     "Spec exceeds token budget: ~{} tokens (max: {})",
@@ -1,10 +1,12 @@
 /// Secure secret management module for encrypted storage.
 /// Implements AES-256-GCM encryption with key derivation from master password.
 /// Supports multiple secret types, versioning, and audit logging.
-use crate::errors::StateStoreResult;
+use crate::errors::{StateStoreError, StateStoreResult};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
 use uuid::Uuid;
 use zeroize::Zeroize;
 
@@ -400,6 +402,107 @@ impl Drop for EncryptionContext {
     }
 }
 
+/// Where a configured secret comes from
+///
+/// Config values of the form `env:VAR` or `file:/path` are references that
+/// are only resolved when the secret is needed, so the plaintext never has to
+/// be written into the config file. Anything else is an inline value. Debug
+/// and Display output never includes an inline value.
+#[derive(Clone, PartialEq, Eq)]
+pub enum SecretRef {
+    /// The value itself
+    Inline(String),
+    /// Environment variable holding the value
+    Env(String),
+    /// File holding the value; must not be accessible to group or others
+    File(PathBuf),
+}
+
+impl SecretRef {
+    /// Parse a config value into a reference
+    pub fn parse(value: &str) -> StateStoreResult<Self> {
+        if let Some(var) = value.strip_prefix("env:") {
+            if var.is_empty() {
+                return Err(StateStoreError::InvalidSecret(
+                    "env: reference without a variable name".to_string(),
+                ));
+            }
+            Ok(SecretRef::Env(var.to_string()))
+        } else if let Some(path) = value.strip_prefix("file:") {
+            if path.is_empty() {
+                return Err(StateStoreError::InvalidSecret(
+                    "file: reference without a path".to_string(),
+                ));
+            }
+            Ok(SecretRef::File(PathBuf::from(path)))
+        } else {
+            Ok(SecretRef::Inline(value.to_string()))
+        }
+    }
+
+    /// Read the secret value
+    ///
+    /// File contents have trailing whitespace trimmed.
+    pub fn resolve(&self) -> StateStoreResult<String> {
+        match self {
+            SecretRef::Inline(value) => Ok(value.clone()),
+            SecretRef::Env(var) => std::env::var(var).map_err(|_| {
+                StateStoreError::InvalidSecret(format!("environment variable {} is not set", var))
+            }),
+            SecretRef::File(path) => {
+                let metadata = std::fs::metadata(path).map_err(|e| {
+                    StateStoreError::InvalidSecret(format!(
+                        "cannot read secret file {}: {}",
+                        path.display(),
+                        e
+                    ))
+                })?;
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+                    let mode = metadata.permissions().mode() & 0o777;
+                    if mode & 0o077 != 0 {
+                        return Err(StateStoreError::AccessDenied(format!(
+                            "secret file {} is accessible to other users (mode {:o}); \
+                             restrict it with chmod 600",
+                            path.display(),
+                            mode
+                        )));
+                    }
+                }
+                #[cfg(not(unix))]
+                let _ = metadata;
+
+                let mut value = std::fs::read_to_string(path).map_err(|e| {
+                    StateStoreError::InvalidSecret(format!(
+                        "cannot read secret file {}: {}",
+                        path.display(),
+                        e
+                    ))
+                })?;
+                value.truncate(value.trim_end().len());
+                Ok(value)
+            }
+        }
+    }
+}
+
+impl fmt::Display for SecretRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SecretRef::Inline(_) => write!(f, "<redacted>"),
+            SecretRef::Env(var) => write!(f, "env:{}", var),
+            SecretRef::File(path) => write!(f, "file:{}", path.display()),
+        }
+    }
+}
+
+impl fmt::Debug for SecretRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretRef({})", self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -420,6 +523,50 @@ mod tests {
         assert!(PermissionLevel::Delete < PermissionLevel::Admin);
     }
 
+    #[test]
+    fn test_secret_ref_kinds() {
+        let inline = SecretRef::parse("sk-inline").unwrap();
+        assert_eq!(inline.resolve().unwrap(), "sk-inline");
+        assert_eq!(
+            format!("{:?} {}", inline, inline),
+            "SecretRef(<redacted>) <redacted>"
+        );
+
+        std::env::set_var("DESCARTES_TEST_SECRET_REF", "sk-from-env");
+        let env = SecretRef::parse("env:DESCARTES_TEST_SECRET_REF").unwrap();
+        assert_eq!(env, SecretRef::Env("DESCARTES_TEST_SECRET_REF".to_string()));
+        assert_eq!(env.resolve().unwrap(), "sk-from-env");
+        let unset = SecretRef::parse("env:DESCARTES_TEST_SECRET_REF_UNSET").unwrap();
+        let err = unset.resolve().unwrap_err();
+        assert!(err.to_string().contains("is not set"));
+
+        assert!(SecretRef::parse("env:").is_err());
+        assert!(SecretRef::parse("file:").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_secret_ref_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("api_key");
+        std::fs::write(&path, "sk-from-file\n").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+
+        let file = SecretRef::parse(&format!("file:{}", path.display())).unwrap();
+        assert_eq!(file.resolve().unwrap(), "sk-from-file");
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let err = file.resolve().unwrap_err();
+        assert!(matches!(err, StateStoreError::AccessDenied(_)), "{}", err);
+
+        let missing = SecretRef::File(temp_dir.path().join("missing"));
+        let err = missing.resolve().unwrap_err();
+        assert!(matches!(err, StateStoreError::InvalidSecret(_)));
+        assert!(err.to_string().contains("cannot read secret file"));
+    }
+
     #[test]
     fn test_key_derivation_params_default() {
         let params = KeyDerivationParams::default();
@@ -3,6 +3,7 @@
 /// Implements PBKDF2 and Argon2id for key derivation
 use crate::errors::{StateStoreError, StateStoreResult};
 use crate::secrets::{EncryptedSecretData, EncryptionContext, KeyDerivationParams};
+use zeroize::Zeroize;
 
 /// Constants for cryptographic operations
 pub mod constants {
@@ -442,6 +443,40 @@ impl KeyManager {
     pub fn crypto_provider(&self) -> &dyn CryptoProvider {
         &*self.crypto_provider
     }
+
+    /// Re-encrypt secret values under a new key, e.g. for master key rotation
+    ///
+    /// Every value is decrypted before any is re-encrypted, so a value that
+    /// doesn't open with `old_key` fails the whole batch and the caller never
+    /// ends up with a mix of old- and new-key ciphertexts. Results are in
+    /// input order.
+    pub fn reencrypt_all(
+        &self,
+        old_key: &[u8],
+        new_key: &[u8],
+        secrets: &[EncryptedSecretData],
+    ) -> StateStoreResult<Vec<EncryptedSecretData>> {
+        let mut plaintexts = Vec::with_capacity(secrets.len());
+        for (index, data) in secrets.iter().enumerate() {
+            match self.crypto_provider.decrypt(old_key, data) {
+                Ok(plaintext) => plaintexts.push(plaintext),
+                Err(e) => {
+                    plaintexts.iter_mut().for_each(Zeroize::zeroize);
+                    return Err(StateStoreError::EncryptionError(format!(
+                        "Key rotation aborted at secret {}: {}",
+                        index, e
+                    )));
+                }
+            }
+        }
+
+        let reencrypted = plaintexts
+            .iter()
+            .map(|plaintext| self.crypto_provider.encrypt(new_key, plaintext))
+            .collect();
+        plaintexts.iter_mut().for_each(Zeroize::zeroize);
+        reencrypted
+    }
 }
 
 impl Default for KeyManager {
@@ -474,6 +509,35 @@ mod tests {
         assert_eq!(decrypted, plaintext);
     }
 
+    #[test]
+    fn test_reencrypt_all_rotates_every_secret() {
+        let manager = KeyManager::new();
+        let provider = manager.crypto_provider();
+        let old_key = vec![1u8; 32];
+        let new_key = vec![2u8; 32];
+        let values: [&[u8]; 3] = [b"api-key", b"db-password", b""];
+        let secrets: Vec<_> = values
+            .iter()
+            .map(|v| provider.encrypt(&old_key, v).unwrap())
+            .collect();
+
+        let rotated = manager.reencrypt_all(&old_key, &new_key, &secrets).unwrap();
+        assert_eq!(rotated.len(), values.len());
+        for (data, value) in rotated.iter().zip(values) {
+            assert_eq!(provider.decrypt(&new_key, data).unwrap(), value);
+            assert!(provider.decrypt(&old_key, data).is_err());
+        }
+
+        // One value under another key fails the batch instead of rotating the rest
+        let mut mixed = secrets.clone();
+        mixed.push(provider.encrypt(&new_key, b"already rotated").unwrap());
+        let err = manager
+            .reencrypt_all(&old_key, &new_key, &mixed)
+            .unwrap_err();
+        assert!(matches!(err, StateStoreError::EncryptionError(_)));
+        assert!(err.to_string().contains("secret 3"));
+    }
+
     #[test]
     fn test_nonce_uniqueness() {
         let provider = Aes256GcmProvider::new();
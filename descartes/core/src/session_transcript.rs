@@ -3,11 +3,14 @@
 //! Transcripts capture the full conversation history including user messages,
 //! assistant responses, and tool calls for later review and debugging.
 
+use crate::traits::FinishReason;
 use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use std::fs::{self, File};
 use std::io::{BufWriter, Write};
 use std::path::PathBuf;
+use std::sync::Arc;
 use uuid::Uuid;
 
 /// A session transcript entry.
@@ -36,6 +39,9 @@ pub struct TranscriptMetadata {
     pub is_sub_session: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_level: Option<String>,
+    /// Why the final generation ended (stop, max tokens, tool use, error)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finish_reason: Option<FinishReason>,
 }
 
 /// Writer for session transcripts.
@@ -88,6 +94,7 @@ impl TranscriptWriter {
             parent_session_id,
             is_sub_session,
             tool_level: tool_level.map(|s| s.to_string()),
+            finish_reason: None,
         };
 
         Ok(Self {
@@ -158,6 +165,16 @@ impl TranscriptWriter {
         });
     }
 
+    /// Record why the final generation ended.
+    pub fn set_finish_reason(&mut self, reason: FinishReason) {
+        self.metadata.finish_reason = Some(reason);
+    }
+
+    /// Why the final generation ended, if recorded.
+    pub fn finish_reason(&self) -> Option<&FinishReason> {
+        self.metadata.finish_reason.as_ref()
+    }
+
     /// Save the transcript to disk.
     pub fn save(&mut self) -> std::io::Result<PathBuf> {
         // Update ended_at
@@ -194,6 +211,87 @@ impl TranscriptWriter {
     }
 }
 
+/// Thread-safe handle to a [`TranscriptWriter`] shared by parallel subagents.
+///
+/// Clones refer to the same transcript. Each `add_*` call appends exactly one
+/// complete entry under a lock, so entries are never lost or interleaved.
+/// Entries from a single task keep the order that task recorded them in;
+/// entries from different tasks are ordered by when each call took the lock.
+#[derive(Clone)]
+pub struct SharedTranscript {
+    inner: Arc<Mutex<TranscriptWriter>>,
+}
+
+impl SharedTranscript {
+    /// Share an existing writer.
+    pub fn new(writer: TranscriptWriter) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(writer)),
+        }
+    }
+
+    /// Add a user message to the transcript.
+    pub fn add_user_message(&self, content: &str) {
+        self.inner.lock().add_user_message(content);
+    }
+
+    /// Add an assistant message to the transcript.
+    pub fn add_assistant_message(&self, content: &str) {
+        self.inner.lock().add_assistant_message(content);
+    }
+
+    /// Add a tool call to the transcript.
+    pub fn add_tool_call(&self, tool_name: &str, tool_id: &str, arguments: &str) {
+        self.inner
+            .lock()
+            .add_tool_call(tool_name, tool_id, arguments);
+    }
+
+    /// Add a tool result to the transcript.
+    pub fn add_tool_result(&self, tool_id: &str, result: &str) {
+        self.inner.lock().add_tool_result(tool_id, result);
+    }
+
+    /// Add a generic entry to the transcript.
+    pub fn add_entry(
+        &self,
+        role: &str,
+        content: &str,
+        tool_name: Option<&str>,
+        tool_id: Option<&str>,
+    ) {
+        self.inner
+            .lock()
+            .add_entry(role, content, tool_name, tool_id);
+    }
+
+    /// Record why the final generation ended.
+    pub fn set_finish_reason(&self, reason: FinishReason) {
+        self.inner.lock().set_finish_reason(reason);
+    }
+
+    /// Save the transcript to disk.
+    pub fn save(&self) -> std::io::Result<PathBuf> {
+        self.inner.lock().save()
+    }
+
+    /// Get the session ID.
+    pub fn session_id(&self) -> Uuid {
+        self.inner.lock().session_id()
+    }
+
+    /// Get the number of entries.
+    pub fn entry_count(&self) -> usize {
+        self.inner.lock().entry_count()
+    }
+}
+
+impl From<TranscriptWriter> for SharedTranscript {
+    fn from(writer: TranscriptWriter) -> Self {
+        Self::new(writer)
+    }
+}
+
 /// Get the default sessions directory path.
 pub fn default_sessions_dir() -> PathBuf {
     // Use .scud/sessions in current directory, or ~/.descartes/sessions
@@ -254,6 +352,24 @@ mod tests {
         assert_eq!(writer.entry_count(), 4);
     }
 
+    #[test]
+    fn test_finish_reason_saved_in_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let sessions_dir = temp_dir.path().join("sessions");
+        let mut writer =
+            TranscriptWriter::new(&sessions_dir, "openai", "gpt-4", "test task", None, None)
+                .unwrap();
+
+        writer.add_assistant_message("The answer is");
+        writer.set_finish_reason(FinishReason::MaxTokens);
+        assert_eq!(writer.finish_reason(), Some(&FinishReason::MaxTokens));
+
+        let path = writer.save().unwrap();
+        let saved: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(saved["metadata"]["finish_reason"], "MaxTokens");
+    }
+
     #[test]
     fn test_transcript_save() {
         let temp_dir = TempDir::new().unwrap();
@@ -314,4 +430,56 @@ mod tests {
             parent_id.to_string()
         );
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_shared_transcript_concurrent_recording() {
+        let temp_dir = TempDir::new().unwrap();
+        let sessions_dir = temp_dir.path().join("sessions");
+
+        let writer = TranscriptWriter::new(
+            &sessions_dir,
+            "anthropic",
+            "claude-3-5-sonnet",
+            "parent task",
+            None,
+            Some("orchestrator"),
+        )
+        .unwrap();
+        let transcript = SharedTranscript::new(writer);
+
+        let mut handles = Vec::new();
+        for agent in 0..8 {
+            let transcript = transcript.clone();
+            handles.push(tokio::spawn(async move {
+                for step in 0..25 {
+                    transcript.add_tool_call(
+                        "spawn_agent",
+                        &format!("agent-{}", agent),
+                        &format!("step {}", step),
+                    );
+                    tokio::task::yield_now().await;
+                }
+            }));
+        }
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert_eq!(transcript.entry_count(), 200);
+
+        let saved = transcript.save().unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(saved).unwrap()).unwrap();
+        let entries = json["entries"].as_array().unwrap();
+        for agent in 0..8 {
+            let id = format!("agent-{}", agent);
+            let steps: Vec<&str> = entries
+                .iter()
+                .filter(|e| e["tool_id"] == id.as_str())
+                .map(|e| e["content"].as_str().unwrap())
+                .collect();
+            let expected: Vec<String> = (0..25).map(|s| format!("step {}", s)).collect();
+            assert_eq!(steps, expected);
+        }
+    }
 }
@@ -8,8 +8,10 @@
 //! - State history and rollback capabilities
 //! - Support for concurrent multi-agent workflows
 
+use crate::expression_eval::{context_from_json, ExpressionEvaluator};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 use std::sync::Arc;
 use thiserror::Error;
@@ -54,6 +56,12 @@ pub enum StateMachineError {
 
     #[error("Metadata error: {0}")]
     MetadataError(String),
+
+    #[error("Guard rejected transition {0} -> {1}: {2}")]
+    GuardFailed(String, String, String),
+
+    #[error("Invalid guard for transition {0} -> {1}: {2}")]
+    InvalidGuard(String, String, String),
 }
 
 pub type StateMachineResult<T> = Result<T, StateMachineError>;
@@ -217,6 +225,27 @@ pub struct TransitionMetadata {
 
     /// Handler execution details
     pub handler_details: Option<String>,
+
+    /// Parallel region the transition happened in; `None` for the workflow itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+}
+
+/// Condition that must hold for a transition to be taken
+///
+/// The expression is evaluated against the workflow context (see
+/// [`crate::expression_eval`]), so `retries < 3 && review.approved` reads the
+/// `retries` and `review` context keys.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransitionGuard {
+    /// Source state
+    pub from_state: WorkflowState,
+
+    /// Target state
+    pub to_state: WorkflowState,
+
+    /// Guard expression; `None` lets the transition through unconditionally
+    pub guard: Option<String>,
 }
 
 /// Complete state history entry
@@ -315,6 +344,12 @@ pub struct WorkflowStateMachine {
 
     /// Last transition timestamp
     last_transition_at: Arc<RwLock<String>>,
+
+    /// Guards on individual transitions
+    guards: Arc<RwLock<Vec<TransitionGuard>>>,
+
+    /// Active parallel regions and their states, empty outside a fork
+    regions: Arc<RwLock<BTreeMap<String, WorkflowState>>>,
 }
 
 impl WorkflowStateMachine {
@@ -335,6 +370,8 @@ impl WorkflowStateMachine {
             context: Arc::new(RwLock::new(serde_json::json!({}))),
             created_at: now.clone(),
             last_transition_at: Arc::new(RwLock::new(now)),
+            guards: Arc::new(RwLock::new(Vec::new())),
+            regions: Arc::new(RwLock::new(BTreeMap::new())),
         }
     }
 
@@ -373,6 +410,16 @@ impl WorkflowStateMachine {
             ));
         }
 
+        // Check the transition's guard against the current context
+        self.check_guard(current, target_state).await?;
+
+        // A forked workflow only completes through its join
+        if target_state == WorkflowState::Completed && !self.regions.read().await.is_empty() {
+            return Err(StateMachineError::StateLocked(
+                "Parallel regions still active".to_string(),
+            ));
+        }
+
         // Call exit handler
         self.handler.on_exit(current).await?;
 
@@ -380,6 +427,9 @@ impl WorkflowStateMachine {
         let mut state_guard = self.current_state.write().await;
         *state_guard = target_state;
         drop(state_guard);
+        if target_state.is_terminal() {
+            self.regions.write().await.clear();
+        }
 
         // Call enter handler
         self.handler.on_enter(target_state).await?;
@@ -400,6 +450,7 @@ impl WorkflowStateMachine {
             duration_ms: duration,
             error: None,
             handler_details: None,
+            region: None,
         };
 
         self.add_history_entry(transition).await?;
@@ -473,6 +524,153 @@ impl WorkflowStateMachine {
         self.context.read().await.clone()
     }
 
+    /// Gate the `from -> to` transition on a guard expression
+    ///
+    /// The expression is parsed up front so a malformed guard is rejected here
+    /// rather than when the event arrives. Passing `None` removes the guard.
+    pub async fn set_guard(
+        &self,
+        from: WorkflowState,
+        to: WorkflowState,
+        guard: Option<&str>,
+    ) -> StateMachineResult<()> {
+        if let Some(expr) = guard {
+            ExpressionEvaluator::new().parse(expr).map_err(|e| {
+                StateMachineError::InvalidGuard(
+                    format!("{}", from),
+                    format!("{}", to),
+                    e.to_string(),
+                )
+            })?;
+        }
+
+        let mut guards = self.guards.write().await;
+        guards.retain(|g| !(g.from_state == from && g.to_state == to));
+        if guard.is_some() {
+            guards.push(TransitionGuard {
+                from_state: from,
+                to_state: to,
+                guard: guard.map(str::to_string),
+            });
+        }
+        Ok(())
+    }
+
+    /// Get all transition guards
+    pub async fn get_guards(&self) -> Vec<TransitionGuard> {
+        self.guards.read().await.clone()
+    }
+
+    /// Fork into the parallel regions of `parallel`
+    ///
+    /// Each substate becomes a region that starts `Running` and advances
+    /// independently via [`process_region_event`](Self::process_region_event).
+    /// The workflow stays `Running` until every region reaches a terminal
+    /// state, then joins: it completes if all regions completed and fails
+    /// otherwise.
+    pub async fn fork(&self, parallel: &HierarchicalState) -> StateMachineResult<()> {
+        if !parallel.is_parallel || parallel.substates.is_empty() {
+            return Err(StateMachineError::InvalidEvent(format!(
+                "{} has no parallel regions",
+                parallel.name
+            )));
+        }
+
+        let current = *self.current_state.read().await;
+        if current != WorkflowState::Running {
+            return Err(StateMachineError::InvalidTransition(
+                format!("{}", current),
+                format!("fork({})", parallel.name),
+            ));
+        }
+
+        let mut regions = self.regions.write().await;
+        if !regions.is_empty() {
+            return Err(StateMachineError::StateLocked(
+                "Parallel regions already active".to_string(),
+            ));
+        }
+        let forked: BTreeMap<_, _> = parallel
+            .substates
+            .iter()
+            .map(|region| (region.name.clone(), WorkflowState::Running))
+            .collect();
+        if forked.len() != parallel.substates.len() {
+            return Err(StateMachineError::InvalidEvent(format!(
+                "{} has duplicate region names",
+                parallel.name
+            )));
+        }
+        *regions = forked;
+        Ok(())
+    }
+
+    /// Process an event in one parallel region, joining once all are final
+    pub async fn process_region_event(
+        &self,
+        region: &str,
+        event: WorkflowEvent,
+    ) -> StateMachineResult<()> {
+        let start_time = std::time::Instant::now();
+        let mut regions = self.regions.write().await;
+        let current = *regions
+            .get(region)
+            .ok_or_else(|| StateMachineError::NotFound(region.to_string()))?;
+
+        let target_state = self.determine_target_state(current, &event)?;
+        if !current.can_transition_to(target_state) {
+            return Err(StateMachineError::InvalidTransition(
+                format!("{}:{}", region, current),
+                format!("{}:{}", region, target_state),
+            ));
+        }
+        regions.insert(region.to_string(), target_state);
+
+        // Join: `Some(None)` when every region completed, `Some(Some(name))`
+        // naming a region that ended otherwise
+        let join = if regions.values().all(|state| state.is_terminal()) {
+            let unfinished = regions
+                .iter()
+                .find(|(_, state)| **state != WorkflowState::Completed)
+                .map(|(name, _)| name.clone());
+            regions.clear();
+            Some(unfinished)
+        } else {
+            None
+        };
+        drop(regions);
+
+        self.add_history_entry(TransitionMetadata {
+            transition_id: Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            from_state: current,
+            to_state: target_state,
+            event: format!("{}", event),
+            duration_ms: start_time.elapsed().as_millis() as u64,
+            error: None,
+            handler_details: None,
+            region: Some(region.to_string()),
+        })
+        .await?;
+
+        match join {
+            Some(None) => self.process_event(WorkflowEvent::Complete).await,
+            Some(Some(name)) => {
+                self.process_event(WorkflowEvent::Fail(format!(
+                    "Parallel region {} did not complete",
+                    name
+                )))
+                .await
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Get the active parallel regions and their states
+    pub async fn active_regions(&self) -> BTreeMap<String, WorkflowState> {
+        self.regions.read().await.clone()
+    }
+
     /// Get workflow metadata
     pub async fn get_metadata(&self) -> WorkflowMetadata {
         WorkflowMetadata {
@@ -522,6 +720,27 @@ impl WorkflowStateMachine {
         Ok(target)
     }
 
+    async fn check_guard(&self, from: WorkflowState, to: WorkflowState) -> StateMachineResult<()> {
+        let guards = self.guards.read().await;
+        let Some(expr) = guards
+            .iter()
+            .find(|g| g.from_state == from && g.to_state == to)
+            .and_then(|g| g.guard.as_deref())
+        else {
+            return Ok(());
+        };
+
+        let context = context_from_json(self.context.read().await.clone());
+        let failed = |reason: String| {
+            StateMachineError::GuardFailed(format!("{}", from), format!("{}", to), reason)
+        };
+        match ExpressionEvaluator::new().evaluate_bool(expr, &context) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(failed(format!("'{}' is false", expr))),
+            Err(e) => Err(failed(format!("'{}': {}", expr, e))),
+        }
+    }
+
     async fn add_history_entry(&self, transition: TransitionMetadata) -> StateMachineResult<()> {
         let context_snapshot = self.context.read().await.clone();
 
@@ -570,6 +789,9 @@ pub struct WorkflowMetadata {
 // ============================================================================
 
 /// Represents hierarchical state structure
+///
+/// A state with `is_parallel` set is a fork: its substates are regions that
+/// are active at the same time (see [`WorkflowStateMachine::fork`]).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HierarchicalState {
     pub name: String,
@@ -692,6 +914,10 @@ pub struct SerializedWorkflow {
     pub history: Vec<StateHistoryEntry>,
     pub context: serde_json::Value,
     pub metadata: WorkflowMetadata,
+    #[serde(default)]
+    pub guards: Vec<TransitionGuard>,
+    #[serde(default)]
+    pub active_regions: BTreeMap<String, WorkflowState>,
 }
 
 impl WorkflowStateMachine {
@@ -703,6 +929,8 @@ impl WorkflowStateMachine {
             history: self.history.read().await.clone(),
             context: self.context.read().await.clone(),
             metadata: self.get_metadata().await,
+            guards: self.guards.read().await.clone(),
+            active_regions: self.regions.read().await.clone(),
         })
     }
 
@@ -710,13 +938,20 @@ impl WorkflowStateMachine {
     pub async fn deserialize(
         serialized: SerializedWorkflow,
     ) -> StateMachineResult<Arc<WorkflowStateMachine>> {
-        let sm = Arc::new(WorkflowStateMachine::new(serialized.workflow_id));
+        Ok(Arc::new(Self::from_serialized(serialized)))
+    }
 
-        *sm.current_state.write().await = serialized.current_state;
-        *sm.history.write().await = serialized.history;
-        *sm.context.write().await = serialized.context;
+    /// Restore workflow from serialized state without wrapping it in an `Arc`
+    pub fn from_serialized(serialized: SerializedWorkflow) -> Self {
+        let mut sm = WorkflowStateMachine::new(serialized.workflow_id);
 
-        Ok(sm)
+        sm.current_state = Arc::new(RwLock::new(serialized.current_state));
+        sm.history = Arc::new(RwLock::new(serialized.history));
+        sm.context = Arc::new(RwLock::new(serialized.context));
+        sm.guards = Arc::new(RwLock::new(serialized.guards));
+        sm.regions = Arc::new(RwLock::new(serialized.active_regions));
+
+        sm
     }
 }
 
@@ -868,6 +1103,102 @@ mod tests {
         assert_eq!(restored.current_state().await, WorkflowState::Running);
     }
 
+    #[tokio::test]
+    async fn test_fork_join() {
+        let sm = WorkflowStateMachine::new("workflow-fork".to_string());
+        let checks = HierarchicalState::parallel().with_substates(vec![
+            HierarchicalState::new("lint".to_string()),
+            HierarchicalState::new("test".to_string()),
+        ]);
+
+        // Forking needs a running workflow
+        assert!(sm.fork(&checks).await.is_err());
+        sm.process_event(WorkflowEvent::Start).await.unwrap();
+        sm.fork(&checks).await.unwrap();
+        assert_eq!(sm.active_regions().await.len(), 2);
+
+        sm.process_region_event("lint", WorkflowEvent::Complete)
+            .await
+            .unwrap();
+        // The join waits for the remaining region
+        assert_eq!(sm.current_state().await, WorkflowState::Running);
+        assert!(matches!(
+            sm.process_event(WorkflowEvent::Complete).await,
+            Err(StateMachineError::StateLocked(_))
+        ));
+
+        // The active set survives persistence
+        let restored = WorkflowStateMachine::deserialize(sm.serialize().await.unwrap())
+            .await
+            .unwrap();
+        assert_eq!(
+            restored.active_regions().await.get("lint"),
+            Some(&WorkflowState::Completed)
+        );
+
+        restored
+            .process_region_event("test", WorkflowEvent::Complete)
+            .await
+            .unwrap();
+        assert_eq!(restored.current_state().await, WorkflowState::Completed);
+        assert!(restored.active_regions().await.is_empty());
+
+        // A failed region fails the parent at the join
+        let sm = WorkflowStateMachine::new("workflow-fork-fail".to_string());
+        sm.process_event(WorkflowEvent::Start).await.unwrap();
+        sm.fork(&checks).await.unwrap();
+        sm.process_region_event("lint", WorkflowEvent::Fail("style".to_string()))
+            .await
+            .unwrap();
+        sm.process_region_event("test", WorkflowEvent::Complete)
+            .await
+            .unwrap();
+        assert_eq!(sm.current_state().await, WorkflowState::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_guarded_transition() {
+        let sm = WorkflowStateMachine::new("workflow-guard".to_string());
+        sm.set_guard(
+            WorkflowState::Running,
+            WorkflowState::Completed,
+            Some("tests_passed && review.approved"),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(
+            sm.set_guard(WorkflowState::Idle, WorkflowState::Running, Some("a &&"))
+                .await,
+            Err(StateMachineError::InvalidGuard(..))
+        ));
+
+        sm.process_event(WorkflowEvent::Start).await.unwrap();
+        sm.set_context("tests_passed", serde_json::json!(true))
+            .await
+            .unwrap();
+        sm.set_context("review", serde_json::json!({"approved": false}))
+            .await
+            .unwrap();
+        let result = sm.process_event(WorkflowEvent::Complete).await;
+        assert!(matches!(result, Err(StateMachineError::GuardFailed(..))));
+        assert_eq!(sm.current_state().await, WorkflowState::Running);
+
+        // The guard survives a serialize/deserialize round trip
+        let restored = WorkflowStateMachine::deserialize(sm.serialize().await.unwrap())
+            .await
+            .unwrap();
+        assert_eq!(restored.get_guards().await, sm.get_guards().await);
+        restored
+            .set_context("review", serde_json::json!({"approved": true}))
+            .await
+            .unwrap();
+        restored
+            .process_event(WorkflowEvent::Complete)
+            .await
+            .unwrap();
+        assert_eq!(restored.current_state().await, WorkflowState::Completed);
+    }
+
     #[test]
     fn test_state_transitions_valid() {
         assert!(WorkflowState::Idle.can_transition_to(WorkflowState::Running));
@@ -6,11 +6,13 @@
 //! - Checkpoint and recovery support
 //! - State snapshots for recovery
 
+use crate::errors::{StateStoreError, StateStoreResult};
 use crate::state_machine::*;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use serde_json::json;
 use sqlx::sqlite::SqlitePool;
 use std::sync::Arc;
+use tracing::warn;
 
 // ============================================================================
 // PERSISTENCE MODELS
@@ -26,6 +28,8 @@ pub struct WorkflowRecord {
     pub created_at: String,
     pub updated_at: String,
     pub is_terminal: bool,
+    pub guards: String,         // JSON
+    pub active_regions: String, // JSON
 }
 
 /// Persistent record of a state transition in history
@@ -42,6 +46,7 @@ pub struct TransitionRecord {
     pub handler_details: Option<String>,
     pub context_snapshot: String, // JSON
     pub created_at: String,
+    pub region: Option<String>,
 }
 
 /// Configuration for state store
@@ -101,7 +106,9 @@ impl SqliteWorkflowStore {
                 context TEXT NOT NULL DEFAULT '{}',
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
-                is_terminal INTEGER NOT NULL DEFAULT 0
+                is_terminal INTEGER NOT NULL DEFAULT 0,
+                guards TEXT NOT NULL DEFAULT '[]',
+                active_regions TEXT NOT NULL DEFAULT '{}'
             )
             "#,
         )
@@ -123,6 +130,7 @@ impl SqliteWorkflowStore {
                 handler_details TEXT,
                 context_snapshot TEXT NOT NULL DEFAULT '{}',
                 created_at TEXT NOT NULL,
+                region TEXT,
                 FOREIGN KEY (workflow_id) REFERENCES workflows(workflow_id)
             )
             "#,
@@ -130,6 +138,28 @@ impl SqliteWorkflowStore {
         .execute(&self.pool)
         .await?;
 
+        // Databases created by older versions lack the later columns
+        for (table, column, definition) in [
+            ("workflows", "guards", "TEXT NOT NULL DEFAULT '[]'"),
+            ("workflows", "active_regions", "TEXT NOT NULL DEFAULT '{}'"),
+            ("state_transitions", "region", "TEXT"),
+        ] {
+            let exists: bool =
+                sqlx::query_scalar("SELECT COUNT(*) > 0 FROM pragma_table_info(?) WHERE name = ?")
+                    .bind(table)
+                    .bind(column)
+                    .fetch_one(&self.pool)
+                    .await?;
+            if !exists {
+                sqlx::query(&format!(
+                    "ALTER TABLE {} ADD COLUMN {} {}",
+                    table, column, definition
+                ))
+                .execute(&self.pool)
+                .await?;
+            }
+        }
+
         // Create indexes separately for SQLite compatibility
         sqlx::query(
             r#"CREATE INDEX IF NOT EXISTS idx_workflow_id ON state_transitions(workflow_id)"#,
@@ -173,17 +203,21 @@ impl SqliteWorkflowStore {
         let now = Utc::now().to_rfc3339();
         let is_terminal = serialized.current_state.is_terminal();
         let context_json = serde_json::to_string(&serialized.context).unwrap_or_default();
+        let guards_json = serde_json::to_string(&serialized.guards).unwrap_or_default();
+        let regions_json = serde_json::to_string(&serialized.active_regions).unwrap_or_default();
 
         // Upsert workflow record
         sqlx::query(
             r#"
-            INSERT INTO workflows (workflow_id, current_state, context, created_at, updated_at, is_terminal)
-            VALUES (?, ?, ?, ?, ?, ?)
+            INSERT INTO workflows (workflow_id, current_state, context, created_at, updated_at, is_terminal, guards, active_regions)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(workflow_id) DO UPDATE SET
                 current_state = excluded.current_state,
                 context = excluded.context,
                 updated_at = excluded.updated_at,
-                is_terminal = excluded.is_terminal
+                is_terminal = excluded.is_terminal,
+                guards = excluded.guards,
+                active_regions = excluded.active_regions
             "#,
         )
         .bind(&serialized.workflow_id)
@@ -192,6 +226,8 @@ impl SqliteWorkflowStore {
         .bind(&serialized.metadata.created_at)
         .bind(now)
         .bind(is_terminal as i32)
+        .bind(guards_json)
+        .bind(regions_json)
         .execute(&self.pool)
         .await?;
 
@@ -199,15 +235,22 @@ impl SqliteWorkflowStore {
     }
 
     /// Load a workflow state from storage
-    pub async fn load_workflow(
-        &self,
-        workflow_id: &str,
-    ) -> Result<SerializedWorkflow, sqlx::Error> {
+    ///
+    /// Fails with [`StateMachineError::NotFound`] if the workflow was never
+    /// saved, and with [`StateMachineError::InvalidGuard`] if its saved guards
+    /// can't be read back.
+    pub async fn load_workflow(&self, workflow_id: &str) -> StateMachineResult<SerializedWorkflow> {
         let record =
             sqlx::query_as::<_, WorkflowRecord>("SELECT * FROM workflows WHERE workflow_id = ?")
                 .bind(workflow_id)
                 .fetch_one(&self.pool)
-                .await?;
+                .await
+                .map_err(|e| match e {
+                    sqlx::Error::RowNotFound => {
+                        StateMachineError::NotFound(workflow_id.to_string())
+                    }
+                    e => StateMachineError::StateStoreError(e.to_string()),
+                })?;
 
         let history = sqlx::query_as::<_, TransitionRecord>(
             r#"
@@ -220,7 +263,8 @@ impl SqliteWorkflowStore {
         .bind(workflow_id)
         .bind(self.config.max_history_per_workflow as i64)
         .fetch_all(&self.pool)
-        .await?;
+        .await
+        .map_err(|e| StateMachineError::StateStoreError(e.to_string()))?;
 
         let state = match record.current_state.as_str() {
             "Running" => WorkflowState::Running,
@@ -231,44 +275,20 @@ impl SqliteWorkflowStore {
         };
 
         let context: serde_json::Value = serde_json::from_str(&record.context).unwrap_or(json!({}));
+        let guards = serde_json::from_str(&record.guards).map_err(|e| {
+            StateMachineError::InvalidGuard(
+                "*".to_string(),
+                "*".to_string(),
+                format!(
+                    "saved guards of workflow {} are unreadable: {}",
+                    workflow_id, e
+                ),
+            )
+        })?;
+        let active_regions = serde_json::from_str(&record.active_regions).unwrap_or_default();
 
-        let history_entries: Vec<StateHistoryEntry> = history
-            .into_iter()
-            .filter_map(|rec| {
-                let from_state = match rec.from_state.as_str() {
-                    "Running" => WorkflowState::Running,
-                    "Paused" => WorkflowState::Paused,
-                    "Completed" => WorkflowState::Completed,
-                    "Failed" => WorkflowState::Failed,
-                    _ => WorkflowState::Idle,
-                };
-
-                let to_state = match rec.to_state.as_str() {
-                    "Running" => WorkflowState::Running,
-                    "Paused" => WorkflowState::Paused,
-                    "Completed" => WorkflowState::Completed,
-                    "Failed" => WorkflowState::Failed,
-                    _ => WorkflowState::Idle,
-                };
-
-                let context_snapshot =
-                    serde_json::from_str(&rec.context_snapshot).unwrap_or(json!({}));
-
-                Some(StateHistoryEntry {
-                    transition: TransitionMetadata {
-                        transition_id: rec.transition_id,
-                        timestamp: rec.created_at,
-                        from_state,
-                        to_state,
-                        event: rec.event,
-                        duration_ms: rec.duration_ms as u64,
-                        error: rec.error_message,
-                        handler_details: rec.handler_details,
-                    },
-                    context_snapshot,
-                })
-            })
-            .collect();
+        let history_entries: Vec<StateHistoryEntry> =
+            history.into_iter().map(history_entry).collect();
 
         let metadata = WorkflowMetadata {
             workflow_id: record.workflow_id.clone(),
@@ -284,6 +304,8 @@ impl SqliteWorkflowStore {
             history: history_entries,
             context,
             metadata,
+            guards,
+            active_regions,
         })
     }
 
@@ -300,8 +322,8 @@ impl SqliteWorkflowStore {
             r#"
             INSERT INTO state_transitions
             (workflow_id, transition_id, from_state, to_state, event, duration_ms,
-             error_message, handler_details, context_snapshot, created_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+             error_message, handler_details, context_snapshot, created_at, region)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(workflow_id)
@@ -314,6 +336,7 @@ impl SqliteWorkflowStore {
         .bind(&transition.handler_details)
         .bind(context_json)
         .bind(&transition.timestamp)
+        .bind(&transition.region)
         .execute(&self.pool)
         .await?;
 
@@ -343,43 +366,7 @@ impl SqliteWorkflowStore {
         .fetch_all(&self.pool)
         .await?;
 
-        let entries = records
-            .into_iter()
-            .filter_map(|rec| {
-                let from_state = match rec.from_state.as_str() {
-                    "Running" => WorkflowState::Running,
-                    "Paused" => WorkflowState::Paused,
-                    "Completed" => WorkflowState::Completed,
-                    "Failed" => WorkflowState::Failed,
-                    _ => WorkflowState::Idle,
-                };
-
-                let to_state = match rec.to_state.as_str() {
-                    "Running" => WorkflowState::Running,
-                    "Paused" => WorkflowState::Paused,
-                    "Completed" => WorkflowState::Completed,
-                    "Failed" => WorkflowState::Failed,
-                    _ => WorkflowState::Idle,
-                };
-
-                let context_snapshot =
-                    serde_json::from_str(&rec.context_snapshot).unwrap_or(json!({}));
-
-                Some(StateHistoryEntry {
-                    transition: TransitionMetadata {
-                        transition_id: rec.transition_id,
-                        timestamp: rec.created_at,
-                        from_state,
-                        to_state,
-                        event: rec.event,
-                        duration_ms: rec.duration_ms as u64,
-                        error: rec.error_message,
-                        handler_details: rec.handler_details,
-                    },
-                    context_snapshot,
-                })
-            })
-            .collect();
+        let entries = records.into_iter().map(history_entry).collect();
 
         Ok(entries)
     }
@@ -448,6 +435,21 @@ impl SqliteWorkflowStore {
     // PRIVATE HELPERS
     // ============================================================================
 
+    /// Every retained transition of a workflow, in the order it was logged
+    async fn transition_log(
+        &self,
+        workflow_id: &str,
+    ) -> Result<Vec<StateHistoryEntry>, sqlx::Error> {
+        let records = sqlx::query_as::<_, TransitionRecord>(
+            "SELECT * FROM state_transitions WHERE workflow_id = ? ORDER BY id ASC",
+        )
+        .bind(workflow_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records.into_iter().map(history_entry).collect())
+    }
+
     async fn cleanup_old_history(&self, workflow_id: &str) -> Result<(), sqlx::Error> {
         // Remove entries beyond max_history_per_workflow limit
         sqlx::query(
@@ -471,14 +473,152 @@ impl SqliteWorkflowStore {
     }
 }
 
+fn history_entry(rec: TransitionRecord) -> StateHistoryEntry {
+    let from_state = match rec.from_state.as_str() {
+        "Running" => WorkflowState::Running,
+        "Paused" => WorkflowState::Paused,
+        "Completed" => WorkflowState::Completed,
+        "Failed" => WorkflowState::Failed,
+        _ => WorkflowState::Idle,
+    };
+
+    let to_state = match rec.to_state.as_str() {
+        "Running" => WorkflowState::Running,
+        "Paused" => WorkflowState::Paused,
+        "Completed" => WorkflowState::Completed,
+        "Failed" => WorkflowState::Failed,
+        _ => WorkflowState::Idle,
+    };
+
+    let context_snapshot = serde_json::from_str(&rec.context_snapshot).unwrap_or(json!({}));
+
+    StateHistoryEntry {
+        transition: TransitionMetadata {
+            transition_id: rec.transition_id,
+            timestamp: rec.created_at,
+            from_state,
+            to_state,
+            event: rec.event,
+            duration_ms: rec.duration_ms as u64,
+            error: rec.error_message,
+            handler_details: rec.handler_details,
+            region: rec.region,
+        },
+        context_snapshot,
+    }
+}
+
+fn database_error(e: sqlx::Error) -> StateStoreError {
+    StateStoreError::DatabaseError(e.to_string())
+}
+
+fn parse_timestamp(timestamp: &str) -> StateStoreResult<DateTime<chrono::FixedOffset>> {
+    DateTime::parse_from_rfc3339(timestamp).map_err(|e| {
+        StateStoreError::SerializationError(format!("Invalid timestamp {}: {}", timestamp, e))
+    })
+}
+
 // ============================================================================
 // RECOVERY UTILITIES
 // ============================================================================
 
 /// Helpers for workflow recovery and restoration
-pub struct WorkflowRecovery;
+pub struct WorkflowRecovery<'a> {
+    store: &'a SqliteWorkflowStore,
+}
+
+impl<'a> WorkflowRecovery<'a> {
+    /// Create a recovery helper over `store`
+    pub fn new(store: &'a SqliteWorkflowStore) -> Self {
+        Self { store }
+    }
+
+    /// Rebuild a workflow after a crash by replaying its transition log
+    ///
+    /// Recovery starts from the persisted workflow record. Transitions logged
+    /// after the record was last saved were written but never applied, so they
+    /// are replayed forward from the saved state and the record is saved again
+    /// with their outcome. Replay stops at the first transition that doesn't
+    /// follow from the state reached so far. The log itself is never modified:
+    /// when it is empty or its prefix has been pruned, the saved record is
+    /// trusted as-is.
+    pub async fn recover(&self, workflow_id: &str) -> StateStoreResult<WorkflowStateMachine> {
+        let mut serialized = match self.store.load_workflow(workflow_id).await {
+            Ok(serialized) => serialized,
+            Err(StateMachineError::NotFound(_)) => {
+                return Err(StateStoreError::NotFound(format!(
+                    "workflow {}",
+                    workflow_id
+                )))
+            }
+            Err(StateMachineError::StateStoreError(e)) => {
+                return Err(StateStoreError::DatabaseError(e))
+            }
+            Err(e) => return Err(StateStoreError::SerializationError(e.to_string())),
+        };
+        let saved_at = parse_timestamp(&serialized.metadata.last_transition_at)?;
+        let log = self
+            .store
+            .transition_log(workflow_id)
+            .await
+            .map_err(database_error)?;
+
+        let mut state = serialized.current_state;
+        let mut history: Vec<StateHistoryEntry> = Vec::with_capacity(log.len());
+        let mut replayed = 0;
+        for entry in log {
+            if parse_timestamp(&entry.transition.timestamp)? <= saved_at {
+                history.push(entry);
+                continue;
+            }
+
+            let transition = &entry.transition;
+            if transition.region.is_none() {
+                // Mirror `WorkflowStateMachine::rollback`, which restores the
+                // state reached by the second-to-last transition
+                let next = if transition.event == "Rollback" {
+                    history
+                        .len()
+                        .checked_sub(2)
+                        .map(|i| history[i].transition.to_state)
+                } else {
+                    Some(transition.to_state)
+                };
+                match next {
+                    Some(next) if transition.from_state == state => state = next,
+                    _ => {
+                        warn!(
+                            "Transition log for {} can't be replayed from {} ({} -> {}, expected from {})",
+                            workflow_id,
+                            transition.transition_id,
+                            transition.from_state,
+                            transition.to_state,
+                            state
+                        );
+                        break;
+                    }
+                }
+            }
+            serialized.context = entry.context_snapshot.clone();
+            history.push(entry);
+            replayed += 1;
+        }
+
+        serialized.current_state = state;
+        serialized.metadata.current_state = state;
+        serialized.metadata.history_size = history.len();
+        serialized.history = history;
+
+        let sm = WorkflowStateMachine::from_serialized(serialized);
+        if replayed > 0 {
+            self.store
+                .save_workflow(&sm)
+                .await
+                .map_err(database_error)?;
+        }
+        Ok(sm)
+    }
 
-impl WorkflowRecovery {
     /// Recover a workflow from persistent storage
     pub async fn recover_workflow(
         store: &SqliteWorkflowStore,
@@ -549,4 +689,194 @@ mod tests {
         assert_eq!(loaded.current_state, WorkflowState::Running);
         assert_eq!(loaded.context.get("key"), Some(&serde_json::json!("value")));
     }
+
+    #[tokio::test]
+    async fn test_save_and_load_guards() {
+        let store = create_test_store().await;
+
+        let sm = Arc::new(WorkflowStateMachine::new("guarded-workflow".to_string()));
+        sm.set_guard(
+            WorkflowState::Running,
+            WorkflowState::Completed,
+            Some("key == \"value\""),
+        )
+        .await
+        .unwrap();
+        store.save_workflow(&sm).await.expect("Failed to save");
+
+        let loaded = store
+            .load_workflow("guarded-workflow")
+            .await
+            .expect("Failed to load");
+        assert_eq!(loaded.guards, sm.get_guards().await);
+    }
+
+    #[tokio::test]
+    async fn test_load_rejects_unreadable_guards() {
+        let store = create_test_store().await;
+
+        let sm = Arc::new(WorkflowStateMachine::new("guarded-workflow".to_string()));
+        store.save_workflow(&sm).await.expect("Failed to save");
+        sqlx::query("UPDATE workflows SET guards = 'not json' WHERE workflow_id = ?")
+            .bind("guarded-workflow")
+            .execute(&store.pool)
+            .await
+            .unwrap();
+
+        let err = store.load_workflow("guarded-workflow").await.unwrap_err();
+        assert!(
+            matches!(err, StateMachineError::InvalidGuard(..)),
+            "unexpected error: {:?}",
+            err
+        );
+        assert!(matches!(
+            store.load_workflow("missing").await,
+            Err(StateMachineError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_save_and_load_regions() {
+        let store = create_test_store().await;
+
+        let sm = Arc::new(WorkflowStateMachine::new("forked-workflow".to_string()));
+        sm.process_event(WorkflowEvent::Start).await.unwrap();
+        let parallel = HierarchicalState::parallel()
+            .with_substates(vec![HierarchicalState::new("build".to_string())]);
+        sm.fork(&parallel).await.unwrap();
+        store.save_workflow(&sm).await.expect("Failed to save");
+
+        let loaded = store
+            .load_workflow("forked-workflow")
+            .await
+            .expect("Failed to load");
+        assert_eq!(loaded.active_regions, sm.active_regions().await);
+        assert!(loaded.active_regions.contains_key("build"));
+    }
+
+    #[tokio::test]
+    async fn test_recover_replays_transition_log() {
+        let store = create_test_store().await;
+        let sm = WorkflowStateMachine::new("crashed".to_string());
+        store.save_workflow(&sm).await.unwrap();
+
+        apply_and_save(
+            &store,
+            &sm,
+            [
+                WorkflowEvent::Start,
+                WorkflowEvent::Pause,
+                WorkflowEvent::Resume,
+            ],
+        )
+        .await;
+
+        // Crash after logging `Complete` but before saving the record
+        sm.process_event(WorkflowEvent::Complete).await.unwrap();
+        let entry = sm.get_history_tail(1).await.remove(0);
+        store
+            .save_transition("crashed", &entry.transition, &entry.context_snapshot)
+            .await
+            .unwrap();
+        let stale = store.load_workflow("crashed").await.unwrap();
+        assert_eq!(stale.current_state, WorkflowState::Running);
+
+        let recovery = WorkflowRecovery::new(&store);
+        let recovered = recovery.recover("crashed").await.unwrap();
+        assert_eq!(recovered.current_state().await, WorkflowState::Completed);
+        assert_eq!(recovered.get_history().await.len(), 4);
+        let saved = store.load_workflow("crashed").await.unwrap();
+        assert_eq!(saved.current_state, WorkflowState::Completed);
+
+        // A gap in the log doesn't override the saved record or lose rows
+        sqlx::query("DELETE FROM state_transitions WHERE event = 'Pause'")
+            .execute(&store.pool)
+            .await
+            .unwrap();
+        let recovered = recovery.recover("crashed").await.unwrap();
+        assert_eq!(recovered.current_state().await, WorkflowState::Completed);
+        assert_eq!(
+            store.get_workflow_history("crashed").await.unwrap().len(),
+            3
+        );
+        let saved = store.load_workflow("crashed").await.unwrap();
+        assert_eq!(saved.current_state, WorkflowState::Completed);
+    }
+
+    /// Log each event's transition, then save the workflow record
+    async fn apply_and_save(
+        store: &SqliteWorkflowStore,
+        sm: &WorkflowStateMachine,
+        events: impl IntoIterator<Item = WorkflowEvent>,
+    ) {
+        for event in events {
+            sm.process_event(event).await.unwrap();
+            let entry = sm.get_history_tail(1).await.remove(0);
+            store
+                .save_transition(sm.workflow_id(), &entry.transition, &entry.context_snapshot)
+                .await
+                .unwrap();
+            store.save_workflow(sm).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recover_with_pruned_history_replays_from_record() {
+        let config = StateStoreConfig {
+            max_history_per_workflow: 2,
+            ..Default::default()
+        };
+        let store = SqliteWorkflowStore::new("sqlite::memory:", config)
+            .await
+            .unwrap();
+        let sm = WorkflowStateMachine::new("pruned".to_string());
+        store.save_workflow(&sm).await.unwrap();
+        apply_and_save(
+            &store,
+            &sm,
+            [
+                WorkflowEvent::Start,
+                WorkflowEvent::Pause,
+                WorkflowEvent::Resume,
+            ],
+        )
+        .await;
+
+        // Crash after logging `Pause`; pruning has dropped `Start` and `Pause`
+        sm.process_event(WorkflowEvent::Pause).await.unwrap();
+        let entry = sm.get_history_tail(1).await.remove(0);
+        store
+            .save_transition("pruned", &entry.transition, &entry.context_snapshot)
+            .await
+            .unwrap();
+        let log = store.get_workflow_history("pruned").await.unwrap();
+        assert_eq!(log[0].transition.event, "Resume");
+
+        let recovery = WorkflowRecovery::new(&store);
+        let recovered = recovery.recover("pruned").await.unwrap();
+        assert_eq!(recovered.current_state().await, WorkflowState::Paused);
+        assert_eq!(store.get_workflow_history("pruned").await.unwrap().len(), 2);
+        let saved = store.load_workflow("pruned").await.unwrap();
+        assert_eq!(saved.current_state, WorkflowState::Paused);
+    }
+
+    #[tokio::test]
+    async fn test_recover_with_empty_log_trusts_record() {
+        let store = create_test_store().await;
+        let sm = WorkflowStateMachine::new("unlogged".to_string());
+        sm.process_event(WorkflowEvent::Start).await.unwrap();
+        sm.process_event(WorkflowEvent::Complete).await.unwrap();
+        store.save_workflow(&sm).await.unwrap();
+
+        let recovery = WorkflowRecovery::new(&store);
+        let recovered = recovery.recover("unlogged").await.unwrap();
+        assert_eq!(recovered.current_state().await, WorkflowState::Completed);
+        let saved = store.load_workflow("unlogged").await.unwrap();
+        assert_eq!(saved.current_state, WorkflowState::Completed);
+
+        assert!(matches!(
+            recovery.recover("missing").await,
+            Err(StateStoreError::NotFound(_))
+        ));
+    }
 }
@@ -1,7 +1,7 @@
 /// SQLite-backed implementation of the StateStore trait
 /// Provides persistent agent state management with history tracking and migrations
 use crate::errors::{StateStoreError, StateStoreResult};
-use crate::traits::{ActorType, Event, StateStore, Task, TaskStatus};
+use crate::traits::{ActorType, AgentRecord, Event, StateStore, Task, TaskStatus};
 use async_trait::async_trait;
 use chrono::Utc;
 use serde_json::{json, Value};
@@ -11,6 +11,9 @@ use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use uuid::Uuid;
 
+/// `agent_states` metadata kind marking rows written by `save_agent_record`
+const AGENT_RECORD_KIND: &str = "agent_record";
+
 /// SQLite-backed state store implementation
 pub struct SqliteStateStore {
     /// Connection pool to SQLite database
@@ -717,6 +720,41 @@ impl StateStore for SqliteStateStore {
 
         Ok(events)
     }
+
+    async fn save_agent_record(&self, record: &AgentRecord) -> StateStoreResult<()> {
+        let state_data = serde_json::to_string(record).map_err(|e| {
+            StateStoreError::SerializationError(format!("Failed to serialize agent record: {}", e))
+        })?;
+
+        self.save_agent_state(&AgentState {
+            agent_id: record.id.to_string(),
+            name: record.config.name.clone(),
+            status: record.status.to_string(),
+            metadata: json!({
+                "kind": AGENT_RECORD_KIND,
+                "restartable": record.restartable,
+            }),
+            state_data,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+            version: 1,
+        })
+        .await
+    }
+
+    async fn get_agent_records(&self) -> StateStoreResult<Vec<AgentRecord>> {
+        let mut records: Vec<AgentRecord> = self
+            .list_agents()
+            .await?
+            .into_iter()
+            .filter(|state| {
+                state.metadata.get("kind").and_then(Value::as_str) == Some(AGENT_RECORD_KIND)
+            })
+            .filter_map(|state| serde_json::from_str(&state.state_data).ok())
+            .collect();
+        records.sort_by_key(|r| r.created_at);
+        Ok(records)
+    }
 }
 
 // Additional agent state management methods (not part of StateStore trait but useful)
@@ -5,13 +5,20 @@ use futures::{SinkExt, StreamExt};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 use tokio::net::TcpStream;
-use tokio::sync::{mpsc, oneshot, RwLock};
+use tokio::sync::{mpsc, oneshot, watch, RwLock};
 use tokio_util::codec::Framed;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+/// Timeout used by [`SwankClient::eval`].
+pub const DEFAULT_EVAL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long to wait for the debugger after an interrupt before giving up.
+const INTERRUPT_GRACE: Duration = Duration::from_secs(2);
+
 #[derive(Error, Debug)]
 pub enum SwankError {
     #[error("Connection failed: {0}")]
@@ -45,6 +52,8 @@ pub enum SwankMessage {
     Return { id: u64, value: String },
     /// Evaluation aborted
     Abort { id: u64, reason: String },
+    /// Line the SBCL process wrote to stderr
+    Stderr(String),
 }
 
 #[derive(Debug, Clone)]
@@ -77,6 +86,8 @@ pub struct SwankClient {
     next_id: AtomicU64,
     /// Whether the client is connected
     connected: Arc<RwLock<bool>>,
+    /// Thread currently in the debugger, if any
+    debug_thread: Arc<watch::Sender<Option<i64>>>,
 }
 
 impl SwankClient {
@@ -100,6 +111,7 @@ impl SwankClient {
         let pending: Arc<RwLock<HashMap<u64, oneshot::Sender<Result<String, SwankError>>>>> =
             Arc::new(RwLock::new(HashMap::new()));
         let connected = Arc::new(RwLock::new(true));
+        let debug_thread = Arc::new(watch::Sender::new(None));
 
         let client = Arc::new(Self {
             agent_id,
@@ -109,6 +121,7 @@ impl SwankClient {
             event_tx: event_tx.clone(),
             next_id: AtomicU64::new(1),
             connected: Arc::clone(&connected),
+            debug_thread: Arc::clone(&debug_thread),
         });
 
         // Spawn write loop
@@ -121,6 +134,8 @@ impl SwankClient {
                     break;
                 }
             }
+            // Closing our side lets Swank drop the connection (and the read loop end)
+            let _ = sink.close().await;
         });
 
         // Spawn read loop
@@ -131,7 +146,8 @@ impl SwankClient {
             while let Some(result) = stream.next().await {
                 match result {
                     Ok(msg) => {
-                        Self::handle_message(&msg, &pending_read, &event_tx_read).await;
+                        Self::handle_message(&msg, &pending_read, &event_tx_read, &debug_thread)
+                            .await;
                     }
                     Err(e) => {
                         error!("Swank read error: {}", e);
@@ -156,6 +172,7 @@ impl SwankClient {
         msg: &str,
         pending: &Arc<RwLock<HashMap<u64, oneshot::Sender<Result<String, SwankError>>>>>,
         event_tx: &mpsc::Sender<SwankMessage>,
+        debug_thread: &watch::Sender<Option<i64>>,
     ) {
         debug!("Received Swank message: {}", msg);
 
@@ -171,6 +188,9 @@ impl SwankClient {
         } else if msg.starts_with("(:debug") {
             // Parse debug message
             if let Some(debug_msg) = Self::parse_debug_message(msg) {
+                if let SwankMessage::Debug { thread, .. } = &debug_msg {
+                    debug_thread.send_replace(Some(*thread));
+                }
                 let _ = event_tx.send(debug_msg).await;
             }
         } else if msg.starts_with("(:write-string") {
@@ -184,6 +204,7 @@ impl SwankClient {
         } else if msg.starts_with("(:debug-return") {
             // Debug returned - debugger exited
             debug!("Debug returned");
+            debug_thread.send_replace(None);
         } else if msg.starts_with("(:indentation-update") {
             // Ignore indentation updates
         } else if msg.starts_with("(:new-features") {
@@ -405,7 +426,56 @@ impl SwankClient {
     }
 
     /// Evaluate code in the Lisp runtime.
+    ///
+    /// Gives up after [`DEFAULT_EVAL_TIMEOUT`] without touching the running
+    /// evaluation; use [`Self::eval_with_timeout`] to interrupt it instead.
     pub async fn eval(&self, code: &str, package: &str) -> Result<String, SwankError> {
+        let (_, rx) = self.send_eval(code, package).await?;
+
+        tokio::time::timeout(DEFAULT_EVAL_TIMEOUT, rx)
+            .await
+            .map_err(|_| SwankError::Timeout)?
+            .map_err(|_| SwankError::Disconnected)?
+    }
+
+    /// Evaluate code, interrupting it if it runs longer than `timeout`.
+    ///
+    /// On timeout the evaluation is interrupted and aborted back to the top
+    /// level, so the REPL is usable again once this returns
+    /// [`SwankError::Timeout`]. If the evaluation is paused in the debugger
+    /// it is left there for the caller to pick a restart.
+    pub async fn eval_with_timeout(
+        &self,
+        code: &str,
+        package: &str,
+        timeout: Duration,
+    ) -> Result<String, SwankError> {
+        let (id, rx) = self.send_eval(code, package).await?;
+
+        match tokio::time::timeout(timeout, rx).await {
+            Ok(result) => result.map_err(|_| SwankError::Disconnected)?,
+            Err(_) => {
+                self.pending.write().await.remove(&id);
+                if self.debug_thread.borrow().is_some() {
+                    warn!("Evaluation {} timed out in the debugger", id);
+                } else {
+                    warn!(
+                        "Evaluation {} timed out after {:?}, interrupting",
+                        id, timeout
+                    );
+                    self.interrupt().await?;
+                }
+                Err(SwankError::Timeout)
+            }
+        }
+    }
+
+    /// Send an evaluation request and register for its reply.
+    async fn send_eval(
+        &self,
+        code: &str,
+        package: &str,
+    ) -> Result<(u64, oneshot::Receiver<Result<String, SwankError>>), SwankError> {
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
         let escaped = Self::escape_for_sexp(code);
         let msg = format!(
@@ -417,11 +487,32 @@ impl SwankClient {
         self.pending.write().await.insert(id, tx);
 
         self.send_raw(&msg).await?;
+        Ok((id, rx))
+    }
 
-        tokio::time::timeout(std::time::Duration::from_secs(30), rx)
-            .await
-            .map_err(|_| SwankError::Timeout)?
-            .map_err(|_| SwankError::Disconnected)?
+    /// Interrupt the running evaluation and return to the top level.
+    ///
+    /// Does nothing beyond the interrupt request if the debugger isn't
+    /// entered within a short grace period (e.g. nothing was running).
+    pub async fn interrupt(&self) -> Result<(), SwankError> {
+        let mut debug_thread = self.debug_thread.subscribe();
+        self.send_raw("(:emacs-interrupt t)").await?;
+
+        let wait = debug_thread.wait_for(|thread| thread.is_some());
+        let thread = match tokio::time::timeout(INTERRUPT_GRACE, wait).await {
+            Ok(Ok(thread)) => *thread,
+            _ => None,
+        };
+        let Some(thread) = thread else {
+            return Ok(());
+        };
+
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        self.send_raw(&format!(
+            "(:emacs-rex (swank:throw-to-toplevel) nil {} {})",
+            thread, id
+        ))
+        .await
     }
 
     /// Compile a code string.
@@ -508,6 +599,7 @@ impl SwankClient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tokio::net::TcpListener;
 
     #[test]
     fn test_extract_return_id() {
@@ -566,4 +658,128 @@ mod tests {
             "path\\\\file\\nwith \\\"quotes\\\""
         );
     }
+
+    /// Stand-in Swank server that never answers `(loop)` until interrupted.
+    ///
+    /// Returns the messages it received once `(+ 1 2)` has been answered.
+    async fn serve_runaway_eval(listener: TcpListener) -> Vec<String> {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut framed = Framed::new(stream, SwankCodec);
+        let mut received = Vec::new();
+        let mut looping = None;
+
+        while let Some(Ok(msg)) = framed.next().await {
+            let id = SwankClient::extract_return_id(&msg);
+            let reply = if msg.contains("(loop)") {
+                looping = id;
+                None
+            } else if msg.starts_with("(:emacs-interrupt") {
+                Some(
+                    r#"(:debug 7 1 ("Interactive interrupt" "" nil) (("ABORT" "Return to top level")) () nil)"#
+                        .to_string(),
+                )
+            } else if msg.contains("swank:throw-to-toplevel") {
+                let aborted = format!("(:return (:abort nil) {})", looping.unwrap());
+                framed.send(aborted).await.unwrap();
+                Some("(:debug-return 7 1 nil)".to_string())
+            } else if msg.contains("(+ 1 2)") {
+                Some(format!(r#"(:return (:ok ("" "3")) {})"#, id.unwrap()))
+            } else {
+                None
+            };
+            let done = msg.contains("(+ 1 2)");
+            received.push(msg);
+            if let Some(reply) = reply {
+                framed.send(reply).await.unwrap();
+            }
+            if done {
+                break;
+            }
+        }
+        received
+    }
+
+    #[tokio::test]
+    async fn test_eval_with_timeout_interrupts() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(serve_runaway_eval(listener));
+
+        let (event_tx, _event_rx) = mpsc::channel(64);
+        let client = SwankClient::connect(Uuid::new_v4(), port, event_tx)
+            .await
+            .unwrap();
+
+        let result = client
+            .eval_with_timeout("(loop)", "CL-USER", Duration::from_millis(100))
+            .await;
+        assert!(matches!(result, Err(SwankError::Timeout)));
+
+        // Control is back: the next evaluation is answered
+        let value = client.eval("(+ 1 2)", "CL-USER").await.unwrap();
+        assert!(value.contains('3'));
+
+        let received = server.await.unwrap();
+        assert!(received.iter().any(|m| m == "(:emacs-interrupt t)"));
+        assert!(received
+            .iter()
+            .any(|m| m.starts_with("(:emacs-rex (swank:throw-to-toplevel) nil 7 ")));
+    }
+
+    #[tokio::test]
+    async fn test_eval_with_timeout_leaves_open_debugger_alone() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut framed = Framed::new(stream, SwankCodec);
+            let mut received = Vec::new();
+
+            while let Some(Ok(msg)) = framed.next().await {
+                let id = SwankClient::extract_return_id(&msg);
+                let reply = if msg.contains("(error") {
+                    Some(
+                        r#"(:debug 7 1 ("boom" "" nil) (("ABORT" "Return to top level")) () nil)"#
+                            .to_string(),
+                    )
+                } else if msg.contains("swank:invoke-nth-restart-for-emacs") {
+                    Some(format!(r#"(:return (:ok nil) {})"#, id.unwrap()))
+                } else {
+                    None
+                };
+                let done = msg.contains("swank:invoke-nth-restart-for-emacs");
+                received.push(msg);
+                if let Some(reply) = reply {
+                    framed.send(reply).await.unwrap();
+                }
+                if done {
+                    break;
+                }
+            }
+            received
+        });
+
+        let (event_tx, mut event_rx) = mpsc::channel(64);
+        let client = SwankClient::connect(Uuid::new_v4(), port, event_tx)
+            .await
+            .unwrap();
+
+        let result = client
+            .eval_with_timeout("(error \"boom\")", "CL-USER", Duration::from_millis(100))
+            .await;
+        assert!(matches!(result, Err(SwankError::Timeout)));
+        assert!(matches!(
+            event_rx.recv().await,
+            Some(SwankMessage::Debug { thread: 7, .. })
+        ));
+
+        // The debugger is still waiting for a restart
+        client.invoke_restart(0).await.unwrap();
+
+        let received = server.await.unwrap();
+        assert!(!received.iter().any(|m| m.starts_with("(:emacs-interrupt")));
+        assert!(!received
+            .iter()
+            .any(|m| m.contains("swank:throw-to-toplevel")));
+    }
 }
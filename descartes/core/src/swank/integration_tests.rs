@@ -3,7 +3,11 @@
 //! These tests require SBCL to be installed and available in PATH.
 //! Run with: cargo test -p descartes-core swank -- --ignored --nocapture
 
-use super::{find_available_port, SwankClient, SwankLauncher, SwankMessage, DEFAULT_SWANK_PORT};
+use super::{
+    find_available_port, SwankClient, SwankError, SwankLauncher, SwankMessage, SwankPool,
+    SwankPoolConfig, SwankSessionRegistry, DEFAULT_SWANK_PORT,
+};
+use std::sync::Arc;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
@@ -145,6 +149,82 @@ async fn test_debugger_triggered() {
     assert!(eval_result.is_ok(), "Eval task didn't complete after restart");
 }
 
+#[tokio::test]
+#[ignore] // Requires SBCL
+async fn test_eval_timeout_interrupts_runaway_form() {
+    if !sbcl_available() {
+        eprintln!("Skipping test: SBCL not available");
+        return;
+    }
+
+    let port = find_available_port(DEFAULT_SWANK_PORT).await.unwrap();
+    let _child = SwankLauncher::start_sbcl(port).await.unwrap();
+
+    let (event_tx, _event_rx) = mpsc::channel(64);
+    let agent_id = Uuid::new_v4();
+    let client = SwankClient::connect(agent_id, port, event_tx)
+        .await
+        .unwrap();
+
+    let result = client
+        .eval_with_timeout("(loop)", "CL-USER", std::time::Duration::from_millis(500))
+        .await;
+    assert!(
+        matches!(result, Err(SwankError::Timeout)),
+        "Got: {:?}",
+        result
+    );
+
+    // The interrupt returned control to the REPL
+    let value = client.eval("(+ 1 2)", "CL-USER").await.unwrap();
+    assert!(value.contains('3'), "Expected 3, got: {}", value);
+}
+
+#[tokio::test]
+#[ignore] // Requires SBCL
+async fn test_pool_reuses_reset_process() {
+    if !sbcl_available() {
+        eprintln!("Skipping test: SBCL not available");
+        return;
+    }
+
+    let pool = SwankPool::new(
+        Arc::new(SwankSessionRegistry::new()),
+        SwankPoolConfig::default(),
+    );
+
+    let (event_tx, _event_rx) = mpsc::channel(64);
+    let first = pool.acquire(Uuid::new_v4(), event_tx).await.unwrap();
+    first
+        .eval("(defpackage :scratch (:use :cl))", "CL-USER")
+        .await
+        .unwrap();
+    first
+        .eval("(setf *print-base* 16)", "CL-USER")
+        .await
+        .unwrap();
+    pool.release(&first.agent_id()).await;
+    assert_eq!(pool.warm_count(), 1);
+
+    let (event_tx, _event_rx) = mpsc::channel(64);
+    let second = pool.acquire(Uuid::new_v4(), event_tx).await.unwrap();
+    assert_eq!(second.port(), first.port());
+    let value = second
+        .eval("(find-package :scratch)", "CL-USER")
+        .await
+        .unwrap();
+    assert!(
+        value.contains("NIL"),
+        "Package survived the reset: {}",
+        value
+    );
+    let value = second.eval("(= *print-base* 10)", "CL-USER").await.unwrap();
+    assert!(value.contains('T'), "Special survived the reset: {}", value);
+
+    pool.shutdown();
+    assert_eq!(pool.leased_count(), 0);
+}
+
 #[tokio::test]
 #[ignore] // Requires SBCL
 async fn test_connection_cleanup() {
@@ -1,23 +1,105 @@
 //! SBCL process launcher for Swank.
 
-use std::process::Stdio;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::process::{ExitStatus, Stdio};
+use std::sync::Arc;
 use thiserror::Error;
-use tokio::process::{Child, Command};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{Child, ChildStderr, Command};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
 use tokio::time::{timeout, Duration};
 use tracing::{debug, info};
 
+/// Stderr lines kept for startup error reports.
+const STDERR_TAIL_LINES: usize = 10;
+
 #[derive(Error, Debug)]
 pub enum LauncherError {
     #[error("SBCL not found in PATH")]
     SbclNotFound,
+    #[error("SBCL binary not found: {0}")]
+    BinaryNotFound(String),
     #[error("Failed to spawn SBCL: {0}")]
     SpawnFailed(String),
-    #[error("Swank server did not start on port {0} within timeout")]
-    StartupTimeout(u16),
+    #[error(
+        "Swank server did not start on port {port} within timeout{}",
+        stderr_suffix(stderr)
+    )]
+    StartupTimeout { port: u16, stderr: Vec<String> },
+    #[error("SBCL exited during startup ({status}){}", stderr_suffix(stderr))]
+    Exited {
+        status: ExitStatus,
+        stderr: Vec<String>,
+    },
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 }
 
+fn stderr_suffix(stderr: &[String]) -> String {
+    if stderr.is_empty() {
+        String::new()
+    } else {
+        format!("; stderr:\n{}", stderr.join("\n"))
+    }
+}
+
+/// Stderr of a running SBCL process.
+///
+/// The last few lines are kept for error reports; every line is also
+/// broadcast to subscribers.
+#[derive(Clone, Debug)]
+pub struct SbclStderr {
+    tail: Arc<Mutex<VecDeque<String>>>,
+    lines: broadcast::Sender<String>,
+}
+
+impl SbclStderr {
+    pub(crate) fn capture(stderr: ChildStderr) -> (Self, JoinHandle<()>) {
+        let capture = Self {
+            tail: Arc::new(Mutex::new(VecDeque::with_capacity(STDERR_TAIL_LINES))),
+            lines: broadcast::channel(64).0,
+        };
+
+        let tail = Arc::clone(&capture.tail);
+        let lines_tx = capture.lines.clone();
+        let reader = tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                debug!("SBCL stderr: {}", line);
+                {
+                    let mut tail = tail.lock();
+                    if tail.len() == STDERR_TAIL_LINES {
+                        tail.pop_front();
+                    }
+                    tail.push_back(line.clone());
+                }
+                let _ = lines_tx.send(line);
+            }
+        });
+
+        (capture, reader)
+    }
+
+    /// The most recent stderr lines, oldest first.
+    pub fn tail(&self) -> Vec<String> {
+        self.tail.lock().iter().cloned().collect()
+    }
+
+    /// Receive stderr lines written from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<String> {
+        self.lines.subscribe()
+    }
+}
+
+/// How waiting for the Swank port ended.
+enum Startup {
+    Ready,
+    Exited(ExitStatus),
+    TimedOut,
+}
+
 /// Launcher for SBCL with Swank server.
 pub struct SwankLauncher;
 
@@ -55,7 +137,18 @@ impl SwankLauncher {
     /// Start SBCL with Swank server on the specified port.
     pub async fn start_sbcl(port: u16) -> Result<Child, LauncherError> {
         Self::check_sbcl().await?;
+        let (child, _stderr) = Self::start_sbcl_binary("sbcl", port).await?;
+        Ok(child)
+    }
 
+    /// Start the given SBCL binary with Swank server on the specified port.
+    ///
+    /// The process's stderr is captured; if SBCL exits or Swank doesn't come
+    /// up, the error carries its last lines.
+    pub async fn start_sbcl_binary(
+        sbcl: &str,
+        port: u16,
+    ) -> Result<(Child, SbclStderr), LauncherError> {
         // Lisp code to start Swank server
         // Uses ASDF to load swank if available, falls back to quicklisp
         let swank_init = format!(
@@ -78,24 +171,51 @@ impl SwankLauncher {
             port
         );
 
-        info!("Starting SBCL with Swank on port {}", port);
+        info!("Starting {} with Swank on port {}", sbcl, port);
 
-        let child = Command::new("sbcl")
+        let mut child = Command::new(sbcl)
             .arg("--noinform")
             .arg("--eval")
             .arg(&swank_init)
             .stdin(Stdio::null())
-            .stdout(Stdio::piped())
+            .stdout(Stdio::null())
             .stderr(Stdio::piped())
             .kill_on_drop(true)
             .spawn()
-            .map_err(|e| LauncherError::SpawnFailed(e.to_string()))?;
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => LauncherError::BinaryNotFound(sbcl.to_string()),
+                _ => LauncherError::SpawnFailed(format!("{}: {}", sbcl, e)),
+            })?;
+        let (stderr, reader) = match child.stderr.take() {
+            Some(pipe) => SbclStderr::capture(pipe),
+            None => {
+                return Err(LauncherError::SpawnFailed(
+                    "stderr not captured".to_string(),
+                ))
+            }
+        };
 
         // Wait for port to become available
-        Self::wait_for_port(port, Duration::from_secs(30)).await?;
+        match Self::wait_for_port(port, Duration::from_secs(30), &mut child).await? {
+            Startup::Ready => {}
+            Startup::Exited(status) => {
+                // Let the reader drain what the process wrote before exiting
+                let _ = timeout(Duration::from_secs(1), reader).await;
+                return Err(LauncherError::Exited {
+                    status,
+                    stderr: stderr.tail(),
+                });
+            }
+            Startup::TimedOut => {
+                return Err(LauncherError::StartupTimeout {
+                    port,
+                    stderr: stderr.tail(),
+                })
+            }
+        }
 
         info!("SBCL Swank server ready on port {}", port);
-        Ok(child)
+        Ok((child, stderr))
     }
 
     /// Start SBCL with a custom init file.
@@ -132,29 +252,51 @@ impl SwankLauncher {
             .stderr(Stdio::piped())
             .kill_on_drop(true);
 
-        let child = cmd
+        let mut child = cmd
             .spawn()
             .map_err(|e| LauncherError::SpawnFailed(e.to_string()))?;
 
         // Wait for port to become available
-        Self::wait_for_port(port, Duration::from_secs(30)).await?;
+        match Self::wait_for_port(port, Duration::from_secs(30), &mut child).await? {
+            Startup::Ready => {}
+            Startup::Exited(status) => {
+                return Err(LauncherError::Exited {
+                    status,
+                    stderr: Vec::new(),
+                })
+            }
+            Startup::TimedOut => {
+                return Err(LauncherError::StartupTimeout {
+                    port,
+                    stderr: Vec::new(),
+                })
+            }
+        }
 
         info!("SBCL Swank server ready on port {}", port);
         Ok(child)
     }
 
-    /// Wait for a port to become available (accepting connections).
-    async fn wait_for_port(port: u16, max_wait: Duration) -> Result<(), LauncherError> {
+    /// Wait for a port to become available (accepting connections), unless
+    /// `child` exits first.
+    async fn wait_for_port(
+        port: u16,
+        max_wait: Duration,
+        child: &mut Child,
+    ) -> Result<Startup, LauncherError> {
         use tokio::net::TcpStream;
 
         let start = std::time::Instant::now();
         let addr = format!("127.0.0.1:{}", port);
 
         while start.elapsed() < max_wait {
+            if let Some(status) = child.try_wait()? {
+                return Ok(Startup::Exited(status));
+            }
             match timeout(Duration::from_millis(500), TcpStream::connect(&addr)).await {
                 Ok(Ok(_)) => {
                     debug!("Port {} is now available", port);
-                    return Ok(());
+                    return Ok(Startup::Ready);
                 }
                 _ => {
                     tokio::time::sleep(Duration::from_millis(100)).await;
@@ -162,7 +304,7 @@ impl SwankLauncher {
             }
         }
 
-        Err(LauncherError::StartupTimeout(port))
+        Ok(Startup::TimedOut)
     }
 }
 
@@ -179,6 +321,43 @@ mod tests {
         let _ = result;
     }
 
+    #[tokio::test]
+    async fn test_missing_binary_is_reported() {
+        let err = SwankLauncher::start_sbcl_binary("/nonexistent/sbcl", 40150)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, LauncherError::BinaryNotFound(_)));
+        assert_eq!(err.to_string(), "SBCL binary not found: /nonexistent/sbcl");
+    }
+
+    #[tokio::test]
+    async fn test_startup_failure_includes_stderr() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let script = dir.path().join("sbcl");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\necho 'loading swank' >&2\necho 'Component SWANK not found' >&2\nexit 1\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let err = SwankLauncher::start_sbcl_binary(script.to_str().unwrap(), 40151)
+            .await
+            .unwrap_err();
+        match &err {
+            LauncherError::Exited { status, stderr } => {
+                assert_eq!(status.code(), Some(1));
+                assert_eq!(stderr, &["loading swank", "Component SWANK not found"]);
+            }
+            other => panic!("Unexpected error: {}", other),
+        }
+        assert!(err
+            .to_string()
+            .ends_with("stderr:\nloading swank\nComponent SWANK not found"));
+    }
+
     #[tokio::test]
     async fn test_sbcl_version() {
         // This test depends on whether SBCL is installed
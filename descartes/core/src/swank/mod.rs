@@ -7,10 +7,14 @@
 mod client;
 mod codec;
 mod launcher;
+mod pool;
 mod registry;
 
-pub use client::{SwankClient, SwankError, SwankMessage, SwankRestart, SwankFrame};
-pub use launcher::{SwankLauncher, LauncherError};
+pub use client::{
+    SwankClient, SwankError, SwankFrame, SwankMessage, SwankRestart, DEFAULT_EVAL_TIMEOUT,
+};
+pub use launcher::{LauncherError, SbclStderr, SwankLauncher};
+pub use pool::{SwankPool, SwankPoolConfig, SwankPoolError};
 pub use registry::SwankSessionRegistry;
 
 /// Default Swank port (used as starting point for allocation).
@@ -0,0 +1,381 @@
+//! Pool of warm SBCL processes shared by Lisp agents.
+//!
+//! Starting SBCL and loading Swank takes seconds. When a Lisp agent finishes,
+//! [`SwankPool::release`] resets its process and keeps it warm for the next
+//! agent, so [`SwankPool::acquire`] only pays the startup cost when the pool is
+//! empty. Every process is launched the same way, so any warm process can
+//! serve any agent. Leased sessions are registered in a
+//! [`SwankSessionRegistry`] under their agent ID.
+
+use super::{
+    find_available_port, LauncherError, SbclStderr, SwankClient, SwankError, SwankLauncher,
+    SwankMessage, SwankSessionRegistry, DEFAULT_SWANK_PORT,
+};
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::process::Child;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Remembers the packages, ASDF systems and standard special variables of a
+/// fresh process, so resets can put them back.
+const BASELINE_FORM: &str = r#"(let ((specials '()))
+  (do-external-symbols (s :common-lisp)
+    (when (and (boundp s) (not (constantp s)))
+      (handler-case (push (cons s (sb-ext:symbol-global-value s)) specials)
+        (error () nil))))
+  (setf (get :descartes :baseline-packages) (list-all-packages)
+        (get :descartes :baseline-systems) (asdf:already-loaded-systems)
+        (get :descartes :baseline-specials) specials)
+  t)"#;
+
+/// Deletes packages created since the baseline, forgets ASDF systems loaded
+/// since, restores the global values of the standard special variables and
+/// uninterns everything interned in CL-USER. Returns T only if a baseline was
+/// recorded.
+const RESET_FORM: &str = r#"(let ((baseline (get :descartes :baseline-packages)))
+  (when baseline
+    (dolist (name (asdf:already-loaded-systems))
+      (unless (member name (get :descartes :baseline-systems) :test #'string=)
+        (asdf:clear-system name)))
+    (dolist (p (list-all-packages))
+      (unless (member p baseline)
+        (ignore-errors (delete-package p))))
+    (dolist (special (get :descartes :baseline-specials))
+      (setf (sb-ext:symbol-global-value (car special)) (cdr special)))
+    (do-symbols (s :cl-user)
+      (when (eq (symbol-package s) (find-package :cl-user))
+        (unintern s :cl-user)))
+    t))"#;
+
+/// How long a reset may take before the process is discarded instead.
+const RESET_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Error, Debug)]
+pub enum SwankPoolError {
+    #[error("Failed to find available port: {0}")]
+    NoPort(std::io::Error),
+    #[error("Failed to start SBCL: {0}")]
+    Launch(#[from] LauncherError),
+    #[error("Failed to connect to Swank: {0}")]
+    Connect(#[from] SwankError),
+}
+
+/// Settings for [`SwankPool`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwankPoolConfig {
+    /// Most processes kept warm; 0 disables reuse
+    pub max_warm: usize,
+    /// How long a warm process may sit unused before it is killed
+    pub idle_ttl: Duration,
+    /// SBCL binary to launch
+    pub sbcl: String,
+}
+
+impl Default for SwankPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_warm: 2,
+            idle_ttl: Duration::from_secs(300),
+            sbcl: "sbcl".to_string(),
+        }
+    }
+}
+
+struct SbclProcess {
+    port: u16,
+    child: Child,
+    stderr: SbclStderr,
+}
+
+impl SbclProcess {
+    fn kill(mut self) {
+        match self.child.start_kill() {
+            Ok(()) => info!("Killed SBCL process on port {}", self.port),
+            Err(e) => warn!("Failed to kill SBCL process on port {}: {}", self.port, e),
+        }
+    }
+}
+
+struct WarmProcess {
+    process: SbclProcess,
+    idle_since: Instant,
+}
+
+struct Lease {
+    process: SbclProcess,
+    client: Arc<SwankClient>,
+    /// Forwards the process's stderr to the agent's event channel
+    stderr_forwarder: JoinHandle<()>,
+}
+
+/// SBCL processes leased to agents, plus warm ones waiting for the next agent.
+pub struct SwankPool {
+    config: SwankPoolConfig,
+    registry: Arc<SwankSessionRegistry>,
+    warm: Mutex<Vec<WarmProcess>>,
+    leased: DashMap<Uuid, Lease>,
+}
+
+impl SwankPool {
+    /// Create an empty pool registering its sessions in `registry`.
+    pub fn new(registry: Arc<SwankSessionRegistry>, config: SwankPoolConfig) -> Self {
+        Self {
+            config,
+            registry,
+            warm: Mutex::new(Vec::new()),
+            leased: DashMap::new(),
+        }
+    }
+
+    /// The pool's limits.
+    pub fn config(&self) -> &SwankPoolConfig {
+        &self.config
+    }
+
+    /// Start a Swank session for `agent_id`, reusing a warm process if any.
+    pub async fn acquire(
+        &self,
+        agent_id: Uuid,
+        event_tx: mpsc::Sender<SwankMessage>,
+    ) -> Result<Arc<SwankClient>, SwankPoolError> {
+        self.reap_idle();
+        let warm = self.warm.lock().pop();
+        let reused = warm.is_some();
+        let process = match warm {
+            Some(warm) => {
+                debug!("Reusing warm SBCL process on port {}", warm.process.port);
+                warm.process
+            }
+            None => self.launch().await?,
+        };
+
+        let stderr_forwarder = Self::forward_stderr(&process.stderr, event_tx.clone());
+        let client = match SwankClient::connect(agent_id, process.port, event_tx).await {
+            Ok(client) => client,
+            Err(e) => {
+                stderr_forwarder.abort();
+                process.kill();
+                return Err(e.into());
+            }
+        };
+        if !reused {
+            if let Err(e) = client.eval(BASELINE_FORM, "CL-USER").await {
+                warn!(
+                    "Failed to record SBCL baseline, process won't be reused: {}",
+                    e
+                );
+            }
+        }
+
+        self.registry.insert(agent_id, Arc::clone(&client));
+        self.leased.insert(
+            agent_id,
+            Lease {
+                process,
+                client: Arc::clone(&client),
+                stderr_forwarder,
+            },
+        );
+        Ok(client)
+    }
+
+    /// End `agent_id`'s session.
+    ///
+    /// The process goes back to the pool if it resets cleanly and there is
+    /// room; otherwise it is killed.
+    pub async fn release(&self, agent_id: &Uuid) {
+        self.registry.remove(agent_id);
+        let Some((_, lease)) = self.leased.remove(agent_id) else {
+            return;
+        };
+
+        lease.stderr_forwarder.abort();
+        let reusable = self.config.max_warm > 0 && Self::reset(&lease.client).await;
+        let _ = lease.client.disconnect().await;
+        if reusable {
+            self.park(lease.process);
+        } else {
+            lease.process.kill();
+        }
+    }
+
+    /// Kill warm processes idle for longer than the TTL, returning how many.
+    pub fn reap_idle(&self) -> usize {
+        let ttl = self.config.idle_ttl;
+        let mut warm = self.warm.lock();
+        let (keep, expired): (Vec<_>, Vec<_>) =
+            warm.drain(..).partition(|w| w.idle_since.elapsed() < ttl);
+        *warm = keep;
+        drop(warm);
+
+        let count = expired.len();
+        for w in expired {
+            w.process.kill();
+        }
+        count
+    }
+
+    /// Reap idle processes in the background until the pool is dropped.
+    pub fn spawn_idle_reaper(self: &Arc<Self>) -> JoinHandle<()> {
+        let pool = Arc::downgrade(self);
+        let period = (self.config.idle_ttl / 2).max(Duration::from_secs(1));
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            // The first tick completes immediately
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(pool) = pool.upgrade() else {
+                    break;
+                };
+                pool.reap_idle();
+            }
+        })
+    }
+
+    /// Kill every process, warm or leased.
+    pub fn shutdown(&self) {
+        let warm: Vec<_> = self.warm.lock().drain(..).collect();
+        for w in warm {
+            w.process.kill();
+        }
+
+        let agent_ids: Vec<Uuid> = self.leased.iter().map(|entry| *entry.key()).collect();
+        for agent_id in agent_ids {
+            self.registry.remove(&agent_id);
+            if let Some((_, lease)) = self.leased.remove(&agent_id) {
+                lease.stderr_forwarder.abort();
+                lease.process.kill();
+            }
+        }
+    }
+
+    /// Number of processes waiting for an agent.
+    pub fn warm_count(&self) -> usize {
+        self.warm.lock().len()
+    }
+
+    /// Number of processes in use by agents.
+    pub fn leased_count(&self) -> usize {
+        self.leased.len()
+    }
+
+    async fn launch(&self) -> Result<SbclProcess, SwankPoolError> {
+        let port = find_available_port(DEFAULT_SWANK_PORT)
+            .await
+            .map_err(SwankPoolError::NoPort)?;
+        let (child, stderr) = SwankLauncher::start_sbcl_binary(&self.config.sbcl, port).await?;
+        Ok(SbclProcess {
+            port,
+            child,
+            stderr,
+        })
+    }
+
+    fn forward_stderr(stderr: &SbclStderr, event_tx: mpsc::Sender<SwankMessage>) -> JoinHandle<()> {
+        let mut lines = stderr.subscribe();
+        tokio::spawn(async move {
+            loop {
+                match lines.recv().await {
+                    Ok(line) => {
+                        if event_tx.send(SwankMessage::Stderr(line)).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    async fn reset(client: &SwankClient) -> bool {
+        match client
+            .eval_with_timeout(RESET_FORM, "CL-USER", RESET_TIMEOUT)
+            .await
+        {
+            Ok(value) => value.contains("\"T\""),
+            Err(e) => {
+                warn!("Failed to reset SBCL process: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Keep a reset process warm, or kill it if the pool is full.
+    fn park(&self, process: SbclProcess) {
+        let mut warm = self.warm.lock();
+        if warm.len() < self.config.max_warm {
+            warm.push(WarmProcess {
+                process,
+                idle_since: Instant::now(),
+            });
+        } else {
+            drop(warm);
+            process.kill();
+        }
+    }
+}
+
+impl Drop for SwankPool {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Stdio;
+    use tokio::process::Command;
+
+    fn sleeping_process(port: u16) -> SbclProcess {
+        let mut child = Command::new("sleep")
+            .arg("30")
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .unwrap();
+        let (stderr, _reader) = SbclStderr::capture(child.stderr.take().unwrap());
+        SbclProcess {
+            port,
+            child,
+            stderr,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pool_caps_warm_processes_and_reaps_idle_ones() {
+        let pool = SwankPool::new(
+            Arc::new(SwankSessionRegistry::new()),
+            SwankPoolConfig {
+                max_warm: 2,
+                idle_ttl: Duration::from_millis(100),
+                ..Default::default()
+            },
+        );
+
+        // The third process doesn't fit and is killed
+        for port in 0..3 {
+            pool.park(sleeping_process(port));
+        }
+        assert_eq!(pool.warm_count(), 2);
+        assert_eq!(pool.reap_idle(), 0);
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(pool.reap_idle(), 2);
+        pool.park(sleeping_process(3));
+        assert_eq!(pool.warm_count(), 1);
+
+        pool.shutdown();
+        assert_eq!(pool.warm_count(), 0);
+    }
+}
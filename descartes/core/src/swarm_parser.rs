@@ -16,6 +16,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use thiserror::Error;
+use uuid::Uuid;
 
 /// Error types for Swarm.toml parsing and validation
 #[derive(Error, Debug)]
@@ -167,6 +168,9 @@ pub struct State {
     pub timeout_target: Option<String>,
     #[serde(default)]
     pub required_resources: Vec<String>,
+    /// DAG node this state was exported from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dag: Option<DagNodeInfo>,
 }
 
 /// Event handler for state transitions
@@ -176,10 +180,56 @@ pub struct Handler {
     pub target: String,
     #[serde(default)]
     pub guards: Vec<String>,
+    /// DAG edge this handler was exported from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dag: Option<DagEdgeInfo>,
+}
+
+/// DAG node data that has no Swarm.toml equivalent
+///
+/// Written by the DAG exporter so that importing the file gives back the
+/// same node. Metadata values are stored as JSON text because TOML cannot
+/// represent every JSON value (e.g. null).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DagNodeInfo {
+    pub node_id: Uuid,
+    pub label: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<Uuid>,
+    /// The node had no description, so the state's is a generated placeholder
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub no_description: bool,
+    #[serde(default)]
+    pub x: f64,
+    #[serde(default)]
+    pub y: f64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Metadata the state's own fields don't reproduce on import
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+    /// Metadata keys the import derives from the state but the node didn't have
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub omit_metadata: Vec<String>,
+}
+
+/// DAG edge data that has no Swarm.toml equivalent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DagEdgeInfo {
+    pub edge_id: Uuid,
+    pub edge_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Metadata the handler's own fields don't reproduce on import
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+    /// Metadata keys the import derives from the handler but the edge didn't have
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub omit_metadata: Vec<String>,
 }
 
 /// Contract specification for state inputs/outputs
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Contract {
     pub name: Option<String>,
     pub description: Option<String>,
@@ -187,6 +237,57 @@ pub struct Contract {
     pub input: HashMap<String, String>,
     #[serde(default)]
     pub output: HashMap<String, String>,
+    /// Expressions that must hold before the state's handler runs
+    #[serde(default)]
+    pub preconditions: Vec<String>,
+    /// Expressions that must hold once the handler has produced its output
+    #[serde(default)]
+    pub postconditions: Vec<String>,
+}
+
+/// Category of a non-fatal lint warning
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwarmLintKind {
+    /// A non-terminal state with no handlers or timeout, so it can never be left
+    NoHandlers,
+    /// A state that cannot be reached from the initial state
+    UnreachableState,
+    /// A resource that no state in any workflow requires
+    UnusedResource,
+    /// A contract input that no other contract can produce
+    UnsatisfiableContract,
+}
+
+impl std::fmt::Display for SwarmLintKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SwarmLintKind::NoHandlers => write!(f, "no handlers"),
+            SwarmLintKind::UnreachableState => write!(f, "unreachable state"),
+            SwarmLintKind::UnusedResource => write!(f, "unused resource"),
+            SwarmLintKind::UnsatisfiableContract => write!(f, "unsatisfiable contract"),
+        }
+    }
+}
+
+/// A likely mistake that does not stop the workflow from validating
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwarmLintWarning {
+    pub kind: SwarmLintKind,
+    /// Workflow the warning was found in
+    pub workflow: String,
+    /// State, resource or contract the warning is about
+    pub subject: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for SwarmLintWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} (workflow {})",
+            self.kind, self.message, self.workflow
+        )
+    }
 }
 
 /// Parsed and validated workflow structure
@@ -199,6 +300,8 @@ pub struct ValidatedWorkflow {
     pub resources: HashMap<String, ResourceConfig>,
     pub guards: HashMap<String, String>,
     pub contracts: HashMap<String, Contract>,
+    /// Non-fatal problems found by [`SwarmParser::lint_workflow`]
+    pub warnings: Vec<SwarmLintWarning>,
 }
 
 /// Validated state with dependency information
@@ -375,9 +478,111 @@ impl SwarmParser {
             resources: config.resources.clone(),
             guards: workflow.guards.clone(),
             contracts: workflow.contracts.clone(),
+            warnings: self.lint_workflow(workflow, config),
         })
     }
 
+    /// Find likely mistakes in a workflow that validation lets through
+    ///
+    /// Warnings are sorted by kind, then subject.
+    pub fn lint_workflow(
+        &self,
+        workflow: &Workflow,
+        config: &SwarmConfig,
+    ) -> Vec<SwarmLintWarning> {
+        let mut warnings = Vec::new();
+        let warn = |kind, subject: &str, message: String| SwarmLintWarning {
+            kind,
+            workflow: workflow.name.clone(),
+            subject: subject.to_string(),
+            message,
+        };
+
+        for (name, state) in &workflow.states {
+            if !state.terminal && state.handlers.is_empty() && state.timeout_target.is_none() {
+                warnings.push(warn(
+                    SwarmLintKind::NoHandlers,
+                    name,
+                    format!("state '{}' is not terminal but has no handlers", name),
+                ));
+            }
+        }
+
+        let reachable = self.compute_reachable_states(workflow);
+        for name in workflow.states.keys() {
+            if !reachable.contains(name) {
+                warnings.push(warn(
+                    SwarmLintKind::UnreachableState,
+                    name,
+                    format!(
+                        "state '{}' is unreachable from '{}'",
+                        name, workflow.metadata.initial_state
+                    ),
+                ));
+            }
+        }
+
+        // Resources are shared, so a resource only counts as unused if no
+        // workflow in the file requires it
+        let used: HashSet<&str> = config
+            .workflows
+            .iter()
+            .flat_map(|w| w.states.values())
+            .flat_map(|s| s.required_resources.iter().map(String::as_str))
+            .collect();
+        for name in config.resources.keys() {
+            if !used.contains(name.as_str()) {
+                warnings.push(warn(
+                    SwarmLintKind::UnusedResource,
+                    name,
+                    format!("resource '{}' is not required by any state", name),
+                ));
+            }
+        }
+
+        // An input is fed by another contract's output of the same name; if
+        // such outputs exist but none has the right type, the input can never
+        // be satisfied
+        for (name, contract) in &workflow.contracts {
+            for (field, field_type) in &contract.input {
+                let producers: Vec<&String> = workflow
+                    .contracts
+                    .iter()
+                    .filter(|(other, _)| *other != name)
+                    .filter_map(|(_, other)| other.output.get(field))
+                    .collect();
+                if field_type.trim().is_empty() {
+                    warnings.push(warn(
+                        SwarmLintKind::UnsatisfiableContract,
+                        name,
+                        format!("contract '{}' input '{}' has no type", name, field),
+                    ));
+                } else if !producers.is_empty() && !producers.contains(&field_type) {
+                    warnings.push(warn(
+                        SwarmLintKind::UnsatisfiableContract,
+                        name,
+                        format!(
+                            "contract '{}' input '{}' expects {} but other contracts output {}",
+                            name,
+                            field,
+                            field_type,
+                            producers
+                                .iter()
+                                .map(|t| t.as_str())
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                    ));
+                }
+            }
+        }
+
+        warnings.sort_by(|a, b| {
+            (a.kind as u8, &a.subject, &a.message).cmp(&(b.kind as u8, &b.subject, &b.message))
+        });
+        warnings
+    }
+
     /// Validate a single state
     fn validate_state(
         &self,
@@ -962,4 +1167,67 @@ terminal = true
         // The impl is "impl TestState" not "impl TestWorkflowState"
         assert!(state_machine.contains("impl TestState"));
     }
+
+    #[test]
+    fn test_lint_warnings() {
+        let toml_content = r#"
+[metadata]
+version = "1.0"
+name = "Test"
+description = "Test"
+
+[agents]
+
+[resources.api]
+type = "http"
+endpoint = "https://example.com"
+
+[resources.db]
+type = "database"
+connection_string = "sqlite://test.db"
+
+[[workflows]]
+name = "test"
+description = "Test"
+
+[workflows.metadata]
+initial_state = "Start"
+
+[workflows.states.Start]
+description = "Start"
+required_resources = ["api"]
+handlers = [
+    { event = "done", target = "End" }
+]
+
+[workflows.states.End]
+description = "End"
+terminal = true
+
+[workflows.states.Orphan]
+description = "Never entered"
+handlers = [
+    { event = "done", target = "End" }
+]
+"#;
+
+        let parser = SwarmParser::new();
+        let config = parser.parse_string(toml_content).unwrap();
+        let validated = parser
+            .validate_workflow(&config.workflows[0], &config)
+            .unwrap();
+
+        let warnings: Vec<_> = validated
+            .warnings
+            .iter()
+            .map(|w| (w.kind, w.subject.as_str()))
+            .collect();
+        assert_eq!(
+            warnings,
+            vec![
+                (SwarmLintKind::UnreachableState, "Orphan"),
+                (SwarmLintKind::UnusedResource, "db"),
+            ]
+        );
+    }
 }
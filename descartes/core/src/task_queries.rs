@@ -5,8 +5,10 @@
 /// capabilities needed for Kanban views, task lists, and dependency management.
 use crate::errors::{StateStoreError, StateStoreResult};
 use crate::traits::{Task, TaskComplexity, TaskPriority, TaskStatus};
+use serde::{Deserialize, Serialize};
 use sqlx::sqlite::SqlitePool;
 use sqlx::Row;
+use std::cmp::Ordering;
 use std::str::FromStr;
 use uuid::Uuid;
 
@@ -55,6 +57,44 @@ pub enum TaskSortField {
     Status,
 }
 
+impl TaskSortField {
+    /// Compare two tasks by this field (ascending)
+    pub fn compare(&self, a: &Task, b: &Task) -> Ordering {
+        match self {
+            TaskSortField::CreatedAt => a.created_at.cmp(&b.created_at),
+            TaskSortField::UpdatedAt => a.updated_at.cmp(&b.updated_at),
+            TaskSortField::Priority => {
+                let pa: u8 = (&a.priority).into();
+                let pb: u8 = (&b.priority).into();
+                pa.cmp(&pb)
+            }
+            TaskSortField::Complexity => {
+                let ca: u32 = a.complexity.into();
+                let cb: u32 = b.complexity.into();
+                ca.cmp(&cb)
+            }
+            TaskSortField::Title => a.title.cmp(&b.title),
+            TaskSortField::Status => format!("{:?}", a.status).cmp(&format!("{:?}", b.status)),
+        }
+    }
+}
+
+impl FromStr for TaskSortField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "created_at" => Ok(TaskSortField::CreatedAt),
+            "updated_at" => Ok(TaskSortField::UpdatedAt),
+            "priority" => Ok(TaskSortField::Priority),
+            "complexity" => Ok(TaskSortField::Complexity),
+            "title" => Ok(TaskSortField::Title),
+            "status" => Ok(TaskSortField::Status),
+            _ => Err(format!("Unknown sort field: {}", s)),
+        }
+    }
+}
+
 /// Sort order for query results
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SortOrder {
@@ -62,6 +102,64 @@ pub enum SortOrder {
     Descending,
 }
 
+impl SortOrder {
+    /// Apply this order to an ascending comparison
+    pub fn apply(&self, ordering: Ordering) -> Ordering {
+        match self {
+            SortOrder::Ascending => ordering,
+            SortOrder::Descending => ordering.reverse(),
+        }
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "asc" | "ascending" => Ok(SortOrder::Ascending),
+            "desc" | "descending" => Ok(SortOrder::Descending),
+            _ => Err(format!("Unknown sort order: {}", s)),
+        }
+    }
+}
+
+/// One page of query results
+///
+/// Generic over the item type so RPC layers can page their own task
+/// summaries with the same shape.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskPage<T = Task> {
+    /// Tasks on this page
+    pub tasks: Vec<T>,
+
+    /// Number of tasks matching the filters across all pages
+    pub total_count: usize,
+
+    /// Offset of the first task on this page
+    pub offset: usize,
+
+    /// Page size that was requested, or None when every remaining task was returned
+    pub limit: Option<usize>,
+}
+
+impl<T> TaskPage<T> {
+    /// Whether more tasks follow this page
+    pub fn has_more(&self) -> bool {
+        self.offset + self.tasks.len() < self.total_count
+    }
+
+    /// Convert the tasks on this page, keeping the paging information
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> TaskPage<U> {
+        TaskPage {
+            tasks: self.tasks.into_iter().map(f).collect(),
+            total_count: self.total_count,
+            offset: self.offset,
+            limit: self.limit,
+        }
+    }
+}
+
 impl Default for TaskQueryBuilder {
     fn default() -> Self {
         Self {
@@ -170,12 +268,8 @@ impl TaskQueryBuilder {
         self
     }
 
-    /// Build the SQL query and parameters
-    fn build_query(&self) -> (String, Vec<String>) {
-        let mut query = String::from(
-            "SELECT id, title, description, status, priority, complexity, assigned_to, dependencies, created_at, updated_at, metadata FROM tasks"
-        );
-
+    /// Build the WHERE clause (with leading space, or empty) and its parameters
+    fn build_where(&self) -> (String, Vec<String>) {
         let mut where_clauses = Vec::new();
         let mut params = Vec::new();
 
@@ -242,10 +336,20 @@ impl TaskQueryBuilder {
         }
 
         // Add WHERE clause if needed
-        if !where_clauses.is_empty() {
-            query.push_str(" WHERE ");
-            query.push_str(&where_clauses.join(" AND "));
+        if where_clauses.is_empty() {
+            (String::new(), params)
+        } else {
+            (format!(" WHERE {}", where_clauses.join(" AND ")), params)
         }
+    }
+
+    /// Build the SQL query and parameters
+    fn build_query(&self) -> (String, Vec<String>) {
+        let (where_clause, params) = self.build_where();
+        let mut query = String::from(
+            "SELECT id, title, description, status, priority, complexity, assigned_to, dependencies, created_at, updated_at, metadata FROM tasks"
+        );
+        query.push_str(&where_clause);
 
         // Add ORDER BY clause
         let order_str = match self.sort_order {
@@ -291,6 +395,35 @@ impl TaskQueryBuilder {
 
         Ok(tasks)
     }
+
+    /// Count the tasks matching the filters, ignoring pagination
+    pub async fn count(&self, pool: &SqlitePool) -> StateStoreResult<i64> {
+        let (where_clause, params) = self.build_where();
+        let query_str = format!("SELECT COUNT(*) FROM tasks{}", where_clause);
+
+        let mut query = sqlx::query_scalar(&query_str);
+        for param in params {
+            query = query.bind(param);
+        }
+
+        query
+            .fetch_one(pool)
+            .await
+            .map_err(|e| StateStoreError::DatabaseError(format!("Failed to count tasks: {}", e)))
+    }
+
+    /// Execute the query and report the total match count alongside the page
+    pub async fn execute_page(&self, pool: &SqlitePool) -> StateStoreResult<TaskPage> {
+        let tasks = self.execute(pool).await?;
+        let total_count = self.count(pool).await?;
+
+        Ok(TaskPage {
+            tasks,
+            total_count: total_count.max(0) as usize,
+            offset: self.offset.max(0) as usize,
+            limit: Some(self.limit.max(0) as usize),
+        })
+    }
 }
 
 /// Parse a database row into a Task
@@ -910,6 +1043,51 @@ mod tests {
         assert_eq!(page2.len(), 5);
     }
 
+    #[tokio::test]
+    async fn test_execute_page_sorts_before_paginating() {
+        let (store, queries) = setup_test_db().await;
+
+        for i in 0..7 {
+            let task = Task {
+                id: Uuid::new_v4(),
+                title: format!("Task {}", i),
+                description: None,
+                status: if i % 2 == 0 {
+                    TaskStatus::Todo
+                } else {
+                    TaskStatus::Done
+                },
+                priority: TaskPriority::Medium,
+                complexity: TaskComplexity::Moderate,
+                assigned_to: None,
+                dependencies: vec![],
+                created_at: 1000 + i,
+                updated_at: 1000 + i,
+                metadata: None,
+            };
+            store.save_task(&task).await.expect("Failed to save task");
+        }
+
+        let page = queries
+            .query()
+            .with_status(TaskStatus::Todo)
+            .sort_by(TaskSortField::CreatedAt)
+            .order(SortOrder::Ascending)
+            .limit(2)
+            .offset(2)
+            .execute_page(queries.pool())
+            .await
+            .expect("Failed to get page");
+
+        assert_eq!(page.total_count, 4);
+        let titles: Vec<_> = page.tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Task 4", "Task 6"]);
+        assert!(!page.has_more());
+
+        assert_eq!("created_at".parse(), Ok(TaskSortField::CreatedAt));
+        assert_eq!("desc".parse(), Ok(SortOrder::Descending));
+    }
+
     #[tokio::test]
     async fn test_kanban_board() {
         let (store, queries) = setup_test_db().await;
@@ -102,6 +102,7 @@ impl ThoughtsConfig {
 ///
 /// Handles all operations related to persistent thought storage including
 /// directory initialization, thought persistence, and symlink management.
+#[derive(Clone)]
 pub struct ThoughtsStorage {
     config: ThoughtsConfig,
 }
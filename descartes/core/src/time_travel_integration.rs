@@ -30,10 +30,11 @@ use crate::agent_history::{
 };
 use crate::body_restore::{
     BodyRestoreManager, GitBodyRestoreManager, RepositoryBackup,
-    RestoreOptions as BodyRestoreOptions, RestoreResult as BodyRestoreResult,
+    RestoreOptions as BodyRestoreOptions, RestorePreview as BodyRestorePreview,
+    RestoreResult as BodyRestoreResult,
 };
 use crate::brain_restore::{
-    BrainRestore, BrainState, DefaultBrainRestore,
+    compare_states, BrainRestore, BrainState, DefaultBrainRestore,
     RestoreOptions as BrainRestoreOptions, RestoreResult as BrainRestoreResult,
 };
 use crate::debugger::{Breakpoint, BreakpointLocation, Debugger};
@@ -51,6 +52,43 @@ use uuid::Uuid;
 // DATA STRUCTURES
 // ============================================================================
 
+/// Which history events the timeline slider can land on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RewindGranularity {
+    /// Every recorded event
+    #[default]
+    Event,
+
+    /// Only conversation messages
+    Message,
+
+    /// Only tool calls
+    ToolCall,
+
+    /// Only events that captured a git commit (body snapshots)
+    Snapshot,
+
+    /// Only named `HistorySnapshot`s (those with a description)
+    ///
+    /// Snapshots are stored outside the event stream, so no event matches;
+    /// the slider picks from the snapshots passed to
+    /// [`slider_to_rewind_point_with_granularity`] instead.
+    NamedSnapshot,
+}
+
+impl RewindGranularity {
+    /// Whether an event is a valid rewind target at this granularity
+    pub fn includes(&self, event: &AgentHistoryEvent) -> bool {
+        match self {
+            RewindGranularity::Event => true,
+            RewindGranularity::Message => event.event_type == HistoryEventType::Communication,
+            RewindGranularity::ToolCall => event.event_type == HistoryEventType::ToolUse,
+            RewindGranularity::Snapshot => event.git_commit_hash.is_some(),
+            RewindGranularity::NamedSnapshot => false,
+        }
+    }
+}
+
 /// Configuration for rewind operations
 #[derive(Debug, Clone)]
 pub struct RewindConfig {
@@ -71,6 +109,9 @@ pub struct RewindConfig {
 
     /// Whether to enable debugging at rewound state
     pub enable_debugging: bool,
+
+    /// Which events the timeline slider can rewind to
+    pub granularity: RewindGranularity,
 }
 
 impl Default for RewindConfig {
@@ -82,6 +123,7 @@ impl Default for RewindConfig {
             allow_uncommitted_changes: false,
             max_undo_history: 10,
             enable_debugging: true,
+            granularity: RewindGranularity::default(),
         }
     }
 }
@@ -101,8 +143,15 @@ impl RewindConfig {
             allow_uncommitted_changes: true,
             max_undo_history: 5,
             enable_debugging: false,
+            granularity: RewindGranularity::default(),
         }
     }
+
+    /// Set the slider granularity
+    pub fn with_granularity(mut self, granularity: RewindGranularity) -> Self {
+        self.granularity = granularity;
+        self
+    }
 }
 
 /// Point in time to rewind to
@@ -198,6 +247,32 @@ impl RewindPoint {
             .map(|event| Self::from_event(event, Some(index)))
     }
 
+    /// Create from slider position (0.0 to 1.0), spreading the slider over
+    /// only the events included at `granularity`
+    ///
+    /// The resulting `event_index` refers to the position in `events`.
+    pub fn from_slider_position_with_granularity(
+        position: f32,
+        events: &[AgentHistoryEvent],
+        granularity: RewindGranularity,
+    ) -> Option<Self> {
+        let candidates: Vec<usize> = events
+            .iter()
+            .enumerate()
+            .filter(|(_, event)| granularity.includes(event))
+            .map(|(index, _)| index)
+            .collect();
+
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let position = position.clamp(0.0, 1.0);
+        let slot = (position * (candidates.len() - 1) as f32) as usize;
+        let index = candidates[slot];
+        Some(Self::from_event(&events[index], Some(index)))
+    }
+
     /// Set the agent ID for this point
     pub fn with_agent_id(mut self, agent_id: String) -> Self {
         self.agent_id = Some(agent_id);
@@ -532,6 +607,38 @@ impl RewindConfirmation {
     }
 }
 
+/// Dry-run preview of a rewind, computed without touching brain or body
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RewindPreview {
+    /// Target point
+    pub target: RewindPoint,
+
+    /// Files that would change when the repository moves to the target commit
+    pub body: BodyRestorePreview,
+
+    /// Brain state that would be restored
+    pub brain_state: Option<BrainState>,
+
+    /// Differences between the current and restored brain states
+    pub brain_differences: Vec<String>,
+
+    /// Events replayed to build the restored brain state
+    pub events_restored: usize,
+
+    /// Events recorded after the target point
+    pub events_after_target: usize,
+
+    /// Whether there are uncommitted changes
+    pub has_uncommitted_changes: bool,
+
+    /// Reasons the rewind may not be safe
+    pub warnings: Vec<String>,
+
+    /// Whether the rewind can proceed without stashing changes and the
+    /// restored brain state is valid
+    pub safe: bool,
+}
+
 // ============================================================================
 // TRAIT DEFINITION
 // ============================================================================
@@ -542,6 +649,9 @@ pub trait RewindManager: Send + Sync {
     /// Check if rewind is possible to a given point
     async fn can_rewind_to(&self, point: &RewindPoint) -> StateStoreResult<RewindConfirmation>;
 
+    /// Preview what a rewind would change without performing it
+    async fn preview(&self, point: &RewindPoint) -> StateStoreResult<RewindPreview>;
+
     /// Rewind to a specific point in time
     async fn rewind_to(
         &self,
@@ -742,6 +852,72 @@ impl<S: AgentHistoryStore + 'static> RewindManager for DefaultRewindManager<S> {
         Ok(confirmation)
     }
 
+    async fn preview(&self, point: &RewindPoint) -> StateStoreResult<RewindPreview> {
+        let agent_id = point.agent_id.as_deref().ok_or_else(|| {
+            StateStoreError::Conflict("RewindPoint must have agent_id set to preview".to_string())
+        })?;
+
+        // Replay both timelines in memory; nothing is persisted
+        let all_events = self
+            .brain_restore
+            .load_events_until(agent_id, i64::MAX)
+            .await?;
+        let target_events = self.load_events_for_point(agent_id, point).await?;
+        let events_restored = target_events.len();
+        let events_after_target = all_events.len().saturating_sub(events_restored);
+
+        let current = self
+            .brain_restore
+            .replay_events(all_events, BrainRestoreOptions::default())
+            .await?;
+        let target = self
+            .brain_restore
+            .replay_events(
+                target_events,
+                BrainRestoreOptions {
+                    validate: true,
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        let brain_differences = match (&current.brain_state, &target.brain_state) {
+            (Some(current), Some(target)) => compare_states(current, target),
+            _ => Vec::new(),
+        };
+
+        let target_commit = match &point.git_commit {
+            Some(commit) => commit.clone(),
+            None => self.find_commit_at_timestamp(point.timestamp).await?,
+        };
+        let body = self.body_restore.preview(&target_commit).await?;
+
+        let has_uncommitted_changes = self.body_restore.has_uncommitted_changes().await?;
+        let mut warnings = Vec::new();
+        if has_uncommitted_changes {
+            warnings.push("Repository has uncommitted changes that will be stashed".to_string());
+        }
+        if !target.success {
+            warnings.push(format!(
+                "Restored brain state is invalid: {:?}",
+                target.validation_errors
+            ));
+        }
+        warnings.extend(target.warnings);
+
+        Ok(RewindPreview {
+            target: point.clone(),
+            body,
+            brain_state: target.brain_state,
+            brain_differences,
+            events_restored,
+            events_after_target,
+            has_uncommitted_changes,
+            safe: !has_uncommitted_changes && target.success,
+            warnings,
+        })
+    }
+
     async fn rewind_to(
         &self,
         point: RewindPoint,
@@ -830,6 +1006,8 @@ impl<S: AgentHistoryStore + 'static> RewindManager for DefaultRewindManager<S> {
             create_backup: false, // We already created a backup
             force: config.allow_uncommitted_changes,
             preserve_untracked: true,
+            agent_id: Some(agent_id.clone()),
+            ..Default::default()
         };
 
         let body_result = self
@@ -1120,6 +1298,36 @@ pub fn slider_to_rewind_point(position: f32, events: &[AgentHistoryEvent]) -> Op
     RewindPoint::from_slider_position(position, events)
 }
 
+/// Convert slider position (0.0 to 1.0) to rewind point at the given granularity
+///
+/// [`RewindGranularity::NamedSnapshot`] spreads the slider over the named
+/// `snapshots` in time order; every other granularity picks from `events`.
+pub fn slider_to_rewind_point_with_granularity(
+    position: f32,
+    events: &[AgentHistoryEvent],
+    snapshots: &[HistorySnapshot],
+    granularity: RewindGranularity,
+) -> Option<RewindPoint> {
+    if granularity != RewindGranularity::NamedSnapshot {
+        return RewindPoint::from_slider_position_with_granularity(position, events, granularity);
+    }
+
+    let mut named: Vec<(&HistorySnapshot, &str)> = snapshots
+        .iter()
+        .filter_map(|s| s.description.as_deref().map(|name| (s, name)))
+        .collect();
+    if named.is_empty() {
+        return None;
+    }
+    named.sort_by_key(|(s, _)| s.timestamp);
+
+    let position = position.clamp(0.0, 1.0);
+    let (snapshot, name) = named[(position * (named.len() - 1) as f32) as usize];
+    let mut point = RewindPoint::from_snapshot(snapshot);
+    point.description = format!("Snapshot: {}", name);
+    Some(point)
+}
+
 /// Get user-friendly description of rewind operation
 pub fn describe_rewind(from: &RewindPoint, to: &RewindPoint) -> String {
     let time_diff = to.timestamp - from.timestamp;
@@ -1232,6 +1440,85 @@ mod tests {
         assert_eq!(point.event_index, Some(1));
     }
 
+    #[test]
+    fn test_slider_honors_granularity() {
+        let events = vec![
+            AgentHistoryEvent::new(
+                "agent-1".to_string(),
+                HistoryEventType::Communication,
+                json!({"role": "user", "content": "hi"}),
+            ),
+            AgentHistoryEvent::new(
+                "agent-1".to_string(),
+                HistoryEventType::ToolUse,
+                json!({"tool": "read"}),
+            ),
+            AgentHistoryEvent::new(
+                "agent-1".to_string(),
+                HistoryEventType::Thought,
+                json!({"content": "thinking"}),
+            )
+            .with_git_commit("abc123".to_string()),
+            AgentHistoryEvent::new(
+                "agent-1".to_string(),
+                HistoryEventType::ToolUse,
+                json!({"tool": "write"}),
+            ),
+            AgentHistoryEvent::new(
+                "agent-1".to_string(),
+                HistoryEventType::Communication,
+                json!({"role": "assistant", "content": "done"}),
+            ),
+        ];
+
+        let at = |position: f32, granularity| {
+            slider_to_rewind_point_with_granularity(position, &events, &[], granularity)
+                .and_then(|p| p.event_index)
+        };
+
+        assert_eq!(at(0.5, RewindGranularity::Event), Some(2));
+        assert_eq!(at(0.0, RewindGranularity::Message), Some(0));
+        assert_eq!(at(1.0, RewindGranularity::Message), Some(4));
+        assert_eq!(at(0.0, RewindGranularity::ToolCall), Some(1));
+        assert_eq!(at(1.0, RewindGranularity::ToolCall), Some(3));
+        assert_eq!(at(0.0, RewindGranularity::Snapshot), Some(2));
+        assert_eq!(at(1.0, RewindGranularity::Snapshot), Some(2));
+
+        // No matching events means no rewind point
+        assert!(slider_to_rewind_point_with_granularity(
+            0.5,
+            &events[..2],
+            &[],
+            RewindGranularity::Snapshot
+        )
+        .is_none());
+
+        // Named snapshots are picked in time order, skipping unnamed ones
+        let snapshot = |timestamp: i64, name: Option<&str>| {
+            let mut snapshot = HistorySnapshot::new("agent-1".to_string(), vec![], None);
+            snapshot.timestamp = timestamp;
+            snapshot.description = name.map(str::to_string);
+            snapshot
+        };
+        let snapshots = vec![
+            snapshot(300, Some("after refactor")),
+            snapshot(100, Some("baseline")),
+            snapshot(200, None),
+        ];
+        let named = |position: f32| {
+            slider_to_rewind_point_with_granularity(
+                position,
+                &events,
+                &snapshots,
+                RewindGranularity::NamedSnapshot,
+            )
+            .map(|p| p.description)
+        };
+        assert_eq!(named(0.0).as_deref(), Some("Snapshot: baseline"));
+        assert_eq!(named(1.0).as_deref(), Some("Snapshot: after refactor"));
+        assert!(at(0.5, RewindGranularity::NamedSnapshot).is_none());
+    }
+
     #[tokio::test]
     async fn test_get_rewind_points() {
         let (store, _temp_file) = create_test_store().await;
@@ -1262,6 +1549,94 @@ mod tests {
         assert!(!points.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_preview_rewind_to_snapshot() {
+        let (store, _temp_file) = create_test_store().await;
+        let (_temp, repo_path) = create_test_repo();
+        let git = |args: &[&str]| {
+            let output = Command::new("git")
+                .args(args)
+                .current_dir(&repo_path)
+                .output()
+                .unwrap();
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        };
+        let first_commit = git(&["rev-parse", "HEAD"]);
+        std::fs::write(repo_path.join("test.txt"), "changed content\n").unwrap();
+        git(&["commit", "-am", "Second commit"]);
+        let second_commit = git(&["rev-parse", "HEAD"]);
+
+        // Two thoughts before the first snapshot, a decision and a thought after
+        let base = chrono::Utc::now().timestamp() - 100;
+        let events: Vec<_> = [
+            (
+                HistoryEventType::Thought,
+                json!({"content": "read the code"}),
+            ),
+            (HistoryEventType::Thought, json!({"content": "plan a fix"})),
+            (HistoryEventType::Decision, json!({"decision_type": "edit"})),
+            (HistoryEventType::Thought, json!({"content": "fix applied"})),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, (event_type, data))| {
+            let mut event = AgentHistoryEvent::new("agent-1".to_string(), event_type, data);
+            event.timestamp = base + 10 * i as i64;
+            event
+        })
+        .collect();
+        for event in &events {
+            store.record_event(event).await.unwrap();
+        }
+
+        let snapshot = |events: &[AgentHistoryEvent], commit: &str| {
+            let mut snapshot = HistorySnapshot::new(
+                "agent-1".to_string(),
+                events.to_vec(),
+                Some(commit.to_string()),
+            );
+            snapshot.timestamp = events.last().unwrap().timestamp;
+            snapshot
+        };
+        let early = snapshot(&events[..2], &first_commit);
+        store.create_snapshot(&early).await.unwrap();
+        store
+            .create_snapshot(&snapshot(&events, &second_commit))
+            .await
+            .unwrap();
+
+        let manager = DefaultRewindManager::new(store, repo_path.clone(), 10).unwrap();
+        let preview = manager
+            .preview(&RewindPoint::from_snapshot(&early))
+            .await
+            .unwrap();
+
+        assert_eq!(preview.body.current_commit, second_commit);
+        assert_eq!(preview.body.target_commit, first_commit);
+        assert_eq!(preview.body.files.len(), 1);
+        assert_eq!(preview.body.files[0].path, "test.txt");
+        assert_eq!(preview.events_restored, 2);
+        assert_eq!(preview.events_after_target, 2);
+        let brain_state = preview.brain_state.as_ref().unwrap();
+        assert_eq!(brain_state.thought_history.len(), 2);
+        assert!(brain_state.decision_tree.is_empty());
+        assert!(preview
+            .brain_differences
+            .iter()
+            .any(|d| d.contains("Thought history length differs: 3 vs 2")));
+        // The working tree is clean and the brain state valid
+        assert!(!preview.has_uncommitted_changes);
+        assert!(preview.safe);
+        assert!(preview.warnings.is_empty());
+
+        // Nothing was rewound
+        assert_eq!(git(&["rev-parse", "HEAD"]), second_commit);
+        assert_eq!(
+            std::fs::read_to_string(repo_path.join("test.txt")).unwrap(),
+            "changed content\n"
+        );
+    }
+
     #[tokio::test]
     async fn test_validation_result() {
         let success = ValidationResult::success();
@@ -0,0 +1,289 @@
+//! Pluggable token counting
+//!
+//! Budgets (conversation windows, loop spec limits, provider estimates) need a
+//! token count, and the right answer depends on the model. A [`Tokenizer`]
+//! counts tokens for one family of models; the [`TokenizerRegistry`] picks one
+//! by model name:
+//!
+//! 1. Tokenizers registered with [`TokenizerRegistry::register`], longest
+//!    matching prefix first
+//! 2. The exact BPE encoding for OpenAI-family models (via tiktoken)
+//! 3. A characters-per-token heuristic tuned per provider
+//!
+//! Most callers use the process-wide registry through [`count_tokens`] and
+//! [`tokenizer_for_model`].
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tiktoken_rs::tokenizer::{get_tokenizer, Tokenizer as TiktokenEncoding};
+use tiktoken_rs::CoreBPE;
+
+/// Counts tokens for a family of models
+pub trait Tokenizer: Send + Sync {
+    /// Identifier shown in diagnostics (e.g. "cl100k_base")
+    fn name(&self) -> &str;
+
+    /// Number of tokens `text` encodes to
+    fn count_tokens(&self, text: &str) -> usize;
+}
+
+/// Exact BPE token counts for OpenAI-family models
+pub struct TiktokenTokenizer {
+    name: String,
+    bpe: CoreBPE,
+}
+
+impl TiktokenTokenizer {
+    /// Tokenizer for an OpenAI model name, if tiktoken knows it
+    pub fn for_model(model: &str) -> Option<Self> {
+        get_tokenizer(model).and_then(Self::for_encoding)
+    }
+
+    /// The cl100k_base encoding used by GPT-4 and GPT-3.5
+    pub fn cl100k() -> Self {
+        Self::for_encoding(TiktokenEncoding::Cl100kBase).expect("cl100k_base is bundled")
+    }
+
+    fn for_encoding(encoding: TiktokenEncoding) -> Option<Self> {
+        let bpe = tiktoken_rs::get_bpe_from_tokenizer(encoding).ok()?;
+        Some(Self {
+            name: encoding_name(encoding).to_string(),
+            bpe,
+        })
+    }
+}
+
+impl Tokenizer for TiktokenTokenizer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn count_tokens(&self, text: &str) -> usize {
+        self.bpe.encode_with_special_tokens(text).len()
+    }
+}
+
+impl std::fmt::Debug for TiktokenTokenizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TiktokenTokenizer")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+fn encoding_name(encoding: TiktokenEncoding) -> &'static str {
+    match encoding {
+        TiktokenEncoding::O200kBase => "o200k_base",
+        TiktokenEncoding::Cl100kBase => "cl100k_base",
+        TiktokenEncoding::P50kBase => "p50k_base",
+        TiktokenEncoding::R50kBase => "r50k_base",
+        TiktokenEncoding::P50kEdit => "p50k_edit",
+        TiktokenEncoding::Gpt2 => "gpt2",
+    }
+}
+
+/// Approximate counts from a fixed characters-per-token ratio
+#[derive(Debug, Clone)]
+pub struct HeuristicTokenizer {
+    name: String,
+    chars_per_token: usize,
+}
+
+impl HeuristicTokenizer {
+    /// Heuristic counting one token per `chars_per_token` bytes (minimum 1)
+    pub fn new(chars_per_token: usize) -> Self {
+        let chars_per_token = chars_per_token.max(1);
+        Self {
+            name: format!("heuristic-{}", chars_per_token),
+            chars_per_token,
+        }
+    }
+}
+
+impl Default for HeuristicTokenizer {
+    /// ~4 characters per token, a reasonable default for English text and code
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+impl Tokenizer for HeuristicTokenizer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn count_tokens(&self, text: &str) -> usize {
+        text.len().div_ceil(self.chars_per_token)
+    }
+}
+
+/// Selects a [`Tokenizer`] by model name
+pub struct TokenizerRegistry {
+    custom: Vec<(String, Arc<dyn Tokenizer>)>,
+    tiktoken: HashMap<TiktokenEncoding, Arc<dyn Tokenizer>>,
+    claude: Arc<dyn Tokenizer>,
+    fallback: Arc<dyn Tokenizer>,
+}
+
+impl TokenizerRegistry {
+    /// Registry with the built-in tokenizers only
+    pub fn new() -> Self {
+        Self {
+            custom: Vec::new(),
+            tiktoken: HashMap::new(),
+            // Matches the estimate AnthropicProvider has always used
+            claude: Arc::new(HeuristicTokenizer::new(3)),
+            fallback: Arc::new(HeuristicTokenizer::default()),
+        }
+    }
+
+    /// Use `tokenizer` for every model whose name starts with `model_prefix`
+    ///
+    /// Registering the same prefix again replaces the earlier tokenizer.
+    pub fn register(&mut self, model_prefix: impl Into<String>, tokenizer: Arc<dyn Tokenizer>) {
+        let model_prefix = model_prefix.into();
+        self.custom.retain(|(prefix, _)| *prefix != model_prefix);
+        self.custom.push((model_prefix, tokenizer));
+        // Longest prefix wins
+        self.custom
+            .sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+    }
+
+    /// Tokenizer to use for `model`
+    pub fn for_model(&mut self, model: &str) -> Arc<dyn Tokenizer> {
+        if let Some(tokenizer) = self.cached_for_model(model) {
+            return tokenizer;
+        }
+
+        // Only a tiktoken encoding that hasn't been loaded yet gets here
+        let encoding = get_tokenizer(model);
+        if let Some((encoding, tokenizer)) =
+            encoding.and_then(|e| Some((e, TiktokenTokenizer::for_encoding(e)?)))
+        {
+            let tokenizer: Arc<dyn Tokenizer> = Arc::new(tokenizer);
+            self.tiktoken.insert(encoding, tokenizer.clone());
+            return tokenizer;
+        }
+
+        self.fallback_for_model(model)
+    }
+
+    /// Tokenizer for `model` if choosing it doesn't require loading a BPE
+    /// encoding, so lookups can share a read lock
+    pub fn cached_for_model(&self, model: &str) -> Option<Arc<dyn Tokenizer>> {
+        if let Some((_, tokenizer)) = self
+            .custom
+            .iter()
+            .find(|(prefix, _)| model.starts_with(prefix.as_str()))
+        {
+            return Some(tokenizer.clone());
+        }
+
+        match get_tokenizer(model) {
+            Some(encoding) => self.tiktoken.get(&encoding).cloned(),
+            None => Some(self.fallback_for_model(model)),
+        }
+    }
+
+    fn fallback_for_model(&self, model: &str) -> Arc<dyn Tokenizer> {
+        if model.starts_with("claude") {
+            return self.claude.clone();
+        }
+
+        self.fallback.clone()
+    }
+}
+
+impl Default for TokenizerRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static GLOBAL_REGISTRY: Lazy<RwLock<TokenizerRegistry>> =
+    Lazy::new(|| RwLock::new(TokenizerRegistry::new()));
+
+/// Register a custom tokenizer with the process-wide registry
+pub fn register_tokenizer(model_prefix: impl Into<String>, tokenizer: Arc<dyn Tokenizer>) {
+    GLOBAL_REGISTRY.write().register(model_prefix, tokenizer);
+}
+
+/// Tokenizer the process-wide registry selects for `model`
+pub fn tokenizer_for_model(model: &str) -> Arc<dyn Tokenizer> {
+    // The write lock is only needed the first time an encoding is loaded
+    if let Some(tokenizer) = GLOBAL_REGISTRY.read().cached_for_model(model) {
+        return tokenizer;
+    }
+    GLOBAL_REGISTRY.write().for_model(model)
+}
+
+/// Count the tokens in `text` as `model` would
+pub fn count_tokens(model: &str, text: &str) -> usize {
+    tokenizer_for_model(model).count_tokens(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct WordTokenizer;
+
+    impl Tokenizer for WordTokenizer {
+        fn name(&self) -> &str {
+            "words"
+        }
+
+        fn count_tokens(&self, text: &str) -> usize {
+            text.split_whitespace().count()
+        }
+    }
+
+    #[test]
+    fn test_openai_tokenizer_matches_known_counts() {
+        let tokenizer = TiktokenTokenizer::cl100k();
+        assert_eq!(tokenizer.count_tokens("hello world"), 2);
+        assert_eq!(tokenizer.count_tokens("tiktoken is great!"), 6);
+        assert_eq!(tokenizer.count_tokens(""), 0);
+    }
+
+    #[test]
+    fn test_registry_dispatches_by_model() {
+        let mut registry = TokenizerRegistry::new();
+
+        assert_eq!(registry.for_model("gpt-4").name(), "cl100k_base");
+        assert_eq!(registry.for_model("gpt-4o").name(), "o200k_base");
+        assert_eq!(
+            registry.for_model("claude-3-5-sonnet").name(),
+            "heuristic-3"
+        );
+        assert_eq!(registry.for_model("llama3").name(), "heuristic-4");
+
+        registry.register("llama", Arc::new(WordTokenizer));
+        registry.register("llama3-special", Arc::new(HeuristicTokenizer::new(2)));
+        assert_eq!(registry.for_model("llama3").name(), "words");
+        assert_eq!(
+            registry.for_model("llama3-special-8b").name(),
+            "heuristic-2"
+        );
+        assert_eq!(registry.for_model("llama3").count_tokens("a b c"), 3);
+    }
+
+    #[test]
+    fn test_cached_lookup_only_misses_unloaded_encodings() {
+        let mut registry = TokenizerRegistry::new();
+
+        assert!(registry.cached_for_model("gpt-4").is_none());
+        assert_eq!(registry.for_model("gpt-4").name(), "cl100k_base");
+        // Same encoding, now served without mutating the registry
+        assert_eq!(
+            registry.cached_for_model("gpt-3.5-turbo").unwrap().name(),
+            "cl100k_base"
+        );
+        assert_eq!(
+            registry.cached_for_model("claude-3-opus").unwrap().name(),
+            "heuristic-3"
+        );
+    }
+}
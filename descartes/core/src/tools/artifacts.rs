@@ -0,0 +1,215 @@
+//! Per-run artifact collection.
+//!
+//! Files an agent writes or edits are registered as [`Artifact`]s so each run
+//! leaves a manifest of what it produced. Attach an [`ArtifactCollector`] to
+//! the [`ExecutionContext`](crate::tools::ExecutionContext) and the `write`
+//! and `edit` tools register their files automatically, optionally copying
+//! them into `.descartes/artifacts/<session_id>/`.
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Artifact directory, relative to the project root.
+pub const ARTIFACTS_DIR: &str = ".descartes/artifacts";
+
+/// Name of the manifest written into each run's artifact directory.
+pub const ARTIFACT_MANIFEST: &str = "manifest.json";
+
+/// A file produced or modified during a run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Artifact {
+    /// Path the tool wrote to
+    pub path: PathBuf,
+    /// Tool that last touched the file ("write", "edit")
+    pub tool: String,
+    /// Copy inside the artifact directory, if copying is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub copied_to: Option<PathBuf>,
+    /// When the file was last registered
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Collects the artifacts of one run.
+///
+/// Clones share the same list. Registering a path again (e.g. an edit after
+/// a write) updates its entry rather than adding a second one.
+#[derive(Clone, Debug)]
+pub struct ArtifactCollector {
+    dir: PathBuf,
+    copy_files: bool,
+    artifacts: Arc<Mutex<Vec<Artifact>>>,
+}
+
+impl ArtifactCollector {
+    /// Collect into `dir`, recording paths only.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            copy_files: false,
+            artifacts: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Collect into `.descartes/artifacts/<session_id>/` under `project_root`.
+    pub fn for_session(project_root: &Path, session_id: Uuid) -> Self {
+        Self::new(
+            project_root
+                .join(ARTIFACTS_DIR)
+                .join(session_id.to_string()),
+        )
+    }
+
+    /// Also copy each registered file into the artifact directory.
+    pub fn with_copies(mut self) -> Self {
+        self.copy_files = true;
+        self
+    }
+
+    /// The run's artifact directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Register a file written by `tool`.
+    ///
+    /// `working_dir` decides where the copy goes: files inside it keep their
+    /// relative path, others are copied by file name.
+    pub fn record(&self, tool: &str, path: &Path, working_dir: &Path) -> Artifact {
+        let copied_to = if self.copy_files {
+            self.copy_into_dir(path, working_dir)
+        } else {
+            None
+        };
+
+        let artifact = Artifact {
+            path: path.to_path_buf(),
+            tool: tool.to_string(),
+            copied_to,
+            recorded_at: Utc::now(),
+        };
+
+        let mut artifacts = self.artifacts.lock();
+        match artifacts.iter_mut().find(|a| a.path == artifact.path) {
+            Some(existing) => *existing = artifact.clone(),
+            None => artifacts.push(artifact.clone()),
+        }
+        artifact
+    }
+
+    /// Artifacts registered so far, in first-registered order.
+    pub fn artifacts(&self) -> Vec<Artifact> {
+        self.artifacts.lock().clone()
+    }
+
+    /// Write the manifest into the artifact directory, returning its path.
+    pub fn write_manifest(&self) -> std::io::Result<PathBuf> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(ARTIFACT_MANIFEST);
+        let json = serde_json::to_string_pretty(&self.artifacts())?;
+        fs::write(&path, json)?;
+        Ok(path)
+    }
+
+    fn copy_into_dir(&self, path: &Path, working_dir: &Path) -> Option<PathBuf> {
+        let relative = match path.strip_prefix(working_dir) {
+            // Keep the layout, but never let ".." escape the artifact directory
+            Ok(rel) if rel.components().all(|c| matches!(c, Component::Normal(_))) => {
+                rel.to_path_buf()
+            }
+            _ => PathBuf::from(path.file_name()?),
+        };
+        let dest = self.dir.join(relative);
+
+        let copied = dest
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::copy(path, &dest));
+        match copied {
+            Ok(_) => Some(dest),
+            Err(e) => {
+                warn!("Failed to copy artifact {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::executors::execute_tool;
+    use crate::tools::ExecutionContext;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_written_files_are_registered_as_artifacts() {
+        let project = TempDir::new().unwrap();
+        let session_id = Uuid::new_v4();
+        let collector = ArtifactCollector::for_session(project.path(), session_id).with_copies();
+        let ctx = ExecutionContext::for_agent(session_id).with_artifacts(collector.clone());
+
+        let write = |path: &str, content: &str| {
+            execute_tool(
+                "write",
+                &json!({"path": path, "content": content}),
+                project.path(),
+                None,
+                Some(&ctx),
+            )
+        };
+        let result = write("src/lib.rs", "fn a() {}");
+        assert!(result.success);
+        write("README.md", "hello");
+        let edit = execute_tool(
+            "edit",
+            &json!({"path": "src/lib.rs", "old_text": "a()", "new_text": "b()"}),
+            project.path(),
+            None,
+            Some(&ctx),
+        );
+        assert!(edit.success);
+        // Reads don't produce artifacts
+        execute_tool(
+            "read",
+            &json!({"path": "README.md"}),
+            project.path(),
+            None,
+            Some(&ctx),
+        );
+
+        let artifacts = collector.artifacts();
+        let paths: Vec<_> = artifacts.iter().map(|a| a.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                project.path().join("src/lib.rs"),
+                project.path().join("README.md")
+            ]
+        );
+        assert_eq!(artifacts[0].tool, "edit");
+
+        let run_dir = project
+            .path()
+            .join(ARTIFACTS_DIR)
+            .join(session_id.to_string());
+        let copy = artifacts[0].copied_to.clone().unwrap();
+        assert_eq!(copy, run_dir.join("src/lib.rs"));
+        assert_eq!(fs::read_to_string(copy).unwrap(), "fn b() {}");
+        assert_eq!(
+            result.metadata.unwrap()["artifact_path"],
+            run_dir.join("src/lib.rs").display().to_string()
+        );
+
+        let manifest = collector.write_manifest().unwrap();
+        let saved: Vec<Artifact> =
+            serde_json::from_str(&fs::read_to_string(manifest).unwrap()).unwrap();
+        assert_eq!(saved, artifacts);
+    }
+}
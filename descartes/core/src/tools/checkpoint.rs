@@ -0,0 +1,382 @@
+//! Safe checkpoints before risky tool calls.
+//!
+//! Before a tool call that is hard to undo (destructive bash, git history
+//! rewrites, file writes and edits), a snapshot is requested through a
+//! [`CheckpointHook`] so the user can always rewind to "just before the agent
+//! did X". The default hook is backed by a [`RewindManager`].
+//!
+//! Attach a [`ToolCheckpoints`] to the [`ExecutionContext`] and
+//! [`execute_tool_async`] takes these checkpoints automatically.
+
+use async_trait::async_trait;
+use serde_json::Value;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::time_travel_integration::RewindManager;
+use crate::tools::context::ExecutionContext;
+use crate::tools::executors::{dispatch_tool_async, ToolResult};
+
+/// Tools that trigger a checkpoint by default.
+pub const DEFAULT_RISKY_TOOLS: &[&str] = &["write", "edit"];
+
+/// Bash command prefixes treated as risky by default.
+pub const DEFAULT_RISKY_BASH_PATTERNS: &[&str] = &[
+    "rm",
+    "rmdir",
+    "mv",
+    "dd",
+    "mkfs",
+    "truncate",
+    "shred",
+    "git reset",
+    "git checkout",
+    "git clean",
+    "git rebase",
+    "git merge",
+    "git push",
+    "git stash",
+    "git restore",
+    "git switch",
+    "git branch -D",
+];
+
+/// Which tool calls trigger a safe checkpoint.
+#[derive(Debug, Clone)]
+pub struct CheckpointConfig {
+    /// Whether checkpoints are taken at all
+    pub enabled: bool,
+    /// Tools that always trigger a checkpoint
+    pub risky_tools: Vec<String>,
+    /// Bash commands that trigger a checkpoint, matched as whole words
+    /// anywhere in the command (e.g. "rm" matches `cd x && rm -rf y`)
+    pub risky_bash_patterns: Vec<String>,
+    /// Refuse to run the tool if the checkpoint could not be created
+    pub require_checkpoint: bool,
+}
+
+impl Default for CheckpointConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            risky_tools: DEFAULT_RISKY_TOOLS.iter().map(|t| t.to_string()).collect(),
+            risky_bash_patterns: DEFAULT_RISKY_BASH_PATTERNS
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+            require_checkpoint: false,
+        }
+    }
+}
+
+impl CheckpointConfig {
+    /// Describe the operation if the tool call is risky, or `None` if it is not.
+    pub fn classify(&self, tool: &str, args: &Value) -> Option<String> {
+        if !self.enabled {
+            return None;
+        }
+
+        if self.risky_tools.iter().any(|t| t == tool) {
+            let target = args
+                .get("path")
+                .and_then(|v| v.as_str())
+                .unwrap_or("<unknown>");
+            return Some(format!("{}: {}", tool, target));
+        }
+
+        if tool == "bash" {
+            let command = args.get("command").and_then(|v| v.as_str())?;
+            // Split on whitespace and shell separators so patterns match whole words
+            let normalized = format!(
+                " {} ",
+                command
+                    .split(|c: char| c.is_whitespace() || matches!(c, ';' | '|' | '&' | '(' | ')'))
+                    .filter(|w| !w.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            );
+            if self
+                .risky_bash_patterns
+                .iter()
+                .any(|p| normalized.contains(&format!(" {} ", p.trim())))
+            {
+                return Some(format!("bash: {}", command));
+            }
+        }
+
+        None
+    }
+}
+
+/// Creates a snapshot before a risky operation.
+#[async_trait]
+pub trait CheckpointHook: Send + Sync {
+    /// Snapshot the agent's state, tagged with the triggering operation.
+    async fn checkpoint(&self, agent_id: &str, operation: &str) -> Result<Uuid, String>;
+}
+
+/// [`CheckpointHook`] that records a snapshot through a [`RewindManager`].
+pub struct RewindCheckpointHook<M: RewindManager> {
+    manager: Arc<M>,
+}
+
+impl<M: RewindManager> RewindCheckpointHook<M> {
+    /// Create a hook backed by the given rewind manager.
+    pub fn new(manager: Arc<M>) -> Self {
+        Self { manager }
+    }
+}
+
+#[async_trait]
+impl<M: RewindManager> CheckpointHook for RewindCheckpointHook<M> {
+    async fn checkpoint(&self, agent_id: &str, operation: &str) -> Result<Uuid, String> {
+        self.manager
+            .create_snapshot(agent_id, format!("Safe checkpoint before {}", operation))
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// A checkpoint hook paired with the policy deciding when it fires.
+#[derive(Clone)]
+pub struct ToolCheckpoints {
+    pub hook: Arc<dyn CheckpointHook>,
+    pub config: CheckpointConfig,
+}
+
+impl ToolCheckpoints {
+    /// Take checkpoints through `hook` for calls matching `config`.
+    pub fn new(hook: Arc<dyn CheckpointHook>, config: CheckpointConfig) -> Self {
+        Self { hook, config }
+    }
+}
+
+impl fmt::Debug for ToolCheckpoints {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToolCheckpoints")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Execute a tool, taking a safe checkpoint first if the call is risky.
+///
+/// The checkpoint ID is added to the result metadata as `checkpoint_id`. If
+/// the checkpoint fails the tool still runs, unless
+/// [`CheckpointConfig::require_checkpoint`] is set.
+pub async fn execute_tool_with_checkpoint(
+    name: &str,
+    args: &Value,
+    working_dir: &Path,
+    descartes_bin: Option<&Path>,
+    context: &ExecutionContext,
+    hook: &dyn CheckpointHook,
+    config: &CheckpointConfig,
+) -> ToolResult {
+    let mut checkpoint_id = None;
+
+    if let Some(operation) = config.classify(name, args) {
+        let agent_id = context.agent_id.to_string();
+        match hook.checkpoint(&agent_id, &operation).await {
+            Ok(id) => {
+                info!("Safe checkpoint {} before {}", id, operation);
+                checkpoint_id = Some(id);
+            }
+            Err(e) if config.require_checkpoint => {
+                return ToolResult {
+                    success: false,
+                    output: format!(
+                        "Refusing to run {}: safe checkpoint failed: {}",
+                        operation, e
+                    ),
+                    metadata: None,
+                };
+            }
+            Err(e) => warn!("Safe checkpoint before {} failed: {}", operation, e),
+        }
+    }
+
+    let mut result =
+        dispatch_tool_async(name, args, working_dir, descartes_bin, Some(context)).await;
+
+    if let Some(id) = checkpoint_id {
+        result
+            .metadata
+            .get_or_insert_with(Default::default)
+            .insert("checkpoint_id".to_string(), id.to_string());
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::executors::execute_tool_async;
+    use parking_lot::Mutex;
+    use serde_json::json;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    /// Records each checkpoint along with whether the watched file still existed.
+    struct RecordingHook {
+        watched: PathBuf,
+        calls: Mutex<Vec<(String, bool)>>,
+    }
+
+    #[async_trait]
+    impl CheckpointHook for RecordingHook {
+        async fn checkpoint(&self, _agent_id: &str, operation: &str) -> Result<Uuid, String> {
+            self.calls
+                .lock()
+                .push((operation.to_string(), self.watched.exists()));
+            Ok(Uuid::new_v4())
+        }
+    }
+
+    #[test]
+    fn test_classify_risky_operations() {
+        let config = CheckpointConfig::default();
+
+        assert!(config
+            .classify("bash", &json!({"command": "cd build && rm -rf out"}))
+            .is_some());
+        assert!(config
+            .classify("bash", &json!({"command": "git reset --hard HEAD~1"}))
+            .is_some());
+        assert!(config
+            .classify("bash", &json!({"command": "ls -la"}))
+            .is_none());
+        assert!(config
+            .classify("bash", &json!({"command": "cargo run --bin format"}))
+            .is_none());
+        assert_eq!(
+            config.classify("write", &json!({"path": "a.txt"})),
+            Some("write: a.txt".to_string())
+        );
+        assert_eq!(
+            config.classify("edit", &json!({"path": "b.rs"})),
+            Some("edit: b.rs".to_string())
+        );
+        assert!(config.classify("read", &json!({"path": "a.txt"})).is_none());
+
+        let config = CheckpointConfig {
+            risky_tools: Vec::new(),
+            ..Default::default()
+        };
+        assert!(config
+            .classify("write", &json!({"path": "a.txt"}))
+            .is_none());
+
+        let disabled = CheckpointConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        assert!(disabled
+            .classify("bash", &json!({"command": "rm x"}))
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_before_destructive_bash() {
+        let temp_dir = TempDir::new().unwrap();
+        let victim = temp_dir.path().join("victim.txt");
+        std::fs::write(&victim, "precious").unwrap();
+
+        let hook = RecordingHook {
+            watched: victim.clone(),
+            calls: Mutex::new(Vec::new()),
+        };
+        let context = ExecutionContext::for_agent(Uuid::new_v4());
+
+        let result = execute_tool_with_checkpoint(
+            "bash",
+            &json!({"command": "rm victim.txt"}),
+            temp_dir.path(),
+            None,
+            &context,
+            &hook,
+            &CheckpointConfig::default(),
+        )
+        .await;
+
+        assert!(result.success);
+        assert!(!victim.exists());
+        assert!(result
+            .metadata
+            .as_ref()
+            .unwrap()
+            .contains_key("checkpoint_id"));
+
+        let calls = hook.calls.lock();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].0, "bash: rm victim.txt");
+        assert!(calls[0].1, "checkpoint must run before the file is deleted");
+    }
+
+    #[tokio::test]
+    async fn test_execute_tool_async_uses_context_checkpoints() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path().join("notes.txt");
+
+        let hook = Arc::new(RecordingHook {
+            watched: target.clone(),
+            calls: Mutex::new(Vec::new()),
+        });
+        let context = ExecutionContext::for_agent(Uuid::new_v4()).with_checkpoints(
+            ToolCheckpoints::new(hook.clone(), CheckpointConfig::default()),
+        );
+
+        let result = execute_tool_async(
+            "write",
+            &json!({"path": "notes.txt", "content": "hello"}),
+            temp_dir.path(),
+            None,
+            Some(&context),
+        )
+        .await;
+
+        assert!(result.success, "{}", result.output);
+        assert!(target.exists());
+        assert!(result
+            .metadata
+            .as_ref()
+            .unwrap()
+            .contains_key("checkpoint_id"));
+
+        let calls = hook.calls.lock();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].0, "write: notes.txt");
+        assert!(
+            !calls[0].1,
+            "checkpoint must run before the file is written"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_checkpoint_for_safe_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let hook = RecordingHook {
+            watched: temp_dir.path().to_path_buf(),
+            calls: Mutex::new(Vec::new()),
+        };
+        let context = ExecutionContext::for_agent(Uuid::new_v4());
+
+        let result = execute_tool_with_checkpoint(
+            "bash",
+            &json!({"command": "echo hello"}),
+            temp_dir.path(),
+            None,
+            &context,
+            &hook,
+            &CheckpointConfig::default(),
+        )
+        .await;
+
+        assert!(result.success);
+        assert!(hook.calls.lock().is_empty());
+    }
+}
@@ -2,6 +2,9 @@
 
 use uuid::Uuid;
 
+use crate::tools::artifacts::ArtifactCollector;
+use crate::tools::checkpoint::ToolCheckpoints;
+
 /// Context passed to tool executors for session-aware operations.
 ///
 /// This struct provides session and agent identification to tool executors,
@@ -12,6 +15,10 @@ pub struct ExecutionContext {
     pub session_id: Uuid,
     /// Agent identifier (may be same as session_id)
     pub agent_id: Uuid,
+    /// Safe checkpoints taken before risky tool calls, if configured
+    pub checkpoints: Option<ToolCheckpoints>,
+    /// Collector that files written by tools are registered with, if configured
+    pub artifacts: Option<ArtifactCollector>,
 }
 
 impl ExecutionContext {
@@ -20,6 +27,8 @@ impl ExecutionContext {
         Self {
             session_id,
             agent_id,
+            checkpoints: None,
+            artifacts: None,
         }
     }
 
@@ -28,8 +37,22 @@ impl ExecutionContext {
         Self {
             session_id: agent_id,
             agent_id,
+            checkpoints: None,
+            artifacts: None,
         }
     }
+
+    /// Take safe checkpoints before risky tool calls run with this context.
+    pub fn with_checkpoints(mut self, checkpoints: ToolCheckpoints) -> Self {
+        self.checkpoints = Some(checkpoints);
+        self
+    }
+
+    /// Register files written or edited with this context as artifacts.
+    pub fn with_artifacts(mut self, artifacts: ArtifactCollector) -> Self {
+        self.artifacts = Some(artifacts);
+        self
+    }
 }
 
 #[cfg(test)]
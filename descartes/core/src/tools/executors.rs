@@ -11,6 +11,7 @@ use tracing::{debug, info, warn};
 
 use crate::agent_definitions::AgentDefinitionLoader;
 use crate::swank::SwankSessionRegistry;
+use crate::tools::checkpoint::execute_tool_with_checkpoint;
 use crate::tools::context::ExecutionContext;
 use crate::tools::ToolLevel;
 use once_cell::sync::Lazy;
@@ -522,8 +523,8 @@ pub fn execute_tool(
 ) -> ToolResult {
     match name {
         "read" => execute_read(args, working_dir),
-        "write" => execute_write(args, working_dir),
-        "edit" => execute_edit(args, working_dir),
+        "write" => record_artifact(name, execute_write(args, working_dir), working_dir, context),
+        "edit" => record_artifact(name, execute_edit(args, working_dir), working_dir, context),
         "bash" => execute_bash(args, working_dir),
         "spawn_session" => execute_spawn_session(args, working_dir, descartes_bin),
         // Swank tools are async - must use execute_tool_async()
@@ -540,6 +541,39 @@ pub fn execute_tool(
     }
 }
 
+/// Register the file a successful write/edit touched with the context's
+/// artifact collector, noting the copy's location in the result metadata.
+fn record_artifact(
+    tool: &str,
+    mut result: ToolResult,
+    working_dir: &Path,
+    context: Option<&ExecutionContext>,
+) -> ToolResult {
+    let Some(collector) = context.and_then(|ctx| ctx.artifacts.as_ref()) else {
+        return result;
+    };
+    if !result.success {
+        return result;
+    }
+    let Some(path) = result
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get("path"))
+        .cloned()
+    else {
+        return result;
+    };
+
+    let artifact = collector.record(tool, Path::new(&path), working_dir);
+    if let Some(copy) = artifact.copied_to {
+        result
+            .metadata
+            .get_or_insert_with(Default::default)
+            .insert("artifact_path".to_string(), copy.display().to_string());
+    }
+    result
+}
+
 /// Execute the `swank_eval` tool (async).
 pub async fn execute_swank_eval(
     args: &Value,
@@ -773,12 +807,41 @@ pub async fn execute_swank_restart(
 }
 
 /// Execute a tool by name (async version for Swank tools).
+///
+/// If the context carries [`ToolCheckpoints`](crate::tools::ToolCheckpoints),
+/// risky calls are preceded by a safe checkpoint.
 pub async fn execute_tool_async(
     name: &str,
     args: &Value,
     working_dir: &Path,
     descartes_bin: Option<&Path>,
     context: Option<&ExecutionContext>,
+) -> ToolResult {
+    if let Some(ctx) = context {
+        if let Some(checkpoints) = &ctx.checkpoints {
+            return execute_tool_with_checkpoint(
+                name,
+                args,
+                working_dir,
+                descartes_bin,
+                ctx,
+                checkpoints.hook.as_ref(),
+                &checkpoints.config,
+            )
+            .await;
+        }
+    }
+
+    dispatch_tool_async(name, args, working_dir, descartes_bin, context).await
+}
+
+/// Run a tool by name without checkpointing.
+pub(crate) async fn dispatch_tool_async(
+    name: &str,
+    args: &Value,
+    working_dir: &Path,
+    descartes_bin: Option<&Path>,
+    context: Option<&ExecutionContext>,
 ) -> ToolResult {
     match name {
         // Sync tools - delegate to sync execute_tool
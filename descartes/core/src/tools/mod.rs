@@ -9,11 +9,15 @@
 //! - `ReadOnly`: read, bash (for exploration/planning)
 //! - `LispDeveloper`: swank_eval, swank_compile, swank_inspect, swank_restart + read, bash
 
+mod artifacts;
+mod checkpoint;
 mod context;
 mod definitions;
 mod executors;
 mod registry;
 
+pub use artifacts::*;
+pub use checkpoint::*;
 pub use context::*;
 pub use definitions::*;
 pub use executors::*;
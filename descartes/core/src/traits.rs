@@ -65,7 +65,7 @@ pub struct ToolCall {
 }
 
 /// Why the model stopped responding.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum FinishReason {
     Stop,
     MaxTokens,
@@ -75,6 +75,18 @@ pub enum FinishReason {
     Streaming,
 }
 
+impl FinishReason {
+    /// Map a provider's stop reason ("length", "max_tokens", "tool_use", ...)
+    pub fn from_provider(reason: &str) -> Self {
+        match reason {
+            "length" | "max_tokens" => FinishReason::MaxTokens,
+            "tool_use" | "tool_calls" | "function_call" => FinishReason::ToolUse,
+            "error" | "content_filter" => FinishReason::Error,
+            _ => FinishReason::Stop,
+        }
+    }
+}
+
 /// Configuration for model provider mode.
 #[derive(Debug, Clone)]
 pub enum ModelProviderMode {
@@ -358,6 +370,14 @@ pub trait AgentHandle: Send + Sync {
     /// Returns a receiver that will receive all stderr output from the agent.
     /// This is used for real-time log streaming.
     fn subscribe_stderr(&self) -> tokio::sync::broadcast::Receiver<Vec<u8>>;
+
+    /// Subscribe to the agent's exit.
+    ///
+    /// The value becomes `Some` once the agent exits or is killed. Returns `None`
+    /// if this handle cannot report exits.
+    fn subscribe_exit(&self) -> Option<tokio::sync::watch::Receiver<Option<ExitStatus>>> {
+        None
+    }
 }
 
 /// Exit status of an agent.
@@ -367,6 +387,21 @@ pub struct ExitStatus {
     pub success: bool,
 }
 
+/// A spawned agent's config, persisted so the daemon still knows the agent
+/// after a restart.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AgentRecord {
+    pub id: Uuid,
+    pub config: AgentConfig,
+    /// Last status the daemon saw
+    pub status: AgentStatus,
+    /// Whether a restarted daemon may spawn this agent again
+    #[serde(default)]
+    pub restartable: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
 /// StateStore trait - persistence layer.
 #[async_trait]
 pub trait StateStore: Send + Sync {
@@ -393,6 +428,18 @@ pub trait StateStore: Send + Sync {
 
     /// Search events by full-text query.
     async fn search_events(&self, query: &str) -> StateStoreResult<Vec<Event>>;
+
+    /// Save (or update) a spawned agent's record.
+    ///
+    /// Stores that don't persist agents may ignore this.
+    async fn save_agent_record(&self, _record: &AgentRecord) -> StateStoreResult<()> {
+        Ok(())
+    }
+
+    /// Get all saved agent records, oldest first.
+    async fn get_agent_records(&self) -> StateStoreResult<Vec<AgentRecord>> {
+        Ok(Vec::new())
+    }
 }
 
 /// An event in the Descartes system.
@@ -0,0 +1,353 @@
+//! Full-text search over session transcripts.
+//!
+//! Transcript JSON files stay the source of truth. A [`TranscriptIndex`] keeps
+//! an SQLite FTS5 index of their content alongside them, so searching doesn't
+//! re-read every file. Transcripts are added as they are saved
+//! ([`TranscriptIndex::index_transcript`]); [`TranscriptIndex::sync`] picks up
+//! files written or removed behind the index's back, and
+//! [`TranscriptIndex::rebuild`] recreates it from scratch.
+
+use crate::errors::{StateStoreError, StateStoreResult};
+use crate::session_transcript::{TranscriptEntry, TranscriptMetadata};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sqlx::Row;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::UNIX_EPOCH;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Index database file, stored in the sessions directory.
+pub const TRANSCRIPT_INDEX_FILE: &str = "transcripts.index.db";
+
+/// A transcript matching a search.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptHit {
+    pub path: PathBuf,
+    pub session_id: Uuid,
+    pub task: String,
+    pub started_at: DateTime<Utc>,
+    /// Matching text with the match in [brackets]
+    pub snippet: String,
+}
+
+/// Transcript file layout written by [`TranscriptWriter::save`](crate::TranscriptWriter::save).
+#[derive(Deserialize)]
+struct SavedTranscript {
+    metadata: TranscriptMetadata,
+    entries: Vec<TranscriptEntry>,
+}
+
+/// SQLite FTS index over the transcripts in one sessions directory.
+pub struct TranscriptIndex {
+    pool: SqlitePool,
+    sessions_dir: PathBuf,
+}
+
+impl TranscriptIndex {
+    /// Open (or create) the index for `sessions_dir`.
+    pub async fn open(sessions_dir: impl AsRef<Path>) -> StateStoreResult<Self> {
+        let sessions_dir = sessions_dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&sessions_dir)?;
+
+        let db_path = sessions_dir.join(TRANSCRIPT_INDEX_FILE);
+        let options = SqliteConnectOptions::from_str(db_path.to_string_lossy().as_ref())
+            .map_err(|e| {
+                StateStoreError::DatabaseError(format!("Failed to parse database path: {}", e))
+            })?
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .map_err(|e| {
+                StateStoreError::DatabaseError(format!("Failed to open transcript index: {}", e))
+            })?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS transcripts (
+                path TEXT PRIMARY KEY,
+                session_id TEXT NOT NULL,
+                task TEXT NOT NULL,
+                started_at TEXT NOT NULL,
+                modified INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .map_err(db_error)?;
+        sqlx::query(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS transcript_fts \
+             USING fts5(path UNINDEXED, content)",
+        )
+        .execute(&pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(Self { pool, sessions_dir })
+    }
+
+    /// Directory whose transcripts are indexed.
+    pub fn sessions_dir(&self) -> &Path {
+        &self.sessions_dir
+    }
+
+    /// Add or refresh one saved transcript.
+    pub async fn index_transcript(&self, path: &Path) -> StateStoreResult<()> {
+        let modified = modified_millis(path)?;
+        let json = std::fs::read_to_string(path)?;
+        let saved: SavedTranscript = serde_json::from_str(&json).map_err(|e| {
+            StateStoreError::SerializationError(format!(
+                "Invalid transcript {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        let mut content = saved.metadata.task.clone();
+        for entry in &saved.entries {
+            content.push('\n');
+            content.push_str(&entry.content);
+        }
+
+        let path = path.to_string_lossy();
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        sqlx::query("DELETE FROM transcript_fts WHERE path = ?")
+            .bind(path.as_ref())
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        sqlx::query("INSERT INTO transcript_fts (path, content) VALUES (?, ?)")
+            .bind(path.as_ref())
+            .bind(&content)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        sqlx::query(
+            "INSERT OR REPLACE INTO transcripts (path, session_id, task, started_at, modified) \
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(path.as_ref())
+        .bind(saved.metadata.session_id.to_string())
+        .bind(&saved.metadata.task)
+        .bind(saved.metadata.started_at.to_rfc3339())
+        .bind(modified)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        tx.commit().await.map_err(db_error)?;
+        Ok(())
+    }
+
+    /// Bring the index up to date with the sessions directory.
+    ///
+    /// Only new or modified files are read; entries for deleted files are
+    /// dropped. Files that aren't valid transcripts are logged and skipped,
+    /// and any earlier entry for them is dropped. Returns the number of
+    /// transcripts (re)indexed.
+    pub async fn sync(&self) -> StateStoreResult<usize> {
+        let rows = sqlx::query("SELECT path, modified FROM transcripts")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(db_error)?;
+        let mut indexed: HashMap<String, i64> = rows
+            .iter()
+            .map(|row| (row.get("path"), row.get("modified")))
+            .collect();
+
+        let mut count = 0;
+        for path in self.transcript_files()? {
+            let key = path.to_string_lossy().to_string();
+            let current = indexed.remove(&key);
+            if current != Some(modified_millis(&path)?) {
+                match self.index_transcript(&path).await {
+                    Ok(()) => count += 1,
+                    Err(StateStoreError::SerializationError(e)) => {
+                        warn!("Skipping transcript: {}", e);
+                        if current.is_some() {
+                            self.remove(&key).await?;
+                        }
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        for stale in indexed.keys() {
+            self.remove(stale).await?;
+        }
+        Ok(count)
+    }
+
+    /// Drop everything and index the sessions directory from scratch.
+    pub async fn rebuild(&self) -> StateStoreResult<usize> {
+        sqlx::query("DELETE FROM transcript_fts")
+            .execute(&self.pool)
+            .await
+            .map_err(db_error)?;
+        sqlx::query("DELETE FROM transcripts")
+            .execute(&self.pool)
+            .await
+            .map_err(db_error)?;
+        self.sync().await
+    }
+
+    /// Transcripts containing `text`, newest first.
+    ///
+    /// `text` is matched as a phrase of whole words, case-insensitively.
+    pub async fn search(&self, text: &str) -> StateStoreResult<Vec<TranscriptHit>> {
+        let phrase = format!("\"{}\"", text.replace('"', "\"\""));
+        let rows = sqlx::query(
+            "SELECT t.path, t.session_id, t.task, t.started_at, \
+                    snippet(transcript_fts, 1, '[', ']', '...', 12) AS snippet \
+             FROM transcript_fts JOIN transcripts t ON t.path = transcript_fts.path \
+             WHERE transcript_fts MATCH ? \
+             ORDER BY t.started_at DESC",
+        )
+        .bind(phrase)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        rows.iter()
+            .map(|row| {
+                let path: String = row.get("path");
+                let session_id: String = row.get("session_id");
+                let started_at: String = row.get("started_at");
+                Ok(TranscriptHit {
+                    path: PathBuf::from(path),
+                    session_id: Uuid::parse_str(&session_id)
+                        .map_err(|e| StateStoreError::SerializationError(e.to_string()))?,
+                    task: row.get("task"),
+                    started_at: DateTime::parse_from_rfc3339(&started_at)
+                        .map_err(|e| StateStoreError::SerializationError(e.to_string()))?
+                        .with_timezone(&Utc),
+                    snippet: row.get("snippet"),
+                })
+            })
+            .collect()
+    }
+
+    async fn remove(&self, path: &str) -> StateStoreResult<()> {
+        for table in ["transcript_fts", "transcripts"] {
+            sqlx::query(&format!("DELETE FROM {} WHERE path = ?", table))
+                .bind(path)
+                .execute(&self.pool)
+                .await
+                .map_err(db_error)?;
+        }
+        Ok(())
+    }
+
+    fn transcript_files(&self) -> StateStoreResult<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(&self.sessions_dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                files.push(path);
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+}
+
+fn modified_millis(path: &Path) -> StateStoreResult<i64> {
+    let modified = std::fs::metadata(path)?.modified()?;
+    Ok(modified
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as i64))
+}
+
+fn db_error(e: sqlx::Error) -> StateStoreError {
+    StateStoreError::DatabaseError(format!("Transcript index error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session_transcript::TranscriptWriter;
+    use tempfile::TempDir;
+
+    fn save_transcript(dir: &Path, task: &str, reply: &str) -> PathBuf {
+        let mut writer = TranscriptWriter::new(
+            &dir.to_path_buf(),
+            "anthropic",
+            "claude-3-5-sonnet",
+            task,
+            None,
+            None,
+        )
+        .unwrap();
+        writer.add_user_message(task);
+        writer.add_assistant_message(reply);
+        writer.save().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_grep_queries_the_index() {
+        let temp_dir = TempDir::new().unwrap();
+        let sessions = temp_dir.path();
+        let parser = save_transcript(sessions, "Fix the parser", "The tokenizer drops commas");
+        save_transcript(sessions, "Write docs", "Added a usage section");
+
+        let index = TranscriptIndex::open(sessions).await.unwrap();
+        assert_eq!(index.rebuild().await.unwrap(), 2);
+
+        let hits = index.search("drops commas").await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].path, parser);
+        assert_eq!(hits[0].task, "Fix the parser");
+        assert!(hits[0].snippet.contains("[drops commas]"));
+        assert!(index.search("\"unbalanced").await.unwrap().is_empty());
+
+        // Saving and indexing a new transcript makes it searchable without a rescan
+        let retry = save_transcript(sessions, "Retry the parser fix", "Commas handled now");
+        index.index_transcript(&retry).await.unwrap();
+        let hits = index.search("parser").await.unwrap();
+        let paths: Vec<_> = hits.iter().map(|h| h.path.clone()).collect();
+        assert_eq!(paths.len(), 2);
+        assert!(paths.contains(&parser) && paths.contains(&retry));
+        assert_eq!(index.sync().await.unwrap(), 0);
+
+        std::fs::remove_file(&parser).unwrap();
+        index.sync().await.unwrap();
+        let hits = index.search("parser").await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].path, retry);
+    }
+
+    #[tokio::test]
+    async fn test_sync_skips_unparseable_transcripts() {
+        let temp_dir = TempDir::new().unwrap();
+        let sessions = temp_dir.path();
+        save_transcript(sessions, "Fix the parser", "The tokenizer drops commas");
+        let broken = sessions.join("broken.json");
+        std::fs::write(&broken, "{ not a transcript").unwrap();
+        save_transcript(sessions, "Write docs", "Added a usage section");
+
+        let index = TranscriptIndex::open(sessions).await.unwrap();
+        assert_eq!(index.sync().await.unwrap(), 2);
+        assert_eq!(index.search("commas").await.unwrap().len(), 1);
+        assert_eq!(index.search("usage").await.unwrap().len(), 1);
+
+        // A transcript that goes bad after indexing drops out of the index
+        let docs = index.search("usage").await.unwrap().remove(0).path;
+        std::fs::write(&docs, "[]").unwrap();
+        let modified = std::fs::metadata(&docs).unwrap().modified().unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&docs)
+            .unwrap()
+            .set_modified(modified + std::time::Duration::from_secs(1))
+            .unwrap();
+        assert_eq!(index.sync().await.unwrap(), 0);
+        assert!(index.search("usage").await.unwrap().is_empty());
+        assert_eq!(index.search("commas").await.unwrap().len(), 1);
+    }
+}
@@ -5,11 +5,21 @@
 //!
 //! These commands follow the `/cl:*` pattern from Claude Code.
 
-use std::path::PathBuf;
+use parking_lot::RwLock;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 use crate::agent_definitions::AgentDefinitionLoader;
+use crate::providers::RetryPolicy;
+use crate::state_machine::WorkflowStateMachine;
+use crate::state_machine_store::SqliteWorkflowStore;
+use crate::swarm_parser::Contract;
 use crate::thoughts::ThoughtsStorage;
 
 /// Errors that can occur during workflow execution
@@ -26,13 +36,19 @@ pub enum WorkflowError {
 
     #[error("Workflow step failed: {0}")]
     StepFailed(String),
+
+    #[error("Variable error: {0}")]
+    VariableError(String),
+
+    #[error("Invalid workflow: {0}")]
+    InvalidWorkflow(String),
 }
 
 /// Result type for workflow operations
 pub type WorkflowResult<T> = Result<T, WorkflowError>;
 
 /// A single step in a workflow
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct WorkflowStep {
     /// Name of the step
     pub name: String,
@@ -42,8 +58,26 @@ pub struct WorkflowStep {
     pub task: String,
     /// Whether this step can run in parallel with previous steps
     pub parallel: bool,
+    /// Names of steps that must finish before this one starts
+    ///
+    /// When any step in a workflow declares dependencies, the executor
+    /// schedules the whole workflow as a DAG instead of by `parallel` flags.
+    pub depends_on: Vec<String>,
     /// Output file path (relative to thoughts directory)
     pub output: Option<String>,
+    /// Pre/postconditions checked around the step's execution
+    pub contract: Option<Contract>,
+    /// Retry a failed attempt with backoff (None runs the step once)
+    pub retry: Option<RetryPolicy>,
+    /// Task that undoes this step's side effects, run by the same agent if a
+    /// later step fails
+    pub compensation: Option<String>,
+    /// Workflow variable to store the step's output in (parsed as JSON when
+    /// it is valid JSON, otherwise stored as a string)
+    pub set_variable: Option<String>,
+    /// Abort the agent and fail the step if it runs longer than this
+    /// (None waits indefinitely)
+    pub timeout: Option<Duration>,
 }
 
 /// A workflow command definition
@@ -80,8 +114,7 @@ impl WorkflowCommand {
             name: format!("Step {}", step_num),
             agent: agent.into(),
             task: task.into(),
-            parallel: false,
-            output: None,
+            ..Default::default()
         });
         self
     }
@@ -94,12 +127,164 @@ impl WorkflowCommand {
             agent: agent.into(),
             task: task.into(),
             parallel: true,
-            output: None,
+            ..Default::default()
         });
         self
     }
 }
 
+/// Directory, relative to a project root, scanned for workflow files
+pub const WORKFLOWS_DIR: &str = ".descartes/workflows";
+
+/// On-disk form of a workflow command
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WorkflowFile {
+    /// Defaults to the file stem
+    name: Option<String>,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    steps: Vec<WorkflowStepFile>,
+}
+
+/// On-disk form of a workflow step
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WorkflowStepFile {
+    name: String,
+    agent: String,
+    task: String,
+    #[serde(default)]
+    parallel: bool,
+    #[serde(default)]
+    depends_on: Vec<String>,
+    output: Option<String>,
+    contract: Option<Contract>,
+    /// Retries after the first attempt, with the default backoff
+    retries: Option<u32>,
+    compensation: Option<String>,
+    set_variable: Option<String>,
+    /// Seconds an attempt may run before the agent is aborted
+    timeout_secs: Option<u64>,
+}
+
+impl From<WorkflowStepFile> for WorkflowStep {
+    fn from(step: WorkflowStepFile) -> Self {
+        Self {
+            name: step.name,
+            agent: step.agent,
+            task: step.task,
+            parallel: step.parallel,
+            depends_on: step.depends_on,
+            output: step.output,
+            contract: step.contract,
+            retry: step.retries.map(|max_retries| RetryPolicy {
+                max_retries,
+                ..Default::default()
+            }),
+            compensation: step.compensation,
+            set_variable: step.set_variable,
+            timeout: step.timeout_secs.map(Duration::from_secs),
+        }
+    }
+}
+
+impl WorkflowCommand {
+    /// Parse a workflow from TOML, naming it `default_name` if the file
+    /// doesn't set a name
+    ///
+    /// ```toml
+    /// description = "Review a pull request"
+    ///
+    /// [[steps]]
+    /// name = "Read Diff"
+    /// agent = "researcher"
+    /// task = "Summarize the changes."
+    ///
+    /// [[steps]]
+    /// name = "Review"
+    /// agent = "reviewer"
+    /// task = "Review the changes for bugs."
+    /// depends_on = ["Read Diff"]
+    /// ```
+    pub fn from_toml(content: &str, default_name: &str) -> WorkflowResult<Self> {
+        let file: WorkflowFile =
+            toml::from_str(content).map_err(|e| WorkflowError::InvalidWorkflow(e.to_string()))?;
+        let command = Self {
+            name: file.name.unwrap_or_else(|| default_name.to_string()),
+            description: file.description,
+            steps: file.steps.into_iter().map(WorkflowStep::from).collect(),
+        };
+        command.validate()?;
+        Ok(command)
+    }
+
+    /// Load a workflow from a TOML file, defaulting its name to the file stem
+    pub fn from_file(path: &Path) -> WorkflowResult<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self::from_toml(&content, &stem)
+    }
+
+    /// Check that the workflow is runnable: it has a name and steps, step
+    /// names are unique, and dependencies name other steps
+    pub fn validate(&self) -> WorkflowResult<()> {
+        let invalid = |msg: String| Err(WorkflowError::InvalidWorkflow(msg));
+        if self.name.trim().is_empty() {
+            return invalid("workflow has no name".to_string());
+        }
+        if self.steps.is_empty() {
+            return invalid(format!("workflow '{}' has no steps", self.name));
+        }
+
+        let mut names = HashSet::new();
+        for step in &self.steps {
+            if step.name.trim().is_empty() {
+                return invalid(format!("workflow '{}' has a step with no name", self.name));
+            }
+            if step.agent.trim().is_empty() {
+                return invalid(format!("step '{}' has no agent", step.name));
+            }
+            if !names.insert(step.name.as_str()) {
+                return invalid(format!("duplicate step name '{}'", step.name));
+            }
+        }
+        for step in &self.steps {
+            for dep in &step.depends_on {
+                if dep == &step.name {
+                    return invalid(format!("step '{}' depends on itself", step.name));
+                }
+                if !names.contains(dep.as_str()) {
+                    return invalid(format!(
+                        "step '{}' depends on unknown step '{}'",
+                        step.name, dep
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A workflow file that discovery skipped
+#[derive(Debug, Clone)]
+pub struct WorkflowDiscoveryWarning {
+    /// The offending file
+    pub path: PathBuf,
+    /// Why it was skipped
+    pub message: String,
+}
+
+impl std::fmt::Display for WorkflowDiscoveryWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.message)
+    }
+}
+
 /// Registry of built-in workflow commands
 pub struct WorkflowRegistry {
     commands: Vec<WorkflowCommand>,
@@ -121,6 +306,67 @@ impl WorkflowRegistry {
         registry
     }
 
+    /// Create a registry with the built-in commands plus the workflows found
+    /// in `project_root`'s [`WORKFLOWS_DIR`]
+    ///
+    /// Files that fail to load are skipped and returned as warnings.
+    pub fn for_project(project_root: &Path) -> (Self, Vec<WorkflowDiscoveryWarning>) {
+        let mut registry = Self::new();
+        let warnings = registry.discover(&project_root.join(WORKFLOWS_DIR));
+        (registry, warnings)
+    }
+
+    /// Register a workflow, replacing any existing one with the same name
+    pub fn register(&mut self, command: WorkflowCommand) {
+        match self.commands.iter_mut().find(|c| c.name == command.name) {
+            Some(existing) => *existing = command,
+            None => self.commands.push(command),
+        }
+    }
+
+    /// Load and register every `*.toml` workflow in `dir`
+    ///
+    /// A missing directory registers nothing. Files that fail to parse or
+    /// validate are skipped and returned as warnings rather than failing
+    /// discovery.
+    pub fn discover(&mut self, dir: &Path) -> Vec<WorkflowDiscoveryWarning> {
+        let mut warnings = Vec::new();
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return warnings,
+            Err(e) => {
+                warnings.push(WorkflowDiscoveryWarning {
+                    path: dir.to_path_buf(),
+                    message: e.to_string(),
+                });
+                return warnings;
+            }
+        };
+
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            match WorkflowCommand::from_file(&path) {
+                Ok(command) => {
+                    debug!("Discovered workflow '{}' in {:?}", command.name, path);
+                    self.register(command);
+                }
+                Err(e) => {
+                    warn!("Skipping workflow file {:?}: {}", path, e);
+                    warnings.push(WorkflowDiscoveryWarning {
+                        path,
+                        message: e.to_string(),
+                    });
+                }
+            }
+        }
+        warnings
+    }
+
     /// Register the built-in workflow commands
     fn register_builtins(&mut self) {
         // research_codebase: Find and analyze code
@@ -134,8 +380,8 @@ impl WorkflowRegistry {
                 agent: "codebase-locator".to_string(),
                 task: "Find all files related to the topic. Report file paths organized by purpose."
                     .to_string(),
-                parallel: false,
                 output: Some("research/locations.md".to_string()),
+                ..Default::default()
             })
             .add_step(WorkflowStep {
                 name: "Analyze Implementation".to_string(),
@@ -145,6 +391,7 @@ impl WorkflowRegistry {
                         .to_string(),
                 parallel: true, // Can run in parallel with locator
                 output: Some("research/analysis.md".to_string()),
+                ..Default::default()
             })
             .add_step(WorkflowStep {
                 name: "Find Patterns".to_string(),
@@ -153,6 +400,7 @@ impl WorkflowRegistry {
                     .to_string(),
                 parallel: true,
                 output: Some("research/patterns.md".to_string()),
+                ..Default::default()
             }),
         );
 
@@ -167,16 +415,16 @@ impl WorkflowRegistry {
                 agent: "researcher".to_string(),
                 task: "Research the codebase to understand the current state and constraints."
                     .to_string(),
-                parallel: false,
                 output: Some("research/context.md".to_string()),
+                ..Default::default()
             })
             .add_step(WorkflowStep {
                 name: "Plan".to_string(),
                 agent: "planner".to_string(),
                 task: "Create a detailed implementation plan with phases, steps, and verification criteria."
                     .to_string(),
-                parallel: false,
                 output: Some("plans/implementation.md".to_string()),
+                ..Default::default()
             }),
         );
 
@@ -191,8 +439,7 @@ impl WorkflowRegistry {
                 agent: "researcher".to_string(),
                 task: "Read and summarize the implementation plan. Identify the next incomplete phase."
                     .to_string(),
-                parallel: false,
-                output: None,
+                ..Default::default()
             }),
             // Note: Actual implementation requires a more capable agent
             // This is a starting point for the workflow structure
@@ -215,6 +462,118 @@ impl WorkflowRegistry {
     }
 }
 
+/// Context key under which [`WorkflowVariables`] are persisted in a
+/// [`WorkflowStateMachine`]
+pub const WORKFLOW_VARIABLES_KEY: &str = "variables";
+
+/// Named values shared between the steps of a workflow
+///
+/// Clones share the same store. Values are held as JSON, so anything
+/// serializable can be stored and read back as its own type.
+#[derive(Debug, Clone, Default)]
+pub struct WorkflowVariables {
+    values: Arc<RwLock<serde_json::Map<String, serde_json::Value>>>,
+}
+
+impl WorkflowVariables {
+    /// Create an empty variable store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a variable, replacing any previous value
+    pub fn set<T: Serialize>(&self, name: impl Into<String>, value: T) -> WorkflowResult<()> {
+        let name = name.into();
+        let value = serde_json::to_value(value)
+            .map_err(|e| WorkflowError::VariableError(format!("Cannot store '{}': {}", name, e)))?;
+        self.values.write().insert(name, value);
+        Ok(())
+    }
+
+    /// Read a variable as `T`, or None if it is not set
+    pub fn get<T: DeserializeOwned>(&self, name: &str) -> WorkflowResult<Option<T>> {
+        match self.values.read().get(name) {
+            Some(value) => serde_json::from_value(value.clone())
+                .map(Some)
+                .map_err(|e| {
+                    WorkflowError::VariableError(format!("Cannot read '{}': {}", name, e))
+                }),
+            None => Ok(None),
+        }
+    }
+
+    /// Raw JSON value of a variable
+    pub fn get_value(&self, name: &str) -> Option<serde_json::Value> {
+        self.values.read().get(name).cloned()
+    }
+
+    /// Remove a variable, returning its value
+    pub fn remove(&self, name: &str) -> Option<serde_json::Value> {
+        self.values.write().remove(name)
+    }
+
+    /// All variables as a JSON object
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::Value::Object(self.values.read().clone())
+    }
+
+    /// Whether no variables are set
+    pub fn is_empty(&self) -> bool {
+        self.values.read().is_empty()
+    }
+
+    /// A separate store holding a copy of the current variables
+    ///
+    /// Unlike a clone, changes to the snapshot are not seen here.
+    pub fn snapshot(&self) -> Self {
+        Self {
+            values: Arc::new(RwLock::new(self.values.read().clone())),
+        }
+    }
+
+    /// Set the variables `other` changed relative to `base`
+    ///
+    /// Used to fold a [snapshot](Self::snapshot) taken from `base` back in
+    /// without undoing changes merged from other snapshots.
+    pub fn merge_changes(&self, base: &WorkflowVariables, other: &WorkflowVariables) {
+        let base = base.values.read().clone();
+        let changed: Vec<_> = other
+            .values
+            .read()
+            .iter()
+            .filter(|(name, value)| base.get(name.as_str()) != Some(value))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        self.values.write().extend(changed);
+    }
+
+    /// Store the variables in `machine`'s context so they are persisted with it
+    pub async fn save(&self, machine: &WorkflowStateMachine) -> WorkflowResult<()> {
+        machine
+            .set_context(WORKFLOW_VARIABLES_KEY, self.to_json())
+            .await
+            .map_err(|e| WorkflowError::VariableError(e.to_string()))
+    }
+
+    /// Replace the variables with those saved in `machine`'s context
+    ///
+    /// Leaves the store empty if `machine` has no saved variables.
+    pub async fn restore(&self, machine: &WorkflowStateMachine) -> WorkflowResult<()> {
+        let values = match machine.get_context(WORKFLOW_VARIABLES_KEY).await {
+            Some(serde_json::Value::Object(values)) => values,
+            Some(other) => {
+                return Err(WorkflowError::VariableError(format!(
+                    "Saved variables are not an object: {}",
+                    other
+                )))
+            }
+            None => serde_json::Map::new(),
+        };
+        *self.values.write() = values;
+        Ok(())
+    }
+}
+
 /// Context for executing a workflow
 pub struct WorkflowContext {
     /// Working directory for the workflow
@@ -227,6 +586,12 @@ pub struct WorkflowContext {
     pub thoughts: ThoughtsStorage,
     /// Agent loader for loading agent definitions
     pub agent_loader: AgentDefinitionLoader,
+    /// Variables shared between steps
+    pub variables: WorkflowVariables,
+    /// State machine the variables are persisted with, if any
+    pub state_machine: Option<Arc<WorkflowStateMachine>>,
+    /// Store the state machine is saved to when it changes, if any
+    pub state_store: Option<Arc<SqliteWorkflowStore>>,
 }
 
 impl WorkflowContext {
@@ -246,6 +611,9 @@ impl WorkflowContext {
             context: None,
             thoughts,
             agent_loader,
+            variables: WorkflowVariables::new(),
+            state_machine: None,
+            state_store: None,
         })
     }
 
@@ -254,6 +622,67 @@ impl WorkflowContext {
         self.context = Some(context.into());
         self
     }
+
+    /// A copy of this context for a step running alongside others
+    ///
+    /// The variables are a [snapshot](WorkflowVariables::snapshot), so
+    /// concurrent steps don't see each other's changes until they are merged
+    /// back. The state machine and its store are shared.
+    pub fn fork(&self) -> Self {
+        Self {
+            working_dir: self.working_dir.clone(),
+            topic: self.topic.clone(),
+            context: self.context.clone(),
+            thoughts: self.thoughts.clone(),
+            agent_loader: self.agent_loader.clone(),
+            variables: self.variables.snapshot(),
+            state_machine: self.state_machine.clone(),
+            state_store: self.state_store.clone(),
+        }
+    }
+
+    /// Set a workflow variable (see [`WorkflowVariables::set`])
+    pub fn set<T: Serialize>(&self, name: impl Into<String>, value: T) -> WorkflowResult<()> {
+        self.variables.set(name, value)
+    }
+
+    /// Read a workflow variable (see [`WorkflowVariables::get`])
+    pub fn get<T: DeserializeOwned>(&self, name: &str) -> WorkflowResult<Option<T>> {
+        self.variables.get(name)
+    }
+
+    /// Persist this run with `machine`, restoring the variables it holds
+    ///
+    /// Any variables set before this call are replaced. With a `store`, the
+    /// machine is saved there on every [`persist`](Self::persist), so a run
+    /// recovered from the store resumes with the same variables.
+    pub async fn with_state(
+        mut self,
+        machine: Arc<WorkflowStateMachine>,
+        store: Option<Arc<SqliteWorkflowStore>>,
+    ) -> WorkflowResult<Self> {
+        self.variables.restore(&machine).await?;
+        self.state_machine = Some(machine);
+        self.state_store = store;
+        Ok(self)
+    }
+
+    /// Save the variables into the attached state machine and its store
+    ///
+    /// Does nothing if no state machine is attached.
+    pub async fn persist(&self) -> WorkflowResult<()> {
+        let Some(machine) = &self.state_machine else {
+            return Ok(());
+        };
+        self.variables.save(machine).await?;
+        if let Some(store) = &self.state_store {
+            store
+                .save_workflow(machine)
+                .await
+                .map_err(|e| WorkflowError::StorageError(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 /// Result of executing a workflow step
@@ -336,6 +765,76 @@ pub fn get_workflow(name: &str) -> Option<WorkflowCommand> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_discover_workflow_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join(WORKFLOWS_DIR);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("review.toml"),
+            r#"
+description = "Review a pull request"
+
+[[steps]]
+name = "Read Diff"
+agent = "researcher"
+task = "Summarize the changes."
+
+[[steps]]
+name = "Review"
+agent = "reviewer"
+task = "Review the changes for bugs."
+depends_on = ["Read Diff"]
+retries = 2
+timeout_secs = 600
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("release.toml"),
+            r#"
+name = "ship_release"
+
+[[steps]]
+name = "Tag"
+agent = "releaser"
+task = "Tag the release."
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("broken.toml"),
+            r#"
+[[steps]]
+name = "Review"
+agent = "reviewer"
+task = "Review."
+depends_on = ["Missing"]
+"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a workflow").unwrap();
+
+        let (registry, warnings) = WorkflowRegistry::for_project(temp.path());
+
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].path.ends_with("broken.toml"));
+        assert!(warnings[0].message.contains("unknown step 'Missing'"));
+
+        let names = registry.list();
+        assert!(names.contains(&"review"));
+        assert!(names.contains(&"ship_release"));
+        assert!(names.contains(&"research_codebase"));
+        assert!(!names.contains(&"broken"));
+
+        let review = registry.get("review").unwrap();
+        assert_eq!(review.description, "Review a pull request");
+        assert_eq!(review.steps[1].depends_on, vec!["Read Diff".to_string()]);
+        assert_eq!(review.steps[1].retry.unwrap().max_retries, 2);
+        assert_eq!(review.steps[1].timeout, Some(Duration::from_secs(600)));
+        assert_eq!(review.steps[0].timeout, None);
+    }
+
     #[test]
     fn test_workflow_command_builder() {
         let workflow = WorkflowCommand::new("test", "Test workflow")
@@ -2,12 +2,17 @@
 //!
 //! Executes workflow steps using the appropriate agents and providers.
 
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::Semaphore;
 use tracing::{debug, info, warn};
 
 use crate::agent_definitions::AgentDefinitionError;
+use crate::expression_eval::{EvalContext, ExpressionEvaluator};
+use crate::state_machine::WorkflowStateMachine;
+use crate::swarm_parser::Contract;
 use crate::thoughts::ThoughtsError;
 use crate::workflow_commands::WorkflowError;
 use crate::{
@@ -23,6 +28,111 @@ pub struct StepExecutionResult {
     pub saved_to: Option<PathBuf>,
     pub duration_ms: u64,
     pub error: Option<String>,
+    /// Attempts made, including retries (0 if the step never started)
+    pub attempts: u32,
+    /// Set when the step was undone because a later step failed
+    pub compensation: Option<CompensationResult>,
+    /// Whether the step was aborted for exceeding its timeout
+    pub timed_out: bool,
+    /// Where the step's wall-clock time went
+    pub profile: StepProfile,
+}
+
+/// Outcome of running a step's compensation task
+#[derive(Debug, Clone)]
+pub struct CompensationResult {
+    pub success: bool,
+    pub output: String,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+}
+
+/// Wall-clock breakdown of a single step, in milliseconds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct StepProfile {
+    /// Waiting for a parallel execution slot
+    pub queued_ms: u64,
+    /// Loading the agent definition and building the request
+    pub setup_ms: u64,
+    /// Waiting on the model backend
+    pub agent_ms: u64,
+    /// Writing the step output to thoughts storage
+    pub save_ms: u64,
+}
+
+/// Context key under which a run's [`WorkflowProfile`] is persisted in its
+/// [`WorkflowStateMachine`]
+pub const WORKFLOW_PROFILE_KEY: &str = "profile";
+
+/// Per-step timing for a whole workflow run
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WorkflowProfile {
+    pub steps: Vec<(String, StepProfile)>,
+}
+
+impl WorkflowProfile {
+    /// Collect the profiles recorded on each step result
+    pub fn from_results(results: &[StepExecutionResult]) -> Self {
+        Self {
+            steps: results
+                .iter()
+                .map(|r| (r.step_name.clone(), r.profile))
+                .collect(),
+        }
+    }
+
+    /// Render as a fixed-width table, one row per step plus a total
+    pub fn render_table(&self) -> String {
+        let width = self
+            .steps
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0)
+            .max("TOTAL".len());
+
+        let mut out = format!(
+            "{:<width$}  {:>9}  {:>9}  {:>9}  {:>9}\n",
+            "STEP", "QUEUED", "SETUP", "AGENT", "SAVE"
+        );
+        let mut total = StepProfile::default();
+        for (name, p) in &self.steps {
+            out.push_str(&format_profile_row(name, p, width));
+            total.queued_ms += p.queued_ms;
+            total.setup_ms += p.setup_ms;
+            total.agent_ms += p.agent_ms;
+            total.save_ms += p.save_ms;
+        }
+        out.push_str(&format_profile_row("TOTAL", &total, width));
+        out
+    }
+
+    /// Store the profile in `machine`'s context so it is persisted with the run
+    pub async fn save(&self, machine: &WorkflowStateMachine) -> Result<(), WorkflowError> {
+        let value =
+            serde_json::to_value(self).map_err(|e| WorkflowError::StorageError(e.to_string()))?;
+        machine
+            .set_context(WORKFLOW_PROFILE_KEY, value)
+            .await
+            .map_err(|e| WorkflowError::StorageError(e.to_string()))
+    }
+
+    /// The profile saved in `machine`'s context, if any
+    pub async fn load(machine: &WorkflowStateMachine) -> Result<Option<Self>, WorkflowError> {
+        machine
+            .get_context(WORKFLOW_PROFILE_KEY)
+            .await
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| WorkflowError::StorageError(e.to_string()))
+    }
+}
+
+fn format_profile_row(name: &str, p: &StepProfile, width: usize) -> String {
+    format!(
+        "{:<width$}  {:>7}ms  {:>7}ms  {:>7}ms  {:>7}ms\n",
+        name, p.queued_ms, p.setup_ms, p.agent_ms, p.save_ms
+    )
 }
 
 /// Workflow executor configuration
@@ -63,10 +173,35 @@ pub enum WorkflowExecutionError {
     #[error("Task join error: {0}")]
     JoinError(String),
 
+    #[error("Contract '{contract}' violated in step '{step}': {phase} `{condition}` {reason}")]
+    ContractViolation {
+        step: String,
+        contract: String,
+        phase: ContractPhase,
+        condition: String,
+        reason: String,
+    },
+
     #[error("Workflow error: {0}")]
     WorkflowError(#[from] WorkflowError),
 }
 
+/// Which side of a step a contract condition guards
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractPhase {
+    Precondition,
+    Postcondition,
+}
+
+impl std::fmt::Display for ContractPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContractPhase::Precondition => write!(f, "precondition"),
+            ContractPhase::Postcondition => write!(f, "postcondition"),
+        }
+    }
+}
+
 impl From<AgentDefinitionError> for WorkflowExecutionError {
     fn from(e: AgentDefinitionError) -> Self {
         WorkflowExecutionError::AgentError(e.to_string())
@@ -85,18 +220,162 @@ impl From<crate::errors::AgentError> for WorkflowExecutionError {
     }
 }
 
+/// Check one side of a step's contract against the evaluation context
+fn check_contract(
+    step: &WorkflowStep,
+    contract: &Contract,
+    phase: ContractPhase,
+    eval_context: &EvalContext,
+) -> Result<(), WorkflowExecutionError> {
+    let conditions = match phase {
+        ContractPhase::Precondition => &contract.preconditions,
+        ContractPhase::Postcondition => &contract.postconditions,
+    };
+    let evaluator = ExpressionEvaluator::new();
+
+    for condition in conditions {
+        let reason = match evaluator.evaluate_bool(condition, eval_context) {
+            Ok(true) => continue,
+            Ok(false) => "is not satisfied".to_string(),
+            Err(e) => format!("could not be evaluated: {}", e),
+        };
+        return Err(WorkflowExecutionError::ContractViolation {
+            step: step.name.clone(),
+            contract: contract.name.clone().unwrap_or_else(|| step.name.clone()),
+            phase,
+            condition: condition.clone(),
+            reason,
+        });
+    }
+    Ok(())
+}
+
 /// Execute a single workflow step
+///
+/// If the step carries a [`Contract`], its preconditions are checked against
+/// `topic`, `task`, `context` and the workflow variables (`vars`) before the
+/// agent runs, and its postconditions against the same plus the agent's
+/// `output` before anything is saved. An unmet condition fails the step with
+/// [`WorkflowExecutionError::ContractViolation`].
+///
+/// A step with `set_variable` stores its output in the workflow variables,
+/// where later steps and their contracts can read it. The variables are then
+/// persisted with the context's state machine, if it has one.
+///
+/// A step with a retry policy is re-run after a failed attempt, backing off
+/// between attempts, until it succeeds or runs out of retries.
+///
+/// A step with a timeout races the agent call against it. On expiry the call
+/// is dropped, aborting the agent, and the step fails with `timed_out` set.
 pub async fn execute_step(
     step: &WorkflowStep,
     task: &str,
     context: &WorkflowContext,
     backend: &dyn ModelBackend,
     config: &WorkflowExecutorConfig,
+) -> Result<StepExecutionResult, WorkflowExecutionError> {
+    let max_retries = step.retry.map_or(0, |policy| policy.max_retries);
+    let mut attempt = 0;
+    loop {
+        let mut result = execute_step_once(step, task, context, backend, config).await?;
+        attempt += 1;
+        result.attempts = attempt;
+        if result.success || attempt > max_retries {
+            return Ok(result);
+        }
+
+        let delay = step
+            .retry
+            .map(|policy| policy.backoff(attempt, None))
+            .unwrap_or_default();
+        warn!(
+            "Step {} failed (attempt {}), retrying in {:?}: {}",
+            step.name,
+            attempt,
+            delay,
+            result.error.as_deref().unwrap_or("unknown error")
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Run a step's compensation task, recording the outcome on its result
+async fn compensate_step(
+    step: &WorkflowStep,
+    result: &mut StepExecutionResult,
+    context: &WorkflowContext,
+    backend: &dyn ModelBackend,
+    config: &WorkflowExecutorConfig,
+) {
+    let Some(task) = &step.compensation else {
+        return;
+    };
+    info!("Compensating step {}", step.name);
+
+    let compensation_step = WorkflowStep {
+        name: format!("{} (compensation)", step.name),
+        agent: step.agent.clone(),
+        task: task.clone(),
+        retry: step.retry,
+        timeout: step.timeout,
+        ..Default::default()
+    };
+    let start = std::time::Instant::now();
+    let outcome = execute_step(&compensation_step, task, context, backend, config).await;
+    result.compensation = Some(match outcome {
+        Ok(r) => CompensationResult {
+            success: r.success,
+            output: r.output,
+            duration_ms: r.duration_ms,
+            error: r.error,
+        },
+        Err(e) => CompensationResult {
+            success: false,
+            output: String::new(),
+            duration_ms: start.elapsed().as_millis() as u64,
+            error: Some(e.to_string()),
+        },
+    });
+}
+
+/// Undo the successful steps at `completed` (indices into `steps` and
+/// `results`), most recent first
+async fn compensate_steps(
+    steps: &[(WorkflowStep, String)],
+    results: &mut [StepExecutionResult],
+    completed: &[usize],
+    context: &WorkflowContext,
+    backend: &dyn ModelBackend,
+    config: &WorkflowExecutorConfig,
+) {
+    for &i in completed.iter().rev() {
+        if results[i].success {
+            compensate_step(&steps[i].0, &mut results[i], context, backend, config).await;
+        }
+    }
+}
+
+async fn execute_step_once(
+    step: &WorkflowStep,
+    task: &str,
+    context: &WorkflowContext,
+    backend: &dyn ModelBackend,
+    config: &WorkflowExecutorConfig,
 ) -> Result<StepExecutionResult, WorkflowExecutionError> {
     let start = std::time::Instant::now();
+    let mut profile = StepProfile::default();
 
     info!("Executing step: {} with agent: {}", step.name, step.agent);
 
+    let mut eval_context = EvalContext::new()
+        .with_variable("topic", serde_json::json!(context.topic))
+        .with_variable("task", serde_json::json!(task))
+        .with_variable("context", serde_json::json!(context.context))
+        .with_nested("vars", context.variables.to_json());
+    if let Some(contract) = &step.contract {
+        check_contract(step, contract, ContractPhase::Precondition, &eval_context)?;
+    }
+
     // Load agent definition for tool level and system prompt
     let agent_def = match context.agent_loader.load_agent(&step.agent) {
         Ok(def) => def,
@@ -110,6 +389,13 @@ pub async fn execute_step(
                 saved_to: None,
                 duration_ms: start.elapsed().as_millis() as u64,
                 error: Some(format!("Agent '{}' not found: {}", step.agent, e)),
+                attempts: 1,
+                compensation: None,
+                timed_out: false,
+                profile: StepProfile {
+                    setup_ms: start.elapsed().as_millis() as u64,
+                    ..profile
+                },
             });
         }
     };
@@ -123,12 +409,15 @@ pub async fn execute_step(
     );
 
     // Build the full task with context
-    let full_task = format!(
+    let mut full_task = format!(
         "Topic: {}\n\nTask: {}\n\nContext: {}",
         context.topic,
         task,
         context.context.as_deref().unwrap_or("None")
     );
+    if !context.variables.is_empty() {
+        full_task.push_str(&format!("\n\nVariables: {}", context.variables.to_json()));
+    }
 
     // Create model request
     let messages = vec![Message {
@@ -145,10 +434,18 @@ pub async fn execute_step(
         tools: Some(tools),
     };
 
+    profile.setup_ms = start.elapsed().as_millis() as u64;
+
     // Execute
-    let response = match backend.complete(request).await {
-        Ok(r) => r,
-        Err(e) => {
+    let agent_start = std::time::Instant::now();
+    let response = match step.timeout {
+        Some(limit) => tokio::time::timeout(limit, backend.complete(request)).await,
+        None => Ok(backend.complete(request).await),
+    };
+    profile.agent_ms = agent_start.elapsed().as_millis() as u64;
+    let response = match response {
+        Ok(Ok(r)) => r,
+        Ok(Err(e)) => {
             return Ok(StepExecutionResult {
                 step_name: step.name.clone(),
                 success: false,
@@ -156,11 +453,46 @@ pub async fn execute_step(
                 saved_to: None,
                 duration_ms: start.elapsed().as_millis() as u64,
                 error: Some(format!("Provider error: {}", e)),
+                attempts: 1,
+                compensation: None,
+                timed_out: false,
+                profile,
+            });
+        }
+        Err(_) => {
+            warn!("Step {} timed out after {:?}", step.name, step.timeout);
+            return Ok(StepExecutionResult {
+                step_name: step.name.clone(),
+                success: false,
+                output: String::new(),
+                saved_to: None,
+                duration_ms: start.elapsed().as_millis() as u64,
+                error: Some(format!(
+                    "Step timed out after {:?}",
+                    step.timeout.unwrap_or_default()
+                )),
+                attempts: 1,
+                timed_out: true,
+                compensation: None,
+                profile,
             });
         }
     };
 
+    if let Some(contract) = &step.contract {
+        eval_context.set("output", serde_json::json!(response.content));
+        check_contract(step, contract, ContractPhase::Postcondition, &eval_context)?;
+    }
+
+    if let Some(name) = &step.set_variable {
+        let value = serde_json::from_str(response.content.trim())
+            .unwrap_or_else(|_| serde_json::json!(response.content));
+        context.variables.set(name.clone(), value)?;
+        context.persist().await?;
+    }
+
     // Save output if configured
+    let save_start = std::time::Instant::now();
     let saved_to = if config.save_outputs {
         if let Some(output_path) = &step.output {
             let content = format!("# {}\n\n{}", step.name, response.content);
@@ -181,6 +513,8 @@ pub async fn execute_step(
         None
     };
 
+    profile.save_ms = save_start.elapsed().as_millis() as u64;
+
     Ok(StepExecutionResult {
         step_name: step.name.clone(),
         success: true,
@@ -188,16 +522,53 @@ pub async fn execute_step(
         saved_to,
         duration_ms: start.elapsed().as_millis() as u64,
         error: None,
+        attempts: 1,
+        compensation: None,
+        timed_out: false,
+        profile,
     })
 }
 
 /// Execute multiple steps, respecting parallel flags
+///
+/// If any step declares `depends_on`, the steps are scheduled as a dependency
+/// graph instead and the `parallel` flags are ignored.
+///
+/// If any step declares a compensation task, the workflow runs as a saga: the
+/// first failure stops it, and the steps that already succeeded are
+/// compensated in reverse order. Otherwise failed steps are reported and the
+/// remaining steps still run.
+///
+/// If the context has a state machine attached, the run's [`WorkflowProfile`]
+/// is saved in it under [`WORKFLOW_PROFILE_KEY`] and persisted once all steps
+/// have finished.
 pub async fn execute_workflow(
     steps: Vec<(WorkflowStep, String)>,
     context: &WorkflowContext,
     backend: Arc<dyn ModelBackend + Send + Sync>,
     config: &WorkflowExecutorConfig,
 ) -> Result<Vec<StepExecutionResult>, WorkflowExecutionError> {
+    let results = execute_workflow_steps(steps, context, backend, config).await?;
+    if let Some(machine) = &context.state_machine {
+        WorkflowProfile::from_results(&results)
+            .save(machine)
+            .await?;
+        context.persist().await?;
+    }
+    Ok(results)
+}
+
+async fn execute_workflow_steps(
+    steps: Vec<(WorkflowStep, String)>,
+    context: &WorkflowContext,
+    backend: Arc<dyn ModelBackend + Send + Sync>,
+    config: &WorkflowExecutorConfig,
+) -> Result<Vec<StepExecutionResult>, WorkflowExecutionError> {
+    let saga = steps.iter().any(|(step, _)| step.compensation.is_some());
+    if steps.iter().any(|(step, _)| !step.depends_on.is_empty()) {
+        return execute_workflow_graph(&steps, context, backend.as_ref(), config, saga).await;
+    }
+
     let mut results = Vec::new();
     let semaphore = Arc::new(Semaphore::new(config.max_parallel));
 
@@ -221,7 +592,23 @@ pub async fn execute_workflow(
             // Execute sequentially
             let (step, task) = &steps[i];
             info!("Executing step {} sequentially", step.name);
-            let result = execute_step(step, task, context, backend.as_ref(), config).await?;
+            let result = match execute_step(step, task, context, backend.as_ref(), config).await {
+                Ok(result) => result,
+                // A saga still has to undo earlier steps, so record the error instead
+                Err(e) if saga => StepExecutionResult {
+                    step_name: step.name.clone(),
+                    success: false,
+                    output: String::new(),
+                    saved_to: None,
+                    duration_ms: 0,
+                    error: Some(format!("Execution error: {}", e)),
+                    attempts: 1,
+                    timed_out: false,
+                    compensation: None,
+                    profile: StepProfile::default(),
+                },
+                Err(e) => return Err(e),
+            };
             results.push(result);
             i += 1;
         } else {
@@ -232,43 +619,26 @@ pub async fn execute_workflow(
             );
 
             let mut handles = Vec::new();
+            let mut step_variables = Vec::new();
 
             for &idx in &parallel_batch {
                 let (step, task) = steps[idx].clone();
                 let backend = backend.clone();
                 let config = config.clone();
                 let sem = semaphore.clone();
-
-                // Clone context fields for the async block
-                let topic = context.topic.clone();
-                let ctx_context = context.context.clone();
-                let working_dir = context.working_dir.clone();
+                let wf_context = context.fork();
+                step_variables.push(wf_context.variables.clone());
 
                 handles.push(tokio::spawn(async move {
+                    let queued = std::time::Instant::now();
                     let _permit = sem.acquire().await.expect("Semaphore closed");
-
-                    // Recreate context in spawn
-                    let wf_context = match WorkflowContext::new(working_dir, &topic) {
-                        Ok(mut c) => {
-                            if let Some(ctx) = ctx_context {
-                                c = c.with_context(ctx);
-                            }
-                            c
-                        }
-                        Err(e) => {
-                            return StepExecutionResult {
-                                step_name: step.name.clone(),
-                                success: false,
-                                output: String::new(),
-                                saved_to: None,
-                                duration_ms: 0,
-                                error: Some(format!("Context error: {}", e)),
-                            };
-                        }
-                    };
+                    let queued_ms = queued.elapsed().as_millis() as u64;
 
                     match execute_step(&step, &task, &wf_context, backend.as_ref(), &config).await {
-                        Ok(result) => result,
+                        Ok(mut result) => {
+                            result.profile.queued_ms = queued_ms;
+                            result
+                        }
                         Err(e) => StepExecutionResult {
                             step_name: step.name.clone(),
                             success: false,
@@ -276,6 +646,13 @@ pub async fn execute_workflow(
                             saved_to: None,
                             duration_ms: 0,
                             error: Some(format!("Execution error: {}", e)),
+                            attempts: 1,
+                            compensation: None,
+                            timed_out: false,
+                            profile: StepProfile {
+                                queued_ms,
+                                ..Default::default()
+                            },
                         },
                     }
                 }));
@@ -290,14 +667,197 @@ pub async fn execute_workflow(
                 }
             }
 
+            // Fold each step's changes back in step order, then save them together
+            let base = context.variables.snapshot();
+            for variables in &step_variables {
+                context.variables.merge_changes(&base, variables);
+            }
+            context.persist().await?;
+
             i = j;
         }
+
+        if saga && results.iter().any(|r| !r.success) {
+            warn!("Workflow step failed, compensating completed steps");
+            let completed: Vec<usize> = (0..results.len()).collect();
+            compensate_steps(
+                &steps,
+                &mut results,
+                &completed,
+                context,
+                backend.as_ref(),
+                config,
+            )
+            .await;
+            break;
+        }
     }
 
     info!("Workflow execution complete: {} steps", results.len());
     Ok(results)
 }
 
+/// Execute steps as a dependency graph
+///
+/// A step starts once every step it depends on has succeeded, with at most
+/// `max_parallel` steps in flight. A step that fails or errors is recorded as
+/// failed; steps downstream of it are skipped and reported as failed, while
+/// independent branches run to completion. Results are returned in the order
+/// the steps were given.
+///
+/// As a saga, no new steps start after a failure and the completed steps are
+/// compensated in reverse completion order.
+///
+/// Step names identify dependencies, so duplicate names are rejected.
+async fn execute_workflow_graph(
+    steps: &[(WorkflowStep, String)],
+    context: &WorkflowContext,
+    backend: &dyn ModelBackend,
+    config: &WorkflowExecutorConfig,
+    saga: bool,
+) -> Result<Vec<StepExecutionResult>, WorkflowExecutionError> {
+    let mut index: HashMap<&str, usize> = HashMap::with_capacity(steps.len());
+    for (i, (step, _)) in steps.iter().enumerate() {
+        if index.insert(step.name.as_str(), i).is_some() {
+            return Err(WorkflowError::InvalidWorkflow(format!(
+                "Duplicate step name '{}'",
+                step.name
+            ))
+            .into());
+        }
+    }
+    let mut dependencies = Vec::with_capacity(steps.len());
+    for (step, _) in steps {
+        let deps = step
+            .depends_on
+            .iter()
+            .map(|name| {
+                index.get(name.as_str()).copied().ok_or_else(|| {
+                    WorkflowExecutionError::StepFailed(format!(
+                        "Step '{}' depends on unknown step '{}'",
+                        step.name, name
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        dependencies.push(deps);
+    }
+
+    info!(
+        "Executing workflow graph with {} steps, max_parallel={}",
+        steps.len(),
+        config.max_parallel
+    );
+
+    let max_parallel = config.max_parallel.max(1);
+    let mut results: Vec<Option<StepExecutionResult>> = vec![None; steps.len()];
+    let mut started = vec![false; steps.len()];
+    let mut completed = Vec::new();
+    let mut stopped = false;
+    let mut in_flight = FuturesUnordered::new();
+
+    loop {
+        // Skipping a step can unblock (and skip) others, so repeat until stable
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (i, (step, task)) in steps.iter().enumerate() {
+                if started[i] || !dependencies[i].iter().all(|&d| results[d].is_some()) {
+                    continue;
+                }
+
+                let failed_dependency = dependencies[i]
+                    .iter()
+                    .find(|&&d| results[d].as_ref().is_some_and(|r| !r.success));
+                if let Some(&failed) = failed_dependency {
+                    warn!(
+                        "Skipping step {}: dependency {} failed",
+                        step.name, steps[failed].0.name
+                    );
+                    started[i] = true;
+                    results[i] = Some(StepExecutionResult {
+                        step_name: step.name.clone(),
+                        success: false,
+                        output: String::new(),
+                        saved_to: None,
+                        duration_ms: 0,
+                        error: Some(format!(
+                            "Skipped: dependency '{}' failed",
+                            steps[failed].0.name
+                        )),
+                        attempts: 0,
+                        compensation: None,
+                        timed_out: false,
+                        profile: StepProfile::default(),
+                    });
+                    changed = true;
+                } else if !stopped && in_flight.len() < max_parallel {
+                    debug!("Starting step {}", step.name);
+                    started[i] = true;
+                    in_flight.push(async move {
+                        (i, execute_step(step, task, context, backend, config).await)
+                    });
+                }
+            }
+        }
+
+        let Some((i, result)) = in_flight.next().await else {
+            break;
+        };
+        let result = result.unwrap_or_else(|e| {
+            warn!("Step {} failed: {}", steps[i].0.name, e);
+            StepExecutionResult {
+                step_name: steps[i].0.name.clone(),
+                success: false,
+                output: String::new(),
+                saved_to: None,
+                duration_ms: 0,
+                error: Some(format!("Execution error: {}", e)),
+                attempts: 1,
+                timed_out: false,
+                compensation: None,
+                profile: StepProfile::default(),
+            }
+        });
+        stopped |= saga && !result.success;
+        results[i] = Some(result);
+        completed.push(i);
+    }
+
+    if stopped {
+        warn!("Workflow step failed, compensating completed steps");
+        for (i, (step, _)) in steps.iter().enumerate() {
+            if results[i].is_none() {
+                results[i] = Some(StepExecutionResult {
+                    step_name: step.name.clone(),
+                    success: false,
+                    output: String::new(),
+                    saved_to: None,
+                    duration_ms: 0,
+                    error: Some("Skipped: workflow stopped after a failure".to_string()),
+                    attempts: 0,
+                    timed_out: false,
+                    compensation: None,
+                    profile: StepProfile::default(),
+                });
+            }
+        }
+        let mut results: Vec<_> = results.into_iter().flatten().collect();
+        compensate_steps(steps, &mut results, &completed, context, backend, config).await;
+        return Ok(results);
+    }
+
+    if let Some(i) = results.iter().position(Option::is_none) {
+        return Err(WorkflowExecutionError::StepFailed(format!(
+            "Dependency cycle involving step '{}'",
+            steps[i].0.name
+        )));
+    }
+
+    info!("Workflow graph execution complete: {} steps", steps.len());
+    Ok(results.into_iter().flatten().collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -319,10 +879,652 @@ mod tests {
             saved_to: Some(PathBuf::from("/tmp/test.md")),
             duration_ms: 100,
             error: None,
+            attempts: 1,
+            compensation: None,
+            timed_out: false,
+            profile: StepProfile::default(),
         };
 
         assert!(result.success);
         assert_eq!(result.step_name, "test-step");
         assert!(result.saved_to.is_some());
     }
+
+    struct DelayedBackend {
+        mode: crate::ModelProviderMode,
+        /// "start:<task>" / "end:<task>" for every completion, in order
+        calls: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl DelayedBackend {
+        fn new() -> Self {
+            Self {
+                mode: crate::ModelProviderMode::Local {
+                    endpoint: String::new(),
+                    timeout_secs: 1,
+                },
+                calls: Default::default(),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ModelBackend for DelayedBackend {
+        fn name(&self) -> &str {
+            "delayed"
+        }
+
+        fn mode(&self) -> &crate::ModelProviderMode {
+            &self.mode
+        }
+
+        async fn initialize(&mut self) -> crate::AgentResult<()> {
+            Ok(())
+        }
+
+        async fn health_check(&self) -> crate::AgentResult<bool> {
+            Ok(true)
+        }
+
+        async fn complete(
+            &self,
+            request: ModelRequest,
+        ) -> crate::AgentResult<crate::ModelResponse> {
+            // The task text names how long the "model" should take
+            let content = &request.messages[0].content;
+            let task = content
+                .split("Task: ")
+                .nth(1)
+                .and_then(|rest| rest.lines().next())
+                .unwrap_or_default()
+                .to_string();
+            let delay = if content.contains("slow") { 80 } else { 10 };
+            let attempt = {
+                let started = format!("start:{}", task);
+                let mut calls = self.calls.lock().unwrap();
+                calls.push(started.clone());
+                calls.iter().filter(|c| **c == started).count()
+            };
+            if content.contains("hang") {
+                std::future::pending::<()>().await;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+            self.calls.lock().unwrap().push(format!("end:{}", task));
+            // "fail" tasks always fail; "flaky" tasks fail on their first attempt
+            if task.contains("fail") || (task.contains("flaky") && attempt == 1) {
+                return Err(crate::errors::AgentError::ExecutionError(format!(
+                    "{} failed",
+                    task
+                )));
+            }
+            Ok(crate::ModelResponse {
+                content: "done".to_string(),
+                finish_reason: crate::FinishReason::Stop,
+                tokens_used: None,
+                tool_calls: None,
+            })
+        }
+
+        async fn stream(
+            &self,
+            _request: ModelRequest,
+        ) -> crate::AgentResult<
+            Box<
+                dyn futures::Stream<Item = crate::AgentResult<crate::ModelResponse>> + Unpin + Send,
+            >,
+        > {
+            Ok(Box::new(futures::stream::empty()))
+        }
+
+        async fn list_models(&self) -> crate::AgentResult<Vec<String>> {
+            Ok(vec![])
+        }
+
+        async fn estimate_tokens(&self, text: &str) -> crate::AgentResult<usize> {
+            Ok(text.len() / 4)
+        }
+
+        async fn shutdown(&mut self) -> crate::AgentResult<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_workflow_profile_records_per_step_timings() {
+        let temp = tempfile::tempdir().unwrap();
+        let agent_loader =
+            crate::agent_definitions::AgentDefinitionLoader::with_dir(temp.path().join("agents"))
+                .unwrap();
+        std::fs::write(
+            temp.path().join("agents/researcher.md"),
+            "---\nname: researcher\n---\nYou research.",
+        )
+        .unwrap();
+        let thoughts =
+            crate::thoughts::ThoughtsStorage::with_config(crate::thoughts::ThoughtsConfig {
+                global_root: temp.path().join("thoughts"),
+                ..Default::default()
+            })
+            .unwrap();
+        let context = WorkflowContext {
+            working_dir: temp.path().to_path_buf(),
+            topic: "profiling".to_string(),
+            context: None,
+            thoughts,
+            agent_loader,
+            variables: Default::default(),
+            state_machine: None,
+            state_store: None,
+        };
+
+        let step = |name: &str, output: Option<&str>| WorkflowStep {
+            name: name.to_string(),
+            agent: "researcher".to_string(),
+            task: String::new(),
+            output: output.map(String::from),
+            ..Default::default()
+        };
+        let steps = vec![
+            (step("fast", None), "quick look".to_string()),
+            (step("slow", Some("slow.md")), "slow dig".to_string()),
+        ];
+        let backend = Arc::new(DelayedBackend::new());
+
+        let results =
+            execute_workflow(steps, &context, backend, &WorkflowExecutorConfig::default())
+                .await
+                .unwrap();
+        assert!(results.iter().all(|r| r.success));
+
+        let profile = WorkflowProfile::from_results(&results);
+        let (fast, slow) = (profile.steps[0].1, profile.steps[1].1);
+        assert!(fast.agent_ms >= 10);
+        assert!(slow.agent_ms >= 80);
+        assert!(slow.agent_ms > fast.agent_ms);
+        assert!(results[1].saved_to.is_some());
+
+        let table = profile.render_table();
+        assert_eq!(table.lines().count(), 4);
+        assert!(table.lines().nth(2).unwrap().starts_with("slow"));
+        assert!(table.lines().last().unwrap().starts_with("TOTAL"));
+    }
+
+    #[tokio::test]
+    async fn test_profile_persisted_with_workflow_run() {
+        use crate::state_machine_store::{SqliteWorkflowStore, StateStoreConfig, WorkflowRecovery};
+
+        let temp = tempfile::tempdir().unwrap();
+        let store = Arc::new(
+            SqliteWorkflowStore::new("sqlite::memory:", StateStoreConfig::default())
+                .await
+                .unwrap(),
+        );
+        let machine = Arc::new(WorkflowStateMachine::new("research-1".to_string()));
+        let context = test_context(temp.path(), "profiling", "researcher")
+            .with_state(machine, Some(Arc::clone(&store)))
+            .await
+            .unwrap();
+
+        let step = |name: &str| WorkflowStep {
+            name: name.to_string(),
+            agent: "researcher".to_string(),
+            task: String::new(),
+            ..Default::default()
+        };
+        let steps = vec![
+            (step("fast"), "quick look".to_string()),
+            (step("slow"), "slow dig".to_string()),
+        ];
+        let results = execute_workflow(
+            steps,
+            &context,
+            Arc::new(DelayedBackend::new()),
+            &WorkflowExecutorConfig::default(),
+        )
+        .await
+        .unwrap();
+
+        let machine = WorkflowRecovery::recover_workflow(&store, "research-1")
+            .await
+            .unwrap();
+        let saved = WorkflowProfile::load(&machine).await.unwrap().unwrap();
+        assert_eq!(saved, WorkflowProfile::from_results(&results));
+        assert_eq!(saved.steps.len(), 2);
+    }
+
+    /// A context in `dir` with a single agent called `agent`
+    fn test_context(dir: &std::path::Path, topic: &str, agent: &str) -> WorkflowContext {
+        let agent_loader =
+            crate::agent_definitions::AgentDefinitionLoader::with_dir(dir.join("agents")).unwrap();
+        std::fs::write(
+            dir.join(format!("agents/{}.md", agent)),
+            format!("---\nname: {}\n---\nYou help.", agent),
+        )
+        .unwrap();
+        let thoughts =
+            crate::thoughts::ThoughtsStorage::with_config(crate::thoughts::ThoughtsConfig {
+                global_root: dir.join("thoughts"),
+                ..Default::default()
+            })
+            .unwrap();
+        WorkflowContext {
+            working_dir: dir.to_path_buf(),
+            topic: topic.to_string(),
+            context: None,
+            thoughts,
+            agent_loader,
+            variables: Default::default(),
+            state_machine: None,
+            state_store: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_postcondition_fails_step() {
+        let temp = tempfile::tempdir().unwrap();
+        let context = test_context(temp.path(), "contracts", "reviewer");
+        let step = WorkflowStep {
+            name: "review".to_string(),
+            agent: "reviewer".to_string(),
+            task: String::new(),
+            output: Some("review.md".to_string()),
+            contract: Some(Contract {
+                name: Some("review_contract".to_string()),
+                preconditions: vec!["topic == \"contracts\"".to_string()],
+                postconditions: vec!["output == \"approved\"".to_string()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let backend = DelayedBackend::new();
+
+        let err = execute_step(
+            &step,
+            "review it",
+            &context,
+            &backend,
+            &WorkflowExecutorConfig::default(),
+        )
+        .await
+        .unwrap_err();
+
+        match &err {
+            WorkflowExecutionError::ContractViolation {
+                step,
+                contract,
+                phase,
+                condition,
+                ..
+            } => {
+                assert_eq!(step, "review");
+                assert_eq!(contract, "review_contract");
+                assert_eq!(*phase, ContractPhase::Postcondition);
+                assert_eq!(condition, "output == \"approved\"");
+            }
+            other => panic!("expected a contract violation, got {:?}", other),
+        }
+        assert!(err
+            .to_string()
+            .contains("postcondition `output == \"approved\"` is not satisfied"));
+        assert!(!temp.path().join("thoughts/research/review.md").exists());
+    }
+
+    #[tokio::test]
+    async fn test_variables_survive_resume() {
+        use crate::state_machine_store::{SqliteWorkflowStore, StateStoreConfig, WorkflowRecovery};
+
+        let temp = tempfile::tempdir().unwrap();
+        let backend = DelayedBackend::new();
+        let config = WorkflowExecutorConfig::default();
+        let step = |name: &str| WorkflowStep {
+            name: name.to_string(),
+            agent: "planner".to_string(),
+            task: String::new(),
+            ..Default::default()
+        };
+
+        let store = Arc::new(
+            SqliteWorkflowStore::new("sqlite::memory:", StateStoreConfig::default())
+                .await
+                .unwrap(),
+        );
+        let machine = Arc::new(crate::state_machine::WorkflowStateMachine::new(
+            "release-1".to_string(),
+        ));
+        let context = test_context(temp.path(), "release", "planner")
+            .with_state(machine, Some(Arc::clone(&store)))
+            .await
+            .unwrap();
+        context.set("pr_number", 42).unwrap();
+        let open_pr = WorkflowStep {
+            set_variable: Some("pr_status".to_string()),
+            ..step("open-pr")
+        };
+        // Setting pr_status saves every variable with the state machine
+        let result = execute_step(&open_pr, "open pr", &context, &backend, &config)
+            .await
+            .unwrap();
+        assert!(result.success);
+
+        // Resume in a fresh context
+        let machine = WorkflowRecovery::recover_workflow(&store, "release-1")
+            .await
+            .unwrap();
+        let resumed = test_context(temp.path(), "release", "planner")
+            .with_state(machine, Some(store))
+            .await
+            .unwrap();
+        assert_eq!(resumed.get::<u64>("pr_number").unwrap(), Some(42));
+        assert!(resumed.get::<bool>("pr_status").is_err());
+
+        let merge = WorkflowStep {
+            contract: Some(Contract {
+                preconditions: vec![
+                    "vars.pr_number == 42 && vars.pr_status == \"done\"".to_string()
+                ],
+                ..Default::default()
+            }),
+            ..step("merge")
+        };
+        let result = execute_step(&merge, "merge", &resumed, &backend, &config)
+            .await
+            .unwrap();
+        assert!(result.success);
+    }
+
+    #[tokio::test]
+    async fn test_parallel_steps_share_variables_and_state() {
+        use crate::state_machine_store::{SqliteWorkflowStore, StateStoreConfig, WorkflowRecovery};
+
+        let temp = tempfile::tempdir().unwrap();
+        let store = Arc::new(
+            SqliteWorkflowStore::new("sqlite::memory:", StateStoreConfig::default())
+                .await
+                .unwrap(),
+        );
+        let machine = Arc::new(crate::state_machine::WorkflowStateMachine::new(
+            "fanout-1".to_string(),
+        ));
+        let context = test_context(temp.path(), "fanout", "planner")
+            .with_state(machine, Some(Arc::clone(&store)))
+            .await
+            .unwrap();
+        context.set("pr_number", 42).unwrap();
+
+        // Each branch needs the parent's variables and sets its own
+        let step = |name: &str, parallel: bool| WorkflowStep {
+            name: name.to_string(),
+            agent: "planner".to_string(),
+            task: String::new(),
+            parallel,
+            set_variable: Some(format!("{}_status", name)),
+            contract: Some(Contract {
+                preconditions: vec!["vars.pr_number == 42".to_string()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let steps = vec![
+            (step("lint", false), "lint".to_string()),
+            (step("test", true), "test".to_string()),
+        ];
+
+        let results = execute_workflow(
+            steps,
+            &context,
+            Arc::new(DelayedBackend::new()),
+            &WorkflowExecutorConfig::default(),
+        )
+        .await
+        .unwrap();
+        assert!(results.iter().all(|r| r.success), "{:?}", results);
+
+        assert_eq!(context.get::<u64>("pr_number").unwrap(), Some(42));
+        assert_eq!(
+            context.get::<String>("lint_status").unwrap().as_deref(),
+            Some("done")
+        );
+        assert_eq!(
+            context.get::<String>("test_status").unwrap().as_deref(),
+            Some("done")
+        );
+
+        // Both branches' variables were saved with the state machine
+        let machine = WorkflowRecovery::recover_workflow(&store, "fanout-1")
+            .await
+            .unwrap();
+        let resumed = test_context(temp.path(), "fanout", "planner")
+            .with_state(machine, Some(store))
+            .await
+            .unwrap();
+        assert_eq!(resumed.variables.to_json(), context.variables.to_json());
+    }
+
+    #[tokio::test]
+    async fn test_independent_steps_run_concurrently_before_dependent() {
+        let temp = tempfile::tempdir().unwrap();
+        let context = test_context(temp.path(), "graph", "planner");
+        let step = |name: &str, depends_on: &[&str]| WorkflowStep {
+            name: name.to_string(),
+            agent: "planner".to_string(),
+            task: String::new(),
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        };
+        let steps = vec![
+            (step("left", &[]), "slow left".to_string()),
+            (step("right", &[]), "slow right".to_string()),
+            (step("merge", &["left", "right"]), "merge".to_string()),
+        ];
+        let backend = Arc::new(DelayedBackend::new());
+
+        let results = execute_workflow(
+            steps,
+            &context,
+            backend.clone(),
+            &WorkflowExecutorConfig::default(),
+        )
+        .await
+        .unwrap();
+        let names: Vec<_> = results.iter().map(|r| r.step_name.as_str()).collect();
+        assert_eq!(names, vec!["left", "right", "merge"]);
+        assert!(results.iter().all(|r| r.success));
+
+        let calls = backend.calls.lock().unwrap().clone();
+        let position = |call: &str| calls.iter().position(|c| c == call).unwrap();
+        // Both branches start before either finishes
+        assert!(position("start:slow right") < position("end:slow left"));
+        assert!(position("start:slow left") < position("end:slow right"));
+        // The join waits for both
+        assert!(position("start:merge") > position("end:slow left"));
+        assert!(position("start:merge") > position("end:slow right"));
+    }
+
+    #[tokio::test]
+    async fn test_step_error_skips_dependents_but_not_independent_branches() {
+        let temp = tempfile::tempdir().unwrap();
+        let context = test_context(temp.path(), "graph", "planner");
+        let step = |name: &str, depends_on: &[&str]| WorkflowStep {
+            name: name.to_string(),
+            agent: "planner".to_string(),
+            task: String::new(),
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        };
+        let broken = WorkflowStep {
+            contract: Some(Contract {
+                preconditions: vec!["topic == \"elsewhere\"".to_string()],
+                ..Default::default()
+            }),
+            ..step("broken", &[])
+        };
+        let steps = vec![
+            (broken, "broken".to_string()),
+            (step("child", &["broken"]), "child".to_string()),
+            (step("grandchild", &["child"]), "grandchild".to_string()),
+            (step("independent", &[]), "independent".to_string()),
+        ];
+        let backend = Arc::new(DelayedBackend::new());
+
+        let results = execute_workflow(
+            steps,
+            &context,
+            backend.clone(),
+            &WorkflowExecutorConfig::default(),
+        )
+        .await
+        .unwrap();
+
+        assert!(!results[0].success);
+        assert!(results[0]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("precondition"));
+        for skipped in &results[1..3] {
+            assert!(!skipped.success);
+            assert_eq!(skipped.attempts, 0);
+            assert!(skipped.error.as_deref().unwrap().starts_with("Skipped"));
+        }
+        assert_eq!(results[3].step_name, "independent");
+        assert!(results[3].success);
+
+        let calls = backend.calls.lock().unwrap().clone();
+        assert!(calls.iter().all(|c| !c.contains("child")));
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_step_names_are_rejected() {
+        let temp = tempfile::tempdir().unwrap();
+        let context = test_context(temp.path(), "graph", "planner");
+        let step = |name: &str, depends_on: &[&str]| WorkflowStep {
+            name: name.to_string(),
+            agent: "planner".to_string(),
+            task: String::new(),
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        };
+        let steps = vec![
+            (step("build", &[]), "first".to_string()),
+            (step("build", &[]), "second".to_string()),
+            (step("test", &["build"]), "test".to_string()),
+        ];
+
+        let err = execute_workflow(
+            steps,
+            &context,
+            Arc::new(DelayedBackend::new()),
+            &WorkflowExecutorConfig::default(),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(
+            err,
+            WorkflowExecutionError::WorkflowError(WorkflowError::InvalidWorkflow(_))
+        ));
+        assert!(err.to_string().contains("Duplicate step name 'build'"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_step_compensates_earlier_steps_in_reverse() {
+        let temp = tempfile::tempdir().unwrap();
+        let context = test_context(temp.path(), "saga", "operator");
+        let step = |name: &str, compensation: Option<&str>| WorkflowStep {
+            name: name.to_string(),
+            agent: "operator".to_string(),
+            task: String::new(),
+            retry: Some(crate::RetryPolicy {
+                max_retries: 1,
+                initial_backoff_ms: 1,
+            }),
+            compensation: compensation.map(String::from),
+            ..Default::default()
+        };
+        let steps = vec![
+            (
+                step("create", Some("delete branch")),
+                "flaky create".to_string(),
+            ),
+            (
+                step("push", Some("unpush branch")),
+                "push branch".to_string(),
+            ),
+            (step("deploy", None), "fail deploy".to_string()),
+            (step("announce", None), "announce".to_string()),
+        ];
+        let backend = Arc::new(DelayedBackend::new());
+
+        let results = execute_workflow(
+            steps,
+            &context,
+            backend.clone(),
+            &WorkflowExecutorConfig::default(),
+        )
+        .await
+        .unwrap();
+
+        // The saga stops at the failure; "announce" never runs
+        let names: Vec<_> = results.iter().map(|r| r.step_name.as_str()).collect();
+        assert_eq!(names, vec!["create", "push", "deploy"]);
+        assert_eq!(results[0].attempts, 2);
+        assert_eq!(results[1].attempts, 1);
+        assert!(!results[2].success);
+        assert_eq!(results[2].attempts, 2);
+        assert!(results[2].compensation.is_none());
+        for result in &results[..2] {
+            let compensation = result.compensation.as_ref().unwrap();
+            assert!(compensation.success);
+        }
+
+        let calls = backend.calls.lock().unwrap().clone();
+        let starts: Vec<_> = calls
+            .iter()
+            .filter_map(|c| c.strip_prefix("start:"))
+            .collect();
+        assert_eq!(
+            starts,
+            vec![
+                "flaky create",
+                "flaky create",
+                "push branch",
+                "fail deploy",
+                "fail deploy",
+                "unpush branch",
+                "delete branch",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hung_step_times_out_and_fails_run() {
+        let temp = tempfile::tempdir().unwrap();
+        let context = test_context(temp.path(), "timeouts", "operator");
+        let step = |name: &str, timeout: Option<u64>| WorkflowStep {
+            name: name.to_string(),
+            agent: "operator".to_string(),
+            task: String::new(),
+            timeout: timeout.map(std::time::Duration::from_millis),
+            ..Default::default()
+        };
+        let steps = vec![
+            (step("wait", Some(20)), "hang forever".to_string()),
+            (step("report", Some(1_000)), "report".to_string()),
+        ];
+        let backend = Arc::new(DelayedBackend::new());
+
+        let results =
+            execute_workflow(steps, &context, backend, &WorkflowExecutorConfig::default())
+                .await
+                .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!(!results[0].success);
+        assert!(results[0].timed_out);
+        assert!(results[0].error.as_deref().unwrap().contains("timed out"));
+        assert!(results[0].profile.agent_ms < 1_000);
+        // A generous timeout doesn't fire
+        assert!(results[1].success);
+        assert!(!results[1].timed_out);
+    }
 }
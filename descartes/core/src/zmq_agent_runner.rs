@@ -34,15 +34,29 @@ use crate::traits::{AgentConfig, AgentInfo, AgentStatus};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::future::Future;
 use std::time::SystemTime;
 use uuid::Uuid;
 
 /// Message version for protocol compatibility checking
-pub const ZMQ_PROTOCOL_VERSION: &str = "1.0.0";
+///
+/// 1.1.0 added zstd-compressed frames (see [`serialize_zmq_message`]).
+pub const ZMQ_PROTOCOL_VERSION: &str = "1.1.0";
 
 /// Maximum message size (10 MB)
 pub const MAX_MESSAGE_SIZE: usize = 10 * 1024 * 1024;
 
+/// Serialized messages larger than this (64 KB) are compressed
+pub const COMPRESSION_THRESHOLD: usize = 64 * 1024;
+
+/// Maximum size a compressed message may expand to (100 MB)
+pub const MAX_DECOMPRESSED_SIZE: usize = 100 * 1024 * 1024;
+
+/// First byte of a compressed frame.
+///
+/// 0xc1 is never used by MessagePack, so it can't start a plain 1.0 frame.
+const COMPRESSED_FRAME_FLAG: u8 = 0xc1;
+
 /// Default timeout for ZMQ operations (30 seconds)
 pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
 
@@ -177,6 +191,9 @@ pub enum ControlCommandType {
 
     /// Stream agent logs
     StreamLogs,
+
+    /// Spawn new agents (batch only, see [`BatchControlCommand::spawn_configs`])
+    Spawn,
 }
 
 /// Control command to send to an agent.
@@ -480,6 +497,14 @@ pub struct BatchControlCommand {
     /// Whether to fail fast or continue on errors
     #[serde(default)]
     pub fail_fast: bool,
+
+    /// Agents to spawn when `command_type` is [`ControlCommandType::Spawn`]
+    #[serde(default)]
+    pub spawn_configs: Vec<AgentConfig>,
+
+    /// For spawn batches, kill the agents already spawned if any spawn fails
+    #[serde(default)]
+    pub all_or_nothing: bool,
 }
 
 /// Batch control response
@@ -502,6 +527,9 @@ pub struct BatchControlResponse {
 }
 
 /// Result for a single agent in a batch operation
+///
+/// Spawn batches report one result per config, in order; configs that produced
+/// no agent carry a nil `agent_id`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BatchAgentResult {
     /// Agent ID
@@ -519,6 +547,74 @@ pub struct BatchAgentResult {
     pub error: Option<String>,
 }
 
+/// Spawn a batch of agents in order, stopping at the first failure.
+///
+/// `spawn` is called with each config's index; configs after a failed one are
+/// reported as skipped. With `all_or_nothing`, the agents spawned before the
+/// failure are passed to `kill` and reported as rolled back.
+///
+/// Shared by the server's batch handler and the default
+/// [`ZmqAgentRunner::spawn_remote_batch`] so both report the same results.
+pub async fn spawn_batch<S, SF, K, KF>(
+    configs: Vec<AgentConfig>,
+    all_or_nothing: bool,
+    mut spawn: S,
+    mut kill: K,
+) -> Vec<BatchAgentResult>
+where
+    S: FnMut(usize, AgentConfig) -> SF,
+    SF: Future<Output = Result<AgentInfo, String>>,
+    K: FnMut(Uuid) -> KF,
+    KF: Future<Output = AgentResult<()>>,
+{
+    let mut results = Vec::with_capacity(configs.len());
+    let mut failure = None;
+
+    for (index, config) in configs.into_iter().enumerate() {
+        if let Some(name) = &failure {
+            results.push(BatchAgentResult {
+                agent_id: Uuid::nil(),
+                success: false,
+                status: None,
+                error: Some(format!("Skipped: spawning '{}' failed", name)),
+            });
+            continue;
+        }
+
+        let name = config.name.clone();
+        match spawn(index, config).await {
+            Ok(info) => results.push(BatchAgentResult {
+                agent_id: info.id,
+                success: true,
+                status: Some(info.status),
+                error: None,
+            }),
+            Err(error) => {
+                results.push(BatchAgentResult {
+                    agent_id: Uuid::nil(),
+                    success: false,
+                    status: None,
+                    error: Some(error),
+                });
+                failure = Some(name);
+            }
+        }
+    }
+
+    if let (Some(name), true) = (&failure, all_or_nothing) {
+        for result in results.iter_mut().filter(|r| r.success) {
+            if let Err(e) = kill(result.agent_id).await {
+                tracing::warn!("Failed to roll back agent {}: {}", result.agent_id, e);
+            }
+            result.success = false;
+            result.status = Some(AgentStatus::Terminated);
+            result.error = Some(format!("Rolled back: spawning '{}' failed", name));
+        }
+    }
+
+    results
+}
+
 /// Output query request for retrieving agent output with filtering
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OutputQueryRequest {
@@ -741,6 +837,42 @@ pub trait ZmqAgentRunner: Send + Sync {
         timeout_secs: Option<u64>,
     ) -> AgentResult<AgentInfo>;
 
+    /// Spawn several agents on the remote server in one request.
+    ///
+    /// Spawning stops at the first failure; with `all_or_nothing` the agents
+    /// already spawned are killed as well. Per-agent outcomes are in the
+    /// response's `results`, in `configs` order.
+    ///
+    /// The default implementation issues one `spawn_remote` per config, so
+    /// runners without a batch message still get the same semantics.
+    async fn spawn_remote_batch(
+        &self,
+        configs: Vec<AgentConfig>,
+        all_or_nothing: bool,
+    ) -> AgentResult<BatchControlResponse> {
+        let results = spawn_batch(
+            configs,
+            all_or_nothing,
+            |_, config| async move {
+                self.spawn_remote(config, None)
+                    .await
+                    .map_err(|e| e.to_string())
+            },
+            |agent_id| async move { self.kill_agent(&agent_id).await },
+        )
+        .await;
+
+        let successful = results.iter().filter(|r| r.success).count();
+        let failed = results.len() - successful;
+        Ok(BatchControlResponse {
+            request_id: Uuid::new_v4().to_string(),
+            success: failed == 0,
+            results,
+            successful,
+            failed,
+        })
+    }
+
     /// List all agents on the remote server.
     ///
     /// # Arguments
@@ -829,6 +961,10 @@ pub trait ZmqAgentRunner: Send + Sync {
 
 /// Serialize a ZmqMessage to MessagePack bytes.
 ///
+/// Messages whose encoding exceeds [`COMPRESSION_THRESHOLD`] are zstd
+/// compressed and prefixed with a flag byte; smaller ones are sent as plain
+/// MessagePack, readable by 1.0 peers.
+///
 /// # Example
 ///
 /// ```rust
@@ -842,12 +978,38 @@ pub trait ZmqAgentRunner: Send + Sync {
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 pub fn serialize_zmq_message(msg: &ZmqMessage) -> AgentResult<Vec<u8>> {
-    rmp_serde::to_vec(msg)
-        .map_err(|e| AgentError::ExecutionError(format!("Failed to serialize ZMQ message: {}", e)))
+    serialize_zmq_message_with(msg, Some(COMPRESSION_THRESHOLD))
+}
+
+/// Serialize a ZmqMessage, compressing it above `compression_threshold` bytes.
+///
+/// `None` never compresses, for peers that only speak protocol 1.0.
+pub fn serialize_zmq_message_with(
+    msg: &ZmqMessage,
+    compression_threshold: Option<usize>,
+) -> AgentResult<Vec<u8>> {
+    let bytes = rmp_serde::to_vec(msg).map_err(|e| {
+        AgentError::ExecutionError(format!("Failed to serialize ZMQ message: {}", e))
+    })?;
+
+    match compression_threshold {
+        Some(threshold) if bytes.len() > threshold => {
+            let compressed = zstd::bulk::compress(&bytes, 0).map_err(|e| {
+                AgentError::ExecutionError(format!("Failed to compress ZMQ message: {}", e))
+            })?;
+            let mut frame = Vec::with_capacity(compressed.len() + 1);
+            frame.push(COMPRESSED_FRAME_FLAG);
+            frame.extend_from_slice(&compressed);
+            Ok(frame)
+        }
+        _ => Ok(bytes),
+    }
 }
 
 /// Deserialize a ZmqMessage from MessagePack bytes.
 ///
+/// Compressed frames are decompressed transparently.
+///
 /// # Example
 ///
 /// ```rust
@@ -862,6 +1024,18 @@ pub fn serialize_zmq_message(msg: &ZmqMessage) -> AgentResult<Vec<u8>> {
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 pub fn deserialize_zmq_message(bytes: &[u8]) -> AgentResult<ZmqMessage> {
+    let decompressed;
+    let bytes = match bytes.split_first() {
+        Some((&COMPRESSED_FRAME_FLAG, compressed)) => {
+            decompressed =
+                zstd::bulk::decompress(compressed, MAX_DECOMPRESSED_SIZE).map_err(|e| {
+                    AgentError::ExecutionError(format!("Failed to decompress ZMQ message: {}", e))
+                })?;
+            &decompressed[..]
+        }
+        _ => bytes,
+    };
+
     rmp_serde::from_slice(bytes).map_err(|e| {
         AgentError::ExecutionError(format!("Failed to deserialize ZMQ message: {}", e))
     })
@@ -1044,6 +1218,41 @@ mod tests {
         assert!(validate_message_size(MAX_MESSAGE_SIZE + 1).is_err());
     }
 
+    #[test]
+    fn test_large_messages_are_compressed() {
+        let output = "cargo test output line\n".repeat(500_000);
+        let msg = ZmqMessage::LogStream(LogStreamMessage {
+            agent_id: Uuid::new_v4(),
+            stream_type: LogStreamType::Stdout,
+            data: output.clone().into_bytes(),
+            timestamp: SystemTime::now(),
+            sequence: 7,
+        });
+
+        // Uncompressed it would hit the size ceiling
+        let plain = serialize_zmq_message_with(&msg, None).unwrap();
+        assert!(validate_message_size(plain.len()).is_err());
+
+        let bytes = serialize_zmq_message(&msg).unwrap();
+        assert_eq!(bytes[0], COMPRESSED_FRAME_FLAG);
+        assert!(validate_message_size(bytes.len()).is_ok());
+        match deserialize_zmq_message(&bytes).unwrap() {
+            ZmqMessage::LogStream(log) => {
+                assert_eq!(log.data, output.into_bytes());
+                assert_eq!(log.sequence, 7);
+            }
+            _ => panic!("Wrong message type"),
+        }
+
+        // Small messages stay plain MessagePack, as 1.0 peers send them
+        let small = ZmqMessage::HealthCheckRequest(HealthCheckRequest {
+            request_id: "health-2".to_string(),
+        });
+        let bytes = serialize_zmq_message(&small).unwrap();
+        assert_eq!(bytes, rmp_serde::to_vec(&small).unwrap());
+        assert!(deserialize_zmq_message(&bytes).is_ok());
+    }
+
     #[test]
     fn test_zmq_runner_config_default() {
         let config = ZmqRunnerConfig::default();
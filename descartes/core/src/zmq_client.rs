@@ -37,7 +37,9 @@ use crate::zmq_agent_runner::{
     OutputQueryResponse, SpawnRequest, StatusUpdate, ZmqAgentRunner, ZmqMessage, ZmqOutputStream,
     ZmqRunnerConfig,
 };
-use crate::zmq_communication::{SocketType, ZmqConnection, ZmqMessageRouter};
+use crate::zmq_communication::{
+    ConnectionState, HeartbeatPolicy, SocketType, ZmqConnection, ZmqMessageRouter,
+};
 use async_trait::async_trait;
 use futures::stream::{Stream, StreamExt};
 use parking_lot::RwLock;
@@ -47,6 +49,9 @@ use std::time::Duration;
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
+/// How long the response reader waits on the socket before letting sends through
+const RESPONSE_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 /// Queued command for offline operation
 #[derive(Debug, Clone)]
 struct QueuedCommand {
@@ -66,8 +71,10 @@ pub struct ZmqClient {
     sub_connection: Arc<Mutex<Option<ZmqConnection>>>,
     /// Configuration
     config: ZmqRunnerConfig,
+    /// Socket type of the command connection
+    socket_type: SocketType,
     /// Message router for request/response correlation
-    _router: Arc<ZmqMessageRouter>,
+    router: Arc<ZmqMessageRouter>,
     /// Status update subscribers
     status_subscribers:
         Arc<RwLock<Vec<tokio::sync::mpsc::UnboundedSender<AgentResult<StatusUpdate>>>>>,
@@ -78,6 +85,10 @@ pub struct ZmqClient {
     command_queue: Arc<Mutex<VecDeque<QueuedCommand>>>,
     /// Maximum queue size (prevents unbounded growth during long disconnections)
     _max_queue_size: usize,
+    /// Heartbeat policy applied to each command connection
+    heartbeat_policy: HeartbeatPolicy,
+    /// Whether the task routing DEALER responses to their calls is running
+    response_reader: Arc<Mutex<bool>>,
 }
 
 impl ZmqClient {
@@ -103,14 +114,17 @@ impl ZmqClient {
         let connection = ZmqConnection::new(SocketType::Req, &config.endpoint, config.clone());
 
         Self {
+            socket_type: SocketType::Req,
+            heartbeat_policy: HeartbeatPolicy::from_config(&config),
             connection: Arc::new(Mutex::new(connection)),
             sub_connection: Arc::new(Mutex::new(None)),
             config,
-            _router: Arc::new(ZmqMessageRouter::new()),
+            router: Arc::new(ZmqMessageRouter::new()),
             status_subscribers: Arc::new(RwLock::new(Vec::new())),
             log_subscribers: Arc::new(RwLock::new(HashMap::new())),
             command_queue: Arc::new(Mutex::new(VecDeque::new())),
             _max_queue_size: 1000, // Default: queue up to 1000 commands
+            response_reader: Arc::new(Mutex::new(false)),
         }
     }
 
@@ -124,17 +138,65 @@ impl ZmqClient {
         let connection = ZmqConnection::new(socket_type, &config.endpoint, config.clone());
 
         Self {
+            socket_type,
+            heartbeat_policy: HeartbeatPolicy::from_config(&config),
             connection: Arc::new(Mutex::new(connection)),
             sub_connection: Arc::new(Mutex::new(None)),
             config,
-            _router: Arc::new(ZmqMessageRouter::new()),
+            router: Arc::new(ZmqMessageRouter::new()),
             status_subscribers: Arc::new(RwLock::new(Vec::new())),
             log_subscribers: Arc::new(RwLock::new(HashMap::new())),
             command_queue: Arc::new(Mutex::new(VecDeque::new())),
             _max_queue_size: 1000, // Default: queue up to 1000 commands
+            response_reader: Arc::new(Mutex::new(false)),
         }
     }
 
+    /// Set the heartbeat policy used by [`ZmqClient::start_heartbeat`]
+    pub fn with_heartbeat_policy(mut self, policy: HeartbeatPolicy) -> Self {
+        self.heartbeat_policy = policy;
+        self
+    }
+
+    /// Current state of the command connection
+    pub async fn connection_state(&self) -> ConnectionState {
+        self.connection.lock().await.state()
+    }
+
+    /// Start heartbeating the command connection in the background
+    ///
+    /// Returns a receiver for the connection state, updated after every
+    /// heartbeat, so callers can react when the server goes
+    /// [`ConnectionState::Stale`] or is disconnected. The task stops once the
+    /// connection is no longer usable or the receiver is dropped; call this
+    /// again after reconnecting. With heartbeats disabled no task is started.
+    pub async fn start_heartbeat(&self) -> tokio::sync::watch::Receiver<ConnectionState> {
+        let (state_tx, state_rx) = tokio::sync::watch::channel(self.connection_state().await);
+        if !self.heartbeat_policy.enabled {
+            return state_rx;
+        }
+
+        // Heartbeats use their own socket, so commands never wait on them
+        let heartbeat = self.connection.lock().await.heartbeater();
+        let interval = self.heartbeat_policy.interval;
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let state = heartbeat.beat().await;
+                state_tx.send_if_modified(|current| {
+                    let changed = *current != state;
+                    *current = state;
+                    changed
+                });
+                let usable = matches!(state, ConnectionState::Connected | ConnectionState::Stale);
+                if !usable || state_tx.is_closed() {
+                    break;
+                }
+            }
+        });
+        state_rx
+    }
+
     /// Send a control command to an agent
     async fn send_control_command(
         &self,
@@ -157,10 +219,7 @@ impl ZmqClient {
             .connection
             .lock()
             .await
-            .request_response(
-                &request,
-                Some(Duration::from_secs(self.config.request_timeout_secs)),
-            )
+            .request_response(&request, None)
             .await?;
 
         match response {
@@ -186,6 +245,134 @@ impl ZmqClient {
         }
     }
 
+    /// Send a control command and wait up to `timeout` for its response
+    ///
+    /// The response is matched to the command by `request_id`, so it needs an
+    /// asynchronous (DEALER) connection; other socket types are rejected. A
+    /// background reader routes each response to its caller, so concurrent
+    /// calls don't wait on each other, and drops late responses to calls that
+    /// already timed out. The timeout covers the whole call, including sending
+    /// the command. Returns [`AgentError::Timeout`] if no matching response
+    /// arrives in time.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use descartes_core::{ControlCommand, ControlCommandType, ZmqClient};
+    /// # use std::time::Duration;
+    /// # use uuid::Uuid;
+    /// # async fn example(client: &ZmqClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// let command = ControlCommand {
+    ///     request_id: Uuid::new_v4().to_string(),
+    ///     agent_id: Uuid::new_v4(),
+    ///     command_type: ControlCommandType::Pause,
+    ///     payload: None,
+    /// };
+    /// let response = client.call_with_timeout(command, Duration::from_secs(5)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn call_with_timeout(
+        &self,
+        command: ControlCommand,
+        timeout: Duration,
+    ) -> AgentResult<CommandResponse> {
+        if self.socket_type != SocketType::Dealer {
+            return Err(AgentError::ExecutionError(format!(
+                "call_with_timeout needs a DEALER connection, not {:?}",
+                self.socket_type
+            )));
+        }
+
+        let deadline = tokio::time::Instant::now() + timeout;
+        let request_id = command.request_id.clone();
+        let response_rx = self.router.register_request(request_id.clone()).await;
+        self.start_response_reader().await;
+
+        let outcome = tokio::time::timeout_at(deadline, async {
+            self.connection
+                .lock()
+                .await
+                .send_message(&ZmqMessage::ControlCommand(command))
+                .await?;
+            response_rx.await.map_err(|_| {
+                AgentError::ExecutionError(format!("Request {} was cancelled", request_id))
+            })?
+        })
+        .await;
+
+        match outcome {
+            Ok(Ok(ZmqMessage::CommandResponse(resp))) => Ok(resp),
+            Ok(Ok(_)) => Err(AgentError::ExecutionError(
+                "Unexpected response type".to_string(),
+            )),
+            Ok(Err(e)) => {
+                self.router.cancel_request(&request_id).await;
+                Err(e)
+            }
+            Err(_) => {
+                self.router.cancel_request(&request_id).await;
+                Err(AgentError::Timeout(format!(
+                    "No response to request {} within {:?}",
+                    request_id, timeout
+                )))
+            }
+        }
+    }
+
+    /// Start the task that reads responses off the command connection and
+    /// routes them to waiting calls by request id, unless it is running
+    ///
+    /// The reader never holds the client's connection lock while it waits, and
+    /// holds the socket for at most [`RESPONSE_POLL_INTERVAL`] at a time so
+    /// commands can be sent in between. It stops once no call is waiting.
+    async fn start_response_reader(&self) {
+        let mut running = self.response_reader.lock().await;
+        if *running {
+            return;
+        }
+        *running = true;
+
+        let response_reader = Arc::clone(&self.response_reader);
+        let connection = Arc::clone(&self.connection);
+        let router = Arc::clone(&self.router);
+        tokio::spawn(async move {
+            loop {
+                // Picks up a connection replaced by a reconnect
+                let receiver = connection.lock().await.receiver();
+                match receiver.try_receive(RESPONSE_POLL_INTERVAL).await {
+                    Ok(Some(ZmqMessage::CommandResponse(resp))) => {
+                        let id = resp.request_id.clone();
+                        if router
+                            .route_response(&id, Ok(ZmqMessage::CommandResponse(resp)))
+                            .await
+                            .is_err()
+                        {
+                            tracing::debug!("Dropping response to unknown request {}", id);
+                        }
+                    }
+                    Ok(Some(other)) => {
+                        tracing::debug!(
+                            "Ignoring unexpected message on command connection: {:?}",
+                            std::mem::discriminant(&other)
+                        );
+                    }
+                    Ok(None) => {}
+                    // Not connected; waiting calls time out on their own
+                    Err(_) => tokio::time::sleep(RESPONSE_POLL_INTERVAL).await,
+                }
+
+                // Checked under the flag, so a call registered after this sees
+                // the reader stopped and starts a new one
+                let mut running = response_reader.lock().await;
+                if router.pending_count().await == 0 {
+                    *running = false;
+                    break;
+                }
+            }
+        });
+    }
+
     /// Send a custom action to an agent
     ///
     /// # Arguments
@@ -239,12 +426,7 @@ impl ZmqClient {
             .connection
             .lock()
             .await
-            .request_response(
-                &message,
-                Some(Duration::from_secs(
-                    timeout_secs.unwrap_or(self.config.request_timeout_secs),
-                )),
-            )
+            .request_response(&message, timeout_secs.map(Duration::from_secs))
             .await?;
 
         match response {
@@ -332,10 +514,7 @@ impl ZmqClient {
             .connection
             .lock()
             .await
-            .request_response(
-                &message,
-                Some(Duration::from_secs(self.config.request_timeout_secs)),
-            )
+            .request_response(&message, None)
             .await?;
 
         match response {
@@ -410,24 +589,28 @@ impl ZmqClient {
         let request_id = Uuid::new_v4().to_string();
 
         let request = BatchControlCommand {
-            request_id: request_id.clone(),
+            request_id,
             agent_ids,
             command_type,
             payload,
             fail_fast,
+            spawn_configs: Vec::new(),
+            all_or_nothing: false,
         };
 
+        self.send_batch(request).await
+    }
+
+    /// Send a batch command and wait for its response
+    async fn send_batch(&self, request: BatchControlCommand) -> AgentResult<BatchControlResponse> {
+        let request_id = request.request_id.clone();
         let message = ZmqMessage::BatchControlCommand(request);
 
-        let response = self
-            .connection
-            .lock()
-            .await
-            .request_response(
-                &message,
-                Some(Duration::from_secs(self.config.request_timeout_secs * 2)),
-            )
-            .await?;
+        let connection = self.connection.lock().await;
+        // A batch fans out to several agents, so allow twice the usual round-trip
+        let timeout = connection.current_timeout() * 2;
+        let response = connection.request_response(&message, Some(timeout)).await?;
+        drop(connection);
 
         match response {
             ZmqMessage::BatchControlResponse(resp) => {
@@ -519,10 +702,7 @@ impl ZmqClient {
                 .connection
                 .lock()
                 .await
-                .request_response(
-                    &queued_cmd.message,
-                    Some(Duration::from_secs(self.config.request_timeout_secs)),
-                )
+                .request_response(&queued_cmd.message, None)
                 .await;
 
             // Send result to the waiting caller (if still listening)
@@ -772,7 +952,9 @@ impl ZmqAgentRunner for ZmqClient {
         }
 
         // Create new connection with updated endpoint
-        let mut new_connection = ZmqConnection::new(SocketType::Req, endpoint, self.config.clone());
+        let mut new_connection =
+            ZmqConnection::new(self.socket_type, endpoint, self.config.clone())
+                .with_heartbeat_policy(self.heartbeat_policy);
 
         new_connection.connect().await?;
 
@@ -827,12 +1009,7 @@ impl ZmqAgentRunner for ZmqClient {
             .connection
             .lock()
             .await
-            .request_response(
-                &message,
-                Some(Duration::from_secs(
-                    timeout_secs.unwrap_or(self.config.request_timeout_secs),
-                )),
-            )
+            .request_response(&message, timeout_secs.map(Duration::from_secs))
             .await?;
 
         match response {
@@ -860,6 +1037,24 @@ impl ZmqAgentRunner for ZmqClient {
         }
     }
 
+    async fn spawn_remote_batch(
+        &self,
+        configs: Vec<AgentConfig>,
+        all_or_nothing: bool,
+    ) -> AgentResult<BatchControlResponse> {
+        let request = BatchControlCommand {
+            request_id: Uuid::new_v4().to_string(),
+            agent_ids: Vec::new(),
+            command_type: ControlCommandType::Spawn,
+            payload: None,
+            fail_fast: true,
+            spawn_configs: configs,
+            all_or_nothing,
+        };
+
+        self.send_batch(request).await
+    }
+
     async fn list_remote_agents(
         &self,
         filter_status: Option<AgentStatus>,
@@ -879,10 +1074,7 @@ impl ZmqAgentRunner for ZmqClient {
             .connection
             .lock()
             .await
-            .request_response(
-                &message,
-                Some(Duration::from_secs(self.config.request_timeout_secs)),
-            )
+            .request_response(&message, None)
             .await?;
 
         match response {
@@ -1014,10 +1206,7 @@ impl ZmqAgentRunner for ZmqClient {
             .connection
             .lock()
             .await
-            .request_response(
-                &message,
-                Some(Duration::from_secs(self.config.request_timeout_secs)),
-            )
+            .request_response(&message, None)
             .await?;
 
         match response {
@@ -1090,6 +1279,239 @@ mod tests {
         assert!(true);
     }
 
+    #[tokio::test]
+    async fn test_heartbeat_detects_unresponsive_server() {
+        use crate::zmq_agent_runner::{serialize_zmq_message, ZMQ_PROTOCOL_VERSION};
+        use zeromq::{Socket, SocketRecv, SocketSend};
+
+        let endpoint = "tcp://127.0.0.1:15570";
+        let mut server = zeromq::RepSocket::new();
+        server.bind(endpoint).await.unwrap();
+
+        // Stub server: answer the first heartbeat, then stop responding
+        let stub = tokio::spawn(async move {
+            let request = server.recv().await.unwrap();
+            let bytes = request.into_vec()[0].to_vec();
+            let request_id = match deserialize_zmq_message(&bytes).unwrap() {
+                ZmqMessage::HealthCheckRequest(req) => req.request_id,
+                other => panic!("Expected a heartbeat, got {:?}", other),
+            };
+            let pong = ZmqMessage::HealthCheckResponse(HealthCheckResponse {
+                request_id,
+                healthy: true,
+                protocol_version: ZMQ_PROTOCOL_VERSION.to_string(),
+                uptime_secs: None,
+                active_agents: None,
+                metadata: None,
+            });
+            server
+                .send(serialize_zmq_message(&pong).unwrap().into())
+                .await
+                .unwrap();
+            std::future::pending::<()>().await;
+        });
+
+        let policy = HeartbeatPolicy {
+            enabled: true,
+            interval: Duration::from_millis(50),
+            timeout: Duration::from_millis(100),
+            stale_after: 1,
+            disconnect_after: 3,
+        };
+        let client = ZmqClient::new(ZmqRunnerConfig {
+            endpoint: endpoint.to_string(),
+            ..Default::default()
+        })
+        .with_heartbeat_policy(policy);
+        client.connect(endpoint).await.unwrap();
+
+        let started = std::time::Instant::now();
+        let mut states = client.start_heartbeat().await;
+        let mut seen = vec![*states.borrow()];
+        tokio::time::timeout(Duration::from_secs(2), async {
+            while *states.borrow() != ConnectionState::Disconnected {
+                states.changed().await.unwrap();
+                seen.push(*states.borrow());
+            }
+        })
+        .await
+        .expect("connection was not marked disconnected in time");
+
+        // One answered heartbeat, then three misses of at most interval + timeout
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(
+            seen,
+            vec![
+                ConnectionState::Connected,
+                ConnectionState::Stale,
+                ConnectionState::Disconnected
+            ]
+        );
+        assert_eq!(
+            client.connection_state().await,
+            ConnectionState::Disconnected
+        );
+        let stats = client.connection.lock().await.stats();
+        assert_eq!(stats.missed_heartbeats, 3);
+        assert!(stats.last_heartbeat.is_some());
+
+        stub.abort();
+    }
+
+    /// Client on a DEALER connection to a stub ROUTER server at `endpoint`
+    async fn dealer_client_with_stub(endpoint: &str) -> (ZmqClient, zeromq::RouterSocket) {
+        use zeromq::Socket;
+
+        let mut server = zeromq::RouterSocket::new();
+        server.bind(endpoint).await.unwrap();
+        let client = ZmqClient::new_with_socket_type(
+            SocketType::Dealer,
+            ZmqRunnerConfig {
+                endpoint: endpoint.to_string(),
+                ..Default::default()
+            },
+        );
+        client.connect(endpoint).await.unwrap();
+        (client, server)
+    }
+
+    fn pause_command() -> ControlCommand {
+        ControlCommand {
+            request_id: Uuid::new_v4().to_string(),
+            agent_id: Uuid::new_v4(),
+            command_type: ControlCommandType::Pause,
+            payload: None,
+        }
+    }
+
+    /// Read one command from the stub server: (peer identity, request id)
+    async fn stub_recv(server: &mut zeromq::RouterSocket) -> (bytes::Bytes, String) {
+        use zeromq::SocketRecv;
+
+        let frames = server.recv().await.unwrap().into_vec();
+        match deserialize_zmq_message(&frames[1]).unwrap() {
+            ZmqMessage::ControlCommand(cmd) => (frames[0].clone(), cmd.request_id),
+            other => panic!("Expected a control command, got {:?}", other),
+        }
+    }
+
+    async fn stub_reply(server: &mut zeromq::RouterSocket, peer: bytes::Bytes, request_id: &str) {
+        use crate::zmq_agent_runner::serialize_zmq_message;
+        use zeromq::SocketSend;
+
+        let response = ZmqMessage::CommandResponse(CommandResponse {
+            request_id: request_id.to_string(),
+            agent_id: Uuid::new_v4(),
+            success: true,
+            error: None,
+            data: None,
+            status: None,
+        });
+        let payload = serialize_zmq_message(&response).unwrap();
+        let mut message = zeromq::ZmqMessage::from(peer);
+        message.push_back(payload.into());
+        server.send(message).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_call_with_timeout_times_out() {
+        let (client, mut server) = dealer_client_with_stub("tcp://127.0.0.1:15571").await;
+
+        let command = pause_command();
+        let request_id = command.request_id.clone();
+        let started = std::time::Instant::now();
+        let result = client
+            .call_with_timeout(command, Duration::from_millis(200))
+            .await;
+
+        assert!(matches!(result, Err(AgentError::Timeout(_))));
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert_eq!(client.router.pending_count().await, 0);
+        // The server did get the command; it just never answered
+        assert_eq!(stub_recv(&mut server).await.1, request_id);
+    }
+
+    #[tokio::test]
+    async fn test_call_with_timeout_matches_out_of_order_responses() {
+        let (client, mut server) = dealer_client_with_stub("tcp://127.0.0.1:15572").await;
+
+        // The first call times out; its response only arrives later
+        let late = pause_command();
+        let result = client
+            .call_with_timeout(late, Duration::from_millis(100))
+            .await;
+        assert!(matches!(result, Err(AgentError::Timeout(_))));
+        let (peer, late_id) = stub_recv(&mut server).await;
+
+        let command = pause_command();
+        let request_id = command.request_id.clone();
+        let stub = tokio::spawn(async move {
+            let (_, id) = stub_recv(&mut server).await;
+            // Answer the stale request first, then the current one
+            stub_reply(&mut server, peer.clone(), &late_id).await;
+            stub_reply(&mut server, peer, &id).await;
+            server
+        });
+
+        let response = client
+            .call_with_timeout(command, Duration::from_secs(2))
+            .await
+            .unwrap();
+        assert_eq!(response.request_id, request_id);
+        assert_eq!(client.router.pending_count().await, 0);
+        stub.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_call_with_timeout_does_not_block_on_hung_call() {
+        let (client, mut server) = dealer_client_with_stub("tcp://127.0.0.1:15574").await;
+        let client = Arc::new(client);
+
+        // The stub never answers the first command, and answers the second
+        let hung = pause_command();
+        let hung_id = hung.request_id.clone();
+        let command = pause_command();
+        let request_id = command.request_id.clone();
+        let stub = tokio::spawn(async move {
+            for _ in 0..2 {
+                let (peer, id) = stub_recv(&mut server).await;
+                if id != hung_id {
+                    stub_reply(&mut server, peer, &id).await;
+                }
+            }
+            server
+        });
+
+        let first = tokio::spawn({
+            let client = Arc::clone(&client);
+            async move { client.call_with_timeout(hung, Duration::from_secs(2)).await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let started = std::time::Instant::now();
+        let response = client
+            .call_with_timeout(command, Duration::from_secs(2))
+            .await
+            .unwrap();
+        assert_eq!(response.request_id, request_id);
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(!first.is_finished());
+
+        assert!(matches!(first.await.unwrap(), Err(AgentError::Timeout(_))));
+        assert_eq!(client.router.pending_count().await, 0);
+        stub.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_call_with_timeout_requires_dealer_connection() {
+        let client = ZmqClient::new(ZmqRunnerConfig::default());
+        let result = client
+            .call_with_timeout(pause_command(), Duration::from_millis(100))
+            .await;
+        assert!(matches!(result, Err(AgentError::ExecutionError(_))));
+        assert_eq!(client.router.pending_count().await, 0);
+    }
+
     #[test]
     fn test_base64_encode_decode() {
         let data = b"Hello, World!";
@@ -32,14 +32,15 @@
 /// ```
 use crate::errors::{AgentError, AgentResult};
 use crate::zmq_agent_runner::{
-    deserialize_zmq_message, serialize_zmq_message, validate_message_size, ZmqMessage,
-    ZmqRunnerConfig, DEFAULT_TIMEOUT_SECS,
+    deserialize_zmq_message, serialize_zmq_message, validate_message_size, HealthCheckRequest,
+    ZmqMessage, ZmqRunnerConfig, DEFAULT_TIMEOUT_SECS,
 };
 use parking_lot::RwLock;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
+use uuid::Uuid;
 use zeromq::{
     DealerSocket, PubSocket, RepSocket, ReqSocket, RouterSocket, Socket, SocketRecv, SocketSend,
     SubSocket,
@@ -63,6 +64,8 @@ pub enum ConnectionState {
     Connecting,
     /// Connected and ready
     Connected,
+    /// Connected, but the peer has missed heartbeats
+    Stale,
     /// Reconnecting after failure
     Reconnecting,
     /// Connection failed
@@ -86,6 +89,158 @@ pub struct ConnectionStats {
     pub reconnections: u64,
     /// Connection uptime
     pub connected_since: Option<Instant>,
+    /// Recent request/response round-trip latencies, oldest first
+    pub latency_samples: VecDeque<Duration>,
+    /// Requests that timed out since the last successful round-trip
+    pub consecutive_timeouts: u32,
+    /// When the peer last answered a heartbeat
+    pub last_heartbeat: Option<Instant>,
+    /// Heartbeats missed since the last answered one
+    pub missed_heartbeats: u32,
+}
+
+impl ConnectionStats {
+    /// Record a round-trip latency, keeping at most `window` recent samples
+    pub fn record_latency(&mut self, latency: Duration, window: usize) {
+        self.consecutive_timeouts = 0;
+        self.push_latency_sample(latency, window);
+    }
+
+    /// Record a request that timed out after `timeout`.
+    ///
+    /// The timeout counts as a latency sample (the true latency was at least
+    /// that long) and backs off the next timeout until a request succeeds.
+    pub fn record_timeout(&mut self, timeout: Duration, window: usize) {
+        self.consecutive_timeouts = self.consecutive_timeouts.saturating_add(1);
+        self.push_latency_sample(timeout, window);
+    }
+
+    fn push_latency_sample(&mut self, latency: Duration, window: usize) {
+        self.latency_samples.push_back(latency);
+        while self.latency_samples.len() > window.max(1) {
+            self.latency_samples.pop_front();
+        }
+    }
+
+    /// Latency at the given percentile (0.0 to 1.0) of the recorded samples
+    pub fn latency_percentile(&self, percentile: f64) -> Option<Duration> {
+        if self.latency_samples.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<Duration> = self.latency_samples.iter().copied().collect();
+        sorted.sort();
+        let rank = (percentile.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.saturating_sub(1).min(sorted.len() - 1)])
+    }
+}
+
+/// Policy for deriving receive timeouts from observed round-trip latencies.
+///
+/// The timeout is `percentile latency × factor`, clamped to
+/// `[min_timeout, max_timeout]`, so slow-but-alive peers are not timed out
+/// while dead ones are detected quickly. Until `min_samples` round-trips have
+/// been observed `initial_timeout` is used. Each consecutive timeout doubles
+/// the result (up to `max_timeout`) so a peer that slowed down can catch up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveTimeoutPolicy {
+    /// Whether to adapt at all (false always uses `initial_timeout`)
+    pub enabled: bool,
+    /// Timeout used until enough samples are available
+    pub initial_timeout: Duration,
+    /// Latency percentile to base the timeout on (e.g. 0.95 for p95)
+    pub percentile: f64,
+    /// Multiplier applied to the percentile latency
+    pub factor: f64,
+    /// Lower bound on the computed timeout
+    pub min_timeout: Duration,
+    /// Upper bound on the computed timeout
+    pub max_timeout: Duration,
+    /// Samples required before the computed timeout is used
+    pub min_samples: usize,
+    /// Number of recent samples considered
+    pub window: usize,
+}
+
+impl Default for AdaptiveTimeoutPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            initial_timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            percentile: 0.95,
+            factor: 3.0,
+            min_timeout: Duration::from_secs(1),
+            max_timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS * 4),
+            min_samples: 5,
+            window: 100,
+        }
+    }
+}
+
+impl AdaptiveTimeoutPolicy {
+    /// Compute the timeout for the given stats
+    pub fn timeout_for(&self, stats: &ConnectionStats) -> Duration {
+        if !self.enabled {
+            return self.initial_timeout;
+        }
+
+        let base = match stats.latency_percentile(self.percentile) {
+            Some(latency) if stats.latency_samples.len() >= self.min_samples => latency
+                .mul_f64(self.factor.max(0.0))
+                .clamp(self.min_timeout, self.max_timeout.max(self.min_timeout)),
+            _ => self.initial_timeout,
+        };
+
+        let backoff = 2u32.saturating_pow(stats.consecutive_timeouts.min(16));
+        base.saturating_mul(backoff)
+            .min(self.max_timeout.max(self.initial_timeout))
+            .max(base)
+    }
+}
+
+/// Policy for detecting dead peers with heartbeats.
+///
+/// A heartbeat is a health check the peer must answer within `timeout`. After
+/// `stale_after` consecutive misses the connection is marked
+/// [`ConnectionState::Stale`]; after `disconnect_after` the socket is dropped
+/// and the connection is [`ConnectionState::Disconnected`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeartbeatPolicy {
+    /// Whether to send heartbeats at all
+    pub enabled: bool,
+    /// Time between heartbeats
+    pub interval: Duration,
+    /// How long the peer has to answer a heartbeat
+    pub timeout: Duration,
+    /// Missed heartbeats before the connection is considered stale
+    pub stale_after: u32,
+    /// Missed heartbeats before the connection is dropped
+    pub disconnect_after: u32,
+}
+
+impl Default for HeartbeatPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: Duration::from_secs(30),
+            timeout: Duration::from_secs(10),
+            stale_after: 1,
+            disconnect_after: 3,
+        }
+    }
+}
+
+impl HeartbeatPolicy {
+    /// Policy using the heartbeat settings of a runner config
+    pub fn from_config(config: &ZmqRunnerConfig) -> Self {
+        let interval = Duration::from_secs(config.heartbeat_interval_secs);
+        let defaults = Self::default();
+        Self {
+            enabled: config.enable_heartbeat,
+            interval,
+            timeout: defaults.timeout.min(interval),
+            ..defaults
+        }
+    }
 }
 
 /// Pending request for request/response correlation
@@ -136,6 +291,12 @@ pub struct ZmqConnection {
     pending_requests: Arc<Mutex<HashMap<String, PendingRequest>>>,
     /// Socket (wrapped in Arc<Mutex> for thread-safe access)
     socket: Arc<Mutex<Option<Box<dyn SocketWrapper>>>>,
+    /// Policy for receive timeouts when none is given explicitly
+    timeout_policy: AdaptiveTimeoutPolicy,
+    /// Policy for heartbeats sent by [`ZmqConnection::heartbeat`]
+    heartbeat_policy: HeartbeatPolicy,
+    /// REQ socket used only for heartbeats, connected on first use
+    heartbeat_socket: Arc<Mutex<Option<ReqSocket>>>,
 }
 
 /// Trait to abstract over different ZMQ socket types
@@ -251,6 +412,189 @@ impl SubSocketWrapper {
     }
 }
 
+/// Receiving side of a [`ZmqConnection`]
+///
+/// Shares the connection's socket, state and statistics, so it can wait for
+/// messages without holding a lock on the connection itself. A receive still
+/// has the socket to itself while it waits. Obtained from
+/// [`ZmqConnection::receiver`].
+pub struct ZmqReceiver {
+    state: Arc<RwLock<ConnectionState>>,
+    stats: Arc<RwLock<ConnectionStats>>,
+    socket: Arc<Mutex<Option<Box<dyn SocketWrapper>>>>,
+}
+
+impl ZmqReceiver {
+    /// Receive a message if one arrives within `wait`
+    ///
+    /// See [`ZmqConnection::try_receive_message`].
+    pub async fn try_receive(&self, wait: Duration) -> AgentResult<Option<ZmqMessage>> {
+        let state = *self.state.read();
+        if !matches!(state, ConnectionState::Connected | ConnectionState::Stale) {
+            return Err(AgentError::ExecutionError(
+                "Cannot receive message: not connected".to_string(),
+            ));
+        }
+
+        let mut socket_guard = self.socket.lock().await;
+        if let Some(socket) = socket_guard.as_mut() {
+            // Receive with timeout
+            let result = tokio::time::timeout(wait, socket.recv()).await;
+
+            match result {
+                Ok(Ok(zmq_msg)) => {
+                    // Extract bytes from ZmqMessage (get first frame)
+                    let bytes: Vec<u8> = zmq_msg
+                        .into_vec()
+                        .into_iter()
+                        .next()
+                        .ok_or_else(|| {
+                            AgentError::ExecutionError("Empty message received".to_string())
+                        })?
+                        .to_vec();
+                    validate_message_size(bytes.len())?;
+
+                    // Deserialize
+                    let message = deserialize_zmq_message(&bytes)?;
+
+                    // Update statistics
+                    let mut stats = self.stats.write();
+                    stats.messages_received += 1;
+                    stats.bytes_received += bytes.len() as u64;
+
+                    tracing::debug!(
+                        "Received ZMQ message: type={:?}, size={} bytes",
+                        std::mem::discriminant(&message),
+                        bytes.len()
+                    );
+
+                    Ok(Some(message))
+                }
+                Ok(Err(e)) => {
+                    self.stats.write().errors += 1;
+                    Err(AgentError::ExecutionError(format!(
+                        "Failed to receive ZMQ message: {}",
+                        e
+                    )))
+                }
+                Err(_) => Ok(None),
+            }
+        } else {
+            Err(AgentError::ExecutionError(
+                "Socket not initialized".to_string(),
+            ))
+        }
+    }
+}
+
+/// Sends heartbeats for a [`ZmqConnection`]
+///
+/// Shares the connection's state and statistics, but pings the peer over a
+/// dedicated REQ socket instead of the command socket. A REQ socket can't send
+/// again until its last request is answered, so the heartbeat socket is
+/// dropped after every miss and reconnected for the next heartbeat; a late
+/// answer never leaves the command socket stuck. Obtained from
+/// [`ZmqConnection::heartbeater`].
+#[derive(Clone)]
+pub struct ZmqHeartbeat {
+    endpoint: String,
+    policy: HeartbeatPolicy,
+    state: Arc<RwLock<ConnectionState>>,
+    stats: Arc<RwLock<ConnectionStats>>,
+    command_socket: Arc<Mutex<Option<Box<dyn SocketWrapper>>>>,
+    socket: Arc<Mutex<Option<ReqSocket>>>,
+}
+
+impl ZmqHeartbeat {
+    /// Send one heartbeat and update the connection state from the outcome
+    ///
+    /// See [`ZmqConnection::heartbeat`].
+    pub async fn beat(&self) -> ConnectionState {
+        let state = *self.state.read();
+        if !matches!(state, ConnectionState::Connected | ConnectionState::Stale) {
+            return state;
+        }
+
+        let answered = match tokio::time::timeout(self.policy.timeout, self.ping()).await {
+            Ok(Ok(answered)) => answered,
+            Ok(Err(e)) => {
+                tracing::debug!("Heartbeat to {} failed: {}", self.endpoint, e);
+                false
+            }
+            Err(_) => false,
+        };
+        if !answered {
+            *self.socket.lock().await = None;
+        }
+
+        let missed = {
+            let mut stats = self.stats.write();
+            if answered {
+                stats.missed_heartbeats = 0;
+                stats.last_heartbeat = Some(Instant::now());
+            } else {
+                stats.missed_heartbeats = stats.missed_heartbeats.saturating_add(1);
+            }
+            stats.missed_heartbeats
+        };
+
+        if answered {
+            *self.state.write() = ConnectionState::Connected;
+        } else if missed >= self.policy.disconnect_after {
+            tracing::warn!(
+                "Peer at {} missed {} heartbeats, disconnecting",
+                self.endpoint,
+                missed
+            );
+            *self.command_socket.lock().await = None;
+            *self.state.write() = ConnectionState::Disconnected;
+            self.stats.write().connected_since = None;
+        } else if missed >= self.policy.stale_after {
+            tracing::debug!("Peer at {} missed {} heartbeats", self.endpoint, missed);
+            *self.state.write() = ConnectionState::Stale;
+        }
+
+        *self.state.read()
+    }
+
+    /// Send a health check on the heartbeat socket and wait for its answer
+    async fn ping(&self) -> AgentResult<bool> {
+        let mut guard = self.socket.lock().await;
+        let socket = match guard.take() {
+            Some(socket) => socket,
+            None => {
+                let mut socket = ReqSocket::new();
+                socket.connect(&self.endpoint).await.map_err(|e| {
+                    AgentError::ExecutionError(format!("Failed to connect heartbeat socket: {}", e))
+                })?;
+                socket
+            }
+        };
+        let socket = guard.insert(socket);
+
+        let request_id = Uuid::new_v4().to_string();
+        let ping = ZmqMessage::HealthCheckRequest(HealthCheckRequest {
+            request_id: request_id.clone(),
+        });
+        socket
+            .send(serialize_zmq_message(&ping)?.into())
+            .await
+            .map_err(|e| AgentError::ExecutionError(format!("Failed to send heartbeat: {}", e)))?;
+        let reply = socket.recv().await.map_err(|e| {
+            AgentError::ExecutionError(format!("Failed to receive heartbeat: {}", e))
+        })?;
+
+        let bytes =
+            reply.into_vec().into_iter().next().ok_or_else(|| {
+                AgentError::ExecutionError("Empty heartbeat response".to_string())
+            })?;
+        Ok(matches!(
+            deserialize_zmq_message(&bytes)?,
+            ZmqMessage::HealthCheckResponse(pong) if pong.request_id == request_id
+        ))
+    }
+}
+
 impl ZmqConnection {
     /// Create a new ZMQ connection
     ///
@@ -276,14 +620,47 @@ impl ZmqConnection {
         Self {
             socket_type,
             endpoint: endpoint.to_string(),
-            config,
             state: Arc::new(RwLock::new(ConnectionState::Disconnected)),
             stats: Arc::new(RwLock::new(ConnectionStats::default())),
             pending_requests: Arc::new(Mutex::new(HashMap::new())),
             socket: Arc::new(Mutex::new(None)),
+            timeout_policy: AdaptiveTimeoutPolicy {
+                initial_timeout: Duration::from_secs(config.request_timeout_secs),
+                ..Default::default()
+            },
+            heartbeat_policy: HeartbeatPolicy::from_config(&config),
+            heartbeat_socket: Arc::new(Mutex::new(None)),
+            config,
         }
     }
 
+    /// Set the policy used to derive receive timeouts from observed latencies
+    pub fn with_timeout_policy(mut self, policy: AdaptiveTimeoutPolicy) -> Self {
+        self.timeout_policy = policy;
+        self
+    }
+
+    /// Get the adaptive timeout policy
+    pub fn timeout_policy(&self) -> &AdaptiveTimeoutPolicy {
+        &self.timeout_policy
+    }
+
+    /// Set the policy used for heartbeats
+    pub fn with_heartbeat_policy(mut self, policy: HeartbeatPolicy) -> Self {
+        self.heartbeat_policy = policy;
+        self
+    }
+
+    /// Get the heartbeat policy
+    pub fn heartbeat_policy(&self) -> &HeartbeatPolicy {
+        &self.heartbeat_policy
+    }
+
+    /// Timeout currently applied to receives without an explicit timeout
+    pub fn current_timeout(&self) -> Duration {
+        self.timeout_policy.timeout_for(&self.stats.read())
+    }
+
     /// Connect to the endpoint
     ///
     /// # Example
@@ -361,6 +738,7 @@ impl ZmqConnection {
     /// Disconnect from the endpoint
     pub async fn disconnect(&mut self) -> AgentResult<()> {
         *self.socket.lock().await = None;
+        *self.heartbeat_socket.lock().await = None;
         *self.state.write() = ConnectionState::Disconnected;
         self.stats.write().connected_since = None;
 
@@ -374,6 +752,14 @@ impl ZmqConnection {
         *self.state.read() == ConnectionState::Connected
     }
 
+    /// Whether messages can be exchanged, including over a stale connection
+    fn is_usable(&self) -> bool {
+        matches!(
+            *self.state.read(),
+            ConnectionState::Connected | ConnectionState::Stale
+        )
+    }
+
     /// Get current connection state
     pub fn state(&self) -> ConnectionState {
         *self.state.read()
@@ -406,7 +792,7 @@ impl ZmqConnection {
     /// # }
     /// ```
     pub async fn send_message(&self, message: &ZmqMessage) -> AgentResult<()> {
-        if !self.is_connected() {
+        if !self.is_usable() {
             return Err(AgentError::ExecutionError(
                 "Cannot send message: not connected".to_string(),
             ));
@@ -446,7 +832,7 @@ impl ZmqConnection {
     ///
     /// # Arguments
     ///
-    /// * `timeout` - Optional timeout duration (uses default if None)
+    /// * `timeout` - Optional timeout duration (uses the adaptive timeout if None)
     ///
     /// # Example
     ///
@@ -462,67 +848,35 @@ impl ZmqConnection {
     /// # }
     /// ```
     pub async fn receive_message(&self, timeout: Option<Duration>) -> AgentResult<ZmqMessage> {
-        if !self.is_connected() {
-            return Err(AgentError::ExecutionError(
-                "Cannot receive message: not connected".to_string(),
-            ));
+        let timeout_duration = timeout.unwrap_or_else(|| self.current_timeout());
+
+        match self.try_receive_message(timeout_duration).await {
+            Ok(Some(message)) => Ok(message),
+            Ok(None) => {
+                self.stats.write().errors += 1;
+                Err(AgentError::ExecutionError(format!(
+                    "Timeout receiving ZMQ message after {:?}",
+                    timeout_duration
+                )))
+            }
+            Err(e) => Err(e),
         }
+    }
 
-        let timeout_duration = timeout.unwrap_or(Duration::from_secs(DEFAULT_TIMEOUT_SECS));
-
-        let mut socket_guard = self.socket.lock().await;
-        if let Some(socket) = socket_guard.as_mut() {
-            // Receive with timeout
-            let result = tokio::time::timeout(timeout_duration, socket.recv()).await;
-
-            match result {
-                Ok(Ok(zmq_msg)) => {
-                    // Extract bytes from ZmqMessage (get first frame)
-                    let bytes: Vec<u8> = zmq_msg
-                        .into_vec()
-                        .into_iter()
-                        .next()
-                        .ok_or_else(|| {
-                            AgentError::ExecutionError("Empty message received".to_string())
-                        })?
-                        .to_vec();
-                    validate_message_size(bytes.len())?;
-
-                    // Deserialize
-                    let message = deserialize_zmq_message(&bytes)?;
-
-                    // Update statistics
-                    let mut stats = self.stats.write();
-                    stats.messages_received += 1;
-                    stats.bytes_received += bytes.len() as u64;
-
-                    tracing::debug!(
-                        "Received ZMQ message: type={:?}, size={} bytes",
-                        std::mem::discriminant(&message),
-                        bytes.len()
-                    );
+    /// Receive a message if one arrives within `wait`
+    ///
+    /// Unlike [`ZmqConnection::receive_message`], running out of time isn't an
+    /// error, so this suits polling.
+    pub async fn try_receive_message(&self, wait: Duration) -> AgentResult<Option<ZmqMessage>> {
+        self.receiver().try_receive(wait).await
+    }
 
-                    Ok(message)
-                }
-                Ok(Err(e)) => {
-                    self.stats.write().errors += 1;
-                    Err(AgentError::ExecutionError(format!(
-                        "Failed to receive ZMQ message: {}",
-                        e
-                    )))
-                }
-                Err(_) => {
-                    self.stats.write().errors += 1;
-                    Err(AgentError::ExecutionError(format!(
-                        "Timeout receiving ZMQ message after {:?}",
-                        timeout_duration
-                    )))
-                }
-            }
-        } else {
-            Err(AgentError::ExecutionError(
-                "Socket not initialized".to_string(),
-            ))
+    /// Handle for receiving on this connection without borrowing it
+    pub fn receiver(&self) -> ZmqReceiver {
+        ZmqReceiver {
+            state: Arc::clone(&self.state),
+            stats: Arc::clone(&self.stats),
+            socket: Arc::clone(&self.socket),
         }
     }
 
@@ -531,7 +885,7 @@ impl ZmqConnection {
     /// # Arguments
     ///
     /// * `request` - The request message to send
-    /// * `timeout` - Optional timeout duration
+    /// * `timeout` - Optional timeout duration; `None` uses the adaptive timeout
     ///
     /// # Returns
     ///
@@ -558,11 +912,55 @@ impl ZmqConnection {
         request: &ZmqMessage,
         timeout: Option<Duration>,
     ) -> AgentResult<ZmqMessage> {
+        let timeout = timeout.unwrap_or_else(|| self.current_timeout());
+        let started = Instant::now();
+
         // Send the request
         self.send_message(request).await?;
 
         // Receive the response
-        self.receive_message(timeout).await
+        let response = match self.receive_message(Some(timeout)).await {
+            Ok(response) => response,
+            Err(e) => {
+                if started.elapsed() >= timeout {
+                    self.stats
+                        .write()
+                        .record_timeout(timeout, self.timeout_policy.window);
+                }
+                return Err(e);
+            }
+        };
+
+        self.stats
+            .write()
+            .record_latency(started.elapsed(), self.timeout_policy.window);
+
+        Ok(response)
+    }
+
+    /// Send one heartbeat and update the connection state from the outcome
+    ///
+    /// The heartbeat is a health check; an answer clears the missed count and
+    /// restores a stale connection. Misses move the connection to `Stale` and
+    /// then `Disconnected` as set by the [`HeartbeatPolicy`]. Returns the
+    /// resulting state; a connection that isn't usable is left untouched.
+    ///
+    /// Heartbeats go over a socket of their own (see [`ZmqHeartbeat`]), so a
+    /// missed one never blocks or wedges the command socket.
+    pub async fn heartbeat(&self) -> ConnectionState {
+        self.heartbeater().beat().await
+    }
+
+    /// Handle for sending this connection's heartbeats without borrowing it
+    pub fn heartbeater(&self) -> ZmqHeartbeat {
+        ZmqHeartbeat {
+            endpoint: self.endpoint.clone(),
+            policy: self.heartbeat_policy,
+            state: Arc::clone(&self.state),
+            stats: Arc::clone(&self.stats),
+            command_socket: Arc::clone(&self.socket),
+            socket: Arc::clone(&self.heartbeat_socket),
+        }
     }
 
     /// Reconnect with exponential backoff
@@ -691,7 +1089,7 @@ impl ZmqConnection {
             ));
         }
 
-        let timeout_duration = timeout.unwrap_or(Duration::from_secs(DEFAULT_TIMEOUT_SECS));
+        let timeout_duration = timeout.unwrap_or_else(|| self.current_timeout());
 
         let mut socket_guard = self.socket.lock().await;
         if let Some(socket) = socket_guard.as_mut() {
@@ -864,6 +1262,17 @@ impl ZmqMessageRouter {
         }
     }
 
+    /// Stop waiting for a request's response, e.g. after it timed out
+    ///
+    /// Returns whether the request was still pending.
+    pub async fn cancel_request(&self, request_id: &str) -> bool {
+        self.pending_requests
+            .lock()
+            .await
+            .remove(request_id)
+            .is_some()
+    }
+
     /// Get the number of pending requests
     pub async fn pending_count(&self) -> usize {
         self.pending_requests.lock().await.len()
@@ -909,6 +1318,189 @@ mod tests {
         assert_eq!(stats.bytes_received, 0);
     }
 
+    #[test]
+    fn test_adaptive_timeout_tracks_latency() {
+        let connection = ZmqConnection::new(
+            SocketType::Req,
+            "tcp://localhost:5555",
+            ZmqRunnerConfig::default(),
+        );
+        let policy = *connection.timeout_policy();
+
+        // Too few samples: fixed default
+        assert_eq!(
+            connection.current_timeout(),
+            Duration::from_secs(DEFAULT_TIMEOUT_SECS)
+        );
+
+        // 100 samples of 1..=100ms: p95 = 95ms, × 3 = 285ms, clamped up to the 1s minimum
+        for ms in 1..=100 {
+            connection
+                .stats
+                .write()
+                .record_latency(Duration::from_millis(ms), policy.window);
+        }
+        assert_eq!(
+            connection.stats().latency_percentile(0.95),
+            Some(Duration::from_millis(95))
+        );
+        assert_eq!(connection.current_timeout(), policy.min_timeout);
+
+        // A slow peer: the window fills with 2..=4s latencies, p95 ≈ 3.9s
+        for i in 0..100u64 {
+            connection
+                .stats
+                .write()
+                .record_latency(Duration::from_millis(2000 + i * 20), policy.window);
+        }
+        assert_eq!(connection.stats().latency_samples.len(), policy.window);
+        let timeout = connection.current_timeout().as_millis();
+        assert!((11_639..=11_641).contains(&timeout), "{}ms", timeout);
+
+        // Pathologically slow samples are capped at the maximum
+        for _ in 0..100 {
+            connection
+                .stats
+                .write()
+                .record_latency(Duration::from_secs(600), policy.window);
+        }
+        assert_eq!(connection.current_timeout(), policy.max_timeout);
+
+        let fixed = ZmqConnection::new(
+            SocketType::Req,
+            "tcp://localhost:5555",
+            ZmqRunnerConfig::default(),
+        )
+        .with_timeout_policy(AdaptiveTimeoutPolicy {
+            enabled: false,
+            ..Default::default()
+        });
+        fixed
+            .stats
+            .write()
+            .record_latency(Duration::from_secs(600), 10);
+        assert_eq!(
+            fixed.current_timeout(),
+            Duration::from_secs(DEFAULT_TIMEOUT_SECS)
+        );
+    }
+
+    #[test]
+    fn test_adaptive_timeout_recovers_after_timeouts() {
+        let config = ZmqRunnerConfig {
+            request_timeout_secs: 10,
+            ..Default::default()
+        };
+        let connection = ZmqConnection::new(SocketType::Req, "tcp://localhost:5555", config);
+        let policy = *connection.timeout_policy();
+        assert_eq!(connection.current_timeout(), Duration::from_secs(10));
+
+        // Fast peer: the timeout drops to the 1s minimum
+        for _ in 0..policy.min_samples {
+            connection
+                .stats
+                .write()
+                .record_latency(Duration::from_millis(10), policy.window);
+        }
+        assert_eq!(connection.current_timeout(), policy.min_timeout);
+
+        // The peer slows to 2s: timeouts are recorded and back off until it fits
+        let mut timeout = connection.current_timeout();
+        let mut attempts = 0;
+        while timeout < Duration::from_secs(2) {
+            connection
+                .stats
+                .write()
+                .record_timeout(timeout, policy.window);
+            let next = connection.current_timeout();
+            assert!(next > timeout, "{:?} did not grow past {:?}", next, timeout);
+            timeout = next;
+            attempts += 1;
+        }
+        assert!(attempts <= 2, "took {} timeouts to recover", attempts);
+
+        // Successful slow round-trips keep the timeout above the new latency
+        for _ in 0..policy.min_samples {
+            connection
+                .stats
+                .write()
+                .record_latency(Duration::from_secs(2), policy.window);
+        }
+        assert_eq!(connection.stats().consecutive_timeouts, 0);
+        assert_eq!(connection.current_timeout(), Duration::from_secs(6));
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_recovers_after_late_reply() {
+        use zeromq::{Socket, SocketRecv, SocketSend};
+
+        let endpoint = "tcp://127.0.0.1:15573";
+        let mut server = RepSocket::new();
+        server.bind(endpoint).await.unwrap();
+
+        // Stub server: answer the first health check too late, then on time
+        tokio::spawn(async move {
+            let mut answered = 0;
+            loop {
+                let request = server.recv().await.unwrap();
+                let bytes = request.into_vec()[0].to_vec();
+                let request_id = match deserialize_zmq_message(&bytes).unwrap() {
+                    ZmqMessage::HealthCheckRequest(req) => req.request_id,
+                    other => panic!("Expected a health check, got {:?}", other),
+                };
+                if answered == 0 {
+                    tokio::time::sleep(Duration::from_millis(150)).await;
+                }
+                answered += 1;
+                let pong = ZmqMessage::HealthCheckResponse(HealthCheckResponse {
+                    request_id,
+                    healthy: true,
+                    protocol_version: "1.0.0".to_string(),
+                    uptime_secs: None,
+                    active_agents: None,
+                    metadata: None,
+                });
+                // The late reply's requester is gone by the time it is sent
+                let _ = server
+                    .send(serialize_zmq_message(&pong).unwrap().into())
+                    .await;
+            }
+        });
+
+        let mut connection =
+            ZmqConnection::new(SocketType::Req, endpoint, ZmqRunnerConfig::default())
+                .with_heartbeat_policy(HeartbeatPolicy {
+                    timeout: Duration::from_millis(50),
+                    stale_after: 1,
+                    disconnect_after: 3,
+                    ..Default::default()
+                });
+        connection.connect().await.unwrap();
+
+        assert_eq!(connection.heartbeat().await, ConnectionState::Stale);
+        assert_eq!(connection.stats().missed_heartbeats, 1);
+        // Next heartbeat after the server has caught up
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(connection.heartbeat().await, ConnectionState::Connected);
+        assert_eq!(connection.stats().missed_heartbeats, 0);
+
+        // The missed heartbeat left the command socket usable
+        let request_id = Uuid::new_v4().to_string();
+        let response = connection
+            .request_response(
+                &ZmqMessage::HealthCheckRequest(HealthCheckRequest {
+                    request_id: request_id.clone(),
+                }),
+                Some(Duration::from_secs(2)),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            response,
+            ZmqMessage::HealthCheckResponse(pong) if pong.request_id == request_id
+        ));
+    }
+
     #[test]
     fn test_message_router_new() {
         let router = ZmqMessageRouter::new();
@@ -29,9 +29,10 @@ use crate::agent_runner::{LocalProcessRunner, ProcessRunnerConfig};
 use crate::errors::{AgentError, AgentResult};
 use crate::traits::{AgentConfig, AgentHandle, AgentInfo, AgentRunner, AgentSignal, AgentStatus};
 use crate::zmq_agent_runner::{
-    CommandResponse, ControlCommand, ControlCommandType, HealthCheckRequest, HealthCheckResponse,
-    ListAgentsRequest, ListAgentsResponse, LogStreamMessage, LogStreamType, SpawnRequest,
-    SpawnResponse, ZmqMessage, ZMQ_PROTOCOL_VERSION,
+    spawn_batch, BatchAgentResult, BatchControlCommand, BatchControlResponse, CommandResponse,
+    ControlCommand, ControlCommandType, HealthCheckRequest, HealthCheckResponse, ListAgentsRequest,
+    ListAgentsResponse, LogStreamMessage, LogStreamType, SpawnRequest, SpawnResponse, ZmqMessage,
+    ZMQ_PROTOCOL_VERSION,
 };
 use crate::zmq_communication::{SocketType, ZmqConnection};
 use dashmap::DashMap;
@@ -424,6 +425,14 @@ impl ZmqAgentServer {
                 self.stats.write().control_commands += 1;
                 ZmqMessage::CommandResponse(self.handle_control_command(cmd).await)
             }
+            ZmqMessage::BatchControlCommand(cmd) => {
+                tracing::debug!(
+                    "Received batch command: {:?} ({})",
+                    cmd.command_type,
+                    cmd.request_id
+                );
+                ZmqMessage::BatchControlResponse(self.handle_batch_command(cmd).await)
+            }
             ZmqMessage::ListAgentsRequest(req) => {
                 tracing::debug!("Received list agents request: {}", req.request_id);
                 self.stats.write().list_requests += 1;
@@ -541,6 +550,77 @@ impl ZmqAgentServer {
         }
     }
 
+    /// Handle a batch command, one agent at a time
+    async fn handle_batch_command(&self, command: BatchControlCommand) -> BatchControlResponse {
+        let results = if command.command_type == ControlCommandType::Spawn {
+            self.handle_batch_spawn(&command).await
+        } else {
+            let mut results = Vec::with_capacity(command.agent_ids.len());
+            for agent_id in &command.agent_ids {
+                self.stats.write().control_commands += 1;
+                let response = self
+                    .handle_control_command(ControlCommand {
+                        request_id: command.request_id.clone(),
+                        agent_id: *agent_id,
+                        command_type: command.command_type.clone(),
+                        payload: command.payload.clone(),
+                    })
+                    .await;
+                let failed = !response.success;
+                results.push(BatchAgentResult {
+                    agent_id: *agent_id,
+                    success: response.success,
+                    status: response.status,
+                    error: response.error,
+                });
+                if failed && command.fail_fast {
+                    break;
+                }
+            }
+            results
+        };
+
+        let successful = results.iter().filter(|r| r.success).count();
+        let failed = results.len() - successful;
+        BatchControlResponse {
+            request_id: command.request_id,
+            success: failed == 0,
+            results,
+            successful,
+            failed,
+        }
+    }
+
+    /// Spawn a batch of agents through [`spawn_batch`], counting each spawn
+    /// request and forgetting rolled-back agents.
+    async fn handle_batch_spawn(&self, command: &BatchControlCommand) -> Vec<BatchAgentResult> {
+        spawn_batch(
+            command.spawn_configs.clone(),
+            command.all_or_nothing,
+            |index, config| async move {
+                self.stats.write().spawn_requests += 1;
+                let response = self
+                    .handle_spawn_request(SpawnRequest {
+                        request_id: format!("{}-{}", command.request_id, index),
+                        config,
+                        timeout_secs: None,
+                        metadata: None,
+                    })
+                    .await;
+                match response.agent_info {
+                    Some(info) if response.success => Ok(info),
+                    _ => Err(response.error.unwrap_or_else(|| "Spawn failed".to_string())),
+                }
+            },
+            |agent_id| async move {
+                let result = self.runner.kill(&agent_id).await;
+                self.agents.remove(&agent_id);
+                result
+            },
+        )
+        .await
+    }
+
     /// Handle a control command
     async fn handle_control_command(&self, command: ControlCommand) -> CommandResponse {
         let agent_id = command.agent_id;
@@ -879,6 +959,9 @@ impl ZmqAgentServer {
                     )),
                 }
             }
+            ControlCommandType::Spawn => Err(AgentError::ExecutionError(
+                "Spawn is only supported in batch commands".to_string(),
+            )),
         };
 
         // Get current status
@@ -1244,4 +1327,54 @@ mod tests {
         assert!(!server.is_log_streaming_enabled());
         assert_eq!(server.pub_endpoint(), None);
     }
+
+    #[tokio::test]
+    async fn test_handle_batch_spawn() {
+        let server = ZmqAgentServer::new(ZmqServerConfig {
+            pub_endpoint: None,
+            ..Default::default()
+        });
+        let config = |name: &str, backend: &str| AgentConfig {
+            name: name.to_string(),
+            // "sleep-cli" runs `sleep <task>`
+            model_backend: backend.to_string(),
+            task: "30".to_string(),
+            ..Default::default()
+        };
+        let command = |all_or_nothing| BatchControlCommand {
+            request_id: "batch".to_string(),
+            agent_ids: Vec::new(),
+            command_type: ControlCommandType::Spawn,
+            payload: None,
+            fail_fast: true,
+            spawn_configs: vec![
+                config("searcher-1", "sleep-cli"),
+                config("searcher-2", "no-such-backend"),
+                config("searcher-3", "sleep-cli"),
+            ],
+            all_or_nothing,
+        };
+
+        // Partial: the first agent is kept, the bad config fails, the rest is skipped
+        let results = server.handle_batch_spawn(&command(false)).await;
+        assert_eq!(results.len(), 3);
+        assert!(results[0].success);
+        assert!(!results[1].success);
+        assert!(results[2].error.as_deref().unwrap().starts_with("Skipped"));
+        assert_eq!(server.active_agent_count(), 1);
+        let kept = results[0].agent_id;
+
+        // All-or-nothing: the first agent is rolled back
+        let results = server.handle_batch_spawn(&command(true)).await;
+        assert!(results.iter().all(|r| !r.success));
+        assert!(results[0]
+            .error
+            .as_deref()
+            .unwrap()
+            .starts_with("Rolled back"));
+        assert_eq!(results[0].status, Some(AgentStatus::Terminated));
+        assert_eq!(server.active_agent_count(), 1);
+
+        server.runner.kill(&kept).await.unwrap();
+    }
 }
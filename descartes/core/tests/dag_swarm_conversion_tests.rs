@@ -347,10 +347,12 @@ fn test_import_simple_workflow() {
                 event: "go".to_string(),
                 target: "End".to_string(),
                 guards: vec![],
+                dag: None,
             }],
             timeout_seconds: None,
             timeout_target: None,
             required_resources: vec![],
+            dag: None,
         },
     );
 
@@ -368,6 +370,7 @@ fn test_import_simple_workflow() {
             timeout_seconds: None,
             timeout_target: None,
             required_resources: vec![],
+            dag: None,
         },
     );
 
@@ -434,6 +437,7 @@ fn test_import_with_metadata() {
             timeout_seconds: Some(300),
             timeout_target: None,
             required_resources: vec!["database".to_string()],
+            dag: None,
         },
     );
 
@@ -559,6 +563,7 @@ fn test_import_multiple_workflows() {
                             timeout_seconds: None,
                             timeout_target: None,
                             required_resources: vec![],
+                            dag: None,
                         },
                     );
                     s
@@ -591,6 +596,7 @@ fn test_import_multiple_workflows() {
                             timeout_seconds: None,
                             timeout_target: None,
                             required_resources: vec![],
+                            dag: None,
                         },
                     );
                     s
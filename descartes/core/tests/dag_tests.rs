@@ -576,7 +576,18 @@ fn test_detect_simple_cycle() {
     dag.add_edge(DAGEdge::dependency(ids[1], ids[2])).unwrap();
     dag.add_edge(DAGEdge::dependency(ids[2], ids[0])).unwrap();
 
-    assert!(matches!(dag.validate(), Err(DAGError::CycleDetected(_))));
+    match dag.validate() {
+        Err(DAGError::Cycle(path)) => {
+            // The path follows the edges, whichever node it starts at
+            let path: Vec<_> = path.iter().map(|n| n.node_id).collect();
+            let start = ids.iter().position(|id| *id == path[0]).unwrap();
+            let expected: Vec<_> = (0..3).map(|i| ids[(start + i) % 3]).collect();
+            assert_eq!(path, expected);
+        }
+        other => panic!("expected a cycle error, got {:?}", other),
+    }
+    let cycle = dag.find_cycle().unwrap();
+    assert_eq!(cycle.len(), 3);
     assert!(dag.topological_sort().is_err());
 }
 
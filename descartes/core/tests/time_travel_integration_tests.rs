@@ -14,7 +14,8 @@ use descartes_core::{
     body_restore::{BodyRestoreManager, GitBodyRestoreManager},
     brain_restore::BrainRestore,
     time_travel_integration::{
-        DefaultRewindManager, ResumeContext, RewindConfig, RewindManager, RewindPoint,
+        DefaultRewindManager, ResumeContext, RewindConfig, RewindGranularity, RewindManager,
+        RewindPoint,
     },
 };
 use serde_json::json;
@@ -262,6 +263,7 @@ async fn test_rewind_to_point() {
         allow_uncommitted_changes: true,
         max_undo_history: 10,
         enable_debugging: false,
+        granularity: RewindGranularity::Event,
     };
 
     let result = manager.rewind_to(point, config).await;
@@ -463,6 +463,8 @@ fn test_batch_control_command_serialization() {
         command_type: ControlCommandType::Pause,
         payload: None,
         fail_fast: false,
+        spawn_configs: Vec::new(),
+        all_or_nothing: false,
     };
 
     let msg = ZmqMessage::BatchControlCommand(command);
@@ -655,6 +657,8 @@ fn test_batch_operation_message_size() {
         command_type: ControlCommandType::GetStatus,
         payload: None,
         fail_fast: false,
+        spawn_configs: Vec::new(),
+        all_or_nothing: false,
     };
 
     let msg = ZmqMessage::BatchControlCommand(command);
@@ -4,7 +4,7 @@
 /// functionality by simulating client-server interactions.
 use descartes_core::{
     AgentConfig, AgentStatus, ZmqAgentRunner, ZmqAgentServer, ZmqClient, ZmqRunnerConfig,
-    ZmqServerConfig,
+    ZmqServerConfig, ZMQ_PROTOCOL_VERSION,
 };
 use std::collections::HashMap;
 use std::time::Duration;
@@ -75,7 +75,7 @@ async fn test_server_health_check() {
     let response = client.health_check().await.unwrap();
 
     assert!(response.healthy);
-    assert_eq!(response.protocol_version, "1.0.0");
+    assert_eq!(response.protocol_version, ZMQ_PROTOCOL_VERSION);
     assert!(response.uptime_secs.is_some());
     assert_eq!(response.active_agents, Some(0));
 
@@ -333,6 +333,72 @@ async fn test_server_statistics() {
     handle.abort();
 }
 
+#[tokio::test]
+#[ignore] // Requires ZMQ setup
+async fn test_server_batch_spawn() {
+    let endpoint = "tcp://127.0.0.1:15565";
+    let (server, handle) = start_test_server(endpoint).await;
+
+    let client = create_test_client(endpoint);
+    client.connect(endpoint).await.unwrap();
+
+    let config = |name: &str, backend: &str| AgentConfig {
+        name: name.to_string(),
+        // "sleep-cli" runs `sleep <task>`
+        model_backend: backend.to_string(),
+        task: "30".to_string(),
+        ..Default::default()
+    };
+    let configs = vec![
+        config("searcher-1", "sleep-cli"),
+        config("searcher-2", "no-such-backend"),
+        config("searcher-3", "sleep-cli"),
+    ];
+
+    // Partial: the first agent is kept, the bad config fails, the rest is skipped
+    let response = client
+        .spawn_remote_batch(configs.clone(), false)
+        .await
+        .unwrap();
+    assert!(!response.success);
+    assert_eq!((response.successful, response.failed), (1, 2));
+    let spawned = response.results[0].agent_id;
+    assert!(response.results[0].success);
+    assert!(response.results[1]
+        .error
+        .as_deref()
+        .unwrap()
+        .contains("Unsupported model backend"));
+    assert!(response.results[2]
+        .error
+        .as_deref()
+        .unwrap()
+        .starts_with("Skipped"));
+    assert_eq!(server.active_agent_count(), 1);
+
+    // All-or-nothing: the first agent is rolled back
+    let response = client.spawn_remote_batch(configs, true).await.unwrap();
+    assert_eq!((response.successful, response.failed), (0, 3));
+    assert!(response.results[0]
+        .error
+        .as_deref()
+        .unwrap()
+        .starts_with("Rolled back"));
+    assert_eq!(server.active_agent_count(), 1);
+
+    let response = client
+        .spawn_remote_batch(vec![config("a", "sleep-cli"), config("b", "sleep-cli")], true)
+        .await
+        .unwrap();
+    assert!(response.success);
+    assert_eq!(server.active_agent_count(), 3);
+
+    client.kill_agent(&spawned).await.unwrap();
+    client.disconnect().await.unwrap();
+    server.stop().await.unwrap();
+    handle.abort();
+}
+
 #[tokio::test]
 async fn test_server_uptime_calculation() {
     let endpoint = "tcp://127.0.0.1:15562";
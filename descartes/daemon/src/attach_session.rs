@@ -37,7 +37,10 @@ use chrono::{DateTime, Utc};
 use descartes_core::AttachTokenStore;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, MissedTickBehavior};
 use uuid::Uuid;
 
 use crate::errors::{DaemonError, DaemonResult};
@@ -100,6 +103,8 @@ pub struct AttachSession {
     pub stderr_bytes: usize,
     /// Last activity timestamp
     pub last_activity: DateTime<Utc>,
+    /// Monotonic time of the last activity, used for idle expiry
+    pub last_activity_at: Instant,
 }
 
 impl AttachSession {
@@ -124,12 +129,19 @@ impl AttachSession {
             stdout_bytes: 0,
             stderr_bytes: 0,
             last_activity: now,
+            last_activity_at: Instant::now(),
         }
     }
 
     /// Update last activity timestamp.
     pub fn touch(&mut self) {
         self.last_activity = Utc::now();
+        self.last_activity_at = Instant::now();
+    }
+
+    /// Time since the last activity.
+    pub fn idle_for(&self) -> Duration {
+        self.last_activity_at.elapsed()
     }
 
     /// Record stdin bytes.
@@ -196,6 +208,10 @@ pub struct AttachSessionConfig {
     pub max_sessions_per_agent: usize,
     /// Base path for ZMQ IPC sockets
     pub zmq_socket_path: String,
+    /// Close sessions with no client I/O for this many seconds (0 = never)
+    pub idle_timeout_secs: u64,
+    /// Interval between idle session sweeps in seconds
+    pub reaper_interval_secs: u64,
 }
 
 impl Default for AttachSessionConfig {
@@ -205,6 +221,8 @@ impl Default for AttachSessionConfig {
             cleanup_interval_secs: 60,
             max_sessions_per_agent: 1, // Single attach initially
             zmq_socket_path: "/tmp/descartes-attach".to_string(),
+            idle_timeout_secs: 1800, // 30 minutes
+            reaper_interval_secs: 30,
         }
     }
 }
@@ -464,6 +482,73 @@ impl AttachSessionManager {
             .collect()
     }
 
+    /// Mark a session as active without recording any bytes.
+    pub async fn touch_session(&self, session_id: &Uuid) {
+        if let Some(session) = self.sessions.write().await.get_mut(session_id) {
+            session.touch();
+        }
+    }
+
+    /// Terminate every session idle for longer than the configured timeout.
+    ///
+    /// Emits an `AttachIdleTimeout` event for each before terminating it, and
+    /// returns the terminated sessions.
+    pub async fn reap_idle_sessions(&self) -> Vec<AttachSession> {
+        if self.config.idle_timeout_secs == 0 {
+            return Vec::new();
+        }
+        let idle_timeout = Duration::from_secs(self.config.idle_timeout_secs);
+
+        let idle: Vec<AttachSession> = self
+            .sessions
+            .read()
+            .await
+            .values()
+            .filter(|s| s.idle_for() >= idle_timeout)
+            .cloned()
+            .collect();
+
+        for session in &idle {
+            tracing::info!(
+                session_id = %session.session_id,
+                agent_id = %session.agent_id,
+                idle_secs = session.idle_for().as_secs(),
+                "Attach session idle, terminating"
+            );
+            self.emit_attach_idle_timeout(session).await;
+            self.terminate_session(&session.session_id).await;
+        }
+        idle
+    }
+
+    /// Spawn a task that reaps idle sessions every `reaper_interval_secs`.
+    ///
+    /// `on_expired` is called for each reaped session, e.g. to stop the attach
+    /// server it was connected through. The task ends once the manager is
+    /// dropped.
+    pub fn spawn_idle_reaper<F>(self: &Arc<Self>, on_expired: F) -> JoinHandle<()>
+    where
+        F: Fn(&AttachSession) + Send + Sync + 'static,
+    {
+        let manager = Arc::downgrade(self);
+        let period = Duration::from_secs(self.config.reaper_interval_secs.max(1));
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            // The first tick completes immediately
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(manager) = manager.upgrade() else {
+                    break;
+                };
+                for session in manager.reap_idle_sessions().await {
+                    on_expired(&session);
+                }
+            }
+        })
+    }
+
     /// Update session statistics (stdin/stdout bytes).
     pub async fn record_activity(
         &self,
@@ -520,6 +605,24 @@ impl AttachSessionManager {
         self.event_bus.publish(DescartesEvent::AgentEvent(event)).await;
     }
 
+    async fn emit_attach_idle_timeout(&self, session: &AttachSession) {
+        let event = AgentEvent {
+            id: Uuid::new_v4().to_string(),
+            agent_id: session.agent_id.to_string(),
+            timestamp: Utc::now(),
+            event_type: AgentEventType::AttachIdleTimeout,
+            data: serde_json::json!({
+                "session_id": session.session_id.to_string(),
+                "client_type": session.client_type.to_string(),
+                "idle_secs": session.idle_for().as_secs(),
+                "idle_timeout_secs": self.config.idle_timeout_secs,
+            }),
+        };
+        self.event_bus
+            .publish(DescartesEvent::AgentEvent(event))
+            .await;
+    }
+
     async fn emit_attach_disconnected(
         &self,
         agent_id: &Uuid,
@@ -775,4 +878,73 @@ mod tests {
         assert_eq!(updated.stdout_bytes, 200);
         assert_eq!(updated.stderr_bytes, 50);
     }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_idle_reaper_terminates_idle_sessions() {
+        let token_store = Arc::new(AttachTokenStore::new());
+        let event_bus = Arc::new(EventBus::new());
+        let manager = Arc::new(AttachSessionManager::new(
+            token_store,
+            Arc::clone(&event_bus),
+            AttachSessionConfig {
+                idle_timeout_secs: 60,
+                reaper_interval_secs: 10,
+                max_sessions_per_agent: 2,
+                ..Default::default()
+            },
+        ));
+        let (_, mut events) = event_bus
+            .subscribe_filtered(
+                crate::events::EventFilter::from_expression("event_type = AttachIdleTimeout")
+                    .unwrap(),
+            )
+            .await;
+
+        let create = |agent_id: Uuid| {
+            let manager = Arc::clone(&manager);
+            async move {
+                let creds = manager
+                    .request_attach(agent_id, ClientType::ClaudeCode)
+                    .await
+                    .unwrap();
+                manager
+                    .create_session(agent_id, creds.token, ClientType::ClaudeCode, "1.0".into())
+                    .await
+                    .unwrap()
+                    .session_id
+            }
+        };
+        let idle_agent = Uuid::new_v4();
+        let busy_agent = Uuid::new_v4();
+        let idle = create(idle_agent).await;
+        let busy = create(busy_agent).await;
+
+        let expired = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let reaper = {
+            let expired = Arc::clone(&expired);
+            manager.spawn_idle_reaper(move |session| {
+                expired.lock().unwrap().push(session.agent_id);
+            })
+        };
+
+        // The busy session keeps sending input while the idle one stays quiet
+        for _ in 0..4 {
+            tokio::time::sleep(Duration::from_secs(20)).await;
+            manager.record_activity(&busy, 8, 0, 0).await;
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        assert!(manager.get_session(&idle).await.is_none());
+        assert!(manager.get_session(&busy).await.is_some());
+        assert_eq!(*expired.lock().unwrap(), vec![idle_agent]);
+        match events.recv().await.unwrap() {
+            DescartesEvent::AgentEvent(e) => {
+                assert_eq!(e.agent_id, idle_agent.to_string());
+                assert_eq!(e.data["session_id"], idle.to_string());
+            }
+            other => panic!("Unexpected event: {:?}", other),
+        }
+
+        reaper.abort();
+    }
 }
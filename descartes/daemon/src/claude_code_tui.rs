@@ -8,17 +8,18 @@
 //! - Historical output replay
 //! - Session timeout handling
 
-use crate::attach_session::AttachSessionManager;
+use crate::attach_session::{AttachSessionManager, ClientType};
 use crate::errors::{DaemonError, DaemonResult};
 use descartes_core::attach_protocol::{
     AttachHandshake, AttachHandshakeResponse, AttachMessage, AttachMessageType, HistoricalOutput,
     OutputData, StdinData, ATTACH_PROTOCOL_VERSION,
 };
+use descartes_core::{OutputChunk, OutputLog, OutputStream, OutputSubscription};
 use std::collections::VecDeque;
 use std::sync::Arc;
 use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::net::UnixStream;
-use tokio::sync::{broadcast, mpsc, RwLock};
+use tokio::sync::{broadcast, mpsc};
 use tokio::time::{timeout, Duration};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
@@ -86,6 +87,18 @@ impl OutputBuffer {
         }
     }
 
+    /// Build a buffer from chunks replayed out of an [`OutputLog`]
+    pub fn from_chunks(max_bytes: usize, max_lines: usize, chunks: &[OutputChunk]) -> Self {
+        let mut buffer = Self::new(max_bytes, max_lines);
+        for chunk in chunks {
+            match chunk.stream {
+                OutputStream::Stdout => buffer.push_stdout(chunk.content.clone()),
+                OutputStream::Stderr => buffer.push_stderr(chunk.content.clone()),
+            }
+        }
+        buffer
+    }
+
     /// Add stdout data to the buffer
     pub fn push_stdout(&mut self, data: Vec<u8>) {
         let now = chrono::Utc::now().timestamp();
@@ -183,14 +196,10 @@ pub struct ClaudeCodeTuiHandler {
     agent_name: String,
     /// Agent task
     agent_task: String,
-    /// Output buffer for history
-    output_buffer: Arc<RwLock<OutputBuffer>>,
+    /// Agent output recorded by the attach server, replayed then followed
+    output_log: OutputLog,
     /// Channel for sending stdin to agent
     stdin_tx: mpsc::Sender<Vec<u8>>,
-    /// Broadcast channel for stdout from agent
-    stdout_rx: broadcast::Receiver<Vec<u8>>,
-    /// Broadcast channel for stderr from agent
-    stderr_rx: broadcast::Receiver<Vec<u8>>,
 }
 
 impl ClaudeCodeTuiHandler {
@@ -203,30 +212,22 @@ impl ClaudeCodeTuiHandler {
         agent_name: String,
         agent_task: String,
         stdin_tx: mpsc::Sender<Vec<u8>>,
-        stdout_rx: broadcast::Receiver<Vec<u8>>,
-        stderr_rx: broadcast::Receiver<Vec<u8>>,
+        output_log: OutputLog,
     ) -> Self {
-        let output_buffer = Arc::new(RwLock::new(OutputBuffer::new(
-            config.max_history_bytes,
-            config.max_history_lines,
-        )));
-
         Self {
             config,
             session_manager,
             agent_id,
             agent_name,
             agent_task,
-            output_buffer,
+            output_log,
             stdin_tx,
-            stdout_rx,
-            stderr_rx,
         }
     }
 
-    /// Get a reference to the output buffer
-    pub fn output_buffer(&self) -> Arc<RwLock<OutputBuffer>> {
-        Arc::clone(&self.output_buffer)
+    /// Get the output log this handler replays from
+    pub fn output_log(&self) -> &OutputLog {
+        &self.output_log
     }
 
     /// Handle a new Unix socket connection from Claude Code
@@ -235,29 +236,40 @@ impl ClaudeCodeTuiHandler {
         let mut reader = BufReader::new(read_half);
         let mut writer = write_half;
 
+        // Subscribe before the handshake so nothing printed in between is lost
+        let mut output = self.output_log.subscribe_from(0);
+        let backlog = output.take_backlog();
+
         // Perform handshake
-        let session_info = self.perform_handshake(&mut reader, &mut writer).await?;
+        let session_id = self
+            .perform_handshake(&mut reader, &mut writer, backlog.len())
+            .await?;
         info!(
             "Claude Code TUI handshake successful for agent {}",
             self.agent_id
         );
 
         // Send historical output
-        self.send_historical_output(&mut writer).await?;
-        info!("Historical output sent to Claude Code client");
-
-        // Start IO forwarding loop
-        self.run_io_loop(&mut reader, &mut writer, session_info)
-            .await?;
+        // Start IO forwarding loop, ending the session however it exits
+        let result = match self.send_historical_output(&mut writer, &backlog).await {
+            Ok(()) => {
+                info!("Historical output sent to Claude Code client");
+                self.run_io_loop(&mut reader, &mut writer, session_id, &mut output)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        self.session_manager.terminate_session(&session_id).await;
 
-        Ok(())
+        result
     }
 
-    /// Perform the protocol handshake
+    /// Perform the protocol handshake, returning the new attach session's ID
     async fn perform_handshake<R, W>(
         &self,
         reader: &mut BufReader<R>,
         writer: &mut W,
+        history_lines: usize,
     ) -> DaemonResult<Uuid>
     where
         R: AsyncReadExt + Unpin,
@@ -320,29 +332,53 @@ impl ClaudeCodeTuiHandler {
             ));
         }
 
+        // Register the session so idle clients can be reaped
+        let session = match self
+            .session_manager
+            .create_session(
+                validated_agent_id,
+                handshake.token.clone(),
+                ClientType::from(handshake.client_type.as_str()),
+                handshake.client_version.clone(),
+            )
+            .await
+        {
+            Ok(session) => session,
+            Err(e) => {
+                let response = AttachHandshakeResponse::failure(&e.to_string());
+                self.send_message(writer, &response.to_message()).await?;
+                return Err(e);
+            }
+        };
+
         // Send success response
-        let buffer = self.output_buffer.read().await;
         let response = AttachHandshakeResponse::success(
             self.agent_id.to_string(),
             self.agent_name.clone(),
             self.agent_task.clone(),
-            buffer.total_lines(),
+            history_lines,
         );
-        drop(buffer);
 
         self.send_message(writer, &response.to_message()).await?;
 
-        Ok(validated_agent_id)
+        Ok(session.session_id)
     }
 
     /// Send historical output to the client
-    async fn send_historical_output<W>(&self, writer: &mut W) -> DaemonResult<()>
+    async fn send_historical_output<W>(
+        &self,
+        writer: &mut W,
+        chunks: &[OutputChunk],
+    ) -> DaemonResult<()>
     where
         W: AsyncWriteExt + Unpin,
     {
-        let buffer = self.output_buffer.read().await;
-        let history = buffer.to_historical_output();
-        drop(buffer);
+        let history = OutputBuffer::from_chunks(
+            self.config.max_history_bytes,
+            self.config.max_history_lines,
+            chunks,
+        )
+        .to_historical_output();
 
         let msg = history.to_message();
         self.send_message(writer, &msg).await
@@ -353,7 +389,8 @@ impl ClaudeCodeTuiHandler {
         &mut self,
         reader: &mut BufReader<R>,
         writer: &mut W,
-        _validated_agent_id: Uuid,
+        session_id: Uuid,
+        output: &mut OutputSubscription,
     ) -> DaemonResult<()>
     where
         R: AsyncReadExt + Unpin,
@@ -363,11 +400,8 @@ impl ClaudeCodeTuiHandler {
         let mut ping_timer = tokio::time::interval(ping_interval);
         let mut seq: u64 = 0;
 
-        // Extract mutable receivers to avoid borrow conflicts in select!
-        let stdout_rx = &mut self.stdout_rx;
-        let stderr_rx = &mut self.stderr_rx;
-        let output_buffer = &self.output_buffer;
         let stdin_tx = &self.stdin_tx;
+        let session_manager = &self.session_manager;
 
         loop {
             tokio::select! {
@@ -375,6 +409,7 @@ impl ClaudeCodeTuiHandler {
                 result = Self::read_message_static(reader) => {
                     match result {
                         Ok(msg) => {
+                            session_manager.touch_session(&session_id).await;
                             if !Self::handle_client_message_static(msg, writer, stdin_tx).await? {
                                 // Client disconnected gracefully
                                 info!("Claude Code client disconnected gracefully");
@@ -388,59 +423,21 @@ impl ClaudeCodeTuiHandler {
                     }
                 }
 
-                // Forward stdout from agent to Claude Code
-                result = stdout_rx.recv() => {
-                    match result {
-                        Ok(data) => {
-                            // Buffer for history
-                            {
-                                let mut buffer = output_buffer.write().await;
-                                buffer.push_stdout(data.clone());
-                            }
-
-                            // Forward to client
-                            let output_data = OutputData::from_bytes(&data);
-                            let msg = output_data.to_stdout_message();
-                            if let Err(e) = Self::send_message_static(writer, &msg).await {
-                                warn!("Error sending stdout to client: {}", e);
-                                break;
-                            }
-                        }
-                        Err(broadcast::error::RecvError::Lagged(n)) => {
-                            warn!("Claude Code client lagged, missed {} stdout messages", n);
-                        }
-                        Err(broadcast::error::RecvError::Closed) => {
-                            info!("Agent stdout channel closed");
-                            break;
-                        }
-                    }
-                }
-
-                // Forward stderr from agent to Claude Code
-                result = stderr_rx.recv() => {
-                    match result {
-                        Ok(data) => {
-                            // Buffer for history
-                            {
-                                let mut buffer = output_buffer.write().await;
-                                buffer.push_stderr(data.clone());
-                            }
-
-                            // Forward to client
-                            let output_data = OutputData::from_bytes(&data);
-                            let msg = output_data.to_stderr_message();
-                            if let Err(e) = Self::send_message_static(writer, &msg).await {
-                                warn!("Error sending stderr to client: {}", e);
-                                break;
-                            }
-                        }
-                        Err(broadcast::error::RecvError::Lagged(n)) => {
-                            warn!("Claude Code client lagged, missed {} stderr messages", n);
-                        }
-                        Err(broadcast::error::RecvError::Closed) => {
-                            info!("Agent stderr channel closed");
-                            break;
-                        }
+                // Forward agent output to Claude Code
+                chunk = output.recv() => {
+                    let Some(chunk) = chunk else {
+                        info!("Agent output log closed");
+                        break;
+                    };
+
+                    let output_data = OutputData::from_bytes(&chunk.content);
+                    let msg = match chunk.stream {
+                        OutputStream::Stdout => output_data.to_stdout_message(),
+                        OutputStream::Stderr => output_data.to_stderr_message(),
+                    };
+                    if let Err(e) = Self::send_message_static(writer, &msg).await {
+                        warn!("Error sending output to client: {}", e);
+                        break;
                     }
                 }
 
@@ -514,7 +511,8 @@ impl ClaudeCodeTuiHandler {
 
             AttachMessageType::ReadOutput => {
                 // Client wants to read buffered output (shouldn't happen normally after history sent)
-                self.send_historical_output(writer).await?;
+                let chunks = self.output_log.snapshot_from(0);
+                self.send_historical_output(writer, &chunks).await?;
                 Ok(true)
             }
 
@@ -686,33 +684,48 @@ pub async fn start_attach_server(
         socket_path
     );
 
+    // Record output for the server's lifetime so a client attaching to a
+    // paused agent sees what it printed before the client connected
+    let output_log = OutputLog::new(config.max_history_lines);
+    let mut stdout_rx = stdout_tx.subscribe();
+    let mut stderr_rx = stderr_tx.subscribe();
+
     // Accept connections
     loop {
-        match listener.accept().await {
-            Ok((stream, _)) => {
-                info!("New Claude Code TUI connection");
-
-                // Create handler for this connection
-                let mut handler = ClaudeCodeTuiHandler::new(
-                    config.clone(),
-                    Arc::clone(&session_manager),
-                    agent_id,
-                    agent_name.clone(),
-                    agent_task.clone(),
-                    stdin_tx.clone(),
-                    stdout_tx.subscribe(),
-                    stderr_tx.subscribe(),
-                );
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    info!("New Claude Code TUI connection");
+
+                    // Create handler for this connection
+                    let mut handler = ClaudeCodeTuiHandler::new(
+                        config.clone(),
+                        Arc::clone(&session_manager),
+                        agent_id,
+                        agent_name.clone(),
+                        agent_task.clone(),
+                        stdin_tx.clone(),
+                        output_log.clone(),
+                    );
 
-                // Handle connection in background
-                tokio::spawn(async move {
-                    if let Err(e) = handler.handle_connection(stream).await {
-                        error!("Claude Code TUI connection error: {}", e);
-                    }
-                });
+                    // Handle connection in background
+                    tokio::spawn(async move {
+                        if let Err(e) = handler.handle_connection(stream).await {
+                            error!("Claude Code TUI connection error: {}", e);
+                        }
+                    });
+                }
+                Err(e) => {
+                    error!("Failed to accept connection: {}", e);
+                }
+            },
+
+            Ok(data) = stdout_rx.recv() => {
+                output_log.append(OutputStream::Stdout, data);
             }
-            Err(e) => {
-                error!("Failed to accept connection: {}", e);
+
+            Ok(data) = stderr_rx.recv() => {
+                output_log.append(OutputStream::Stderr, data);
             }
         }
     }
@@ -785,4 +798,94 @@ mod tests {
         assert_eq!(config.connection_timeout_secs, 300);
         assert_eq!(config.ping_interval_secs, 30);
     }
+
+    #[tokio::test]
+    async fn test_attach_replays_history_then_streams_live_output() {
+        use crate::events::EventBus;
+        use base64::Engine;
+        use descartes_core::AttachTokenStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("claude.sock");
+        let session_manager = Arc::new(AttachSessionManager::with_defaults(
+            Arc::new(AttachTokenStore::new()),
+            Arc::new(EventBus::new()),
+        ));
+        let agent_id = Uuid::new_v4();
+        let (stdin_tx, _stdin_rx) = mpsc::channel(16);
+        let (stdout_tx, _) = broadcast::channel(16);
+        let (stderr_tx, _) = broadcast::channel(16);
+
+        let server = {
+            let socket_path = socket_path.clone();
+            let session_manager = Arc::clone(&session_manager);
+            let (stdout_tx, stderr_tx) = (stdout_tx.clone(), stderr_tx.clone());
+            tokio::spawn(async move {
+                start_attach_server(
+                    &socket_path,
+                    ClaudeCodeTuiConfig::default(),
+                    session_manager,
+                    agent_id,
+                    "agent".to_string(),
+                    "task".to_string(),
+                    stdin_tx,
+                    stdout_tx,
+                    stderr_tx,
+                )
+                .await
+            })
+        };
+        while !socket_path.exists() {
+            tokio::task::yield_now().await;
+        }
+
+        stdout_tx.send(b"before attach\n".to_vec()).unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let creds = session_manager
+            .request_attach(agent_id, ClientType::ClaudeCode)
+            .await
+            .unwrap();
+        let stream = UnixStream::connect(&socket_path).await.unwrap();
+        let (read_half, mut writer) = stream.into_split();
+        let mut reader = BufReader::new(read_half);
+
+        let handshake =
+            AttachHandshake::new(creds.token, "claude-code".to_string(), "1.0".to_string());
+        ClaudeCodeTuiHandler::send_message_static(&mut writer, &handshake.to_message())
+            .await
+            .unwrap();
+        let response = ClaudeCodeTuiHandler::read_message_static(&mut reader)
+            .await
+            .unwrap();
+        let response: AttachHandshakeResponse = serde_json::from_value(response.payload).unwrap();
+        assert!(response.success);
+        assert_eq!(response.buffered_output_lines, 1);
+
+        let history = ClaudeCodeTuiHandler::read_message_static(&mut reader)
+            .await
+            .unwrap();
+        let history: HistoricalOutput = serde_json::from_value(history.payload).unwrap();
+        assert_eq!(history.stdout.len(), 1);
+
+        stderr_tx.send(b"after attach\n".to_vec()).unwrap();
+        let live = loop {
+            let msg = ClaudeCodeTuiHandler::read_message_static(&mut reader)
+                .await
+                .unwrap();
+            if msg.msg_type != AttachMessageType::Ping {
+                break msg;
+            }
+        };
+        assert_eq!(live.msg_type, AttachMessageType::Stderr);
+        let live: OutputData = serde_json::from_value(live.payload).unwrap();
+        assert_eq!(
+            base64::engine::general_purpose::STANDARD
+                .decode(live.data)
+                .unwrap(),
+            b"after attach\n".to_vec()
+        );
+
+        server.abort();
+    }
 }
@@ -1,7 +1,10 @@
 /// Daemon configuration
 use crate::errors::{DaemonError, DaemonResult};
+use crate::event_store::EventRetention;
+use descartes_core::swank::SwankPoolConfig;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// Daemon configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,6 +14,10 @@ pub struct DaemonConfig {
     pub auth: AuthConfig,
     pub pool: PoolConfig,
     pub logging: LoggingConfig,
+    #[serde(default)]
+    pub swank: SwankConfig,
+    #[serde(default)]
+    pub events: EventHistoryConfig,
 }
 
 /// Server configuration
@@ -36,6 +43,27 @@ pub struct ServerConfig {
     pub enable_metrics: bool,
     /// Metrics port
     pub metrics_port: u16,
+    /// Maximum number of agents running at once; further spawns are rejected
+    #[serde(default = "default_max_concurrent_agents")]
+    pub max_concurrent_agents: usize,
+    /// Respawn agents marked restartable that were running when the daemon
+    /// last stopped
+    #[serde(default)]
+    pub restore_agents: bool,
+    /// SQLite state store spawned agents are saved to, so a restarted daemon
+    /// can restore them
+    #[serde(default = "default_state_store_path")]
+    pub state_store_path: PathBuf,
+}
+
+fn default_max_concurrent_agents() -> usize {
+    32
+}
+
+fn default_state_store_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_default()
+        .join(".descartes/data/descartes.db")
 }
 
 impl Default for ServerConfig {
@@ -51,6 +79,9 @@ impl Default for ServerConfig {
             max_connections: 1000,
             enable_metrics: true,
             metrics_port: 9090,
+            max_concurrent_agents: default_max_concurrent_agents(),
+            restore_agents: false,
+            state_store_path: default_state_store_path(),
         }
     }
 }
@@ -127,6 +158,74 @@ impl Default for LoggingConfig {
     }
 }
 
+/// Warm SBCL process pool for Lisp agents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SwankConfig {
+    /// Most idle SBCL processes kept for reuse; 0 starts a fresh one per agent
+    pub pool_size: usize,
+    /// Seconds an idle SBCL process is kept before it is killed
+    pub idle_ttl_secs: u64,
+    /// SBCL binary to launch, looked up on PATH unless absolute
+    pub sbcl_path: String,
+}
+
+impl Default for SwankConfig {
+    fn default() -> Self {
+        SwankConfig {
+            pool_size: 2,
+            idle_ttl_secs: 300,
+            sbcl_path: "sbcl".to_string(),
+        }
+    }
+}
+
+impl From<&SwankConfig> for SwankPoolConfig {
+    fn from(config: &SwankConfig) -> Self {
+        SwankPoolConfig {
+            max_warm: config.pool_size,
+            idle_ttl: Duration::from_secs(config.idle_ttl_secs),
+            sbcl: config.sbcl_path.clone(),
+        }
+    }
+}
+
+/// Persistent history of bus events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventHistoryConfig {
+    /// Record published events so clients can query what they missed
+    pub enabled: bool,
+    /// SQLite file the history is kept in
+    pub path: PathBuf,
+    /// Days an event is kept; 0 keeps events forever
+    pub max_age_days: u64,
+    /// Most events kept; 0 keeps every event
+    pub max_events: usize,
+}
+
+impl Default for EventHistoryConfig {
+    fn default() -> Self {
+        EventHistoryConfig {
+            enabled: false,
+            path: dirs::home_dir()
+                .unwrap_or_default()
+                .join(".descartes/events.db"),
+            max_age_days: 7,
+            max_events: 100_000,
+        }
+    }
+}
+
+impl From<&EventHistoryConfig> for EventRetention {
+    fn from(config: &EventHistoryConfig) -> Self {
+        EventRetention {
+            max_age: (config.max_age_days > 0)
+                .then(|| chrono::Duration::days(config.max_age_days as i64)),
+            max_events: (config.max_events > 0).then_some(config.max_events),
+        }
+    }
+}
 
 impl DaemonConfig {
     /// Load configuration from file
@@ -160,6 +259,12 @@ impl DaemonConfig {
             ));
         }
 
+        if self.server.max_concurrent_agents == 0 {
+            return Err(DaemonError::ConfigError(
+                "max_concurrent_agents must be greater than 0".to_string(),
+            ));
+        }
+
         if self.pool.min_size > self.pool.max_size {
             return Err(DaemonError::ConfigError(
                 "pool.min_size must be <= pool.max_size".to_string(),
@@ -194,6 +299,13 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_config_max_concurrent_agents_validation() {
+        let mut config = DaemonConfig::default();
+        config.server.max_concurrent_agents = 0;
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_config_pool_validation() {
         let mut config = DaemonConfig::default();
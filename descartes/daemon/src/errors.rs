@@ -1,4 +1,5 @@
 /// Error types for the RPC daemon
+use jsonrpsee::types::ErrorObjectOwned;
 use serde_json::json;
 use thiserror::Error;
 
@@ -80,6 +81,14 @@ pub enum DaemonError {
     #[error("Attach session error: {0}")]
     AttachError(String),
 
+    /// Failed to deliver input to an agent
+    #[error("Agent input error: {0}")]
+    InputError(String),
+
+    /// Spawn rejected because the maximum number of agents is running
+    #[error("Daemon at capacity: {0} agents already running")]
+    AtCapacity(usize),
+
     /// IO error
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
@@ -131,6 +140,11 @@ impl DaemonError {
             DaemonError::PauseError(msg) => (-32013, format!("Pause error: {}", msg)),
             DaemonError::ResumeError(msg) => (-32014, format!("Resume error: {}", msg)),
             DaemonError::AttachError(msg) => (-32015, format!("Attach error: {}", msg)),
+            DaemonError::InputError(msg) => (-32016, format!("Input error: {}", msg)),
+            DaemonError::AtCapacity(max) => (
+                -32030,
+                format!("Daemon at capacity: {} agents already running", max),
+            ),
             DaemonError::IoError(e) => (-32603, format!("IO error: {}", e)),
             DaemonError::Timeout => (-32009, "Operation timed out".to_string()),
             DaemonError::ConnectionError(msg) => (-32010, format!("Connection error: {}", msg)),
@@ -165,6 +179,8 @@ impl DaemonError {
             DaemonError::PauseError(_) => -32013,
             DaemonError::ResumeError(_) => -32014,
             DaemonError::AttachError(_) => -32015,
+            DaemonError::InputError(_) => -32016,
+            DaemonError::AtCapacity(_) => -32030,
             DaemonError::IoError(_) => -32603,
             DaemonError::Timeout => -32009,
             DaemonError::ConnectionError(_) => -32010,
@@ -191,3 +207,13 @@ impl From<&str> for DaemonError {
         DaemonError::Other(e.to_string())
     }
 }
+
+impl From<DaemonError> for ErrorObjectOwned {
+    fn from(e: DaemonError) -> Self {
+        let message = e.to_rpc_error()["message"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        ErrorObjectOwned::owned(e.code() as i32, message, None::<()>)
+    }
+}
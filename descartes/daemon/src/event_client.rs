@@ -9,7 +9,7 @@ use futures::{SinkExt, StreamExt};
 use serde_json;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{mpsc, watch, RwLock};
 use tokio_tungstenite::{
     connect_async,
     tungstenite::Message as WsMessage,
@@ -25,8 +25,10 @@ pub struct EventClientConfig {
     pub connect_timeout: Duration,
     /// Reconnection attempts (-1 for infinite)
     pub max_reconnect_attempts: i32,
-    /// Delay between reconnection attempts
+    /// Delay before the first reconnection attempt; doubles after each failure
     pub reconnect_delay: Duration,
+    /// Upper bound on the delay between reconnection attempts
+    pub max_reconnect_delay: Duration,
     /// Event filter
     pub filter: Option<EventFilter>,
 }
@@ -38,6 +40,7 @@ impl Default for EventClientConfig {
             connect_timeout: Duration::from_secs(10),
             max_reconnect_attempts: -1, // Infinite reconnects
             reconnect_delay: Duration::from_secs(5),
+            max_reconnect_delay: Duration::from_secs(60),
             filter: None,
         }
     }
@@ -59,6 +62,12 @@ pub struct EventClient {
     state: Arc<RwLock<EventClientState>>,
     event_tx: mpsc::UnboundedSender<DescartesEvent>,
     subscription_id: Arc<RwLock<Option<String>>>,
+    /// Current filter, re-sent when the client reconnects
+    filter: Arc<RwLock<Option<EventFilter>>>,
+    /// Messages to send on the live connection, if there is one
+    outgoing: Arc<RwLock<Option<mpsc::UnboundedSender<ClientMessage>>>>,
+    /// Set by `disconnect` to stop the connection loop
+    shutdown: watch::Sender<bool>,
 }
 
 impl EventClient {
@@ -67,10 +76,13 @@ impl EventClient {
         let (event_tx, event_rx) = mpsc::unbounded_channel();
 
         let client = Self {
+            filter: Arc::new(RwLock::new(config.filter.clone())),
             config,
             state: Arc::new(RwLock::new(EventClientState::Disconnected)),
             event_tx,
             subscription_id: Arc::new(RwLock::new(None)),
+            outgoing: Arc::new(RwLock::new(None)),
+            shutdown: watch::channel(false).0,
         };
 
         (client, event_rx)
@@ -92,24 +104,37 @@ impl EventClient {
     }
 
     /// Connect to the event stream and start receiving events
+    ///
+    /// Runs until [`EventClient::disconnect`] is called. Whenever the
+    /// connection drops the client reconnects with exponential backoff,
+    /// re-subscribing with the current filter, and reports
+    /// [`EventClientState::Reconnecting`] while it waits. Fails once
+    /// `max_reconnect_attempts` consecutive attempts have failed.
     pub async fn connect(&self) -> DaemonResult<()> {
-        let mut reconnect_count = 0;
+        self.shutdown.send_replace(false);
+        let mut shutdown = self.shutdown.subscribe();
+        let mut failures = 0;
 
         loop {
-            // Update state to connecting
-            *self.state.write().await = EventClientState::Connecting;
-
-            match self.try_connect().await {
-                Ok(_) => {
-                    info!("Event client connected successfully");
-                    reconnect_count = 0;
+            *self.state.write().await = if failures == 0 {
+                EventClientState::Connecting
+            } else {
+                EventClientState::Reconnecting
+            };
+
+            let delay = match self.try_connect(&mut shutdown).await {
+                Ok(()) if *shutdown.borrow() => break,
+                Ok(()) => {
+                    info!("Event stream closed, reconnecting");
+                    failures = 0;
+                    self.config.reconnect_delay
                 }
                 Err(e) => {
                     error!("Failed to connect to event stream: {}", e);
 
                     // Check if we should retry
                     if self.config.max_reconnect_attempts >= 0
-                        && reconnect_count >= self.config.max_reconnect_attempts
+                        && failures >= self.config.max_reconnect_attempts
                     {
                         error!(
                             "Maximum reconnection attempts ({}) exceeded",
@@ -119,27 +144,46 @@ impl EventClient {
                         return Err(e);
                     }
 
-                    reconnect_count += 1;
-                    *self.state.write().await = EventClientState::Reconnecting;
-
-                    warn!(
-                        "Reconnecting in {:?} (attempt {})...",
-                        self.config.reconnect_delay, reconnect_count
-                    );
-                    tokio::time::sleep(self.config.reconnect_delay).await;
+                    failures += 1;
+                    self.backoff_delay(failures)
                 }
+            };
+
+            *self.state.write().await = EventClientState::Reconnecting;
+            warn!(
+                "Reconnecting in {:?} (attempt {})...",
+                delay,
+                failures.max(1)
+            );
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = shutdown.changed() => break,
             }
         }
+
+        *self.state.write().await = EventClientState::Disconnected;
+        Ok(())
     }
 
-    /// Try to connect once (internal method)
-    async fn try_connect(&self) -> DaemonResult<()> {
+    /// Delay before the given reconnection attempt (1-based)
+    fn backoff_delay(&self, attempt: i32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1) as u32);
+        self.config
+            .reconnect_delay
+            .saturating_mul(factor)
+            .min(self.config.max_reconnect_delay)
+    }
+
+    /// Connect once and process messages until the connection closes
+    async fn try_connect(&self, shutdown: &mut watch::Receiver<bool>) -> DaemonResult<()> {
         info!("Connecting to event stream at {}", self.config.url);
 
         // Connect to WebSocket
-        let (ws_stream, _) = connect_async(&self.config.url)
-            .await
-            .map_err(|e| DaemonError::ConnectionError(e.to_string()))?;
+        let (ws_stream, _) =
+            tokio::time::timeout(self.config.connect_timeout, connect_async(&self.config.url))
+                .await
+                .map_err(|_| DaemonError::Timeout)?
+                .map_err(|e| DaemonError::ConnectionError(e.to_string()))?;
 
         let (mut ws_sink, mut ws_stream) = ws_stream.split();
 
@@ -147,7 +191,7 @@ impl EventClient {
 
         // Subscribe to events
         let subscribe_msg = ClientMessage::Subscribe {
-            filter: self.config.filter.clone(),
+            filter: self.filter.read().await.clone(),
         };
         let json = serde_json::to_string(&subscribe_msg)
             .map_err(|e| DaemonError::SerializationError(e.to_string()))?;
@@ -159,8 +203,31 @@ impl EventClient {
 
         info!("Subscribed to events");
 
+        let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded_channel();
+        *self.outgoing.write().await = Some(outgoing_tx);
+
         // Message processing loop
-        while let Some(msg) = ws_stream.next().await {
+        loop {
+            let msg = tokio::select! {
+                msg = ws_stream.next() => match msg {
+                    Some(msg) => msg,
+                    None => break,
+                },
+                Some(outgoing) = outgoing_rx.recv() => {
+                    let json = serde_json::to_string(&outgoing)
+                        .map_err(|e| DaemonError::SerializationError(e.to_string()))?;
+                    if let Err(e) = ws_sink.send(WsMessage::Text(json)).await {
+                        error!("Failed to send message: {}", e);
+                        break;
+                    }
+                    continue;
+                }
+                _ = shutdown.changed() => {
+                    let _ = ws_sink.send(WsMessage::Close(None)).await;
+                    break;
+                }
+            };
+
             match msg {
                 Ok(WsMessage::Text(text)) => {
                     debug!("Received message: {}", text);
@@ -220,6 +287,7 @@ impl EventClient {
         }
 
         // Connection closed or error occurred
+        *self.outgoing.write().await = None;
         *self.state.write().await = EventClientState::Disconnected;
         *self.subscription_id.write().await = None;
 
@@ -227,16 +295,22 @@ impl EventClient {
     }
 
     /// Update the event filter
-    pub async fn update_filter(&self, _filter: EventFilter) -> DaemonResult<()> {
-        // This would require maintaining a WebSocket connection handle
-        // For now, just update the config for next connection
-        // TODO: Implement live filter updates
-        warn!("Live filter updates not yet implemented");
+    ///
+    /// Applied to the live connection if there is one, and used for every
+    /// subscription after a reconnect.
+    pub async fn update_filter(&self, filter: EventFilter) -> DaemonResult<()> {
+        *self.filter.write().await = Some(filter.clone());
+        if let Some(outgoing) = self.outgoing.read().await.as_ref() {
+            outgoing
+                .send(ClientMessage::UpdateFilter { filter })
+                .map_err(|_| DaemonError::ConnectionError("Connection closed".to_string()))?;
+        }
         Ok(())
     }
 
-    /// Disconnect from the event stream
+    /// Disconnect from the event stream and stop reconnecting
     pub async fn disconnect(&self) {
+        self.shutdown.send_replace(true);
         *self.state.write().await = EventClientState::Disconnected;
         info!("Event client disconnected");
     }
@@ -279,6 +353,12 @@ impl EventClientBuilder {
         self
     }
 
+    /// Set the maximum delay between reconnection attempts
+    pub fn max_reconnect_delay(mut self, delay: Duration) -> Self {
+        self.config.max_reconnect_delay = delay;
+        self
+    }
+
     /// Set event filter
     pub fn filter(mut self, filter: EventFilter) -> Self {
         self.config.filter = Some(filter);
@@ -327,4 +407,106 @@ mod tests {
         assert_eq!(client.state().await, EventClientState::Disconnected);
         assert_eq!(client.subscription_id().await, None);
     }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let (client, _rx) = EventClientBuilder::new()
+            .reconnect_delay(Duration::from_millis(100))
+            .max_reconnect_delay(Duration::from_millis(500))
+            .build();
+
+        let delays: Vec<_> = (1..=5).map(|n| client.backoff_delay(n)).collect();
+        assert_eq!(
+            delays,
+            [100, 200, 400, 500, 500]
+                .map(Duration::from_millis)
+                .to_vec()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reconnects_and_resubscribes_after_drop() {
+        use crate::events::AgentEvent;
+        use tokio::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (filters_tx, mut filters_rx) = mpsc::unbounded_channel();
+
+        // Serves one event per connection, dropping the first connection
+        tokio::spawn(async move {
+            for agent in ["first", "second"] {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                if let Some(Ok(WsMessage::Text(text))) = ws.next().await {
+                    if let Ok(ClientMessage::Subscribe { filter }) = serde_json::from_str(&text) {
+                        filters_tx.send(filter).unwrap();
+                    }
+                }
+                let event = ServerMessage::Event(AgentEvent::spawned(
+                    agent.to_string(),
+                    serde_json::json!({}),
+                ));
+                ws.send(WsMessage::Text(serde_json::to_string(&event).unwrap()))
+                    .await
+                    .unwrap();
+                if agent == "second" {
+                    // Keep the second connection open until the client leaves
+                    while let Some(Ok(_)) = ws.next().await {}
+                }
+            }
+        });
+
+        let (client, mut events) = EventClientBuilder::new()
+            .url(url)
+            .reconnect_delay(Duration::from_millis(200))
+            .filter(EventFilter::for_agent("first".to_string()))
+            .build();
+        let client = Arc::new(client);
+        let runner = tokio::spawn({
+            let client = Arc::clone(&client);
+            async move { client.connect().await }
+        });
+
+        let next_agent = |event: Option<DescartesEvent>| match event {
+            Some(DescartesEvent::AgentEvent(e)) => e.agent_id,
+            other => panic!("expected an agent event, got {:?}", other),
+        };
+        let timeout = Duration::from_secs(5);
+        let first = tokio::time::timeout(timeout, events.recv()).await.unwrap();
+        assert_eq!(next_agent(first), "first");
+        assert_eq!(
+            filters_rx.recv().await.unwrap().unwrap().agent_ids,
+            vec!["first"]
+        );
+
+        // The server drops the connection; the client waits, then comes back
+        tokio::time::timeout(timeout, async {
+            while client.state().await != EventClientState::Reconnecting {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("client reports reconnecting");
+        client
+            .update_filter(EventFilter::for_agent("second".to_string()))
+            .await
+            .unwrap();
+
+        let second = tokio::time::timeout(timeout, events.recv()).await.unwrap();
+        assert_eq!(next_agent(second), "second");
+        assert_eq!(
+            filters_rx.recv().await.unwrap().unwrap().agent_ids,
+            vec!["second"]
+        );
+        assert_eq!(client.state().await, EventClientState::Connected);
+
+        client.disconnect().await;
+        let result = tokio::time::timeout(timeout, runner)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(result.is_ok());
+        assert_eq!(client.state().await, EventClientState::Disconnected);
+    }
 }
@@ -0,0 +1,548 @@
+/// Filter expressions for event bus subscriptions
+///
+/// Clients can subscribe with a small query language over event fields:
+///
+/// ```text
+/// agent_id = worker-1 AND event_type IN (Error, Log)
+/// category = task AND NOT data.progress < 50
+/// ```
+///
+/// Supported syntax:
+/// - Comparisons: `=`, `==`, `!=`, `<`, `<=`, `>`, `>=`
+/// - Set membership: `field IN (a, b)`, `field NOT IN (a, b)`
+/// - Boolean operators: `AND`, `OR`, `NOT` and parentheses (keywords are case-insensitive)
+/// - Values: bare words, quoted strings, numbers, `true`, `false`, `null`
+///
+/// Fields are `category`, `id`, `timestamp`, `event_type`, `agent_id`, `task_id`,
+/// `workflow_id`, `execution_id`, `key`, and `data.<path>` into the event payload.
+/// Fields an event does not carry evaluate to `null`, and ordering comparisons
+/// against `null` are false. Enum values such as `event_type` are matched in
+/// either `CamelCase` or `snake_case`.
+///
+/// Expressions are compiled once into a `descartes_core::expression_eval` AST and
+/// evaluated per event. A `FilterExpression` serializes as its source string and
+/// is parsed on deserialization.
+use crate::errors::{DaemonError, DaemonResult};
+use crate::events::DescartesEvent;
+use descartes_core::expression_eval::{
+    context_from_json, BinaryOp, EvalContext, Expr, ExpressionEvaluator, UnaryOp,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+/// Top-level fields available to filter expressions
+const EVENT_FIELDS: &[&str] = &[
+    "category",
+    "id",
+    "timestamp",
+    "event_type",
+    "agent_id",
+    "task_id",
+    "workflow_id",
+    "execution_id",
+    "key",
+];
+
+/// Fields whose values are serialized enum variants
+const ENUM_FIELDS: &[&str] = &["category", "event_type"];
+
+/// A compiled event filter expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterExpression {
+    source: String,
+    ast: Expr,
+    /// Field paths referenced by the expression
+    fields: Vec<String>,
+}
+
+impl FilterExpression {
+    /// Parse and compile a filter expression
+    pub fn parse(source: &str) -> DaemonResult<Self> {
+        let tokens = tokenize(source).map_err(invalid_filter)?;
+        let mut parser = FilterParser {
+            tokens,
+            pos: 0,
+            fields: Vec::new(),
+        };
+        let ast = parser.parse_or().map_err(invalid_filter)?;
+        if let Some(token) = parser.peek() {
+            return Err(invalid_filter(format!("unexpected {}", token.describe())));
+        }
+
+        Ok(Self {
+            source: source.to_string(),
+            ast,
+            fields: parser.fields,
+        })
+    }
+
+    /// The expression as written by the client
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// The compiled expression AST
+    pub fn ast(&self) -> &Expr {
+        &self.ast
+    }
+
+    /// Check if an event matches this expression.
+    ///
+    /// Evaluation errors (e.g. comparing a string field with `<`) count as no match.
+    pub fn matches(&self, event: &DescartesEvent) -> bool {
+        let mut context = event_context(event);
+        for field in &self.fields {
+            if context.get(field).is_none() {
+                // Dotted variables are looked up verbatim when the path does not resolve
+                context.set(field, Value::Null);
+            }
+        }
+
+        ExpressionEvaluator::new()
+            .evaluate_ast_bool(&self.ast, &context)
+            .unwrap_or(false)
+    }
+}
+
+impl Serialize for FilterExpression {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+impl<'de> Deserialize<'de> for FilterExpression {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let source = String::deserialize(deserializer)?;
+        Self::parse(&source).map_err(serde::de::Error::custom)
+    }
+}
+
+fn invalid_filter(message: String) -> DaemonError {
+    DaemonError::InvalidRequest(format!("Invalid event filter: {}", message))
+}
+
+/// Build the evaluation context exposing an event's fields
+pub fn event_context(event: &DescartesEvent) -> EvalContext {
+    let (category, fields) = match event {
+        DescartesEvent::AgentEvent(e) => ("agent", serde_json::to_value(e)),
+        DescartesEvent::TaskEvent(e) => ("task", serde_json::to_value(e)),
+        DescartesEvent::WorkflowEvent(e) => ("workflow", serde_json::to_value(e)),
+        DescartesEvent::SystemEvent(e) => ("system", serde_json::to_value(e)),
+        DescartesEvent::StateEvent(e) => ("state", serde_json::to_value(e)),
+    };
+
+    let mut context = context_from_json(fields.unwrap_or(Value::Null));
+    context.set("category", Value::String(category.to_string()));
+    for field in EVENT_FIELDS {
+        context
+            .variables
+            .entry(field.to_string())
+            .or_insert(Value::Null);
+    }
+    context
+}
+
+/// Convert `CamelCase` enum names to the `snake_case` form events serialize to
+fn to_snake_case(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 4);
+    let mut prev_lower = false;
+    for ch in value.chars() {
+        if ch.is_uppercase() {
+            if prev_lower {
+                out.push('_');
+            }
+            out.extend(ch.to_lowercase());
+            prev_lower = false;
+        } else {
+            out.push(ch);
+            prev_lower = ch.is_lowercase() || ch.is_ascii_digit();
+        }
+    }
+    out
+}
+
+// ============================================================================
+// TOKENIZER
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Bare word: field name, keyword or unquoted value
+    Word(String),
+    /// Quoted string
+    Quoted(String),
+    Op(BinaryOp),
+    LParen,
+    RParen,
+    Comma,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Word(w) => format!("'{}'", w),
+            Token::Quoted(s) => format!("\"{}\"", s),
+            Token::Op(op) => format!("operator {:?}", op),
+            Token::LParen => "'('".to_string(),
+            Token::RParen => "')'".to_string(),
+            Token::Comma => "','".to_string(),
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Word(w) if w.eq_ignore_ascii_case(keyword))
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(pos, ch)) = chars.peek() {
+        if ch.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        match ch {
+            '(' | ')' | ',' => {
+                chars.next();
+                tokens.push(match ch {
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    _ => Token::Comma,
+                });
+            }
+            '=' | '!' | '<' | '>' => {
+                chars.next();
+                let followed_by_eq = chars.peek().map(|&(_, c)| c) == Some('=');
+                if followed_by_eq {
+                    chars.next();
+                }
+                let op = match (ch, followed_by_eq) {
+                    ('=', _) => BinaryOp::Eq,
+                    ('!', true) => BinaryOp::Ne,
+                    ('<', false) => BinaryOp::Lt,
+                    ('<', true) => BinaryOp::Le,
+                    ('>', false) => BinaryOp::Gt,
+                    ('>', true) => BinaryOp::Ge,
+                    _ => return Err(format!("expected '!=' at position {}", pos)),
+                };
+                tokens.push(Token::Op(op));
+            }
+            '"' | '\'' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, c)) if c == ch => break,
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, c)) => value.push(c),
+                            None => return Err("unterminated string".to_string()),
+                        },
+                        Some((_, c)) => value.push(c),
+                        None => return Err("unterminated string".to_string()),
+                    }
+                }
+                tokens.push(Token::Quoted(value));
+            }
+            c if is_word_char(c) => {
+                let mut word = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    if !is_word_char(c) {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+            _ => return Err(format!("unexpected character '{}' at position {}", ch, pos)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':')
+}
+
+// ============================================================================
+// PARSER
+// ============================================================================
+
+struct FilterParser {
+    tokens: Vec<Token>,
+    pos: usize,
+    fields: Vec<String>,
+}
+
+impl FilterParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if self.peek().is_some_and(|t| t.is_keyword(keyword)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(format!(
+                "expected {}, found {}",
+                expected.describe(),
+                token.describe()
+            )),
+            None => Err(format!(
+                "expected {}, found end of input",
+                expected.describe()
+            )),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        let mut left = self.parse_and()?;
+        while self.eat_keyword("OR") {
+            let right = self.parse_and()?;
+            left = binary(left, BinaryOp::Or, right);
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut left = self.parse_unary()?;
+        while self.eat_keyword("AND") {
+            let right = self.parse_unary()?;
+            left = binary(left, BinaryOp::And, right);
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        if self.eat_keyword("NOT") {
+            let expr = self.parse_unary()?;
+            return Ok(Expr::Unary {
+                op: UnaryOp::Not,
+                expr: Box::new(expr),
+            });
+        }
+
+        if self.peek() == Some(&Token::LParen) {
+            self.pos += 1;
+            let expr = self.parse_or()?;
+            self.expect(Token::RParen)?;
+            return Ok(Expr::Group(Box::new(expr)));
+        }
+
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, String> {
+        let field = match self.next() {
+            Some(Token::Word(word)) => word,
+            Some(token) => return Err(format!("expected field name, found {}", token.describe())),
+            None => return Err("expected field name, found end of input".to_string()),
+        };
+        if !EVENT_FIELDS.contains(&field.as_str()) && !field.starts_with("data.") {
+            return Err(format!("unknown field '{}'", field));
+        }
+        let is_enum = ENUM_FIELDS.contains(&field.as_str());
+        if !self.fields.contains(&field) {
+            self.fields.push(field.clone());
+        }
+        let variable = Expr::Variable(field);
+
+        let negated = self.eat_keyword("NOT");
+        if self.eat_keyword("IN") {
+            self.expect(Token::LParen)?;
+            let mut membership: Option<Expr> = None;
+            loop {
+                let value = self.parse_value(is_enum)?;
+                let eq = binary(variable.clone(), BinaryOp::Eq, value);
+                membership = Some(match membership {
+                    Some(acc) => binary(acc, BinaryOp::Or, eq),
+                    None => eq,
+                });
+                match self.next() {
+                    Some(Token::Comma) => continue,
+                    Some(Token::RParen) => break,
+                    Some(token) => {
+                        return Err(format!("expected ',' or ')', found {}", token.describe()))
+                    }
+                    None => return Err("unterminated IN list".to_string()),
+                }
+            }
+
+            let group = Expr::Group(Box::new(membership.expect("IN list has a value")));
+            return Ok(if negated {
+                Expr::Unary {
+                    op: UnaryOp::Not,
+                    expr: Box::new(group),
+                }
+            } else {
+                group
+            });
+        }
+        if negated {
+            return Err("expected IN after NOT".to_string());
+        }
+
+        let op = match self.next() {
+            Some(Token::Op(op)) => op,
+            Some(token) => {
+                return Err(format!(
+                    "expected comparison operator, found {}",
+                    token.describe()
+                ))
+            }
+            None => return Err("expected comparison operator, found end of input".to_string()),
+        };
+        let value = self.parse_value(is_enum)?;
+        Ok(binary(variable, op, value))
+    }
+
+    fn parse_value(&mut self, is_enum: bool) -> Result<Expr, String> {
+        let value = match self.next() {
+            Some(Token::Quoted(s)) => Value::String(s),
+            Some(Token::Word(word)) => match word.as_str() {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                "null" => Value::Null,
+                _ => word
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(serde_json::Number::from_f64)
+                    .map(Value::Number)
+                    .unwrap_or(Value::String(word)),
+            },
+            Some(token) => return Err(format!("expected value, found {}", token.describe())),
+            None => return Err("expected value, found end of input".to_string()),
+        };
+
+        Ok(Expr::Literal(match value {
+            Value::String(s) if is_enum => Value::String(to_snake_case(&s)),
+            other => other,
+        }))
+    }
+}
+
+fn binary(left: Expr, op: BinaryOp, right: Expr) -> Expr {
+    Expr::Binary {
+        left: Box::new(left),
+        op,
+        right: Box::new(right),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{AgentEvent, AgentEventType, SystemEvent, TaskEvent};
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn agent_event(agent_id: &str, event_type: AgentEventType) -> DescartesEvent {
+        DescartesEvent::AgentEvent(AgentEvent {
+            id: "evt-1".to_string(),
+            agent_id: agent_id.to_string(),
+            timestamp: Utc::now(),
+            event_type,
+            data: json!({"level": "warn", "line": 42}),
+        })
+    }
+
+    #[test]
+    fn test_agent_and_event_type_in() {
+        let filter =
+            FilterExpression::parse("agent_id=worker-1 AND event_type IN (Error,Log)").unwrap();
+
+        assert!(filter.matches(&agent_event("worker-1", AgentEventType::Log)));
+        assert!(!filter.matches(&agent_event("worker-1", AgentEventType::Spawned)));
+        assert!(!filter.matches(&agent_event("worker-2", AgentEventType::Log)));
+        assert!(!filter.matches(&SystemEvent::daemon_started()));
+    }
+
+    #[test]
+    fn test_or_not_and_payload_fields() {
+        let filter = FilterExpression::parse(
+            "(category = task OR data.line >= 40) and not event_type in (StatusChanged)",
+        )
+        .unwrap();
+
+        assert!(filter.matches(&agent_event("a", AgentEventType::Log)));
+        assert!(!filter.matches(&agent_event("a", AgentEventType::StatusChanged)));
+        assert!(filter.matches(&TaskEvent::progress(
+            "task-1".to_string(),
+            0.5,
+            "halfway".to_string()
+        )));
+
+        let filter = FilterExpression::parse("data.level = 'warn' AND data.line != 7").unwrap();
+        assert!(filter.matches(&agent_event("a", AgentEventType::Log)));
+        assert!(!filter.matches(&SystemEvent::daemon_started()));
+
+        // A payload field the event lacks is null, so the comparison is false
+        // rather than an error that rejects the whole expression
+        let filter = FilterExpression::parse("data.line >= 40 OR category = task").unwrap();
+        assert!(filter.matches(&TaskEvent::progress(
+            "task-1".to_string(),
+            0.5,
+            "halfway".to_string()
+        )));
+        assert!(filter.matches(&agent_event("a", AgentEventType::Log)));
+        assert!(!filter.matches(&SystemEvent::daemon_started()));
+    }
+
+    #[test]
+    fn test_missing_fields_are_null() {
+        let filter = FilterExpression::parse("task_id = null AND agent_id NOT IN (x, y)").unwrap();
+        assert!(filter.matches(&agent_event("a", AgentEventType::Log)));
+        assert!(!filter.matches(&agent_event("x", AgentEventType::Log)));
+
+        let system = FilterExpression::parse("event_type = daemon_started").unwrap();
+        assert!(system.matches(&SystemEvent::daemon_started()));
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        for source in [
+            "",
+            "agent_id =",
+            "bogus = 1",
+            "agent_id IN (a, b",
+            "agent_id = a AND",
+            "agent_id NOT = a",
+            "agent_id = 'open",
+            "(agent_id = a",
+            "agent_id = a b",
+        ] {
+            let err = FilterExpression::parse(source).unwrap_err();
+            assert!(
+                matches!(err, DaemonError::InvalidRequest(_)),
+                "{}: {:?}",
+                source,
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_to_snake_case() {
+        assert_eq!(to_snake_case("StatusChanged"), "status_changed");
+        assert_eq!(to_snake_case("status_changed"), "status_changed");
+        assert_eq!(to_snake_case("ERROR"), "error");
+    }
+}
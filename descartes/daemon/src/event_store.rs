@@ -0,0 +1,244 @@
+/// SQLite persistence for bus events
+///
+/// The event bus only delivers events to clients that are connected when they
+/// are published. An `EventStore` attached to the bus keeps a bounded history
+/// so clients can fetch what they missed, e.g. to render an audit timeline.
+use crate::errors::{DaemonError, DaemonResult};
+use crate::events::{DescartesEvent, EventFilter};
+use chrono::{DateTime, Duration, Utc};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sqlx::Row;
+use std::path::Path;
+use std::str::FromStr;
+
+/// How long persisted events are kept
+#[derive(Debug, Clone)]
+pub struct EventRetention {
+    /// Drop events older than this (None = keep forever)
+    pub max_age: Option<Duration>,
+    /// Keep at most this many of the newest events (None = unbounded)
+    pub max_events: Option<usize>,
+}
+
+impl Default for EventRetention {
+    fn default() -> Self {
+        Self {
+            max_age: Some(Duration::days(7)),
+            max_events: Some(100_000),
+        }
+    }
+}
+
+/// Persistent event log backed by SQLite
+pub struct EventStore {
+    pool: SqlitePool,
+    retention: EventRetention,
+}
+
+impl EventStore {
+    /// Open (or create) an event store at `path`
+    pub async fn open(path: impl AsRef<Path>, retention: EventRetention) -> DaemonResult<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+
+        let options = SqliteConnectOptions::from_str(path.to_string_lossy().as_ref())
+            .map_err(|e| DaemonError::StateError(format!("Invalid event store path: {}", e)))?
+            .create_if_missing(true);
+        Self::connect(options, 5, retention).await
+    }
+
+    /// Create a store that lives only as long as this process
+    pub async fn in_memory(retention: EventRetention) -> DaemonResult<Self> {
+        let options = SqliteConnectOptions::from_str(":memory:")
+            .map_err(|e| DaemonError::StateError(e.to_string()))?;
+        // Every in-memory connection is its own database, so use exactly one
+        Self::connect(options, 1, retention).await
+    }
+
+    async fn connect(
+        options: SqliteConnectOptions,
+        max_connections: u32,
+        retention: EventRetention,
+    ) -> DaemonResult<Self> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(max_connections)
+            .min_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await
+            .map_err(|e| DaemonError::StateError(format!("Failed to open event store: {}", e)))?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS events (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                id TEXT NOT NULL,
+                timestamp INTEGER NOT NULL,
+                payload TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .map_err(db_error)?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_events_timestamp ON events(timestamp)")
+            .execute(&pool)
+            .await
+            .map_err(db_error)?;
+
+        Ok(Self { pool, retention })
+    }
+
+    /// Retention limits applied by [`EventStore::prune`]
+    pub fn retention(&self) -> &EventRetention {
+        &self.retention
+    }
+
+    /// Persist an event
+    pub async fn append(&self, event: &DescartesEvent) -> DaemonResult<()> {
+        sqlx::query("INSERT INTO events (id, timestamp, payload) VALUES (?, ?, ?)")
+            .bind(event.id())
+            .bind(event.timestamp().timestamp_millis())
+            .bind(serde_json::to_string(event)?)
+            .execute(&self.pool)
+            .await
+            .map_err(db_error)?;
+        Ok(())
+    }
+
+    /// Events with `start <= timestamp <= end` that match `filter`, oldest first
+    pub async fn events_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        filter: &EventFilter,
+    ) -> DaemonResult<Vec<DescartesEvent>> {
+        let rows = sqlx::query(
+            "SELECT payload FROM events WHERE timestamp >= ? AND timestamp <= ? \
+             ORDER BY timestamp, seq",
+        )
+        .bind(start.timestamp_millis())
+        .bind(end.timestamp_millis())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        let mut events = Vec::new();
+        for row in rows {
+            let payload: String = row.get("payload");
+            let event: DescartesEvent = serde_json::from_str(&payload)?;
+            if filter.matches(&event) {
+                events.push(event);
+            }
+        }
+        Ok(events)
+    }
+
+    /// Number of persisted events
+    pub async fn count(&self) -> DaemonResult<usize> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM events")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(db_error)?;
+        Ok(count as usize)
+    }
+
+    /// Apply the retention limits, returning how many events were dropped
+    pub async fn prune(&self) -> DaemonResult<u64> {
+        let mut removed = 0;
+
+        if let Some(max_age) = self.retention.max_age {
+            let cutoff = (Utc::now() - max_age).timestamp_millis();
+            removed += sqlx::query("DELETE FROM events WHERE timestamp < ?")
+                .bind(cutoff)
+                .execute(&self.pool)
+                .await
+                .map_err(db_error)?
+                .rows_affected();
+        }
+
+        if let Some(max_events) = self.retention.max_events {
+            removed += sqlx::query(
+                "DELETE FROM events WHERE seq NOT IN \
+                 (SELECT seq FROM events ORDER BY timestamp DESC, seq DESC LIMIT ?)",
+            )
+            .bind(max_events as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(db_error)?
+            .rows_affected();
+        }
+
+        Ok(removed)
+    }
+}
+
+fn db_error(e: sqlx::Error) -> DaemonError {
+    DaemonError::StateError(format!("Event store error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{AgentEvent, EventCategory, SystemEvent};
+
+    fn at(event: DescartesEvent, timestamp: DateTime<Utc>) -> DescartesEvent {
+        match event {
+            DescartesEvent::AgentEvent(mut e) => {
+                e.timestamp = timestamp;
+                DescartesEvent::AgentEvent(e)
+            }
+            DescartesEvent::SystemEvent(mut e) => {
+                e.timestamp = timestamp;
+                DescartesEvent::SystemEvent(e)
+            }
+            other => other,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prune_by_count_and_age() {
+        let store = EventStore::in_memory(EventRetention {
+            max_age: Some(Duration::hours(1)),
+            max_events: Some(2),
+        })
+        .await
+        .unwrap();
+
+        let now = Utc::now();
+        let stale = at(SystemEvent::daemon_started(), now - Duration::hours(2));
+        store.append(&stale).await.unwrap();
+        for i in 0..3 {
+            let event = AgentEvent::spawned(format!("agent-{}", i), serde_json::json!({}));
+            store
+                .append(&at(event, now - Duration::minutes(3 - i)))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(store.prune().await.unwrap(), 2);
+        assert_eq!(store.count().await.unwrap(), 2);
+
+        let filter = EventFilter {
+            event_categories: vec![EventCategory::Agent],
+            ..Default::default()
+        };
+        let kept = store
+            .events_between(now - Duration::hours(3), now, &filter)
+            .await
+            .unwrap();
+        let agents: Vec<_> = kept
+            .iter()
+            .filter_map(|e| match e {
+                DescartesEvent::AgentEvent(e) => Some(e.agent_id.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(agents, vec!["agent-1", "agent-2"]);
+    }
+}
@@ -3,11 +3,10 @@
 /// This module provides WebSocket endpoint for streaming events from the event bus
 /// to connected clients with filtering and reconnection support.
 use crate::errors::{DaemonError, DaemonResult};
-use crate::events::{DescartesEvent, EventBus, EventFilter};
+use crate::events::{DescartesEvent, EventBus, EventFilter, FilteredReceiver};
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tokio::sync::broadcast;
 use tokio_tungstenite::{
     accept_async,
     tungstenite::Message as WsMessage,
@@ -38,6 +37,12 @@ pub enum ServerMessage {
 pub enum ClientMessage {
     /// Subscribe to events with filter
     Subscribe { filter: Option<EventFilter> },
+    /// Subscribe to task changes only (the `tasks.subscribe` channel), e.g.
+    /// to keep a task board live; empty `task_ids` means every task
+    SubscribeTasks {
+        #[serde(default)]
+        task_ids: Vec<String>,
+    },
     /// Update subscription filter
     UpdateFilter { filter: EventFilter },
     /// Unsubscribe
@@ -68,8 +73,7 @@ pub async fn handle_event_stream(
 
     // Initially no subscription
     let mut subscription_id: Option<String> = None;
-    let mut event_receiver: Option<broadcast::Receiver<DescartesEvent>> = None;
-    let mut filter: Option<EventFilter> = None;
+    let mut event_receiver: Option<FilteredReceiver> = None;
 
     // Heartbeat interval
     let mut heartbeat_interval = tokio::time::interval(tokio::time::Duration::from_secs(30));
@@ -88,7 +92,6 @@ pub async fn handle_event_stream(
                                     &event_bus,
                                     &mut subscription_id,
                                     &mut event_receiver,
-                                    &mut filter,
                                 ).await {
                                     Ok(response) => {
                                         if let Some(resp) = response {
@@ -155,13 +158,6 @@ pub async fn handle_event_stream(
                     std::future::pending().await
                 }
             } => {
-                // Apply filter if present
-                if let Some(ref f) = filter {
-                    if !f.matches(&event) {
-                        continue;
-                    }
-                }
-
                 let server_msg = ServerMessage::Event(event);
                 let json = serde_json::to_string(&server_msg).unwrap();
 
@@ -201,33 +197,32 @@ async fn handle_client_message(
     message: ClientMessage,
     event_bus: &Arc<EventBus>,
     subscription_id: &mut Option<String>,
-    event_receiver: &mut Option<broadcast::Receiver<DescartesEvent>>,
-    filter: &mut Option<EventFilter>,
+    event_receiver: &mut Option<FilteredReceiver>,
 ) -> DaemonResult<Option<ServerMessage>> {
     match message {
-        ClientMessage::Subscribe { filter: new_filter } => {
-            // Unsubscribe from previous subscription if exists
-            if let Some(sub_id) = subscription_id.take() {
-                event_bus.unsubscribe(&sub_id).await;
-            }
+        ClientMessage::Subscribe { filter: new_filter } => Ok(Some(
+            subscribe(
+                event_bus,
+                new_filter.unwrap_or_default(),
+                subscription_id,
+                event_receiver,
+            )
+            .await,
+        )),
 
-            // Subscribe with new filter
-            let (sub_id, rx) = event_bus.subscribe(new_filter.clone()).await;
-            *subscription_id = Some(sub_id.clone());
-            *event_receiver = Some(rx);
-            *filter = new_filter;
-
-            info!("Client subscribed to events: {}", sub_id);
-
-            Ok(Some(ServerMessage::SubscriptionConfirmed {
-                subscription_id: sub_id,
-            }))
-        }
+        ClientMessage::SubscribeTasks { task_ids } => Ok(Some(
+            subscribe(
+                event_bus,
+                EventFilter::tasks(task_ids),
+                subscription_id,
+                event_receiver,
+            )
+            .await,
+        )),
 
         ClientMessage::UpdateFilter { filter: new_filter } => {
-            *filter = Some(new_filter);
-
-            if let Some(ref sub_id) = subscription_id {
+            if let (Some(sub_id), Some(_)) = (subscription_id.as_ref(), event_receiver.as_ref()) {
+                event_bus.update_filter(sub_id, new_filter).await;
                 info!("Updated filter for subscription: {}", sub_id);
                 Ok(Some(ServerMessage::SubscriptionUpdated {
                     subscription_id: sub_id.clone(),
@@ -243,7 +238,6 @@ async fn handle_client_message(
             if let Some(sub_id) = subscription_id.take() {
                 event_bus.unsubscribe(&sub_id).await;
                 *event_receiver = None;
-                *filter = None;
                 info!("Client unsubscribed: {}", sub_id);
             }
             Ok(None)
@@ -256,6 +250,30 @@ async fn handle_client_message(
     }
 }
 
+/// Replace the connection's subscription with one using `filter`
+async fn subscribe(
+    event_bus: &Arc<EventBus>,
+    filter: EventFilter,
+    subscription_id: &mut Option<String>,
+    event_receiver: &mut Option<FilteredReceiver>,
+) -> ServerMessage {
+    // Unsubscribe from previous subscription if exists
+    if let Some(sub_id) = subscription_id.take() {
+        event_bus.unsubscribe(&sub_id).await;
+    }
+
+    // Subscribe with new filter
+    let (sub_id, rx) = event_bus.subscribe_filtered(filter).await;
+    *subscription_id = Some(sub_id.clone());
+    *event_receiver = Some(rx);
+
+    info!("Client subscribed to events: {}", sub_id);
+
+    ServerMessage::SubscriptionConfirmed {
+        subscription_id: sub_id,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -294,4 +312,47 @@ mod tests {
         let json = serde_json::to_string(&msg).unwrap();
         assert!(json.contains("TEST_ERROR"));
     }
+
+    #[tokio::test]
+    async fn test_subscribe_tasks_only_delivers_task_events() {
+        use crate::events::{AgentEvent, TaskEvent};
+
+        let event_bus = Arc::new(EventBus::new());
+        let mut subscription_id = None;
+        let mut event_receiver = None;
+
+        let json = r#"{"type":"SubscribeTasks","payload":{"task_ids":["task-1"]}}"#;
+        let message: ClientMessage = serde_json::from_str(json).unwrap();
+        let response = handle_client_message(
+            message,
+            &event_bus,
+            &mut subscription_id,
+            &mut event_receiver,
+        )
+        .await
+        .unwrap();
+        assert!(matches!(
+            response,
+            Some(ServerMessage::SubscriptionConfirmed { .. })
+        ));
+
+        event_bus
+            .publish(AgentEvent::spawned(
+                "agent-1".to_string(),
+                serde_json::json!({}),
+            ))
+            .await;
+        event_bus
+            .publish(TaskEvent::started("task-2".to_string(), None))
+            .await;
+        event_bus
+            .publish(TaskEvent::started("task-1".to_string(), None))
+            .await;
+
+        let event = event_receiver.as_mut().unwrap().recv().await.unwrap();
+        match event {
+            DescartesEvent::TaskEvent(e) => assert_eq!(e.task_id, "task-1"),
+            other => panic!("Unexpected event: {:?}", other),
+        }
+    }
 }
@@ -5,17 +5,28 @@
 /// - Event bus for publishing and subscribing to events
 /// - WebSocket-based event streaming
 /// - Event filtering and routing
+/// - Optional persistence for historical queries
+use crate::errors::{DaemonError, DaemonResult};
+use crate::event_filter::FilterExpression;
+use crate::event_store::EventStore;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use tokio::sync::broadcast;
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc, oneshot, RwLock};
+use tracing::warn;
 use uuid::Uuid;
 
 /// Maximum number of events to buffer in the broadcast channel
 const EVENT_CHANNEL_CAPACITY: usize = 1000;
 
+/// Apply the event store's retention limits every this many persisted events
+const EVENT_PRUNE_INTERVAL: u64 = 500;
+
+/// Maximum number of events waiting to be written to the event store
+const EVENT_PERSIST_QUEUE_CAPACITY: usize = 1000;
+
 /// System-wide event that can be subscribed to
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", content = "data")]
@@ -32,6 +43,42 @@ pub enum DescartesEvent {
     StateEvent(StateEvent),
 }
 
+impl DescartesEvent {
+    /// Unique event ID
+    pub fn id(&self) -> &str {
+        match self {
+            DescartesEvent::AgentEvent(e) => &e.id,
+            DescartesEvent::TaskEvent(e) => &e.id,
+            DescartesEvent::WorkflowEvent(e) => &e.id,
+            DescartesEvent::SystemEvent(e) => &e.id,
+            DescartesEvent::StateEvent(e) => &e.id,
+        }
+    }
+
+    /// When the event occurred
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        match self {
+            DescartesEvent::AgentEvent(e) => e.timestamp,
+            DescartesEvent::TaskEvent(e) => e.timestamp,
+            DescartesEvent::WorkflowEvent(e) => e.timestamp,
+            DescartesEvent::SystemEvent(e) => e.timestamp,
+            DescartesEvent::StateEvent(e) => e.timestamp,
+        }
+    }
+
+    /// Session the event belongs to, taken from the `session_id` field of its data
+    pub fn session_id(&self) -> Option<&str> {
+        let data = match self {
+            DescartesEvent::AgentEvent(e) => &e.data,
+            DescartesEvent::TaskEvent(e) => &e.data,
+            DescartesEvent::WorkflowEvent(e) => &e.data,
+            DescartesEvent::SystemEvent(e) => &e.data,
+            DescartesEvent::StateEvent(e) => &e.data,
+        };
+        data.get("session_id").and_then(|v| v.as_str())
+    }
+}
+
 /// Agent lifecycle and status events
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentEvent {
@@ -72,6 +119,8 @@ pub enum AgentEventType {
     AttachConnected,
     /// External TUI disconnected from paused agent
     AttachDisconnected,
+    /// Attach session closed after its client went idle
+    AttachIdleTimeout,
     /// Debugger paused (Lisp/Swank - error condition with restarts)
     DebuggerPaused,
     /// Swank output message
@@ -105,6 +154,10 @@ pub enum TaskEventType {
     Failed,
     /// Task cancelled
     Cancelled,
+    /// Task's dependencies are now all done, so it is ready to start
+    Unblocked,
+    /// Previously ready task waits on a dependency again
+    Blocked,
 }
 
 /// Workflow execution events
@@ -161,6 +214,8 @@ pub enum SystemEventType {
     ConnectionEstablished,
     /// Connection closed
     ConnectionClosed,
+    /// Agent spawn rejected because the daemon is at capacity
+    AtCapacity,
     /// Error occurred
     Error,
 }
@@ -200,9 +255,17 @@ pub struct EventFilter {
     /// Filter by workflow IDs (empty = all)
     #[serde(default)]
     pub workflow_ids: Vec<String>,
+    /// Filter by session IDs (empty = all); events without a session never match
+    #[serde(default)]
+    pub session_ids: Vec<String>,
     /// Filter by event categories
     #[serde(default)]
     pub event_categories: Vec<EventCategory>,
+    /// Filter expression over event fields, e.g.
+    /// `agent_id = X AND event_type IN (Error, Log)` (see [`crate::event_filter`]).
+    /// Parsed when the filter is deserialized, so invalid expressions are rejected up front.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expression: Option<FilterExpression>,
 }
 
 /// High-level event categories for filtering
@@ -238,6 +301,31 @@ impl EventFilter {
         }
     }
 
+    /// Create a filter for events of a specific session
+    pub fn for_session(session_id: String) -> Self {
+        Self {
+            session_ids: vec![session_id],
+            ..Default::default()
+        }
+    }
+
+    /// Create a filter for task events only, optionally limited to `task_ids`
+    pub fn tasks(task_ids: Vec<String>) -> Self {
+        Self {
+            task_ids,
+            event_categories: vec![EventCategory::Task],
+            ..Default::default()
+        }
+    }
+
+    /// Create a filter from a filter expression
+    pub fn from_expression(expression: &str) -> DaemonResult<Self> {
+        Ok(Self {
+            expression: Some(FilterExpression::parse(expression)?),
+            ..Default::default()
+        })
+    }
+
     /// Check if an event matches this filter
     pub fn matches(&self, event: &DescartesEvent) -> bool {
         // Check category filter
@@ -274,7 +362,32 @@ impl EventFilter {
             _ => {}
         }
 
-        true
+        if !self.session_ids.is_empty() {
+            match event.session_id() {
+                Some(session_id) if self.session_ids.iter().any(|s| s == session_id) => {}
+                _ => return false,
+            }
+        }
+
+        match &self.expression {
+            Some(expression) => expression.matches(event),
+            None => true,
+        }
+    }
+}
+
+/// Event receiver fed only the events matching its subscription filter
+///
+/// The bus filters before delivery, so non-matching events never reach this
+/// receiver's channel (or count towards its lag).
+pub struct FilteredReceiver {
+    rx: broadcast::Receiver<DescartesEvent>,
+}
+
+impl FilteredReceiver {
+    /// Receive the next matching event
+    pub async fn recv(&mut self) -> Result<DescartesEvent, broadcast::error::RecvError> {
+        self.rx.recv().await
     }
 }
 
@@ -292,8 +405,56 @@ pub struct EventBus {
     tx: broadcast::Sender<DescartesEvent>,
     /// Active subscriptions
     subscriptions: Arc<RwLock<HashMap<String, EventSubscription>>>,
+    /// Per-subscription channels of filtered subscribers
+    filtered: Arc<RwLock<HashMap<String, broadcast::Sender<DescartesEvent>>>>,
     /// Event statistics
     stats: Arc<RwLock<EventBusStats>>,
+    /// Persistent history, if enabled
+    persister: OnceLock<EventPersister>,
+}
+
+/// Work for the task writing events to the store
+enum PersistRequest {
+    Event(Box<DescartesEvent>),
+    /// Answered once every event queued before it has been written
+    Flush(oneshot::Sender<()>),
+}
+
+/// Queue feeding a background task that writes published events to a store
+struct EventPersister {
+    store: Arc<EventStore>,
+    tx: mpsc::Sender<PersistRequest>,
+}
+
+impl EventPersister {
+    fn spawn(store: Arc<EventStore>) -> Self {
+        let (tx, mut rx) = mpsc::channel(EVENT_PERSIST_QUEUE_CAPACITY);
+        let writer = Arc::clone(&store);
+        tokio::spawn(async move {
+            let mut persisted_since_prune = 0;
+            while let Some(request) = rx.recv().await {
+                match request {
+                    PersistRequest::Event(event) => {
+                        if let Err(e) = writer.append(&event).await {
+                            warn!("Failed to persist event {}: {}", event.id(), e);
+                            continue;
+                        }
+                        persisted_since_prune += 1;
+                        if persisted_since_prune >= EVENT_PRUNE_INTERVAL {
+                            persisted_since_prune = 0;
+                            if let Err(e) = writer.prune().await {
+                                warn!("Failed to prune event store: {}", e);
+                            }
+                        }
+                    }
+                    PersistRequest::Flush(done) => {
+                        let _ = done.send(());
+                    }
+                }
+            }
+        });
+        Self { store, tx }
+    }
 }
 
 /// Statistics about event bus usage
@@ -312,7 +473,57 @@ impl EventBus {
         Self {
             tx,
             subscriptions: Arc::new(RwLock::new(HashMap::new())),
+            filtered: Arc::new(RwLock::new(HashMap::new())),
             stats: Arc::new(RwLock::new(EventBusStats::default())),
+            persister: OnceLock::new(),
+        }
+    }
+
+    /// Persist published events to `store` so they can be queried later
+    pub fn with_store(self, store: Arc<EventStore>) -> Self {
+        if let Err(e) = self.attach_store(store) {
+            warn!("{}", e);
+        }
+        self
+    }
+
+    /// Start persisting published events to `store`
+    ///
+    /// Events are written by a background task, so publishing never waits on
+    /// the database. Fails if a store is already attached.
+    pub fn attach_store(&self, store: Arc<EventStore>) -> DaemonResult<()> {
+        let mut attached = false;
+        self.persister.get_or_init(|| {
+            attached = true;
+            EventPersister::spawn(store)
+        });
+        if attached {
+            Ok(())
+        } else {
+            Err(DaemonError::StateError(
+                "An event store is already attached".to_string(),
+            ))
+        }
+    }
+
+    /// The attached event store, if persistence is enabled
+    pub fn store(&self) -> Option<&Arc<EventStore>> {
+        self.persister.get().map(|p| &p.store)
+    }
+
+    /// Wait until every event published so far has been written to the store
+    pub async fn flush(&self) {
+        let Some(persister) = self.persister.get() else {
+            return;
+        };
+        let (done_tx, done_rx) = oneshot::channel();
+        if persister
+            .tx
+            .send(PersistRequest::Flush(done_tx))
+            .await
+            .is_ok()
+        {
+            let _ = done_rx.await;
         }
     }
 
@@ -332,6 +543,32 @@ impl EventBus {
         *stats.events_by_type.entry(event_type).or_insert(0) += 1;
         drop(stats);
 
+        // Queue for persistence before broadcasting; history queries flush
+        // the queue, so a subscriber that sees the event live also finds it
+        if let Some(persister) = self.persister.get() {
+            if let Err(e) = persister
+                .tx
+                .try_send(PersistRequest::Event(Box::new(event.clone())))
+            {
+                warn!("Event {} not persisted: {}", event.id(), e);
+            }
+        }
+
+        // Deliver to filtered subscribers whose filter matches
+        let filtered = self.filtered.read().await;
+        if !filtered.is_empty() {
+            let subscriptions = self.subscriptions.read().await;
+            for (subscription_id, sender) in filtered.iter() {
+                if subscriptions
+                    .get(subscription_id)
+                    .is_some_and(|s| s.filter.matches(&event))
+                {
+                    let _ = sender.send(event.clone());
+                }
+            }
+        }
+        drop(filtered);
+
         // Publish to broadcast channel (ignore send errors if no subscribers)
         let _ = self.tx.send(event);
     }
@@ -365,9 +602,48 @@ impl EventBus {
         (subscription_id, rx)
     }
 
+    /// Subscribe to events matching a filter.
+    ///
+    /// Unlike [`EventBus::subscribe`], the filter is applied by the bus: the
+    /// subscriber gets its own channel and only matching events are sent to it.
+    pub async fn subscribe_filtered(&self, filter: EventFilter) -> (String, FilteredReceiver) {
+        let (tx, rx) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        let (subscription_id, _) = self.subscribe(Some(filter)).await;
+        self.filtered
+            .write()
+            .await
+            .insert(subscription_id.clone(), tx);
+        (subscription_id, FilteredReceiver { rx })
+    }
+
+    /// Replace the filter of an existing subscription.
+    ///
+    /// Filtered subscribers see the new filter applied from the next published
+    /// event. Returns false if the subscription does not exist.
+    pub async fn update_filter(&self, subscription_id: &str, filter: EventFilter) -> bool {
+        match self.subscriptions.write().await.get_mut(subscription_id) {
+            Some(subscription) => {
+                subscription.filter = filter;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get the filter of an existing subscription
+    pub async fn subscription_filter(&self, subscription_id: &str) -> Option<EventFilter> {
+        self.subscriptions
+            .read()
+            .await
+            .get(subscription_id)
+            .map(|s| s.filter.clone())
+    }
+
     /// Unsubscribe from events
     pub async fn unsubscribe(&self, subscription_id: &str) {
         self.subscriptions.write().await.remove(subscription_id);
+        // Dropping the sender closes a filtered subscriber's channel
+        self.filtered.write().await.remove(subscription_id);
 
         // Update stats
         let mut stats = self.stats.write().await;
@@ -383,6 +659,25 @@ impl EventBus {
     pub async fn subscription_count(&self) -> usize {
         self.subscriptions.read().await.len()
     }
+
+    /// Persisted events between `start` and `end` (inclusive) matching `filter`
+    ///
+    /// Includes every event published before the call. Fails if the bus has
+    /// no event store attached.
+    pub async fn events_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        filter: &EventFilter,
+    ) -> DaemonResult<Vec<DescartesEvent>> {
+        self.flush().await;
+        match self.store() {
+            Some(store) => store.events_between(start, end, filter).await,
+            None => Err(DaemonError::StateError(
+                "Event persistence is not enabled".to_string(),
+            )),
+        }
+    }
 }
 
 impl Default for EventBus {
@@ -490,6 +785,18 @@ impl SystemEvent {
             data: metrics,
         })
     }
+
+    pub fn at_capacity(max_concurrent_agents: usize, agent_name: &str) -> DescartesEvent {
+        DescartesEvent::SystemEvent(SystemEvent {
+            id: Uuid::new_v4().to_string(),
+            timestamp: Utc::now(),
+            event_type: SystemEventType::AtCapacity,
+            data: serde_json::json!({
+                "max_concurrent_agents": max_concurrent_agents,
+                "rejected_agent": agent_name,
+            }),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -556,6 +863,206 @@ mod tests {
         assert_eq!(stats.total_events_published, 2);
     }
 
+    #[test]
+    fn test_expression_filter_matches() {
+        let filter = EventFilter {
+            event_categories: vec![EventCategory::Agent],
+            ..EventFilter::from_expression("agent_id = agent-1 AND event_type IN (Spawned, Failed)")
+                .unwrap()
+        };
+
+        assert!(filter.matches(&AgentEvent::spawned(
+            "agent-1".to_string(),
+            serde_json::json!({})
+        )));
+        assert!(filter.matches(&AgentEvent::failed(
+            "agent-1".to_string(),
+            "boom".to_string()
+        )));
+        assert!(!filter.matches(&AgentEvent::status_changed(
+            "agent-1".to_string(),
+            "running".to_string()
+        )));
+        assert!(!filter.matches(&AgentEvent::spawned(
+            "agent-2".to_string(),
+            serde_json::json!({})
+        )));
+
+        assert!(EventFilter::from_expression("agent_id IN (").is_err());
+    }
+
+    #[test]
+    fn test_expression_filter_serde() {
+        let filter: EventFilter =
+            serde_json::from_str(r#"{"expression": "event_type = Log OR agent_id = a-1"}"#)
+                .unwrap();
+        assert!(filter.matches(&AgentEvent::spawned(
+            "a-1".to_string(),
+            serde_json::json!({})
+        )));
+
+        let json = serde_json::to_value(&filter).unwrap();
+        assert_eq!(json["expression"], "event_type = Log OR agent_id = a-1");
+
+        let invalid = serde_json::from_str::<EventFilter>(r#"{"expression": "agent_id ="}"#);
+        assert!(invalid.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_filtered() {
+        let bus = EventBus::new();
+
+        let filter =
+            EventFilter::from_expression("category = agent AND NOT agent_id = noisy").unwrap();
+        let (sub_id, mut rx) = bus.subscribe_filtered(filter).await;
+
+        bus.publish(SystemEvent::daemon_started()).await;
+        bus.publish(AgentEvent::spawned(
+            "noisy".to_string(),
+            serde_json::json!({}),
+        ))
+        .await;
+        bus.publish(AgentEvent::spawned(
+            "quiet".to_string(),
+            serde_json::json!({}),
+        ))
+        .await;
+
+        match rx.recv().await.unwrap() {
+            DescartesEvent::AgentEvent(e) => assert_eq!(e.agent_id, "quiet"),
+            other => panic!("Unexpected event: {:?}", other),
+        }
+
+        let updated = EventFilter::for_agent("noisy".to_string());
+        assert!(bus.update_filter(&sub_id, updated).await);
+        assert_eq!(
+            bus.subscription_filter(&sub_id).await.unwrap().agent_ids,
+            vec!["noisy".to_string()]
+        );
+        assert!(!bus.update_filter("missing", EventFilter::all()).await);
+
+        bus.publish(AgentEvent::spawned(
+            "quiet".to_string(),
+            serde_json::json!({}),
+        ))
+        .await;
+        bus.publish(AgentEvent::spawned(
+            "noisy".to_string(),
+            serde_json::json!({}),
+        ))
+        .await;
+        match rx.recv().await.unwrap() {
+            DescartesEvent::AgentEvent(e) => assert_eq!(e.agent_id, "noisy"),
+            other => panic!("Unexpected event: {:?}", other),
+        }
+
+        bus.unsubscribe(&sub_id).await;
+        assert!(matches!(
+            rx.recv().await,
+            Err(broadcast::error::RecvError::Closed)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_filtered_subscribers_receive_only_matching_events() {
+        let bus = EventBus::new();
+
+        let (_, mut session_rx) = bus
+            .subscribe_filtered(EventFilter::for_session("session-a".to_string()))
+            .await;
+        let (_, mut system_rx) = bus
+            .subscribe_filtered(EventFilter {
+                event_categories: vec![EventCategory::System],
+                ..Default::default()
+            })
+            .await;
+
+        bus.publish(AgentEvent::spawned(
+            "agent-1".to_string(),
+            serde_json::json!({"session_id": "session-b"}),
+        ))
+        .await;
+        bus.publish(SystemEvent::daemon_started()).await;
+        bus.publish(AgentEvent::spawned(
+            "agent-1".to_string(),
+            serde_json::json!({"session_id": "session-a"}),
+        ))
+        .await;
+        bus.publish(AgentEvent::spawned(
+            "agent-2".to_string(),
+            serde_json::json!({}),
+        ))
+        .await;
+
+        // Only matching events are queued for each subscriber
+        assert_eq!(session_rx.rx.len(), 1);
+        assert_eq!(system_rx.rx.len(), 1);
+
+        let event = session_rx.recv().await.unwrap();
+        assert_eq!(event.session_id(), Some("session-a"));
+        assert!(matches!(
+            system_rx.recv().await.unwrap(),
+            DescartesEvent::SystemEvent(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_events_between_returns_persisted_subset() {
+        use crate::event_store::EventRetention;
+
+        let store = EventStore::in_memory(EventRetention::default())
+            .await
+            .unwrap();
+        let bus = EventBus::new().with_store(Arc::new(store));
+
+        let base = Utc::now() - chrono::Duration::minutes(10);
+        let agent_event = |agent_id: &str, minutes: i64, event_type| {
+            DescartesEvent::AgentEvent(AgentEvent {
+                id: Uuid::new_v4().to_string(),
+                agent_id: agent_id.to_string(),
+                timestamp: base + chrono::Duration::minutes(minutes),
+                event_type,
+                data: serde_json::json!({}),
+            })
+        };
+
+        bus.publish(agent_event("agent-1", 0, AgentEventType::Spawned))
+            .await;
+        bus.publish(agent_event("agent-1", 2, AgentEventType::Log))
+            .await;
+        bus.publish(agent_event("agent-2", 3, AgentEventType::Log))
+            .await;
+        bus.publish(agent_event("agent-1", 4, AgentEventType::Failed))
+            .await;
+        bus.publish(agent_event("agent-1", 8, AgentEventType::Completed))
+            .await;
+
+        let filter = EventFilter::for_agent("agent-1".to_string());
+        let events = bus
+            .events_between(
+                base + chrono::Duration::minutes(1),
+                base + chrono::Duration::minutes(5),
+                &filter,
+            )
+            .await
+            .unwrap();
+
+        let types: Vec<_> = events
+            .iter()
+            .map(|e| match e {
+                DescartesEvent::AgentEvent(e) => e.event_type.clone(),
+                other => panic!("unexpected event {:?}", other),
+            })
+            .collect();
+        assert_eq!(types, vec![AgentEventType::Log, AgentEventType::Failed]);
+
+        // Without a store there is no history to query
+        assert!(EventBus::new()
+            .events_between(base, Utc::now(), &EventFilter::all())
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn test_subscription_management() {
         let bus = EventBus::new();
@@ -1,13 +1,17 @@
 /// RPC method handlers
 use crate::auth::AuthContext;
+use crate::config::ServerConfig;
 use crate::errors::{DaemonError, DaemonResult};
+use crate::events::{EventBus, EventFilter, SystemEvent};
 use crate::types::*;
 use chrono::Utc;
 use dashmap::DashMap;
+use descartes_core::traits::{AgentConfig, AgentRecord, StateStore};
 use descartes_core::{AgentRunner, LocalProcessRunner, SqliteStateStore};
 use serde_json::Value;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use tokio::sync::RwLock;
+use tracing::{info, warn};
 use uuid::Uuid;
 
 /// RPC handlers
@@ -16,8 +20,14 @@ pub struct RpcHandlers {
     agents: Arc<DashMap<String, AgentInfo>>,
     /// Optional agent runner for live agent operations
     runner: Option<Arc<LocalProcessRunner>>,
-    /// Optional state store for state queries
-    state_store: Option<Arc<RwLock<SqliteStateStore>>>,
+    /// Optional state store for state queries and saved agent records
+    state_store: OnceLock<Arc<RwLock<SqliteStateStore>>>,
+    /// Most agents running or paused at once; further spawns are rejected
+    max_concurrent_agents: usize,
+    /// Serializes the capacity check with the insert
+    spawn_lock: std::sync::Mutex<()>,
+    /// Optional event bus for system events such as at-capacity rejections
+    event_bus: Option<Arc<EventBus>>,
 }
 
 impl RpcHandlers {
@@ -26,7 +36,10 @@ impl RpcHandlers {
         RpcHandlers {
             agents: Arc::new(DashMap::new()),
             runner: None,
-            state_store: None,
+            state_store: OnceLock::new(),
+            max_concurrent_agents: ServerConfig::default().max_concurrent_agents,
+            spawn_lock: std::sync::Mutex::new(()),
+            event_bus: None,
         }
     }
 
@@ -35,7 +48,10 @@ impl RpcHandlers {
         RpcHandlers {
             agents: Arc::new(DashMap::new()),
             runner: Some(runner),
-            state_store: None,
+            state_store: OnceLock::new(),
+            max_concurrent_agents: ServerConfig::default().max_concurrent_agents,
+            spawn_lock: std::sync::Mutex::new(()),
+            event_bus: None,
         }
     }
 
@@ -47,13 +63,34 @@ impl RpcHandlers {
         RpcHandlers {
             agents: Arc::new(DashMap::new()),
             runner: Some(runner),
-            state_store: Some(state_store),
+            state_store: OnceLock::from(state_store),
+            max_concurrent_agents: ServerConfig::default().max_concurrent_agents,
+            spawn_lock: std::sync::Mutex::new(()),
+            event_bus: None,
         }
     }
 
     /// Set the state store
     pub fn set_state_store(&mut self, state_store: Arc<RwLock<SqliteStateStore>>) {
-        self.state_store = Some(state_store);
+        self.state_store = OnceLock::from(state_store);
+    }
+
+    /// Attach a state store to handlers that are already shared
+    ///
+    /// Spawned agents are saved to it from then on. Fails if a store is
+    /// already attached.
+    pub fn attach_state_store(
+        &self,
+        state_store: Arc<RwLock<SqliteStateStore>>,
+    ) -> DaemonResult<()> {
+        self.state_store
+            .set(state_store)
+            .map_err(|_| DaemonError::StateError("A state store is already attached".to_string()))
+    }
+
+    /// The attached state store, if any
+    pub fn state_store(&self) -> Option<&Arc<RwLock<SqliteStateStore>>> {
+        self.state_store.get()
     }
 
     /// Set the agent runner
@@ -61,6 +98,18 @@ impl RpcHandlers {
         self.runner = Some(runner);
     }
 
+    /// Publish system events (e.g. at-capacity rejections) on this bus
+    pub fn with_event_bus(mut self, event_bus: Arc<EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Limit how many agents may run at once; spawns beyond the limit are rejected
+    pub fn with_max_concurrent_agents(mut self, max_concurrent_agents: usize) -> Self {
+        self.max_concurrent_agents = max_concurrent_agents;
+        self
+    }
+
     /// Handle agent.spawn RPC method
     pub async fn handle_agent_spawn(
         &self,
@@ -70,29 +119,168 @@ impl RpcHandlers {
         let request: AgentSpawnRequest = serde_json::from_value(params)
             .map_err(|e| DaemonError::InvalidRequest(format!("Invalid params: {}", e)))?;
 
-        let agent_id = Uuid::new_v4().to_string();
+        let restartable = request
+            .config
+            .get("restartable")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let agent_config = AgentConfig {
+            name: request.name.clone(),
+            model_backend: request.agent_type,
+            ..serde_json::from_value(request.config.clone()).unwrap_or_default()
+        };
+
+        let agent_id = self
+            .register_agent(request.name, request.config, agent_config, restartable)
+            .await?;
+
+        let response = AgentSpawnResponse {
+            agent_id,
+            status: AgentStatus::Running,
+            message: "Agent spawned successfully".to_string(),
+        };
+
+        serde_json::to_value(response)
+            .map_err(|e| DaemonError::SerializationError(e.to_string()))
+    }
+
+    /// Add a running agent if there is capacity, saving it to the state store
+    async fn register_agent(
+        &self,
+        name: String,
+        config: Value,
+        agent_config: AgentConfig,
+        restartable: bool,
+    ) -> DaemonResult<String> {
+        let agent_id = Uuid::new_v4();
         let now = Utc::now();
 
         let agent = AgentInfo {
-            id: agent_id.clone(),
-            name: request.name,
+            id: agent_id.to_string(),
+            name,
             status: AgentStatus::Running,
             created_at: now,
             updated_at: now,
             pid: Some(std::process::id()),
-            config: request.config,
+            config,
         };
 
-        self.agents.insert(agent_id.clone(), agent.clone());
+        let at_capacity = {
+            let _spawning = self.spawn_lock.lock().unwrap_or_else(|e| e.into_inner());
+            let running = self
+                .agents
+                .iter()
+                .filter(|a| matches!(a.status, AgentStatus::Running | AgentStatus::Paused))
+                .count();
+            let at_capacity = running >= self.max_concurrent_agents;
+            if !at_capacity {
+                self.agents.insert(agent.id.clone(), agent.clone());
+            }
+            at_capacity
+        };
+        if at_capacity {
+            warn!(
+                "Rejecting spawn of '{}': {} agents already running",
+                agent.name, self.max_concurrent_agents
+            );
+            if let Some(event_bus) = &self.event_bus {
+                event_bus
+                    .publish(SystemEvent::at_capacity(
+                        self.max_concurrent_agents,
+                        &agent.name,
+                    ))
+                    .await;
+            }
+            return Err(DaemonError::AtCapacity(self.max_concurrent_agents));
+        }
 
-        let response = AgentSpawnResponse {
-            agent_id,
-            status: AgentStatus::Running,
-            message: "Agent spawned successfully".to_string(),
+        if let Some(state_store) = self.state_store() {
+            let record = AgentRecord {
+                id: agent_id,
+                config: agent_config,
+                status: descartes_core::traits::AgentStatus::Running,
+                restartable,
+                created_at: now.timestamp(),
+                updated_at: now.timestamp(),
+            };
+            if let Err(e) = state_store.read().await.save_agent_record(&record).await {
+                warn!("Failed to persist agent {}: {}", agent_id, e);
+            }
+        }
+
+        Ok(agent.id)
+    }
+
+    /// Save a new status for an agent's record, if it has one
+    async fn save_agent_status(&self, agent_id: &str, status: descartes_core::traits::AgentStatus) {
+        let (Some(state_store), Ok(agent_id)) = (self.state_store(), Uuid::parse_str(agent_id))
+        else {
+            return;
         };
+        let store = state_store.read().await;
+        let records = match store.get_agent_records().await {
+            Ok(records) => records,
+            Err(e) => {
+                warn!("Failed to load agent records: {}", e);
+                return;
+            }
+        };
+        if let Some(mut record) = records.into_iter().find(|r| r.id == agent_id) {
+            record.status = status;
+            record.updated_at = Utc::now().timestamp();
+            if let Err(e) = store.save_agent_record(&record).await {
+                warn!("Failed to persist status of agent {}: {}", agent_id, e);
+            }
+        }
+    }
 
-        serde_json::to_value(response)
-            .map_err(|e| DaemonError::SerializationError(e.to_string()))
+    /// Re-read agents saved by a previous run and register the restartable ones again
+    ///
+    /// Only agents that were still live when the daemon stopped come back;
+    /// each gets a new ID and its old record is marked terminated. Returns the
+    /// old and new IDs of the restored agents.
+    pub async fn restore_agents(&self) -> DaemonResult<Vec<(String, String)>> {
+        let Some(state_store) = self.state_store() else {
+            return Err(DaemonError::StateError(
+                "Cannot restore agents without a state store".to_string(),
+            ));
+        };
+        let records = state_store
+            .read()
+            .await
+            .get_agent_records()
+            .await
+            .map_err(|e| DaemonError::StateError(format!("Failed to load agent records: {}", e)))?;
+
+        let mut restored = Vec::new();
+        for record in records {
+            let old_id = record.id.to_string();
+            if !record.restartable
+                || record.status.is_terminal()
+                || self.agents.contains_key(&old_id)
+            {
+                continue;
+            }
+
+            let config = serde_json::to_value(&record.config).unwrap_or_default();
+            match self
+                .register_agent(record.config.name.clone(), config, record.config, true)
+                .await
+            {
+                Ok(new_id) => {
+                    info!("Restored agent {} as {}", old_id, new_id);
+                    self.save_agent_status(
+                        &old_id,
+                        descartes_core::traits::AgentStatus::Terminated,
+                    )
+                    .await;
+                    restored.push((old_id, new_id));
+                }
+                Err(e) => warn!("Failed to restore agent {}: {}", old_id, e),
+            }
+        }
+
+        Ok(restored)
     }
 
     /// Handle agent.list RPC method
@@ -128,6 +316,12 @@ impl RpcHandlers {
 
         agent.status = AgentStatus::Terminated;
         agent.updated_at = Utc::now();
+        drop(agent);
+        self.save_agent_status(
+            &request.agent_id,
+            descartes_core::traits::AgentStatus::Terminated,
+        )
+        .await;
 
         let response = AgentKillResponse {
             agent_id: request.agent_id,
@@ -279,6 +473,22 @@ impl RpcHandlers {
             .map_err(|e| DaemonError::SerializationError(e.to_string()))
     }
 
+    /// Handle events.between RPC method
+    pub async fn handle_events_between(
+        &self,
+        params: Value,
+        _auth: AuthContext,
+    ) -> DaemonResult<Value> {
+        let request: EventsBetweenRequest = serde_json::from_value(params)
+            .map_err(|e| DaemonError::InvalidRequest(format!("Invalid params: {}", e)))?;
+        let event_bus = self.event_bus.as_ref().ok_or_else(|| {
+            DaemonError::StateError("Event persistence is not enabled".to_string())
+        })?;
+
+        let response = query_events_between(event_bus, request).await?;
+        serde_json::to_value(response).map_err(|e| DaemonError::SerializationError(e.to_string()))
+    }
+
     /// Handle state.query RPC method
     pub async fn handle_state_query(
         &self,
@@ -296,7 +506,7 @@ impl RpcHandlers {
         }
 
         // Fetch state from state store if available
-        let state = if let Some(ref state_store) = self.state_store {
+        let state = if let Some(state_store) = self.state_store() {
             let store = state_store.read().await;
 
             if let Some(agent_id) = &request.agent_id {
@@ -408,6 +618,35 @@ impl Default for RpcHandlers {
     }
 }
 
+/// Persisted bus events in the request's time window, shared by both RPC servers
+pub(crate) async fn query_events_between(
+    event_bus: &EventBus,
+    request: EventsBetweenRequest,
+) -> DaemonResult<EventsBetweenResponse> {
+    if request.from > request.to {
+        return Err(DaemonError::InvalidRequest(format!(
+            "Invalid time range: from ({}) is after to ({})",
+            request.from, request.to
+        )));
+    }
+    let timestamp = |secs: i64| {
+        chrono::DateTime::from_timestamp(secs, 0)
+            .ok_or_else(|| DaemonError::InvalidRequest(format!("Invalid timestamp: {}", secs)))
+    };
+    let filter = match &request.filter {
+        Some(expression) => EventFilter::from_expression(expression)?,
+        None => EventFilter::all(),
+    };
+
+    let events = event_bus
+        .events_between(timestamp(request.from)?, timestamp(request.to)?, &filter)
+        .await?;
+    Ok(EventsBetweenResponse {
+        count: events.len(),
+        events,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -431,6 +670,49 @@ mod tests {
         assert_eq!(response.status, AgentStatus::Running);
     }
 
+    #[tokio::test]
+    async fn test_agent_spawn_rejected_at_capacity() {
+        let event_bus = Arc::new(EventBus::new());
+        let (_sub_id, mut events) = event_bus.subscribe(None).await;
+        let handlers = RpcHandlers::new()
+            .with_max_concurrent_agents(1)
+            .with_event_bus(Arc::clone(&event_bus));
+        let auth = AuthContext::unauthenticated();
+        let params = json!({
+            "name": "test-agent",
+            "agent_type": "basic",
+            "config": {}
+        });
+
+        let first: AgentSpawnResponse = serde_json::from_value(
+            handlers
+                .handle_agent_spawn(params.clone(), auth.clone())
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        let err = handlers
+            .handle_agent_spawn(params.clone(), auth.clone())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), -32030);
+
+        match events.try_recv().unwrap() {
+            crate::events::DescartesEvent::SystemEvent(e) => {
+                assert_eq!(e.event_type, crate::events::SystemEventType::AtCapacity);
+                assert_eq!(e.data["rejected_agent"], "test-agent");
+            }
+            other => panic!("expected an at-capacity event, got {:?}", other),
+        }
+
+        // Killing the first agent frees its slot
+        handlers
+            .handle_agent_kill(json!({ "agent_id": first.agent_id }), auth.clone())
+            .await
+            .unwrap();
+        assert!(handlers.handle_agent_spawn(params, auth).await.is_ok());
+    }
+
     #[tokio::test]
     async fn test_agent_list() {
         let handlers = RpcHandlers::new();
@@ -482,4 +764,60 @@ mod tests {
         let response: AgentKillResponse = serde_json::from_value(result.unwrap()).unwrap();
         assert_eq!(response.status, AgentStatus::Terminated);
     }
+
+    #[tokio::test]
+    async fn test_events_between() {
+        use crate::event_store::{EventRetention, EventStore};
+        use crate::events::{AgentEvent, DescartesEvent};
+
+        let store = EventStore::in_memory(EventRetention::default())
+            .await
+            .unwrap();
+        let event_bus = Arc::new(EventBus::new().with_store(Arc::new(store)));
+        let handlers = RpcHandlers::new().with_event_bus(Arc::clone(&event_bus));
+        let auth = AuthContext::unauthenticated();
+
+        let base = Utc::now().timestamp() - 600;
+        for (agent_id, offset) in [("agent-1", 0), ("agent-2", 120), ("agent-1", 240)] {
+            let mut event = AgentEvent::spawned(agent_id.to_string(), json!({}));
+            if let DescartesEvent::AgentEvent(e) = &mut event {
+                e.timestamp = chrono::DateTime::from_timestamp(base + offset, 0).unwrap();
+            }
+            event_bus.publish(event).await;
+        }
+
+        let result = handlers
+            .handle_events_between(json!({ "from": base + 60, "to": base + 300 }), auth.clone())
+            .await
+            .unwrap();
+        let response: EventsBetweenResponse = serde_json::from_value(result).unwrap();
+        assert_eq!(response.count, 2);
+
+        let result = handlers
+            .handle_events_between(
+                json!({ "from": base, "to": base + 300, "filter": "agent_id = agent-1" }),
+                auth.clone(),
+            )
+            .await
+            .unwrap();
+        let response: EventsBetweenResponse = serde_json::from_value(result).unwrap();
+        assert_eq!(response.count, 2);
+        assert!(response.events.iter().all(|e| matches!(
+            e,
+            DescartesEvent::AgentEvent(e) if e.agent_id == "agent-1"
+        )));
+
+        // An inverted window is rejected
+        assert!(handlers
+            .handle_events_between(json!({ "from": base + 300, "to": base }), auth.clone())
+            .await
+            .is_err());
+
+        // Without an event bus there is no history to query
+        let err = RpcHandlers::new()
+            .handle_events_between(json!({ "from": base, "to": base + 300 }), auth)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), -32006);
+    }
 }
@@ -10,6 +10,8 @@ pub mod config;
 pub mod opencode_tui; // OpenCode TUI attachment handler (Phase 5)
 pub mod errors;
 pub mod event_client;
+pub mod event_filter;
+pub mod event_store;
 pub mod event_stream;
 pub mod events;
 pub mod handlers;
@@ -41,13 +43,20 @@ pub use opencode_tui::{start_opencode_attach_server, OpenCodeTuiConfig, OpenCode
 pub use config::DaemonConfig;
 pub use errors::{DaemonError, DaemonResult};
 pub use event_client::{EventClient, EventClientBuilder, EventClientConfig, EventClientState};
+pub use event_filter::FilterExpression;
+pub use event_store::{EventRetention, EventStore};
 pub use events::{
-    AgentEvent, DescartesEvent, EventBus, EventFilter, SystemEvent, TaskEvent, TaskEventType,
+    AgentEvent, DescartesEvent, EventBus, EventFilter, FilteredReceiver,
+    SystemEvent, TaskEvent, TaskEventType,
+};
+pub use rpc_agent_methods::{
+    AgentHistoryQueryParams, AgentHistoryQueryResult, AgentMonitoringRpcImpl,
+    AgentMonitoringRpcServer, AgentStatusFilter,
 };
-pub use rpc_agent_methods::{AgentMonitoringRpcImpl, AgentMonitoringRpcServer, AgentStatusFilter};
 pub use rpc_client::{UnixSocketRpcClient, UnixSocketRpcClientBuilder};
 pub use rpc_server::{
-    ApprovalResult, DescartesRpcServer, TaskInfo, UnixServerHandle, UnixSocketRpcServer,
+    ApprovalResult, DescartesRpcServer, HealthAgentCounts, HealthConfigSummary, HealthResult,
+    HealthTaskCounts, TaskInfo, UnixServerHandle, UnixSocketRpcServer,
 };
 pub use server::RpcServer;
 pub use task_event_emitter::{
@@ -59,6 +59,13 @@ struct Args {
     )]
     jwt_secret: Option<String>,
 
+    /// Respawn restartable agents from the previous run
+    #[arg(
+        long,
+        help = "Respawn restartable agents that were running when the daemon last stopped"
+    )]
+    restore_agents: bool,
+
     /// Log level
     #[arg(
         short,
@@ -120,6 +127,10 @@ async fn main() -> anyhow::Result<()> {
         config.server.pub_port = port;
     }
 
+    if args.restore_agents {
+        config.server.restore_agents = true;
+    }
+
     if args.enable_auth {
         config.auth.enabled = true;
         if let Some(secret) = args.jwt_secret {
@@ -7,6 +7,20 @@ use std::sync::Arc;
 use std::time::Instant;
 use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
 
+/// Resident memory of the daemon process in megabytes (0 if unavailable)
+pub fn process_memory_mb() -> f64 {
+    let pid = Pid::from_u32(std::process::id());
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        false,
+        ProcessRefreshKind::new().with_memory(),
+    );
+    sys.process(pid)
+        .map(|process| process.memory() as f64 / (1024.0 * 1024.0))
+        .unwrap_or(0.0)
+}
+
 /// Metrics collector
 pub struct MetricsCollector {
     registry: Arc<Registry>,
@@ -76,6 +76,7 @@ impl JsonRpcServer {
                     .await
             }
             "state.query" => self.call_state_query(request.params, auth_context).await,
+            "events.between" => self.call_events_between(request.params, auth_context).await,
             "system.health" => self.call_system_health(request.params, auth_context).await,
             "system.metrics" => self.call_system_metrics(request.params, auth_context).await,
             // Chat methods
@@ -230,6 +231,16 @@ impl JsonRpcServer {
         self.handlers.handle_state_query(params, auth).await
     }
 
+    async fn call_events_between(
+        &self,
+        params: Option<Value>,
+        auth: AuthContext,
+    ) -> DaemonResult<Value> {
+        let params =
+            params.ok_or_else(|| DaemonError::InvalidRequest("Missing params".to_string()))?;
+        self.handlers.handle_events_between(params, auth).await
+    }
+
     async fn call_system_health(
         &self,
         _params: Option<Value>,
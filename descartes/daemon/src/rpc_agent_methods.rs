@@ -10,9 +10,13 @@
 //! - `get_agent_statistics`: Get aggregated statistics
 //! - `subscribe_agent_updates`: Subscribe to agent update stream
 //! - `get_monitoring_health`: Get monitoring system health
+//! - `agent.history.query`: Query an agent's recorded history by time range
 
 use crate::agent_monitor::{AgentMonitor, HealthSummary, MonitorStats};
 use descartes_core::{
+    agent_history::{
+        AgentHistoryEvent, AgentHistoryStore, HistoryEventType, HistoryQuery, HistorySnapshot,
+    },
     agent_state::{AgentRuntimeState, AgentStateCollection, AgentStatus},
     AgentStreamMessage,
 };
@@ -112,6 +116,19 @@ pub trait AgentMonitoringRpc {
     /// True if removed, false if not found
     #[method(name = "remove_agent")]
     async fn remove_agent(&self, agent_id: String) -> Result<bool, ErrorObjectOwned>;
+
+    /// Query an agent's recorded history
+    ///
+    /// # Arguments
+    /// * `params` - Agent ID, optional time range and event type filter
+    ///
+    /// # Returns
+    /// Matching history events (oldest first) and snapshots taken in the range
+    #[method(name = "agent.history.query")]
+    async fn query_agent_history(
+        &self,
+        params: AgentHistoryQueryParams,
+    ) -> Result<AgentHistoryQueryResult, ErrorObjectOwned>;
 }
 
 // ============================================================================
@@ -131,6 +148,35 @@ pub struct AgentStatusFilter {
     pub active_only: Option<bool>,
 }
 
+/// Parameters for `agent.history.query`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentHistoryQueryParams {
+    /// Agent whose history to query
+    pub agent_id: String,
+
+    /// Start of the time range, Unix seconds (inclusive)
+    #[serde(default)]
+    pub from: Option<i64>,
+
+    /// End of the time range, Unix seconds (inclusive)
+    #[serde(default)]
+    pub to: Option<i64>,
+
+    /// Only return events of this type
+    #[serde(default)]
+    pub event_type: Option<HistoryEventType>,
+}
+
+/// Result of `agent.history.query`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentHistoryQueryResult {
+    /// Matching events, oldest first
+    pub events: Vec<AgentHistoryEvent>,
+
+    /// Snapshots taken within the time range, oldest first
+    pub snapshots: Vec<HistorySnapshot>,
+}
+
 // ============================================================================
 // RPC SERVER IMPLEMENTATION
 // ============================================================================
@@ -139,12 +185,24 @@ pub struct AgentStatusFilter {
 pub struct AgentMonitoringRpcImpl {
     /// Agent monitoring system
     monitor: Arc<AgentMonitor>,
+
+    /// History store backing `agent.history.query`
+    history: Option<Arc<dyn AgentHistoryStore>>,
 }
 
 impl AgentMonitoringRpcImpl {
     /// Create a new RPC implementation
     pub fn new(monitor: Arc<AgentMonitor>) -> Self {
-        Self { monitor }
+        Self {
+            monitor,
+            history: None,
+        }
+    }
+
+    /// Serve agent history queries from `store`
+    pub fn with_history(mut self, store: Arc<dyn AgentHistoryStore>) -> Self {
+        self.history = Some(store);
+        self
     }
 }
 
@@ -276,12 +334,62 @@ impl AgentMonitoringRpcServer for AgentMonitoringRpcImpl {
         info!("RPC: remove_agent - removed: {}", removed);
         Ok(removed)
     }
+
+    async fn query_agent_history(
+        &self,
+        params: AgentHistoryQueryParams,
+    ) -> Result<AgentHistoryQueryResult, ErrorObjectOwned> {
+        debug!("RPC: agent.history.query {}", params.agent_id);
+
+        let store = self.history.as_ref().ok_or_else(|| {
+            ErrorObjectOwned::owned(-32603, "Agent history is not available", None::<()>)
+        })?;
+
+        if let (Some(from), Some(to)) = (params.from, params.to) {
+            if from > to {
+                return Err(ErrorObjectOwned::owned(
+                    -32602,
+                    format!("Invalid time range: from ({}) is after to ({})", from, to),
+                    None::<()>,
+                ));
+            }
+        }
+
+        let query = HistoryQuery {
+            agent_id: Some(params.agent_id.clone()),
+            event_type: params.event_type,
+            start_time: params.from,
+            end_time: params.to,
+            ascending: true,
+            ..Default::default()
+        };
+        let events = store.query_events(&query).await.map_err(|e| {
+            error!("Failed to query agent history: {}", e);
+            ErrorObjectOwned::owned(-32603, format!("History query failed: {}", e), None::<()>)
+        })?;
+
+        let mut snapshots = store
+            .list_snapshots(&params.agent_id)
+            .await
+            .map_err(|e| {
+                error!("Failed to list agent snapshots: {}", e);
+                ErrorObjectOwned::owned(-32603, format!("History query failed: {}", e), None::<()>)
+            })?
+            .into_iter()
+            .filter(|s| params.from.is_none_or(|from| s.timestamp >= from))
+            .filter(|s| params.to.is_none_or(|to| s.timestamp <= to))
+            .collect::<Vec<_>>();
+        snapshots.sort_by_key(|s| s.timestamp);
+
+        Ok(AgentHistoryQueryResult { events, snapshots })
+    }
 }
 
 impl Clone for AgentMonitoringRpcImpl {
     fn clone(&self) -> Self {
         Self {
             monitor: Arc::clone(&self.monitor),
+            history: self.history.clone(),
         }
     }
 }
@@ -466,4 +574,77 @@ mod tests {
         let result = rpc.get_agent_status("invalid-uuid".to_string()).await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_query_agent_history_by_range_and_type() {
+        use descartes_core::agent_history::SqliteAgentHistoryStore;
+
+        let mut store = SqliteAgentHistoryStore::new(":memory:").await.unwrap();
+        store.initialize().await.unwrap();
+
+        let event_at = |timestamp: i64, event_type: HistoryEventType| {
+            let mut event =
+                AgentHistoryEvent::new("agent-1".to_string(), event_type, serde_json::json!({}));
+            event.timestamp = timestamp;
+            event
+        };
+        store
+            .record_events(&[
+                event_at(100, HistoryEventType::Thought),
+                event_at(200, HistoryEventType::ToolUse),
+                event_at(300, HistoryEventType::Thought),
+                event_at(400, HistoryEventType::Thought),
+            ])
+            .await
+            .unwrap();
+        let mut other = event_at(250, HistoryEventType::Thought);
+        other.agent_id = "agent-2".to_string();
+        store.record_event(&other).await.unwrap();
+
+        for timestamp in [50, 350] {
+            let mut snapshot = HistorySnapshot::new("agent-1".to_string(), Vec::new(), None);
+            snapshot.timestamp = timestamp;
+            store.create_snapshot(&snapshot).await.unwrap();
+        }
+
+        let rpc = create_test_impl().with_history(Arc::new(store));
+        let result = rpc
+            .query_agent_history(AgentHistoryQueryParams {
+                agent_id: "agent-1".to_string(),
+                from: Some(150),
+                to: Some(400),
+                event_type: Some(HistoryEventType::Thought),
+            })
+            .await
+            .unwrap();
+
+        let timestamps: Vec<_> = result.events.iter().map(|e| e.timestamp).collect();
+        assert_eq!(timestamps, vec![300, 400]);
+        assert_eq!(result.snapshots.len(), 1);
+        assert_eq!(result.snapshots[0].timestamp, 350);
+
+        let invalid = rpc
+            .query_agent_history(AgentHistoryQueryParams {
+                agent_id: "agent-1".to_string(),
+                from: Some(400),
+                to: Some(100),
+                event_type: None,
+            })
+            .await;
+        assert!(invalid.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_query_agent_history_without_store() {
+        let rpc = create_test_impl();
+        let result = rpc
+            .query_agent_history(AgentHistoryQueryParams {
+                agent_id: "agent-1".to_string(),
+                from: None,
+                to: None,
+                event_type: None,
+            })
+            .await;
+        assert!(result.is_err());
+    }
 }
@@ -4,7 +4,9 @@
 //! via Unix sockets using the jsonrpsee library.
 
 use crate::errors::{DaemonError, DaemonResult};
-use crate::rpc_server::{ApprovalResult, TaskInfo};
+use crate::rpc_server::{ApprovalResult, HealthResult, TaskInfo};
+use crate::types::{EventsBetweenRequest, EventsBetweenResponse};
+use descartes_core::TaskPage;
 use serde_json::Value;
 use std::path::PathBuf;
 use std::time::Duration;
@@ -252,6 +254,22 @@ impl UnixSocketRpcClient {
             .map_err(|e| DaemonError::SerializationError(format!("Failed to parse tasks: {}", e)))
     }
 
+    /// List one page of tasks
+    ///
+    /// # Arguments
+    /// * `filter` - Optional filter criteria as JSON; accepts the `list_tasks`
+    ///   fields plus `limit`, `offset`, `sort_by` and `order` ("asc"/"desc")
+    ///
+    /// # Returns
+    /// The page of tasks and the total number of matching tasks
+    pub async fn list_tasks_page(&self, filter: Option<Value>) -> DaemonResult<TaskPage<TaskInfo>> {
+        let params = serde_json::json!([filter]);
+        let result = self.call("list_tasks_page", params).await?;
+
+        serde_json::from_value(result)
+            .map_err(|e| DaemonError::SerializationError(format!("Failed to parse tasks: {}", e)))
+    }
+
     /// Approve or reject a task
     ///
     /// # Arguments
@@ -281,6 +299,33 @@ impl UnixSocketRpcClient {
         self.call("get_state", params).await
     }
 
+    /// Get daemon health: uptime, version, session counts and configuration summary
+    pub async fn system_health(&self) -> DaemonResult<HealthResult> {
+        let result = self.call("system.health", serde_json::json!([])).await?;
+
+        serde_json::from_value(result).map_err(|e| {
+            DaemonError::SerializationError(format!("Failed to parse health result: {}", e))
+        })
+    }
+
+    /// Fetch persisted events published in a time window
+    ///
+    /// # Arguments
+    /// * `from` / `to` - Window bounds in Unix seconds (inclusive)
+    /// * `filter` - Optional filter expression, e.g. `agent_id = agent-1`
+    pub async fn events_between(
+        &self,
+        from: i64,
+        to: i64,
+        filter: Option<String>,
+    ) -> DaemonResult<EventsBetweenResponse> {
+        let params = serde_json::json!([EventsBetweenRequest { from, to, filter }]);
+        let result = self.call("events.between", params).await?;
+
+        serde_json::from_value(result)
+            .map_err(|e| DaemonError::SerializationError(format!("Failed to parse events: {}", e)))
+    }
+
     /// Get the socket path
     pub fn socket_path(&self) -> &PathBuf {
         &self.socket_path
@@ -6,15 +6,23 @@
 //! The server exposes methods for:
 //! - spawn: Create and start new agents
 //! - list_tasks: List all tasks in the system
+//! - list_tasks_page: List one page of tasks with the total match count
 //! - approve: Approve pending tasks or actions
 //! - get_state: Query the current state
+//! - agent.kill: Terminate an agent, escalating from SIGTERM to SIGKILL
+//! - events.between: Query persisted bus events by time window
 
+use crate::config::{DaemonConfig, EventHistoryConfig, ServerConfig};
 use crate::errors::{DaemonError, DaemonResult};
-use crate::events::{AgentEvent, AgentEventType, DescartesEvent, EventBus};
-use crate::types::{RpcError, RpcRequest, RpcResponse};
-use descartes_core::swank::{find_available_port, SwankClient, SwankLauncher, SwankMessage, DEFAULT_SWANK_PORT};
+use crate::event_store::{EventRetention, EventStore};
+use crate::events::{AgentEvent, AgentEventType, DescartesEvent, EventBus, SystemEvent};
+use crate::types::{
+    EventsBetweenRequest, EventsBetweenResponse, RpcError, RpcRequest, RpcResponse,
+};
+use descartes_core::swank::{SwankMessage, SwankPool, SwankPoolConfig};
+use descartes_core::task_queries::{SortOrder, TaskPage, TaskSortField};
 use descartes_core::tools::SWANK_REGISTRY;
-use descartes_core::traits::{AgentConfig, TaskStatus};
+use descartes_core::traits::{AgentConfig, AgentHandle, AgentRecord, AgentStatus, TaskStatus};
 use jsonrpsee::core::async_trait;
 use jsonrpsee::proc_macros::rpc;
 use serde::{Deserialize, Serialize};
@@ -24,8 +32,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::{UnixListener, UnixStream};
-use tokio::process::Child;
-use tokio::sync::oneshot;
+use tokio::sync::{oneshot, OwnedSemaphorePermit, Semaphore};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
@@ -59,6 +66,20 @@ pub trait DescartesRpc {
     #[method(name = "list_tasks")]
     async fn list_tasks(&self, filter: Option<Value>) -> Result<Vec<TaskInfo>, ErrorObjectOwned>;
 
+    /// List one page of tasks
+    ///
+    /// # Arguments
+    /// * `filter` - Optional filter criteria; `limit`, `offset`, `sort_by` and
+    ///   `order` control pagination
+    ///
+    /// # Returns
+    /// The requested page and the number of tasks matching the filter
+    #[method(name = "list_tasks_page")]
+    async fn list_tasks_page(
+        &self,
+        filter: Option<Value>,
+    ) -> Result<TaskPage<TaskInfo>, ErrorObjectOwned>;
+
     /// Approve a pending task or action
     ///
     /// # Arguments
@@ -84,6 +105,27 @@ pub trait DescartesRpc {
     #[method(name = "get_state")]
     async fn get_state(&self, entity_id: Option<String>) -> Result<Value, ErrorObjectOwned>;
 
+    /// Query persisted bus events in a time window
+    ///
+    /// # Arguments
+    /// * `params` - Window bounds in Unix seconds and an optional filter expression
+    ///
+    /// # Returns
+    /// Matching events, oldest first; fails if event history is not enabled
+    #[method(name = "events.between")]
+    async fn events_between(
+        &self,
+        params: EventsBetweenRequest,
+    ) -> Result<EventsBetweenResponse, ErrorObjectOwned>;
+
+    /// Report daemon health for monitoring
+    ///
+    /// # Returns
+    /// Uptime, version, agent/session/subscriber counts, memory usage, task
+    /// counts and a summary of the daemon configuration
+    #[method(name = "system.health")]
+    async fn system_health(&self) -> Result<HealthResult, ErrorObjectOwned>;
+
     /// Pause a running agent
     ///
     /// # Arguments
@@ -109,6 +151,37 @@ pub trait DescartesRpc {
     #[method(name = "agent.resume")]
     async fn resume_agent(&self, agent_id: String) -> Result<ResumeResult, ErrorObjectOwned>;
 
+    /// Write a line of input to a running agent's stdin
+    ///
+    /// # Arguments
+    /// * `agent_id` - The ID of the agent
+    /// * `text` - The input; a trailing newline is added if missing
+    ///
+    /// # Returns
+    /// Confirmation with the number of bytes written
+    #[method(name = "agent.send_input")]
+    async fn send_input(
+        &self,
+        agent_id: String,
+        text: String,
+    ) -> Result<SendInputResult, ErrorObjectOwned>;
+
+    /// Terminate an agent, escalating to SIGKILL if it ignores SIGTERM
+    ///
+    /// # Arguments
+    /// * `agent_id` - The ID of the agent to kill
+    /// * `grace_secs` - How long to wait after SIGTERM before sending SIGKILL
+    ///   (defaults to 10 seconds)
+    ///
+    /// # Returns
+    /// The agent's final exit status and whether SIGKILL was needed
+    #[method(name = "agent.kill")]
+    async fn kill_agent(
+        &self,
+        agent_id: String,
+        grace_secs: Option<u64>,
+    ) -> Result<KillResult, ErrorObjectOwned>;
+
     /// Request attach credentials for a paused agent
     ///
     /// # Arguments
@@ -178,6 +251,26 @@ pub struct ResumeResult {
     pub resumed_at: i64,
 }
 
+/// Send input result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendInputResult {
+    pub agent_id: String,
+    pub bytes_written: usize,
+    pub sent_at: i64,
+}
+
+/// Kill result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KillResult {
+    pub agent_id: String,
+    /// Exit code, if the process exited normally
+    pub exit_code: Option<i32>,
+    pub success: bool,
+    /// Whether the agent outlived the grace period and was sent SIGKILL
+    pub escalated: bool,
+    pub killed_at: i64,
+}
+
 /// Attach credentials result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AttachCredentialsResult {
@@ -210,6 +303,145 @@ pub struct SwankRestartResult {
     pub message: Option<String>,
 }
 
+/// Daemon health snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthResult {
+    pub status: String,
+    pub version: String,
+    pub uptime_secs: u64,
+    pub agents: HealthAgentCounts,
+    pub attach_sessions: usize,
+    pub swank_sessions: usize,
+    pub event_subscribers: usize,
+    pub memory_usage_mb: f64,
+    pub tasks: HealthTaskCounts,
+    pub config: HealthConfigSummary,
+    pub timestamp: i64,
+}
+
+/// Agent counts reported by `system.health`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthAgentCounts {
+    pub total: usize,
+    /// Agents that have not reached a terminal state
+    pub active: usize,
+    pub max_concurrent: usize,
+}
+
+/// Task counts by status reported by `system.health`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HealthTaskCounts {
+    pub total: usize,
+    pub todo: usize,
+    pub in_progress: usize,
+    pub done: usize,
+    pub blocked: usize,
+}
+
+/// The parts of the daemon configuration a monitor cares about
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthConfigSummary {
+    pub auth_enabled: bool,
+    pub http_port: u16,
+    pub ws_port: u16,
+    pub pub_port: u16,
+    /// Metrics port, if the metrics endpoint is enabled
+    pub metrics_port: Option<u16>,
+}
+
+impl From<&DaemonConfig> for HealthConfigSummary {
+    fn from(config: &DaemonConfig) -> Self {
+        Self {
+            auth_enabled: config.auth.enabled,
+            http_port: config.server.http_port,
+            ws_port: config.server.ws_port,
+            pub_port: config.server.pub_port,
+            metrics_port: config
+                .server
+                .enable_metrics
+                .then_some(config.server.metrics_port),
+        }
+    }
+}
+
+/// Pagination and sorting options read from a `list_tasks` filter
+struct TaskPageParams {
+    sort_by: TaskSortField,
+    order: SortOrder,
+    offset: usize,
+    limit: Option<usize>,
+}
+
+impl TaskPageParams {
+    fn from_filter(filter: Option<&Value>) -> Result<Self, ErrorObjectOwned> {
+        let invalid = |msg: String| ErrorObjectOwned::owned(-32602, msg, None::<()>);
+        let field = |name: &str| filter.and_then(|f| f.get(name)).filter(|v| !v.is_null());
+        let count = |name: &str| -> Result<Option<usize>, ErrorObjectOwned> {
+            field(name)
+                .map(|v| {
+                    v.as_u64()
+                        .map(|n| n as usize)
+                        .ok_or_else(|| invalid(format!("{} must be a non-negative integer", name)))
+                })
+                .transpose()
+        };
+        let parse = |name: &str| -> Result<Option<&str>, ErrorObjectOwned> {
+            field(name)
+                .map(|v| {
+                    v.as_str()
+                        .ok_or_else(|| invalid(format!("{} must be a string", name)))
+                })
+                .transpose()
+        };
+
+        // Same default ordering as TaskQueryBuilder
+        let sort_by = match parse("sort_by")? {
+            Some(s) => s.parse().map_err(invalid)?,
+            None => TaskSortField::UpdatedAt,
+        };
+        let order = match parse("order")? {
+            Some(s) => s.parse().map_err(invalid)?,
+            None => SortOrder::Descending,
+        };
+
+        Ok(Self {
+            sort_by,
+            order,
+            offset: count("offset")?.unwrap_or(0),
+            limit: count("limit")?,
+        })
+    }
+}
+
+/// Grace period `agent.kill` allows between SIGTERM and SIGKILL by default
+pub const DEFAULT_KILL_GRACE_SECS: u64 = 10;
+
+/// How often `agent.kill` checks whether the agent has exited
+const KILL_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// State reported for a saved agent the runner no longer knows about
+///
+/// Agents that were still live when the daemon lost track of them are
+/// reported as `unknown`: their process may or may not still be running.
+fn previous_agent_state(record: &AgentRecord) -> Value {
+    let status = if record.status.is_terminal() {
+        "exited"
+    } else {
+        "unknown"
+    };
+    json!({
+        "entity_type": "agent",
+        "entity_id": record.id.to_string(),
+        "name": record.config.name,
+        "status": status,
+        "last_status": record.status,
+        "model_backend": record.config.model_backend,
+        "task": record.config.task,
+        "restartable": record.restartable,
+        "updated_at": record.updated_at,
+    })
+}
+
 /// Check if an agent should use Lisp/Swank.
 fn is_lisp_agent(config: &AgentConfig) -> bool {
     // Check model_backend
@@ -250,10 +482,20 @@ pub struct RpcServerImpl {
     event_bus: Arc<crate::events::EventBus>,
     /// Active attach server handles (agent_id -> JoinHandle)
     attach_servers: Arc<dashmap::DashMap<uuid::Uuid, tokio::task::JoinHandle<()>>>,
-    /// SBCL processes for Lisp agents (agent_id -> Child)
-    sbcl_processes: Arc<dashmap::DashMap<uuid::Uuid, Child>>,
+    /// SBCL processes for Lisp agents, kept warm between agents
+    swank_pool: Arc<SwankPool>,
     /// Swank event forwarding tasks (agent_id -> JoinHandle)
     swank_event_tasks: Arc<dashmap::DashMap<uuid::Uuid, tokio::task::JoinHandle<()>>>,
+    /// Limits how many agents may run at once
+    spawn_limiter: Arc<Semaphore>,
+    /// Capacity of `spawn_limiter`
+    max_concurrent_agents: usize,
+    /// Spawn permits held by live agents (agent_id -> permit)
+    spawn_permits: Arc<dashmap::DashMap<uuid::Uuid, OwnedSemaphorePermit>>,
+    /// When this server was created, for uptime reporting
+    started_at: std::time::Instant,
+    /// Configuration summary reported by `system.health`
+    config_summary: HealthConfigSummary,
 }
 
 impl RpcServerImpl {
@@ -270,6 +512,7 @@ impl RpcServerImpl {
             Arc::clone(&event_bus),
             attach_config,
         ));
+        let default_max_agents = ServerConfig::default().max_concurrent_agents;
         Self {
             agent_runner,
             local_runner: None,
@@ -278,8 +521,16 @@ impl RpcServerImpl {
             attach_manager,
             event_bus,
             attach_servers: Arc::new(dashmap::DashMap::new()),
-            sbcl_processes: Arc::new(dashmap::DashMap::new()),
+            swank_pool: Arc::new(SwankPool::new(
+                Arc::clone(&SWANK_REGISTRY),
+                SwankPoolConfig::default(),
+            )),
             swank_event_tasks: Arc::new(dashmap::DashMap::new()),
+            spawn_limiter: Arc::new(Semaphore::new(default_max_agents)),
+            max_concurrent_agents: default_max_agents,
+            spawn_permits: Arc::new(dashmap::DashMap::new()),
+            started_at: std::time::Instant::now(),
+            config_summary: HealthConfigSummary::from(&DaemonConfig::default()),
         }
     }
 
@@ -298,6 +549,7 @@ impl RpcServerImpl {
             Arc::clone(&event_bus),
             attach_config,
         ));
+        let default_max_agents = ServerConfig::default().max_concurrent_agents;
         Self {
             agent_runner: Arc::clone(&local_runner) as Arc<dyn descartes_core::traits::AgentRunner>,
             local_runner: Some(local_runner),
@@ -306,8 +558,16 @@ impl RpcServerImpl {
             attach_manager,
             event_bus,
             attach_servers: Arc::new(dashmap::DashMap::new()),
-            sbcl_processes: Arc::new(dashmap::DashMap::new()),
+            swank_pool: Arc::new(SwankPool::new(
+                Arc::clone(&SWANK_REGISTRY),
+                SwankPoolConfig::default(),
+            )),
             swank_event_tasks: Arc::new(dashmap::DashMap::new()),
+            spawn_limiter: Arc::new(Semaphore::new(default_max_agents)),
+            max_concurrent_agents: default_max_agents,
+            spawn_permits: Arc::new(dashmap::DashMap::new()),
+            started_at: std::time::Instant::now(),
+            config_summary: HealthConfigSummary::from(&DaemonConfig::default()),
         }
     }
 
@@ -318,6 +578,7 @@ impl RpcServerImpl {
         attach_manager: Arc<crate::attach_session::AttachSessionManager>,
         event_bus: Arc<crate::events::EventBus>,
     ) -> Self {
+        let default_max_agents = ServerConfig::default().max_concurrent_agents;
         Self {
             agent_runner,
             local_runner: None,
@@ -326,8 +587,125 @@ impl RpcServerImpl {
             attach_manager,
             event_bus,
             attach_servers: Arc::new(dashmap::DashMap::new()),
-            sbcl_processes: Arc::new(dashmap::DashMap::new()),
+            swank_pool: Arc::new(SwankPool::new(
+                Arc::clone(&SWANK_REGISTRY),
+                SwankPoolConfig::default(),
+            )),
             swank_event_tasks: Arc::new(dashmap::DashMap::new()),
+            spawn_limiter: Arc::new(Semaphore::new(default_max_agents)),
+            max_concurrent_agents: default_max_agents,
+            spawn_permits: Arc::new(dashmap::DashMap::new()),
+            started_at: std::time::Instant::now(),
+            config_summary: HealthConfigSummary::from(&DaemonConfig::default()),
+        }
+    }
+
+    /// Reap idle attach sessions in the background, stopping the attach server
+    /// of each agent whose session expired
+    pub(crate) fn spawn_attach_reaper(&self) -> tokio::task::JoinHandle<()> {
+        let attach_servers = Arc::clone(&self.attach_servers);
+        self.attach_manager.spawn_idle_reaper(move |session| {
+            if let Some((_, server_handle)) = attach_servers.remove(&session.agent_id) {
+                server_handle.abort();
+                let socket_path = format!("/tmp/descartes-attach-{}.sock", session.agent_id);
+                let _ = std::fs::remove_file(&socket_path);
+                info!(
+                    "Stopped attach server for agent {} after idle timeout",
+                    session.agent_id
+                );
+            }
+        })
+    }
+
+    /// Limit how many agents may run at once; spawns beyond the limit are rejected
+    pub fn with_max_concurrent_agents(mut self, max_concurrent_agents: usize) -> Self {
+        self.spawn_limiter = Arc::new(Semaphore::new(max_concurrent_agents));
+        self.max_concurrent_agents = max_concurrent_agents;
+        self
+    }
+
+    /// Apply the limits and configuration summary from the daemon configuration
+    pub fn with_daemon_config(self, config: &DaemonConfig) -> Self {
+        let mut server = self.with_max_concurrent_agents(config.server.max_concurrent_agents);
+        server.config_summary = HealthConfigSummary::from(config);
+        server.swank_pool = Arc::new(SwankPool::new(
+            Arc::clone(&SWANK_REGISTRY),
+            SwankPoolConfig::from(&config.swank),
+        ));
+        server
+    }
+
+    /// Reserve capacity for a new agent, or fail with -32030 if the daemon is full
+    async fn acquire_spawn_permit(
+        &self,
+        name: &str,
+    ) -> Result<OwnedSemaphorePermit, ErrorObjectOwned> {
+        if let Ok(permit) = Arc::clone(&self.spawn_limiter).try_acquire_owned() {
+            return Ok(permit);
+        }
+
+        // Permits are normally returned on exit; reconcile in case a handle could not report it
+        self.release_finished_agents().await;
+
+        match Arc::clone(&self.spawn_limiter).try_acquire_owned() {
+            Ok(permit) => Ok(permit),
+            Err(_) => {
+                warn!(
+                    "Rejecting spawn of '{}': {} agents already running",
+                    name, self.max_concurrent_agents
+                );
+                self.event_bus
+                    .publish(SystemEvent::at_capacity(self.max_concurrent_agents, name))
+                    .await;
+                Err(ErrorObjectOwned::owned(
+                    -32030,
+                    format!(
+                        "Daemon at capacity: {} agents already running",
+                        self.max_concurrent_agents
+                    ),
+                    None::<()>,
+                ))
+            }
+        }
+    }
+
+    /// Return the spawn permit once the agent exits, is killed, or is dropped by the runner,
+    /// and persist its final status so it isn't restored as still running
+    fn release_permit_on_exit(&self, agent_id: Uuid, agent_handle: &dyn AgentHandle) {
+        let Some(mut exit) = agent_handle.subscribe_exit() else {
+            return;
+        };
+        let spawn_permits = Arc::clone(&self.spawn_permits);
+        let state_store = Arc::clone(&self.state_store);
+        tokio::spawn(async move {
+            // Errors once the runner drops the handle, which also ends the agent's slot
+            let status = match exit.wait_for(|status| status.is_some()).await {
+                Ok(exit_status) if exit_status.as_ref().is_some_and(|s| s.success) => {
+                    AgentStatus::Completed
+                }
+                Ok(_) => AgentStatus::Failed,
+                Err(_) => AgentStatus::Terminated,
+            };
+            spawn_permits.remove(&agent_id);
+            save_agent_status(state_store.as_ref(), &agent_id, status).await;
+        });
+    }
+
+    /// Return spawn permits held by agents that have exited or been removed
+    async fn release_finished_agents(&self) {
+        let agent_ids: Vec<Uuid> = self.spawn_permits.iter().map(|e| *e.key()).collect();
+        for agent_id in agent_ids {
+            let finished = match self.agent_runner.get_agent(&agent_id).await {
+                Ok(Some(info)) => matches!(
+                    info.status,
+                    AgentStatus::Completed | AgentStatus::Failed | AgentStatus::Terminated
+                ),
+                Ok(None) => true,
+                Err(_) => false,
+            };
+            if finished {
+                self.spawn_permits.remove(&agent_id);
+            }
         }
     }
 
@@ -339,6 +717,8 @@ impl RpcServerImpl {
     ) -> Result<String, ErrorObjectOwned> {
         info!("Spawning agent: {} (type: {})", name, agent_type);
 
+        let spawn_permit = self.acquire_spawn_permit(&name).await?;
+
         let environment: HashMap<String, String> = config
             .get("environment")
             .and_then(|e| serde_json::from_value(e.clone()).ok())
@@ -374,6 +754,11 @@ impl RpcServerImpl {
             .and_then(|v| v.as_object())
             .map(|m| m.iter().map(|(k, v)| (k.clone(), v.clone())).collect());
 
+        let restartable = config
+            .get("restartable")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
         let agent_config = AgentConfig {
             name: name.clone(),
             model_backend: agent_type,
@@ -386,8 +771,20 @@ impl RpcServerImpl {
             agents,
         };
 
+        self.spawn_agent_config(agent_config, restartable, spawn_permit)
+            .await
+    }
+
+    /// Spawn an agent from a complete config and remember it in the state store
+    async fn spawn_agent_config(
+        &self,
+        agent_config: AgentConfig,
+        restartable: bool,
+        spawn_permit: OwnedSemaphorePermit,
+    ) -> Result<String, ErrorObjectOwned> {
         // Check if this is a Lisp agent before spawning (agent_config is moved by spawn)
         let needs_swank = is_lisp_agent(&agent_config);
+        let saved_config = agent_config.clone();
 
         let agent_handle = self.agent_runner.spawn(agent_config).await.map_err(|e| {
             error!("Failed to spawn agent: {}", e);
@@ -397,6 +794,7 @@ impl RpcServerImpl {
         let agent_id = agent_handle.id();
         let agent_id_str = agent_id.to_string();
         self.agent_ids.insert(agent_id_str.clone(), agent_id);
+        self.spawn_permits.insert(agent_id, spawn_permit);
 
         // Initialize Swank for Lisp agents - fail spawn if Swank init fails
         if needs_swank {
@@ -413,6 +811,7 @@ impl RpcServerImpl {
                         warn!("Failed to kill agent after Swank init failure: {}", kill_err);
                     }
                     self.agent_ids.remove(&agent_id_str);
+                    self.spawn_permits.remove(&agent_id);
 
                     return Err(ErrorObjectOwned::owned(
                         -32017,
@@ -423,37 +822,100 @@ impl RpcServerImpl {
             }
         }
 
+        let now = chrono::Utc::now().timestamp();
+        let record = AgentRecord {
+            id: agent_id,
+            config: saved_config,
+            status: AgentStatus::Running,
+            restartable,
+            created_at: now,
+            updated_at: now,
+        };
+        if let Err(e) = self.state_store.save_agent_record(&record).await {
+            warn!("Failed to persist agent {}: {}", agent_id_str, e);
+        }
+        // Registered after the record is saved so an early exit still updates it
+        self.release_permit_on_exit(agent_id, agent_handle.as_ref());
+
         info!("Agent spawned successfully with ID: {}", agent_id_str);
         Ok(agent_id_str)
     }
 
-    /// Initialize a Swank session for a Lisp agent.
-    async fn initialize_swank_session(&self, agent_id: Uuid) -> Result<(), String> {
-        // Find available port
-        let port = find_available_port(DEFAULT_SWANK_PORT)
-            .await
-            .map_err(|e| format!("Failed to find available port: {}", e))?;
+    /// Update the persisted status of an agent, if it has a saved record
+    async fn save_agent_status(&self, agent_id: &Uuid, status: AgentStatus) {
+        save_agent_status(self.state_store.as_ref(), agent_id, status).await;
+    }
 
-        info!("Starting SBCL with Swank on port {} for agent {}", port, agent_id);
+    /// Saved agents that the runner no longer knows about, e.g. from before a
+    /// daemon restart
+    async fn previous_agents(&self) -> Result<Vec<AgentRecord>, ErrorObjectOwned> {
+        let records = self.state_store.get_agent_records().await.map_err(|e| {
+            error!("Failed to load agent records: {}", e);
+            ErrorObjectOwned::owned(-32603, format!("Failed to load agent records: {}", e), None::<()>)
+        })?;
 
-        // Start SBCL
-        let sbcl_child = SwankLauncher::start_sbcl(port)
+        let mut previous = Vec::new();
+        for record in records {
+            if matches!(self.agent_runner.get_agent(&record.id).await, Ok(Some(_))) {
+                continue;
+            }
+            previous.push(record);
+        }
+        Ok(previous)
+    }
+
+    /// Re-read agents saved by a previous run and spawn the restartable ones again
+    ///
+    /// Only agents that were still live when the daemon stopped are respawned;
+    /// each gets a new ID and its old record is marked terminated. Returns the
+    /// old and new IDs of the respawned agents.
+    pub async fn restore_agents(&self) -> DaemonResult<Vec<(String, String)>> {
+        let previous = self
+            .previous_agents()
             .await
-            .map_err(|e| format!("Failed to start SBCL: {}", e))?;
+            .map_err(|e| DaemonError::StateError(e.message().to_string()))?;
+        info!("Found {} agent(s) from a previous run", previous.len());
+
+        let mut respawned = Vec::new();
+        for record in previous {
+            if !record.restartable || record.status.is_terminal() {
+                continue;
+            }
+
+            let spawn_permit = match self.acquire_spawn_permit(&record.config.name).await {
+                Ok(permit) => permit,
+                Err(e) => {
+                    warn!("Not restoring agent {}: {}", record.id, e.message());
+                    continue;
+                }
+            };
+            match self
+                .spawn_agent_config(record.config.clone(), true, spawn_permit)
+                .await
+            {
+                Ok(new_id) => {
+                    info!("Restored agent {} as {}", record.id, new_id);
+                    self.save_agent_status(&record.id, AgentStatus::Terminated)
+                        .await;
+                    respawned.push((record.id.to_string(), new_id));
+                }
+                Err(e) => warn!("Failed to restore agent {}: {}", record.id, e.message()),
+            }
+        }
+
+        Ok(respawned)
+    }
 
+    /// Initialize a Swank session for a Lisp agent.
+    async fn initialize_swank_session(&self, agent_id: Uuid) -> Result<(), String> {
         // Create event channel for this session
         let (event_tx, mut event_rx) = tokio::sync::mpsc::channel(64);
 
-        // Connect SwankClient
-        let swank_client = SwankClient::connect(agent_id, port, event_tx)
+        // Lease an SBCL process (warm if available) and register its client
+        self.swank_pool
+            .acquire(agent_id, event_tx)
             .await
-            .map_err(|e| format!("Failed to connect to Swank: {}", e))?;
-
-        // Register in global registry
-        SWANK_REGISTRY.insert(agent_id, swank_client);
-
-        // Store SBCL process for cleanup
-        self.sbcl_processes.insert(agent_id, sbcl_child);
+            .map_err(|e| e.to_string())?;
 
         // Spawn event forwarding task
         let event_bus = Arc::clone(&self.event_bus);
@@ -504,6 +966,15 @@ impl RpcServerImpl {
                     data: serde_json::json!({ "text": text }),
                 })
             }
+            SwankMessage::Stderr(text) => {
+                DescartesEvent::AgentEvent(AgentEvent {
+                    id: Uuid::new_v4().to_string(),
+                    agent_id: agent_id.to_string(),
+                    timestamp: chrono::Utc::now(),
+                    event_type: AgentEventType::SwankOutput,
+                    data: serde_json::json!({ "text": text, "stream": "stderr" }),
+                })
+            }
             SwankMessage::Return { id, value } => {
                 DescartesEvent::AgentEvent(AgentEvent {
                     id: Uuid::new_v4().to_string(),
@@ -543,24 +1014,23 @@ impl RpcServerImpl {
             debug!("Stopped Swank event task for agent {}", agent_id);
         }
 
-        // Remove from global registry
-        SWANK_REGISTRY.remove(agent_id);
-
-        // Kill SBCL process
-        if let Some((_, mut child)) = self.sbcl_processes.remove(agent_id) {
-            if let Err(e) = child.kill().await {
-                warn!("Failed to kill SBCL process for agent {}: {}", agent_id, e);
-            } else {
-                info!("Killed SBCL process for agent {}", agent_id);
-            }
-        }
+        // Unregister the client and return its SBCL process to the pool
+        self.swank_pool.release(agent_id).await;
     }
 
     pub(crate) async fn list_tasks_internal(
         &self,
         filter: Option<Value>,
     ) -> Result<Vec<TaskInfo>, ErrorObjectOwned> {
+        Ok(self.list_tasks_page_internal(filter).await?.tasks)
+    }
+
+    pub(crate) async fn list_tasks_page_internal(
+        &self,
+        filter: Option<Value>,
+    ) -> Result<TaskPage<TaskInfo>, ErrorObjectOwned> {
         info!("Listing tasks with filter: {:?}", filter);
+        let page_params = TaskPageParams::from_filter(filter.as_ref())?;
 
         let tasks = self.state_store.get_tasks().await.map_err(|e| {
             error!("Failed to get tasks: {}", e);
@@ -581,19 +1051,30 @@ impl RpcServerImpl {
             }
         }
 
-        let task_infos: Vec<TaskInfo> = filtered_tasks
-            .into_iter()
-            .map(|task| TaskInfo {
-                id: task.id.to_string(),
-                name: task.title,
-                status: format!("{:?}", task.status),
-                created_at: task.created_at,
-                updated_at: task.updated_at,
-            })
-            .collect();
+        // Sort before paginating so pages are stable
+        filtered_tasks.sort_by(|a, b| page_params.order.apply(page_params.sort_by.compare(a, b)));
+        let total_count = filtered_tasks.len();
+
+        let page = TaskPage {
+            tasks: filtered_tasks
+                .into_iter()
+                .skip(page_params.offset)
+                .take(page_params.limit.unwrap_or(usize::MAX))
+                .collect(),
+            total_count,
+            offset: page_params.offset,
+            limit: page_params.limit,
+        }
+        .map(|task| TaskInfo {
+            id: task.id.to_string(),
+            name: task.title,
+            status: format!("{:?}", task.status),
+            created_at: task.created_at,
+            updated_at: task.updated_at,
+        });
 
-        info!("Found {} tasks", task_infos.len());
-        Ok(task_infos)
+        info!("Found {} tasks ({} total)", page.tasks.len(), total_count);
+        Ok(page)
     }
 
     pub(crate) async fn approve_task_internal(
@@ -685,6 +1166,13 @@ impl RpcServerImpl {
                         "timestamp": chrono::Utc::now().to_rfc3339(),
                     });
                     return Ok(state);
+                } else if let Some(record) = self
+                    .previous_agents()
+                    .await?
+                    .into_iter()
+                    .find(|r| r.id == agent_uuid)
+                {
+                    return Ok(previous_agent_state(&record));
                 } else {
                     return Err(ErrorObjectOwned::owned(
                         -32602,
@@ -711,6 +1199,13 @@ impl RpcServerImpl {
             ErrorObjectOwned::owned(-32603, format!("Failed to get tasks: {}", e), None::<()>)
         })?;
 
+        let previous: Vec<Value> = self
+            .previous_agents()
+            .await?
+            .iter()
+            .map(previous_agent_state)
+            .collect();
+
         Ok(serde_json::json!({
             "entity_type": "system",
             "agents": {
@@ -718,6 +1213,7 @@ impl RpcServerImpl {
                 "running": agents.iter().filter(|a| {
                     matches!(a.status, descartes_core::traits::AgentStatus::Running)
                 }).count(),
+                "previous": previous,
             },
             "tasks": {
                 "total": tasks.len(),
@@ -730,6 +1226,48 @@ impl RpcServerImpl {
         }))
     }
 
+    pub(crate) async fn system_health_internal(&self) -> Result<HealthResult, ErrorObjectOwned> {
+        let agents = self.agent_runner.list_agents().await.map_err(|e| {
+            error!("Failed to list agents: {}", e);
+            ErrorObjectOwned::owned(-32603, format!("Failed to list agents: {}", e), None::<()>)
+        })?;
+
+        let tasks = self.state_store.get_tasks().await.map_err(|e| {
+            error!("Failed to get tasks: {}", e);
+            ErrorObjectOwned::owned(-32603, format!("Failed to get tasks: {}", e), None::<()>)
+        })?;
+        let mut task_counts = HealthTaskCounts {
+            total: tasks.len(),
+            ..Default::default()
+        };
+        for task in &tasks {
+            match task.status {
+                TaskStatus::Todo => task_counts.todo += 1,
+                TaskStatus::InProgress => task_counts.in_progress += 1,
+                TaskStatus::Done => task_counts.done += 1,
+                TaskStatus::Blocked => task_counts.blocked += 1,
+            }
+        }
+
+        Ok(HealthResult {
+            status: "healthy".to_string(),
+            version: crate::VERSION.to_string(),
+            uptime_secs: self.started_at.elapsed().as_secs(),
+            agents: HealthAgentCounts {
+                total: agents.len(),
+                active: agents.iter().filter(|a| !a.status.is_terminal()).count(),
+                max_concurrent: self.max_concurrent_agents,
+            },
+            attach_sessions: self.attach_manager.active_session_count().await,
+            swank_sessions: self.swank_pool.leased_count(),
+            event_subscribers: self.event_bus.subscription_count().await,
+            memory_usage_mb: crate::metrics::process_memory_mb(),
+            tasks: task_counts,
+            config: self.config_summary.clone(),
+            timestamp: chrono::Utc::now().timestamp(),
+        })
+    }
+
     pub(crate) async fn pause_agent_internal(
         &self,
         agent_id: String,
@@ -835,6 +1373,78 @@ impl RpcServerImpl {
         })
     }
 
+    pub(crate) async fn send_input_internal(
+        &self,
+        agent_id: String,
+        text: String,
+    ) -> Result<SendInputResult, ErrorObjectOwned> {
+        info!("Sending input to agent: {}", agent_id);
+
+        let agent_uuid = Uuid::parse_str(&agent_id).map_err(|e| {
+            error!("Invalid agent ID format: {}", e);
+            ErrorObjectOwned::owned(
+                -32602,
+                format!("Invalid agent ID format: {}", e),
+                None::<()>,
+            )
+        })?;
+
+        let agent_info = self
+            .agent_runner
+            .get_agent(&agent_uuid)
+            .await
+            .map_err(|e| {
+                error!("Failed to get agent: {}", e);
+                ErrorObjectOwned::owned(-32603, format!("Failed to get agent: {}", e), None::<()>)
+            })?
+            .ok_or_else(|| {
+                error!("Agent not found: {}", agent_id);
+                ErrorObjectOwned::owned(
+                    -32002,
+                    format!("Agent not found: {}", agent_id),
+                    None::<()>,
+                )
+            })?;
+
+        // Paused agents take input through an attach session instead
+        if !matches!(
+            agent_info.status,
+            descartes_core::traits::AgentStatus::Running
+        ) {
+            return Err(DaemonError::InputError(format!(
+                "Agent is not running (status: {:?})",
+                agent_info.status
+            ))
+            .into());
+        }
+
+        let handle = self
+            .local_runner
+            .as_ref()
+            .and_then(|runner| runner.get_agent_handle(&agent_uuid))
+            .ok_or_else(|| {
+                DaemonError::InputError("Agent runner does not expose stdin".to_string())
+            })?;
+        let stdin_tx = handle.read().get_stdin_sender();
+
+        let mut data = text.into_bytes();
+        if data.last() != Some(&b'\n') {
+            data.push(b'\n');
+        }
+        let bytes_written = data.len();
+
+        stdin_tx.send(data).await.map_err(|e| {
+            error!("Failed to send input to agent {}: {}", agent_id, e);
+            DaemonError::InputError(format!("Failed to send input: {}", e))
+        })?;
+
+        Ok(SendInputResult {
+            agent_id,
+            bytes_written,
+            sent_at: chrono::Utc::now().timestamp(),
+        })
+    }
+
     pub(crate) async fn resume_agent_internal(
         &self,
         agent_id: String,
@@ -901,6 +1511,171 @@ impl RpcServerImpl {
         })
     }
 
+    pub(crate) async fn kill_agent_internal(
+        &self,
+        agent_id: String,
+        grace_secs: Option<u64>,
+    ) -> Result<KillResult, ErrorObjectOwned> {
+        let grace_secs = grace_secs.unwrap_or(DEFAULT_KILL_GRACE_SECS);
+        info!("Killing agent: {} (grace: {}s)", agent_id, grace_secs);
+
+        let agent_uuid = Uuid::parse_str(&agent_id).map_err(|e| {
+            error!("Invalid agent ID format: {}", e);
+            ErrorObjectOwned::owned(-32602, format!("Invalid agent ID format: {}", e), None::<()>)
+        })?;
+
+        let agent_info = self
+            .agent_runner
+            .get_agent(&agent_uuid)
+            .await
+            .map_err(|e| {
+                error!("Failed to get agent: {}", e);
+                ErrorObjectOwned::owned(-32603, format!("Failed to get agent: {}", e), None::<()>)
+            })?
+            .ok_or_else(|| {
+                error!("Agent not found: {}", agent_id);
+                ErrorObjectOwned::owned(-32002, format!("Agent not found: {}", agent_id), None::<()>)
+            })?;
+
+        if agent_info.status.is_terminal() {
+            return Err(ErrorObjectOwned::owned(
+                -32004,
+                format!("Agent has already exited (status: {:?})", agent_info.status),
+                None::<()>,
+            ));
+        }
+
+        // Hold on to the handle: the runner forgets the agent once it is killed,
+        // but the exit status is still needed for the result
+        let handle = self
+            .local_runner
+            .as_ref()
+            .and_then(|runner| runner.get_agent_handle(&agent_uuid));
+
+        self.event_bus
+            .publish(DescartesEvent::AgentEvent(AgentEvent {
+                id: Uuid::new_v4().to_string(),
+                agent_id: agent_id.clone(),
+                timestamp: chrono::Utc::now(),
+                event_type: AgentEventType::StatusChanged,
+                data: json!({ "status": "terminating", "grace_secs": grace_secs }),
+            }))
+            .await;
+
+        self.agent_runner
+            .signal(&agent_uuid, descartes_core::traits::AgentSignal::Terminate)
+            .await
+            .map_err(|e| {
+                error!("Failed to send SIGTERM to agent {}: {}", agent_id, e);
+                ErrorObjectOwned::owned(
+                    -32004,
+                    format!("Failed to terminate agent: {}", e),
+                    None::<()>,
+                )
+            })?;
+        // A SIGSTOPped process only sees the SIGTERM once it is continued
+        if agent_info.pause_mode == Some(descartes_core::traits::PauseMode::Forced) {
+            if let Err(e) = self
+                .agent_runner
+                .signal(&agent_uuid, descartes_core::traits::AgentSignal::Resume)
+                .await
+            {
+                warn!("Failed to continue paused agent {}: {}", agent_id, e);
+            }
+        }
+
+        // The runner reports SIGTERMed agents as terminated straight away, so
+        // a local handle's recorded exit status is the only sign the process
+        // is really gone
+        let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(grace_secs);
+        let mut final_status = None;
+        loop {
+            match &handle {
+                Some(handle) => {
+                    if handle.read().exit_status().is_some() {
+                        final_status = Some(handle.read().agent_info().status);
+                    }
+                }
+                None => match self.agent_runner.get_agent(&agent_uuid).await {
+                    Ok(Some(info)) if !info.status.is_terminal() => {}
+                    Ok(info) => {
+                        final_status = Some(info.map_or(AgentStatus::Terminated, |i| i.status));
+                    }
+                    Err(e) => warn!("Failed to poll agent {} during kill: {}", agent_id, e),
+                },
+            }
+            if final_status.is_some() || tokio::time::Instant::now() >= deadline {
+                break;
+            }
+            tokio::time::sleep(KILL_POLL_INTERVAL).await;
+        }
+
+        let escalated = final_status.is_none();
+        if escalated {
+            warn!(
+                "Agent {} did not exit within {}s, sending SIGKILL",
+                agent_id, grace_secs
+            );
+            self.agent_runner.kill(&agent_uuid).await.map_err(|e| {
+                error!("Failed to kill agent {}: {}", agent_id, e);
+                ErrorObjectOwned::owned(-32004, format!("Failed to kill agent: {}", e), None::<()>)
+            })?;
+        }
+
+        // Same cleanup as resume: the attach server only exists while paused
+        if let Some((_, server_handle)) = self.attach_servers.remove(&agent_uuid) {
+            server_handle.abort();
+            info!("Stopped attach server for agent {}", agent_uuid);
+
+            let socket_path = format!("/tmp/descartes-attach-{}.sock", agent_uuid);
+            let _ = std::fs::remove_file(&socket_path);
+
+            let terminated_count = self.attach_manager.terminate_sessions_for_agent(&agent_uuid).await;
+            if terminated_count > 0 {
+                info!("Terminated {} attach session(s) for agent {}", terminated_count, agent_uuid);
+            }
+        }
+        self.cleanup_swank_session(&agent_uuid).await;
+
+        let exit_status = handle
+            .and_then(|h| h.read().exit_status().cloned())
+            .unwrap_or(descartes_core::traits::ExitStatus {
+                code: None,
+                success: final_status == Some(AgentStatus::Completed),
+            });
+        let killed_at = chrono::Utc::now().timestamp();
+
+        self.event_bus
+            .publish(DescartesEvent::AgentEvent(AgentEvent {
+                id: Uuid::new_v4().to_string(),
+                agent_id: agent_id.clone(),
+                timestamp: chrono::Utc::now(),
+                event_type: AgentEventType::Killed,
+                data: json!({
+                    "exit_code": exit_status.code,
+                    "success": exit_status.success,
+                    "escalated": escalated,
+                }),
+            }))
+            .await;
+
+        self.save_agent_status(&agent_uuid, AgentStatus::Terminated)
+            .await;
+
+        info!(
+            "Agent {} exited (code: {:?}, escalated: {})",
+            agent_id, exit_status.code, escalated
+        );
+
+        Ok(KillResult {
+            agent_id,
+            exit_code: exit_status.code,
+            success: exit_status.success,
+            escalated,
+            killed_at,
+        })
+    }
+
     pub(crate) async fn attach_request_internal(
         &self,
         agent_id: String,
@@ -1083,6 +1858,13 @@ impl DescartesRpcServer for RpcServerImpl {
         self.list_tasks_internal(filter).await
     }
 
+    async fn list_tasks_page(
+        &self,
+        filter: Option<Value>,
+    ) -> Result<TaskPage<TaskInfo>, ErrorObjectOwned> {
+        self.list_tasks_page_internal(filter).await
+    }
+
     async fn approve(
         &self,
         task_id: String,
@@ -1095,6 +1877,19 @@ impl DescartesRpcServer for RpcServerImpl {
         self.get_state_internal(entity_id).await
     }
 
+    async fn events_between(
+        &self,
+        params: EventsBetweenRequest,
+    ) -> Result<EventsBetweenResponse, ErrorObjectOwned> {
+        crate::handlers::query_events_between(&self.event_bus, params)
+            .await
+            .map_err(ErrorObjectOwned::from)
+    }
+
+    async fn system_health(&self) -> Result<HealthResult, ErrorObjectOwned> {
+        self.system_health_internal().await
+    }
+
     async fn pause_agent(
         &self,
         agent_id: String,
@@ -1107,6 +1902,22 @@ impl DescartesRpcServer for RpcServerImpl {
         self.resume_agent_internal(agent_id).await
     }
 
+    async fn send_input(
+        &self,
+        agent_id: String,
+        text: String,
+    ) -> Result<SendInputResult, ErrorObjectOwned> {
+        self.send_input_internal(agent_id, text).await
+    }
+
+    async fn kill_agent(
+        &self,
+        agent_id: String,
+        grace_secs: Option<u64>,
+    ) -> Result<KillResult, ErrorObjectOwned> {
+        self.kill_agent_internal(agent_id, grace_secs).await
+    }
+
     async fn attach_request(
         &self,
         agent_id: String,
@@ -1128,6 +1939,10 @@ impl DescartesRpcServer for RpcServerImpl {
 pub struct UnixSocketRpcServer {
     socket_path: PathBuf,
     server_impl: Arc<RpcServerImpl>,
+    /// Respawn restartable agents from the state store on start
+    restore_agents: bool,
+    /// Where published events are persisted, if enabled
+    event_history: EventHistoryConfig,
 }
 
 /// Handle returned by the Unix socket RPC server.
@@ -1172,9 +1987,34 @@ impl UnixSocketRpcServer {
         Self {
             socket_path,
             server_impl: Arc::new(RpcServerImpl::new(agent_runner, state_store)),
+            restore_agents: false,
+            event_history: EventHistoryConfig::default(),
+        }
+    }
+
+    /// Create a new Unix socket RPC server with limits from the daemon configuration.
+    pub fn with_config(
+        socket_path: PathBuf,
+        agent_runner: Arc<dyn descartes_core::traits::AgentRunner>,
+        state_store: Arc<dyn descartes_core::traits::StateStore>,
+        config: &DaemonConfig,
+    ) -> Self {
+        Self {
+            socket_path,
+            server_impl: Arc::new(
+                RpcServerImpl::new(agent_runner, state_store).with_daemon_config(config),
+            ),
+            restore_agents: config.server.restore_agents,
+            event_history: config.events.clone(),
         }
     }
 
+    /// Respawn restartable agents saved by a previous run when the server starts
+    pub fn with_restore_agents(mut self, restore_agents: bool) -> Self {
+        self.restore_agents = restore_agents;
+        self
+    }
+
     /// Start listening for JSON-RPC requests over a Unix domain socket.
     pub async fn start(&self) -> DaemonResult<UnixServerHandle> {
         if self.socket_path.exists() {
@@ -1198,12 +2038,34 @@ impl UnixSocketRpcServer {
 
         info!("RPC server listening on {:?}", self.socket_path);
 
+        let event_bus = &self.server_impl.event_bus;
+        if self.event_history.enabled && event_bus.store().is_none() {
+            let store = EventStore::open(
+                &self.event_history.path,
+                EventRetention::from(&self.event_history),
+            )
+            .await?;
+            event_bus.attach_store(Arc::new(store))?;
+            info!("Persisting events to {:?}", self.event_history.path);
+        }
+
+        if self.restore_agents {
+            let restored = self.server_impl.restore_agents().await?;
+            info!("Restored {} agent(s) from a previous run", restored.len());
+        }
+
         let (shutdown_tx, shutdown_rx) = oneshot::channel();
         let server_impl = Arc::clone(&self.server_impl);
         let socket_path = self.socket_path.clone();
+        let attach_reaper = self.server_impl.spawn_attach_reaper();
+        let swank_reaper = self.server_impl.swank_pool.spawn_idle_reaper();
 
         tokio::spawn(async move {
+            let swank_pool = Arc::clone(&server_impl.swank_pool);
             Self::run_listener(listener, server_impl, socket_path, shutdown_rx).await;
+            attach_reaper.abort();
+            swank_reaper.abort();
+            swank_pool.shutdown();
         });
 
         Ok(UnixServerHandle {
@@ -1377,6 +2239,20 @@ impl UnixSocketRpcServer {
                 },
                 Err(response) => response,
             },
+            "list_tasks_page" | "task.list_page" => match Self::parse_list_params(&request) {
+                Ok(filter) => match server_impl.list_tasks_page_internal(filter).await {
+                    Ok(page) => match serde_json::to_value(page) {
+                        Ok(value) => RpcResponse::success(value, request.id.clone()),
+                        Err(e) => RpcResponse::error(
+                            -32603,
+                            format!("Serialization error: {}", e),
+                            request.id.clone(),
+                        ),
+                    },
+                    Err(err) => Self::convert_error(err, request.id.clone()),
+                },
+                Err(response) => response,
+            },
             "approve" | "task.approve" => match Self::parse_approve_params(&request) {
                 Ok((task_id, approved)) => {
                     match server_impl.approve_task_internal(task_id, approved).await {
@@ -1400,6 +2276,17 @@ impl UnixSocketRpcServer {
                 },
                 Err(response) => response,
             },
+            "system.health" => match server_impl.system_health_internal().await {
+                Ok(result) => match serde_json::to_value(result) {
+                    Ok(value) => RpcResponse::success(value, request.id.clone()),
+                    Err(e) => RpcResponse::error(
+                        -32603,
+                        format!("Serialization error: {}", e),
+                        request.id.clone(),
+                    ),
+                },
+                Err(err) => Self::convert_error(err, request.id.clone()),
+            },
             "agent.pause" => match Self::parse_pause_params(&request) {
                 Ok((agent_id, force)) => {
                     match server_impl.pause_agent_internal(agent_id, force).await {
@@ -1430,6 +2317,38 @@ impl UnixSocketRpcServer {
                 },
                 Err(response) => response,
             },
+            "agent.send_input" => match Self::parse_send_input_params(&request) {
+                Ok((agent_id, text)) => {
+                    match server_impl.send_input_internal(agent_id, text).await {
+                        Ok(result) => match serde_json::to_value(result) {
+                            Ok(value) => RpcResponse::success(value, request.id.clone()),
+                            Err(e) => RpcResponse::error(
+                                -32603,
+                                format!("Serialization error: {}", e),
+                                request.id.clone(),
+                            ),
+                        },
+                        Err(err) => Self::convert_error(err, request.id.clone()),
+                    }
+                }
+                Err(response) => response,
+            },
+            "agent.kill" => match Self::parse_kill_params(&request) {
+                Ok((agent_id, grace_secs)) => {
+                    match server_impl.kill_agent_internal(agent_id, grace_secs).await {
+                        Ok(result) => match serde_json::to_value(result) {
+                            Ok(value) => RpcResponse::success(value, request.id.clone()),
+                            Err(e) => RpcResponse::error(
+                                -32603,
+                                format!("Serialization error: {}", e),
+                                request.id.clone(),
+                            ),
+                        },
+                        Err(err) => Self::convert_error(err, request.id.clone()),
+                    }
+                }
+                Err(response) => response,
+            },
             "agent.attach.request" => match Self::parse_attach_request_params(&request) {
                 Ok((agent_id, client_type)) => {
                     match server_impl.attach_request_internal(agent_id, client_type).await {
@@ -1628,6 +2547,65 @@ impl UnixSocketRpcServer {
         Ok(agent_id)
     }
 
+    #[allow(clippy::result_large_err)]
+    fn parse_send_input_params(request: &RpcRequest) -> Result<(String, String), RpcResponse> {
+        let params = match &request.params {
+            Some(Value::Array(arr)) => arr,
+            _ => {
+                return Err(Self::invalid_params(
+                    request.id.clone(),
+                    "Expected positional parameters [agent_id, text]",
+                ))
+            }
+        };
+
+        let agent_id = params
+            .first()
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Self::invalid_params(request.id.clone(), "Missing agent_id parameter"))?
+            .to_string();
+
+        let text = params
+            .get(1)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Self::invalid_params(request.id.clone(), "Missing text parameter"))?
+            .to_string();
+
+        Ok((agent_id, text))
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn parse_kill_params(request: &RpcRequest) -> Result<(String, Option<u64>), RpcResponse> {
+        let params = match &request.params {
+            Some(Value::Array(arr)) => arr,
+            _ => {
+                return Err(Self::invalid_params(
+                    request.id.clone(),
+                    "Expected positional parameters [agent_id, grace_secs]",
+                ))
+            }
+        };
+
+        let agent_id = params
+            .first()
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Self::invalid_params(request.id.clone(), "Missing agent_id parameter"))?
+            .to_string();
+
+        // grace_secs falls back to DEFAULT_KILL_GRACE_SECS when omitted
+        let grace_secs = match params.get(1) {
+            None | Some(Value::Null) => None,
+            Some(v) => Some(v.as_u64().ok_or_else(|| {
+                Self::invalid_params(
+                    request.id.clone(),
+                    "grace_secs must be a non-negative integer",
+                )
+            })?),
+        };
+
+        Ok((agent_id, grace_secs))
+    }
+
     #[allow(clippy::result_large_err)]
     fn parse_attach_request_params(request: &RpcRequest) -> Result<(String, String), RpcResponse> {
         let params = match &request.params {
@@ -1734,6 +2712,28 @@ impl UnixSocketRpcServer {
     }
 }
 
+/// Update the persisted status of an agent, if it has a saved record
+async fn save_agent_status(
+    state_store: &dyn descartes_core::traits::StateStore,
+    agent_id: &Uuid,
+    status: AgentStatus,
+) {
+    let records = match state_store.get_agent_records().await {
+        Ok(records) => records,
+        Err(e) => {
+            warn!("Failed to load agent records: {}", e);
+            return;
+        }
+    };
+    if let Some(mut record) = records.into_iter().find(|r| r.id == *agent_id) {
+        record.status = status;
+        record.updated_at = chrono::Utc::now().timestamp();
+        if let Err(e) = state_store.save_agent_record(&record).await {
+            warn!("Failed to persist status of agent {}: {}", agent_id, e);
+        }
+    }
+}
+
 impl Clone for RpcServerImpl {
     fn clone(&self) -> Self {
         Self {
@@ -1744,8 +2744,13 @@ impl Clone for RpcServerImpl {
             attach_manager: Arc::clone(&self.attach_manager),
             event_bus: Arc::clone(&self.event_bus),
             attach_servers: Arc::clone(&self.attach_servers),
-            sbcl_processes: Arc::clone(&self.sbcl_processes),
+            swank_pool: Arc::clone(&self.swank_pool),
             swank_event_tasks: Arc::clone(&self.swank_event_tasks),
+            spawn_limiter: Arc::clone(&self.spawn_limiter),
+            max_concurrent_agents: self.max_concurrent_agents,
+            spawn_permits: Arc::clone(&self.spawn_permits),
+            started_at: self.started_at,
+            config_summary: self.config_summary.clone(),
         }
     }
 }
@@ -1775,6 +2780,71 @@ mod tests {
         (agent_runner, state_store, temp_db)
     }
 
+    #[tokio::test]
+    async fn test_spawn_rejected_at_capacity() {
+        let (agent_runner, state_store, _temp_db) = create_test_dependencies().await;
+        let event_bus = Arc::new(EventBus::new());
+        let attach_manager = Arc::new(crate::attach_session::AttachSessionManager::new(
+            Arc::new(descartes_core::AttachTokenStore::new()),
+            Arc::clone(&event_bus),
+            crate::attach_session::AttachSessionConfig::default(),
+        ));
+        let server_impl = RpcServerImpl::with_attach_manager(
+            Arc::clone(&agent_runner),
+            state_store,
+            attach_manager,
+            Arc::clone(&event_bus),
+        )
+        .with_max_concurrent_agents(2);
+        let (_sub_id, mut events) = event_bus.subscribe(None).await;
+
+        // "sleep-cli" runs `sleep <task>`, which keeps the agent alive
+        let config = json!({ "task": "30" });
+        let first = server_impl
+            .spawn_agent_internal("a1".to_string(), "sleep-cli".to_string(), config.clone())
+            .await
+            .unwrap();
+        let second = server_impl
+            .spawn_agent_internal("a2".to_string(), "sleep-cli".to_string(), config.clone())
+            .await
+            .unwrap();
+
+        let err = server_impl
+            .spawn_agent_internal("a3".to_string(), "sleep-cli".to_string(), config.clone())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), -32030);
+
+        let mut saw_at_capacity = false;
+        while let Ok(event) = events.try_recv() {
+            if let DescartesEvent::SystemEvent(e) = event {
+                saw_at_capacity |= e.event_type == crate::events::SystemEventType::AtCapacity;
+            }
+        }
+        assert!(saw_at_capacity);
+
+        // Killing an agent frees its slot without waiting for the next spawn
+        agent_runner
+            .kill(&Uuid::parse_str(&first).unwrap())
+            .await
+            .unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while server_impl.spawn_permits.len() > 1 {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("permit released on kill");
+        let third = server_impl
+            .spawn_agent_internal("a3".to_string(), "sleep-cli".to_string(), config)
+            .await
+            .unwrap();
+
+        for id in [second, third] {
+            let _ = agent_runner.kill(&Uuid::parse_str(&id).unwrap()).await;
+        }
+    }
+
     #[tokio::test]
     async fn test_server_creation() {
         let dir = tempdir().unwrap();
@@ -1890,6 +2960,286 @@ mod tests {
         assert_eq!(tasks[0].name, "Test Task 2");
     }
 
+    #[tokio::test]
+    async fn test_send_input_reaches_agent_stdin() {
+        use descartes_core::traits::AgentRunner;
+
+        let (_, state_store, _temp_db) = create_test_dependencies().await;
+        let runner = Arc::new(LocalProcessRunner::new());
+        let server_impl = RpcServerImpl::with_local_runner(Arc::clone(&runner), state_store);
+
+        // Generic "<cmd>-cli" backend: runs `cat -`, which echoes stdin
+        let handle = runner
+            .spawn(AgentConfig {
+                name: "echo".to_string(),
+                model_backend: "cat-cli".to_string(),
+                task: "-".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let agent_id = handle.id();
+        let mut stdout = runner
+            .get_agent_handle(&agent_id)
+            .unwrap()
+            .read()
+            .subscribe_stdout();
+
+        let result = server_impl
+            .send_input(agent_id.to_string(), "yes".to_string())
+            .await
+            .unwrap();
+        assert_eq!(result.bytes_written, 4);
+
+        let echoed = tokio::time::timeout(std::time::Duration::from_secs(5), stdout.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&echoed).trim_end(), "yes");
+
+        let missing = server_impl
+            .send_input(Uuid::new_v4().to_string(), "yes".to_string())
+            .await;
+        assert!(missing.is_err());
+
+        runner.pause(&agent_id, true).await.unwrap();
+        let paused = server_impl
+            .send_input(agent_id.to_string(), "yes".to_string())
+            .await
+            .unwrap_err();
+        assert_eq!(paused.code(), -32016);
+        assert!(paused
+            .message()
+            .starts_with("Input error: Agent is not running"));
+
+        runner.kill(&agent_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_kill_agent_escalates_after_grace_period() {
+        use descartes_core::traits::AgentRunner;
+
+        let (_, state_store, temp_db) = create_test_dependencies().await;
+        let runner = Arc::new(LocalProcessRunner::new());
+        let server_impl = RpcServerImpl::with_local_runner(Arc::clone(&runner), state_store);
+        let (_sub_id, mut events) = server_impl.event_bus.subscribe(None).await;
+
+        // `cat -` exits on SIGTERM
+        let cat = runner
+            .spawn(AgentConfig {
+                name: "cat".to_string(),
+                model_backend: "cat-cli".to_string(),
+                task: "-".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let result = server_impl
+            .kill_agent(cat.id().to_string(), Some(5))
+            .await
+            .unwrap();
+        assert!(!result.escalated);
+        assert!(!result.success);
+
+        // A script that ignores SIGTERM needs SIGKILL
+        let script = temp_db.path().join("stubborn.sh");
+        std::fs::write(
+            &script,
+            "trap '' TERM\necho ready\nwhile :; do sleep 1; done\n",
+        )
+        .unwrap();
+        let stubborn = runner
+            .spawn(AgentConfig {
+                name: "stubborn".to_string(),
+                model_backend: "sh-cli".to_string(),
+                task: script.to_string_lossy().to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let mut stdout = runner
+            .get_agent_handle(&stubborn.id())
+            .unwrap()
+            .read()
+            .subscribe_stdout();
+        // Only signal once the trap is installed
+        tokio::time::timeout(std::time::Duration::from_secs(5), stdout.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        let result = server_impl
+            .kill_agent(stubborn.id().to_string(), Some(1))
+            .await
+            .unwrap();
+        assert!(result.escalated);
+        assert_eq!(result.exit_code, None);
+        assert!(runner.get_agent(&stubborn.id()).await.unwrap().is_none());
+
+        let mut kinds = Vec::new();
+        while let Ok(DescartesEvent::AgentEvent(e)) = events.try_recv() {
+            kinds.push((e.agent_id, e.event_type));
+        }
+        let stubborn_id = stubborn.id().to_string();
+        assert!(kinds.contains(&(stubborn_id.clone(), AgentEventType::StatusChanged)));
+        assert!(kinds.contains(&(stubborn_id, AgentEventType::Killed)));
+
+        // Killing an agent that already exited is an error
+        assert!(server_impl
+            .kill_agent(cat.id().to_string(), None)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_restart_reports_and_restores_saved_agents() {
+        use descartes_core::traits::AgentRunner;
+
+        let (_, state_store, _temp_db) = create_test_dependencies().await;
+        let first_runner = Arc::new(LocalProcessRunner::new());
+        let first =
+            RpcServerImpl::with_local_runner(Arc::clone(&first_runner), Arc::clone(&state_store));
+
+        let restartable = first
+            .spawn_agent_internal(
+                "worker".to_string(),
+                "sleep-cli".to_string(),
+                json!({ "task": "30", "restartable": true }),
+            )
+            .await
+            .unwrap();
+        let one_shot = first
+            .spawn_agent_internal(
+                "one-shot".to_string(),
+                "sleep-cli".to_string(),
+                json!({ "task": "30" }),
+            )
+            .await
+            .unwrap();
+
+        // A new server over the same store stands in for a restarted daemon
+        let second_runner = Arc::new(LocalProcessRunner::new());
+        let second = RpcServerImpl::with_local_runner(Arc::clone(&second_runner), state_store);
+
+        let state = second.get_state_internal(None).await.unwrap();
+        let previous = state["agents"]["previous"].as_array().unwrap();
+        assert_eq!(previous.len(), 2);
+        assert!(previous.iter().all(|a| a["status"] == "unknown"));
+
+        let restored = second.restore_agents().await.unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].0, restartable);
+        let new_id = Uuid::parse_str(&restored[0].1).unwrap();
+        let info = second_runner.get_agent(&new_id).await.unwrap().unwrap();
+        assert_eq!(info.name, "worker");
+        assert_eq!(info.task, "30");
+
+        let old = second
+            .get_state_internal(Some(restartable.clone()))
+            .await
+            .unwrap();
+        assert_eq!(old["status"], "exited");
+        let lost = second
+            .get_state_internal(Some(one_shot.clone()))
+            .await
+            .unwrap();
+        assert_eq!(lost["status"], "unknown");
+
+        for id in [&restartable, &one_shot] {
+            first_runner
+                .kill(&Uuid::parse_str(id).unwrap())
+                .await
+                .unwrap();
+        }
+        second_runner.kill(&new_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_agent_that_exits_on_its_own_is_not_restored() {
+        let (_, state_store, _temp_db) = create_test_dependencies().await;
+        let first_runner = Arc::new(LocalProcessRunner::new());
+        let first = RpcServerImpl::with_local_runner(first_runner, Arc::clone(&state_store));
+
+        // `sleep 0` exits successfully right away
+        let finished = first
+            .spawn_agent_internal(
+                "finished".to_string(),
+                "sleep-cli".to_string(),
+                json!({ "task": "0", "restartable": true }),
+            )
+            .await
+            .unwrap();
+        let finished = Uuid::parse_str(&finished).unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            loop {
+                let records = state_store.get_agent_records().await.unwrap();
+                if records
+                    .iter()
+                    .any(|r| r.id == finished && r.status.is_terminal())
+                {
+                    break;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("exit status persisted");
+        let records = state_store.get_agent_records().await.unwrap();
+        assert_eq!(records[0].status, AgentStatus::Completed);
+
+        let second =
+            RpcServerImpl::with_local_runner(Arc::new(LocalProcessRunner::new()), state_store);
+        assert!(second.restore_agents().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_list_tasks_page() {
+        use descartes_core::traits::{TaskComplexity, TaskPriority};
+
+        let (agent_runner, state_store, _temp_db) = create_test_dependencies().await;
+
+        for i in 0..5 {
+            let task = Task {
+                id: Uuid::new_v4(),
+                title: format!("Task {}", i),
+                description: None,
+                status: TaskStatus::Todo,
+                priority: TaskPriority::Medium,
+                complexity: TaskComplexity::Simple,
+                assigned_to: None,
+                dependencies: vec![],
+                created_at: 1000 + i,
+                updated_at: 1000 + i,
+                metadata: None,
+            };
+            state_store.save_task(&task).await.unwrap();
+        }
+
+        let server_impl = RpcServerImpl::new(agent_runner, state_store);
+
+        let filter = serde_json::json!({
+            "sort_by": "created_at",
+            "order": "asc",
+            "limit": 2,
+            "offset": 2
+        });
+        let page = server_impl.list_tasks_page(Some(filter)).await.unwrap();
+        assert_eq!(page.total_count, 5);
+        assert_eq!(page.offset, 2);
+        assert_eq!(page.limit, Some(2));
+        let names: Vec<_> = page.tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["Task 2", "Task 3"]);
+
+        // Without pagination every task comes back, newest first
+        let tasks = server_impl.list_tasks(None).await.unwrap();
+        assert_eq!(tasks.len(), 5);
+        assert_eq!(tasks[0].name, "Task 4");
+
+        let filter = serde_json::json!({ "sort_by": "nonsense" });
+        assert!(server_impl.list_tasks_page(Some(filter)).await.is_err());
+        let filter = serde_json::json!({ "limit": -1 });
+        assert!(server_impl.list_tasks_page(Some(filter)).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_approve_task() {
         use descartes_core::traits::{TaskComplexity, TaskPriority};
@@ -2010,6 +3360,111 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_system_health_reports_daemon_stats() {
+        let (agent_runner, state_store, _temp_db) = create_test_dependencies().await;
+        let task = Task {
+            id: Uuid::new_v4(),
+            title: "Queued".to_string(),
+            description: None,
+            status: TaskStatus::Todo,
+            priority: descartes_core::traits::TaskPriority::Medium,
+            complexity: descartes_core::traits::TaskComplexity::Simple,
+            assigned_to: None,
+            dependencies: vec![],
+            created_at: chrono::Utc::now().timestamp(),
+            updated_at: chrono::Utc::now().timestamp(),
+            metadata: None,
+        };
+        state_store.save_task(&task).await.unwrap();
+
+        let mut config = DaemonConfig::default();
+        config.auth.enabled = true;
+        config.server.max_concurrent_agents = 4;
+        let server_impl = RpcServerImpl::new(agent_runner, state_store).with_daemon_config(&config);
+        let (_sub_id, _events) = server_impl.event_bus.subscribe(None).await;
+
+        let health = server_impl.system_health().await.unwrap();
+        assert_eq!(health.status, "healthy");
+        assert_eq!(health.version, crate::VERSION);
+        assert_eq!(health.agents.active, 0);
+        assert_eq!(health.agents.max_concurrent, 4);
+        assert_eq!(health.attach_sessions, 0);
+        assert_eq!(health.swank_sessions, 0);
+        assert_eq!(health.event_subscribers, 1);
+        assert_eq!(health.tasks.total, 1);
+        assert_eq!(health.tasks.todo, 1);
+        assert!(health.config.auth_enabled);
+        assert_eq!(health.config.http_port, config.server.http_port);
+
+        let value = serde_json::to_value(&health).unwrap();
+        assert!(value["memory_usage_mb"].is_number());
+        assert!(value["config"]["ws_port"].is_number());
+    }
+
+    #[tokio::test]
+    async fn test_swank_startup_failure_reports_cause() {
+        let (agent_runner, state_store, _temp_db) = create_test_dependencies().await;
+        let mut config = DaemonConfig::default();
+        config.swank.sbcl_path = "/nonexistent/sbcl".to_string();
+        let server_impl = RpcServerImpl::new(agent_runner, state_store).with_daemon_config(&config);
+
+        let err = server_impl
+            .initialize_swank_session(Uuid::new_v4())
+            .await
+            .unwrap_err();
+        assert!(
+            err.contains("SBCL binary not found: /nonexistent/sbcl"),
+            "{}",
+            err
+        );
+        assert_eq!(server_impl.swank_pool.leased_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_event_history_attached_from_config() {
+        let (agent_runner, state_store, temp_db) = create_test_dependencies().await;
+        let mut config = DaemonConfig::default();
+        config.events.enabled = true;
+        config.events.path = temp_db.path().join("events.db");
+        let server = UnixSocketRpcServer::with_config(
+            temp_db.path().join("rpc.sock"),
+            agent_runner,
+            state_store,
+            &config,
+        );
+        let _handle = server.start().await.unwrap();
+
+        let event_bus = &server.server_impl.event_bus;
+        let since = chrono::Utc::now() - chrono::Duration::seconds(1);
+        event_bus
+            .publish(SystemEvent::at_capacity(1, "agent"))
+            .await;
+        let events = event_bus
+            .events_between(
+                since,
+                chrono::Utc::now(),
+                &crate::events::EventFilter::all(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(config.events.path.exists());
+
+        // The same window over events.between
+        let response = DescartesRpcServer::events_between(
+            server.server_impl.as_ref(),
+            EventsBetweenRequest {
+                from: since.timestamp(),
+                to: chrono::Utc::now().timestamp() + 1,
+                filter: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.count, 1);
+    }
+
     #[test]
     fn test_lisp_agent_detection() {
         use descartes_core::traits::AgentConfig;
@@ -7,15 +7,16 @@
 //! - File-system watching using notify crate
 //! - Detects changes by comparing with cached state
 //! - Emits TaskEvent to EventBus for WebSocket subscribers
+//! - Emits Unblocked/Blocked events when the set of ready tasks changes
 //! - Debouncing to handle rapid file saves
 //! - Thread-safe with Arc/RwLock
 
 use crate::events::{DescartesEvent, EventBus, TaskEvent, TaskEventType};
 use chrono::Utc;
-use descartes_core::{ScgTaskStorage, Task};
+use descartes_core::{ScgTaskStorage, Task, TaskStatus};
 use notify::{Config, Event as NotifyEvent, RecommendedWatcher, RecursiveMode, Watcher};
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{mpsc, RwLock};
@@ -24,7 +25,8 @@ use uuid::Uuid;
 /// Configuration for SCG task event emitter
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ScgTaskEventEmitterConfig {
-    /// Debounce interval in milliseconds
+    /// Quiet period in milliseconds; a burst of file writes is processed
+    /// once, after no write has been seen for this long
     pub debounce_interval_ms: u64,
 
     /// Include full task data in events
@@ -195,20 +197,12 @@ impl ScgTaskEventEmitter {
         let debounce_ms = self.config.debounce_interval_ms;
 
         tokio::spawn(async move {
-            let mut last_process = std::time::Instant::now();
-
             loop {
                 tokio::select! {
                     Some(()) = rx.recv() => {
-                        // Debounce: wait for debounce interval before processing
-                        let now = std::time::Instant::now();
-                        if now.duration_since(last_process) < Duration::from_millis(debounce_ms) {
-                            continue;
-                        }
-                        last_process = now;
-
-                        // Small delay to let file writes complete
-                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        // Debounce: coalesce the rest of the burst so the final
+                        // write is always processed, and processed once
+                        wait_for_quiet(&mut rx, Duration::from_millis(debounce_ms)).await;
 
                         // Process file changes
                         if let Err(e) = process_file_changes(
@@ -268,6 +262,11 @@ impl ScgTaskEventEmitter {
     }
 }
 
+/// Drain change notifications until none has arrived for `quiet`
+async fn wait_for_quiet(rx: &mut mpsc::Receiver<()>, quiet: Duration) {
+    while let Ok(Some(())) = tokio::time::timeout(quiet, rx.recv()).await {}
+}
+
 /// Process file changes and emit events
 async fn process_file_changes(
     storage: &Arc<ScgTaskStorage>,
@@ -351,6 +350,8 @@ async fn process_file_changes(
         }
     }
 
+    events.extend(readiness_events(&previous_map, &current_map, timestamp));
+
     // Update cache
     *previous_map = current_map;
 
@@ -362,6 +363,76 @@ async fn process_file_changes(
     Ok(())
 }
 
+/// IDs of tasks that are Todo with every dependency Done
+fn ready_task_ids(tasks: &HashMap<String, Task>) -> HashSet<String> {
+    tasks
+        .iter()
+        .filter(|(_, task)| task.status == TaskStatus::Todo)
+        .filter(|(_, task)| {
+            task.dependencies.iter().all(|dep| {
+                tasks
+                    .get(&dep.to_string())
+                    .is_some_and(|d| d.status == TaskStatus::Done)
+            })
+        })
+        .map(|(id, _)| id.clone())
+        .collect()
+}
+
+/// Unblocked/Blocked events for tasks whose readiness changed between two
+/// snapshots
+///
+/// Only tasks present in both snapshots are considered: new tasks are
+/// announced by their Created event, and a task that left the ready set by
+/// being started or deleted is not blocked. Each event carries the full list
+/// of tasks that changed the same way, so a consumer can act on the first.
+fn readiness_events(
+    previous: &HashMap<String, Task>,
+    current: &HashMap<String, Task>,
+    timestamp: i64,
+) -> Vec<DescartesEvent> {
+    let ready_before = ready_task_ids(previous);
+    let ready_after = ready_task_ids(current);
+
+    let mut unblocked: Vec<String> = ready_after
+        .difference(&ready_before)
+        .filter(|id| previous.contains_key(*id))
+        .cloned()
+        .collect();
+    let mut blocked: Vec<String> = ready_before
+        .difference(&ready_after)
+        .filter(|id| {
+            current
+                .get(*id)
+                .is_some_and(|t| t.status == TaskStatus::Todo)
+        })
+        .cloned()
+        .collect();
+    unblocked.sort();
+    blocked.sort();
+
+    let mut events = Vec::new();
+    for (event_type, key, ids) in [
+        (TaskEventType::Unblocked, "ready_task_ids", &unblocked),
+        (TaskEventType::Blocked, "blocked_task_ids", &blocked),
+    ] {
+        for id in ids {
+            events.push(DescartesEvent::TaskEvent(TaskEvent {
+                id: Uuid::new_v4().to_string(),
+                task_id: id.clone(),
+                agent_id: None,
+                timestamp: Utc::now(),
+                event_type: event_type.clone(),
+                data: json!({
+                    key: ids,
+                    "timestamp": timestamp,
+                }),
+            }));
+        }
+    }
+    events
+}
+
 /// Create a TaskEvent wrapped in DescartesEvent
 fn create_task_event(
     task_id: String,
@@ -391,7 +462,6 @@ fn create_task_event(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use descartes_core::TaskStatus;
     use tempfile::TempDir;
 
     async fn setup_test_emitter() -> (ScgTaskEventEmitter, Arc<EventBus>, TempDir) {
@@ -435,4 +505,93 @@ mod tests {
         let result = emitter.initialize_cache().await;
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_readiness_events_report_unblocked_and_blocked_tasks() {
+        let task = |title: &str, status: TaskStatus, dependencies: Vec<Uuid>| Task {
+            id: Uuid::new_v4(),
+            title: title.to_string(),
+            description: None,
+            status,
+            priority: Default::default(),
+            complexity: Default::default(),
+            assigned_to: None,
+            dependencies,
+            created_at: 0,
+            updated_at: 0,
+            metadata: None,
+        };
+        let snapshot = |tasks: &[&Task]| -> HashMap<String, Task> {
+            tasks
+                .iter()
+                .map(|t| (t.id.to_string(), (*t).clone()))
+                .collect()
+        };
+
+        let schema = task("schema", TaskStatus::InProgress, vec![]);
+        let api = task("api", TaskStatus::Todo, vec![schema.id]);
+        let ui = task("ui", TaskStatus::Todo, vec![schema.id]);
+        let docs = task("docs", TaskStatus::Todo, vec![]);
+        let before = snapshot(&[&schema, &api, &ui, &docs]);
+
+        let done = Task {
+            status: TaskStatus::Done,
+            ..schema.clone()
+        };
+        let after = snapshot(&[&done, &api, &ui, &docs]);
+
+        let events = readiness_events(&before, &after, 0);
+        let mut expected = vec![api.id.to_string(), ui.id.to_string()];
+        expected.sort();
+        assert_eq!(events.len(), 2);
+        for event in &events {
+            match event {
+                DescartesEvent::TaskEvent(e) => {
+                    assert_eq!(e.event_type, TaskEventType::Unblocked);
+                    assert!(expected.contains(&e.task_id));
+                    assert_eq!(e.data["ready_task_ids"], json!(expected));
+                }
+                other => panic!("Unexpected event: {:?}", other),
+            }
+        }
+
+        // Reopening the dependency blocks its dependents again
+        let events = readiness_events(&after, &before, 0);
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|e| matches!(
+            e,
+            DescartesEvent::TaskEvent(e) if e.event_type == TaskEventType::Blocked
+        )));
+
+        // Starting a ready task is not a dependency change
+        let started = Task {
+            status: TaskStatus::InProgress,
+            ..docs.clone()
+        };
+        let events = readiness_events(&after, &snapshot(&[&done, &api, &ui, &started]), 0);
+        assert!(events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_quiet_coalesces_bursts() {
+        let (tx, mut rx) = mpsc::channel::<()>(10);
+
+        let writer = tokio::spawn(async move {
+            for _ in 0..5 {
+                tx.send(()).await.unwrap();
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+            tx
+        });
+
+        rx.recv().await.unwrap();
+        wait_for_quiet(&mut rx, Duration::from_millis(50)).await;
+
+        // Every notification in the burst was absorbed by the single wait
+        let _tx = writer.await.unwrap();
+        assert!(matches!(
+            rx.try_recv(),
+            Err(mpsc::error::TryRecvError::Empty)
+        ));
+    }
 }
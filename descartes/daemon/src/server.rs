@@ -2,6 +2,8 @@
 use crate::auth::AuthManager;
 use crate::config::DaemonConfig;
 use crate::errors::{DaemonError, DaemonResult};
+use crate::event_store::{EventRetention, EventStore};
+use crate::events::EventBus;
 use crate::handlers::RpcHandlers;
 use crate::metrics::MetricsCollector;
 use crate::pool::ConnectionPool;
@@ -9,18 +11,20 @@ use crate::rpc::JsonRpcServer;
 use crate::types::*;
 use crate::chat_manager::ChatManager;
 use crate::zmq_publisher::ZmqPublisher;
+use descartes_core::traits::StateStore;
+use descartes_core::SqliteStateStore;
 use hyper::header::AUTHORIZATION;
 use hyper::service::{make_service_fn, service_fn};
 use hyper::{Body, Request, Response, Server, StatusCode};
 use serde_json::json;
 use std::sync::Arc;
+use tokio::sync::RwLock;
 use tracing::{error, info};
 
 /// RPC Server
 #[derive(Clone)]
 pub struct RpcServer {
     config: DaemonConfig,
-    #[allow(dead_code)]
     handlers: Arc<RpcHandlers>,
     #[allow(dead_code)]
     auth: Option<Arc<AuthManager>>,
@@ -28,6 +32,8 @@ pub struct RpcServer {
     #[allow(dead_code)]
     pool: Arc<ConnectionPool>,
     rpc: Arc<JsonRpcServer>,
+    /// Event bus the handlers publish system events on
+    event_bus: Arc<EventBus>,
     /// ZMQ PUB socket for streaming chat output (initialized lazily in run())
     #[allow(dead_code)]
     publisher: Option<Arc<ZmqPublisher>>,
@@ -39,7 +45,12 @@ impl RpcServer {
         config.validate()?;
 
         let metrics = Arc::new(MetricsCollector::new()?);
-        let handlers = Arc::new(RpcHandlers::new());
+        let event_bus = Arc::new(EventBus::new());
+        let handlers = Arc::new(
+            RpcHandlers::new()
+                .with_max_concurrent_agents(config.server.max_concurrent_agents)
+                .with_event_bus(Arc::clone(&event_bus)),
+        );
         let pool = Arc::new(ConnectionPool::new(config.pool.clone()));
 
         let auth = if config.auth.enabled {
@@ -61,6 +72,7 @@ impl RpcServer {
             metrics,
             pool,
             rpc,
+            event_bus,
             publisher: None, // Initialized in run()
         })
     }
@@ -70,6 +82,16 @@ impl RpcServer {
         self.publisher.clone()
     }
 
+    /// Get the event bus handlers publish on
+    pub fn event_bus(&self) -> Arc<EventBus> {
+        Arc::clone(&self.event_bus)
+    }
+
+    /// Get the RPC method handlers
+    pub fn handlers(&self) -> Arc<RpcHandlers> {
+        Arc::clone(&self.handlers)
+    }
+
     /// Get the server config
     pub fn config(&self) -> &DaemonConfig {
         &self.config
@@ -144,8 +166,47 @@ impl RpcServer {
             .map_err(|e| DaemonError::ServerError(format!("Metrics server error: {}", e)))
     }
 
+    /// Open the event history and agent state stores, then restore agents
+    /// from the previous run if configured to
+    pub async fn open_stores(&self) -> DaemonResult<()> {
+        let history = &self.config.events;
+        if history.enabled && self.event_bus.store().is_none() {
+            let store = EventStore::open(&history.path, EventRetention::from(history)).await?;
+            self.event_bus.attach_store(Arc::new(store))?;
+            info!("Persisting events to {:?}", history.path);
+        }
+
+        if self.handlers.state_store().is_none() {
+            let path = &self.config.server.state_store_path;
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| {
+                    DaemonError::StateError(format!("Failed to create state directory: {}", e))
+                })?;
+            }
+            let mut store = SqliteStateStore::new(path, false)
+                .await
+                .map_err(|e| DaemonError::StateError(e.to_string()))?;
+            store
+                .initialize()
+                .await
+                .map_err(|e| DaemonError::StateError(e.to_string()))?;
+            self.handlers
+                .attach_state_store(Arc::new(RwLock::new(store)))?;
+            info!("Saving agents to {:?}", path);
+        }
+
+        if self.config.server.restore_agents {
+            let restored = self.handlers.restore_agents().await?;
+            info!("Restored {} agent(s) from a previous run", restored.len());
+        }
+
+        Ok(())
+    }
+
     /// Run the server
     pub async fn run(&self) -> DaemonResult<()> {
+        self.open_stores().await?;
+
         // Initialize ZMQ publisher and chat manager
         let publisher = match ZmqPublisher::new(
             &self.config.server.pub_addr,
@@ -364,4 +425,69 @@ mod tests {
         let result = RpcServer::new(config);
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_restore_agents_respawns_restartable_agents() {
+        use crate::auth::AuthContext;
+        use serde_json::json;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = DaemonConfig::default();
+        config.server.state_store_path = temp_dir.path().join("data/descartes.db");
+
+        let first = RpcServer::new(config.clone()).unwrap();
+        first.open_stores().await.unwrap();
+        let spawn = |name: &str, restartable: bool| {
+            json!({
+                "name": name,
+                "agent_type": "claude",
+                "config": { "task": "review", "restartable": restartable }
+            })
+        };
+        let handlers = first.handlers();
+        let auth = AuthContext::unauthenticated();
+        let worker: AgentSpawnResponse = serde_json::from_value(
+            handlers
+                .handle_agent_spawn(spawn("worker", true), auth.clone())
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        handlers
+            .handle_agent_spawn(spawn("one-shot", false), auth.clone())
+            .await
+            .unwrap();
+        let killed: AgentSpawnResponse = serde_json::from_value(
+            handlers
+                .handle_agent_spawn(spawn("killed", true), auth.clone())
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        handlers
+            .handle_agent_kill(json!({ "agent_id": killed.agent_id }), auth)
+            .await
+            .unwrap();
+
+        // Without the flag a restarted daemon starts empty
+        let plain = RpcServer::new(config.clone()).unwrap();
+        plain.open_stores().await.unwrap();
+        assert!(plain.handlers().list_agents().is_empty());
+
+        config.server.restore_agents = true;
+        let restarted = RpcServer::new(config.clone()).unwrap();
+        restarted.open_stores().await.unwrap();
+        let agents = restarted.handlers().list_agents();
+        assert_eq!(agents.len(), 1);
+        assert_eq!(agents[0].name, "worker");
+        assert_ne!(agents[0].id, worker.agent_id);
+        assert_eq!(agents[0].config["task"], "review");
+
+        // The old record was retired, so the next restart restores only the new one
+        let again = RpcServer::new(config).unwrap();
+        again.open_stores().await.unwrap();
+        let agents = again.handlers().list_agents();
+        assert_eq!(agents.len(), 1);
+        assert_ne!(agents[0].id, worker.agent_id);
+    }
 }
@@ -180,6 +180,26 @@ pub struct StateQueryResponse {
     pub timestamp: DateTime<Utc>,
 }
 
+/// Events between request (`events.between`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventsBetweenRequest {
+    /// Start of the window, Unix seconds (inclusive)
+    pub from: i64,
+    /// End of the window, Unix seconds (inclusive)
+    pub to: i64,
+    /// Optional filter expression, e.g. `agent_id = agent-1`
+    #[serde(default)]
+    pub filter: Option<String>,
+}
+
+/// Events between response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventsBetweenResponse {
+    /// Persisted events in the window, oldest first
+    pub events: Vec<crate::events::DescartesEvent>,
+    pub count: usize,
+}
+
 /// Metrics response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetricsResponse {
@@ -83,6 +83,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             lease_id: lease.id,
             agent_id: agent_1,
             new_ttl_seconds: Some(120),
+            fencing_token: lease.fencing_token,
         };
 
         let renewal_response = manager.renew_lease(renewal_request).await?;
@@ -113,6 +114,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             let release_request = LeaseReleaseRequest {
                 lease_id: lease.id,
                 agent_id: agent_1,
+                fencing_token: lease.fencing_token,
             };
 
             let release_response = manager.release_lease(release_request).await?;
@@ -146,6 +148,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 let cleanup_request = LeaseReleaseRequest {
                     lease_id: agent2_lease.id,
                     agent_id: agent_2,
+                    fencing_token: agent2_lease.fencing_token,
                 };
                 let _ = manager.release_lease(cleanup_request).await;
             }
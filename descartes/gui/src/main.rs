@@ -22,8 +22,10 @@ mod chat_view;
 mod event_handler;
 mod lisp_debugger;
 mod rpc_client;
+mod rpc_unix_client;
 mod session_selector;
 mod session_state;
+mod task_list_state;
 mod theme;
 mod time_travel;
 mod zmq_subscriber;
@@ -39,7 +41,9 @@ use descartes_daemon::DescartesEvent;
 use event_handler::EventHandler;
 use lisp_debugger::{LispDebuggerMessage, LispDebuggerState};
 use rpc_client::GuiRpcClient;
+use rpc_unix_client::GuiUnixRpcClient;
 use session_state::{SessionMessage, SessionState};
+use task_list_state::{TaskListMessage, TaskListState};
 use time_travel::{TimeTravelMessage, TimeTravelState};
 use uuid::Uuid;
 
@@ -88,6 +92,8 @@ struct DescartesGui {
     chat_graph_state: chat_graph_state::ChatGraphState,
     /// Lisp debugger state
     lisp_debugger_state: LispDebuggerState,
+    /// Paginated task list state
+    task_list_state: TaskListState,
     /// RPC client (wrapped in Arc for cloning)
     rpc_client: Option<Arc<GuiRpcClient>>,
     /// Event handler
@@ -134,6 +140,8 @@ enum Message {
     ChatGraph(chat_graph_state::ChatGraphMessage),
     /// Lisp debugger message
     LispDebugger(LispDebuggerMessage),
+    /// Task list message
+    TaskList(TaskListMessage),
     /// Load sample history data for demo
     LoadSampleHistory,
     /// Clear status message
@@ -159,6 +167,7 @@ impl DescartesGui {
             chat_state: chat_state::ChatState::new(),
             chat_graph_state: chat_graph_state::ChatGraphState::new(),
             lisp_debugger_state: LispDebuggerState::new(),
+            task_list_state: TaskListState::default(),
             rpc_client: None,
             event_handler: None,
             recent_events: Vec::new(),
@@ -238,6 +247,7 @@ impl DescartesGui {
                         self.daemon_connected = true;
                         self.connection_error = None;
                         self.status_message = Some("Connected to daemon successfully!".to_string());
+                        return iced::Task::done(Message::TaskList(TaskListMessage::Refresh));
                     }
                     Err(e) => {
                         tracing::error!("Failed to connect to daemon: {}", e);
@@ -538,6 +548,25 @@ impl DescartesGui {
                 }
                 iced::Task::none()
             }
+            Message::TaskList(msg) => {
+                let Some(offset) = task_list_state::update(&mut self.task_list_state, msg) else {
+                    return iced::Task::none();
+                };
+
+                iced::Task::perform(
+                    async move {
+                        let client = GuiUnixRpcClient::with_defaults().map_err(|e| e.to_string())?;
+                        client
+                            .list_tasks_page(None, offset)
+                            .await
+                            .map_err(|e| e.to_string())
+                    },
+                    |result| match result {
+                        Ok(page) => Message::TaskList(TaskListMessage::PageLoaded(page)),
+                        Err(e) => Message::TaskList(TaskListMessage::LoadFailed(e)),
+                    },
+                )
+            }
             Message::LoadSampleHistory => {
                 tracing::info!("Loading sample history data");
                 self.load_sample_history();
@@ -1401,6 +1430,8 @@ impl DescartesGui {
             .style(container_styles::panel)
         };
 
+        let tasks_section = self.view_task_list();
+
         // Quick actions
         let quick_actions = container(
             column![
@@ -1431,12 +1462,84 @@ impl DescartesGui {
                 container(recent_events_section).width(Length::FillPortion(2)),
             ],
             Space::with_height(16),
+            tasks_section,
+            Space::with_height(16),
             quick_actions,
         ]
         .spacing(0)
         .into()
     }
 
+    /// Paginated task list panel for the dashboard
+    fn view_task_list(&self) -> Element<Message> {
+        let state = &self.task_list_state;
+
+        let header = row![
+            text("Tasks").size(14).color(colors::TEXT_PRIMARY),
+            Space::with_width(Length::Fill),
+            text(state.range_label()).size(12).color(colors::TEXT_MUTED),
+            Space::with_width(12),
+            button(text("Prev").size(12))
+                .on_press_maybe(
+                    state
+                        .has_previous()
+                        .then_some(Message::TaskList(TaskListMessage::PreviousPage)),
+                )
+                .padding([4, 8])
+                .style(button_styles::secondary),
+            Space::with_width(4),
+            button(text("Next").size(12))
+                .on_press_maybe(
+                    state
+                        .has_next()
+                        .then_some(Message::TaskList(TaskListMessage::NextPage)),
+                )
+                .padding([4, 8])
+                .style(button_styles::secondary),
+            Space::with_width(4),
+            button(text("Refresh").size(12))
+                .on_press(Message::TaskList(TaskListMessage::Refresh))
+                .padding([4, 8])
+                .style(button_styles::secondary),
+        ]
+        .align_y(Vertical::Center);
+
+        let body: Element<Message> = if let Some(ref error) = state.error {
+            text(format!("Failed to load tasks: {}", error))
+                .size(12)
+                .color(colors::ERROR)
+                .into()
+        } else if state.loading && state.page.is_none() {
+            text("Loading tasks...").size(12).color(colors::TEXT_MUTED).into()
+        } else if state.tasks().is_empty() {
+            text("No tasks yet").size(12).color(colors::TEXT_MUTED).into()
+        } else {
+            let rows: Vec<Element<Message>> = state
+                .tasks()
+                .iter()
+                .map(|task| {
+                    container(
+                        row![
+                            text(&task.name).size(12).color(colors::TEXT_SECONDARY),
+                            Space::with_width(Length::Fill),
+                            text(&task.status).size(12).color(colors::TEXT_MUTED),
+                        ]
+                        .align_y(Vertical::Center)
+                    )
+                    .padding([6, 0])
+                    .into()
+                })
+                .collect();
+            column(rows).spacing(2).into()
+        };
+
+        container(column![header, Space::with_height(12), body])
+            .padding(16)
+            .width(Length::Fill)
+            .style(container_styles::panel)
+            .into()
+    }
+
     /// Helper to create a stat card
     fn view_stat_card(&self, label: String, value: String, icon: String, color: iced::Color) -> Element<Message> {
         container(
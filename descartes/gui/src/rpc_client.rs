@@ -89,6 +89,27 @@ impl GuiRpcClient {
             .map_err(|e| DaemonError::SerializationError(format!("Failed to parse resume result: {}", e)))
     }
 
+    /// Send a line of input to a running agent's stdin
+    ///
+    /// # Arguments
+    /// * `agent_id` - The ID of the agent
+    /// * `text` - The input line (a trailing newline is added if missing)
+    ///
+    /// # Returns
+    /// Confirmation with the number of bytes written
+    pub async fn send_input(
+        &self,
+        agent_id: Uuid,
+        text: &str,
+    ) -> Result<SendInputResult, DaemonError> {
+        let params = json!([agent_id.to_string(), text]);
+        let result = self.client.call("agent.send_input", Some(params)).await?;
+
+        serde_json::from_value(result).map_err(|e| {
+            DaemonError::SerializationError(format!("Failed to parse send input result: {}", e))
+        })
+    }
+
     /// Request attach credentials for a paused agent
     ///
     /// # Arguments
@@ -232,6 +253,14 @@ pub struct ResumeResult {
     pub resumed_at: i64,
 }
 
+/// Send input result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendInputResult {
+    pub agent_id: String,
+    pub bytes_written: usize,
+    pub sent_at: i64,
+}
+
 /// Attach credentials result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AttachCredentialsResult {
@@ -2,12 +2,16 @@
 ///
 ///This module provides a wrapper around the Unix socket RPC client for use in the Iced GUI.
 /// It handles background communication with the daemon via Unix sockets.
-use descartes_daemon::{DaemonError, UnixSocketRpcClient};
+use descartes_core::TaskPage;
+use descartes_daemon::{DaemonError, TaskInfo, UnixSocketRpcClient};
 use serde_json::Value;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+/// Tasks fetched per page when the caller does not set a `limit`
+pub const DEFAULT_TASK_PAGE_SIZE: usize = 50;
+
 /// GUI-specific RPC client wrapper for Unix sockets
 pub struct GuiUnixRpcClient {
     client: Arc<UnixSocketRpcClient>,
@@ -70,6 +74,23 @@ impl GuiUnixRpcClient {
         self.client.list_tasks(filter).await
     }
 
+    /// List one page of tasks, starting at `offset`
+    ///
+    /// Uses [`DEFAULT_TASK_PAGE_SIZE`] unless `filter` sets its own `limit`.
+    pub async fn list_tasks_page(
+        &self,
+        filter: Option<Value>,
+        offset: usize,
+    ) -> Result<TaskPage<TaskInfo>, DaemonError> {
+        let mut filter = filter.unwrap_or_else(|| serde_json::json!({}));
+        if let Some(obj) = filter.as_object_mut() {
+            obj.entry("limit")
+                .or_insert_with(|| serde_json::json!(DEFAULT_TASK_PAGE_SIZE));
+            obj.insert("offset".to_string(), serde_json::json!(offset));
+        }
+        self.client.list_tasks_page(Some(filter)).await
+    }
+
     /// Approve a task
     pub async fn approve_task(
         &self,
@@ -0,0 +1,161 @@
+//! Task list state and messages for the GUI
+//!
+//! Tasks are fetched one page at a time through
+//! [`GuiUnixRpcClient::list_tasks_page`](crate::rpc_unix_client::GuiUnixRpcClient::list_tasks_page),
+//! so the dashboard never has to hold every task in memory.
+
+use descartes_core::TaskPage;
+use descartes_daemon::TaskInfo;
+
+/// State for the paginated task list
+#[derive(Debug, Clone, Default)]
+pub struct TaskListState {
+    /// Page currently shown
+    pub page: Option<TaskPage<TaskInfo>>,
+    /// Offset of the page being shown or requested
+    pub offset: usize,
+    /// Loading state
+    pub loading: bool,
+    /// Error message
+    pub error: Option<String>,
+}
+
+/// Messages for the task list
+#[derive(Debug, Clone)]
+pub enum TaskListMessage {
+    /// Reload the current page
+    Refresh,
+    /// Load the page after the current one
+    NextPage,
+    /// Load the page before the current one
+    PreviousPage,
+    /// A page was loaded
+    PageLoaded(TaskPage<TaskInfo>),
+    /// Loading a page failed
+    LoadFailed(String),
+}
+
+impl TaskListState {
+    /// Tasks on the current page
+    pub fn tasks(&self) -> &[TaskInfo] {
+        self.page
+            .as_ref()
+            .map(|p| p.tasks.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Whether a page precedes the current one
+    pub fn has_previous(&self) -> bool {
+        self.offset > 0
+    }
+
+    /// Whether a page follows the current one
+    pub fn has_next(&self) -> bool {
+        self.page.as_ref().is_some_and(|p| p.has_more())
+    }
+
+    /// Human-readable range of the current page, e.g. "51-100 of 230"
+    pub fn range_label(&self) -> String {
+        match &self.page {
+            Some(page) if !page.tasks.is_empty() => format!(
+                "{}-{} of {}",
+                page.offset + 1,
+                page.offset + page.tasks.len(),
+                page.total_count
+            ),
+            Some(page) => format!("0 of {}", page.total_count),
+            None => String::new(),
+        }
+    }
+
+    fn page_size(&self) -> usize {
+        self.page
+            .as_ref()
+            .and_then(|p| p.limit)
+            .unwrap_or(crate::rpc_unix_client::DEFAULT_TASK_PAGE_SIZE)
+    }
+}
+
+/// Update task list state
+///
+/// Returns the offset of the page to fetch when the message requires one.
+pub fn update(state: &mut TaskListState, message: TaskListMessage) -> Option<usize> {
+    let offset = match message {
+        TaskListMessage::Refresh => state.offset,
+        TaskListMessage::NextPage => {
+            if !state.has_next() {
+                return None;
+            }
+            state.offset + state.page_size()
+        }
+        TaskListMessage::PreviousPage => {
+            if !state.has_previous() {
+                return None;
+            }
+            state.offset.saturating_sub(state.page_size())
+        }
+        TaskListMessage::PageLoaded(page) => {
+            state.offset = page.offset;
+            state.page = Some(page);
+            state.loading = false;
+            state.error = None;
+            return None;
+        }
+        TaskListMessage::LoadFailed(error) => {
+            state.loading = false;
+            state.error = Some(error);
+            return None;
+        }
+    };
+
+    state.offset = offset;
+    state.loading = true;
+    state.error = None;
+    Some(offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(offset: usize, count: usize, total_count: usize) -> TaskPage<TaskInfo> {
+        TaskPage {
+            tasks: (offset..offset + count)
+                .map(|i| TaskInfo {
+                    id: i.to_string(),
+                    name: format!("Task {}", i),
+                    status: "Todo".to_string(),
+                    created_at: 0,
+                    updated_at: 0,
+                })
+                .collect(),
+            total_count,
+            offset,
+            limit: Some(2),
+        }
+    }
+
+    #[test]
+    fn test_paging_through_tasks() {
+        let mut state = TaskListState::default();
+        assert_eq!(update(&mut state, TaskListMessage::Refresh), Some(0));
+        assert!(state.loading);
+
+        update(&mut state, TaskListMessage::PageLoaded(page(0, 2, 3)));
+        assert_eq!(state.tasks().len(), 2);
+        assert_eq!(state.range_label(), "1-2 of 3");
+        assert!(!state.has_previous());
+        assert_eq!(update(&mut state, TaskListMessage::PreviousPage), None);
+
+        assert_eq!(update(&mut state, TaskListMessage::NextPage), Some(2));
+        update(&mut state, TaskListMessage::PageLoaded(page(2, 1, 3)));
+        assert_eq!(state.range_label(), "3-3 of 3");
+        assert!(!state.has_next());
+        assert_eq!(update(&mut state, TaskListMessage::NextPage), None);
+
+        assert_eq!(update(&mut state, TaskListMessage::PreviousPage), Some(0));
+        update(&mut state, TaskListMessage::LoadFailed("boom".to_string()));
+        assert!(!state.loading);
+        assert_eq!(state.error.as_deref(), Some("boom"));
+    }
+}
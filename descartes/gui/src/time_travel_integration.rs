@@ -4,7 +4,7 @@
 //! providing user feedback, confirmations, and progress tracking.
 
 use descartes_core::{
-    AgentHistoryEvent, RewindConfirmation,
+    AgentHistoryEvent, HistorySnapshot, RewindConfig, RewindConfirmation, RewindGranularity,
     RewindPoint, RewindProgress, RewindResult,
 };
 use iced::widget::{button, column, container, row, text, Column, Space};
@@ -59,6 +59,9 @@ pub enum RewindMessage {
     /// User toggled debugging mode
     ToggleDebugging(bool),
 
+    /// User changed which events the slider can land on
+    SetGranularity(RewindGranularity),
+
     /// User requested snapshot creation
     CreateSnapshot(String),
 
@@ -97,6 +100,12 @@ pub struct RewindState {
 
     /// Success message if any
     pub success_message: Option<String>,
+
+    /// Rewind configuration, including the slider granularity
+    pub config: RewindConfig,
+
+    /// Snapshots the slider can stop on at named-snapshot granularity
+    pub snapshots: Vec<HistorySnapshot>,
 }
 
 
@@ -115,6 +124,20 @@ impl RewindState {
     pub fn last_backup_id(&self) -> Option<uuid::Uuid> {
         self.last_result.as_ref().map(|r| r.backup.backup_id)
     }
+
+    /// Convert a slider position to a rewind point at the configured granularity
+    pub fn slider_to_rewind_point(
+        &self,
+        slider_position: f32,
+        events: &[AgentHistoryEvent],
+    ) -> Option<RewindPoint> {
+        descartes_core::slider_to_rewind_point_with_granularity(
+            slider_position,
+            events,
+            &self.snapshots,
+            self.config.granularity,
+        )
+    }
 }
 
 // ============================================================================
@@ -547,6 +570,10 @@ pub fn update_rewind(state: &mut RewindState, message: RewindMessage) {
             state.debugging_enabled = enabled;
         }
 
+        RewindMessage::SetGranularity(granularity) => {
+            state.config.granularity = granularity;
+        }
+
         RewindMessage::CreateSnapshot(description) => {
             // Snapshot creation would be triggered here
             state.success_message = Some(format!("Creating snapshot: {}", description));
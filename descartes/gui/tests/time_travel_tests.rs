@@ -840,3 +840,27 @@ fn test_zoom_and_navigation_interaction() {
         (state.scroll_offset..state.scroll_offset + visible.len()).collect();
     assert!(visible_indices.contains(&100));
 }
+
+#[test]
+fn test_rewind_slider_uses_configured_granularity() {
+    use descartes_core::RewindGranularity;
+    use descartes_gui::time_travel_integration::{update_rewind, RewindMessage, RewindState};
+
+    let events = generate_sample_events(8);
+    let mut state = RewindState::new();
+
+    // Default granularity lands on every event
+    let point = state.slider_to_rewind_point(0.0, &events).unwrap();
+    assert_eq!(point.event_index, Some(0));
+
+    // Tool-call granularity skips to the first ToolUse event
+    update_rewind(
+        &mut state,
+        RewindMessage::SetGranularity(RewindGranularity::ToolCall),
+    );
+    assert_eq!(state.config.granularity, RewindGranularity::ToolCall);
+    let point = state.slider_to_rewind_point(0.0, &events).unwrap();
+    assert_eq!(point.event_index, Some(2));
+    let point = state.slider_to_rewind_point(1.0, &events).unwrap();
+    assert_eq!(point.event_index, Some(6));
+}